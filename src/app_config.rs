@@ -5,6 +5,15 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 
+use crate::database::repository::CacheEvictionPolicy;
+use crate::database::storage::StorageBackend;
+use crate::translation::cache::CacheKeyFields;
+use crate::translation::document::{ClassPolicy, RetimingConfig};
+use crate::translation::quality::back_translation::BackTranslationConfig;
+use crate::translation::quality::judge::JudgeConfig;
+use crate::validation::AudioSyncConfig;
+use crate::video_timing::TimingTransform;
+
 /// Application configuration module
 /// This module handles the application configuration including loading,
 /// validating and saving configuration settings.
@@ -31,6 +40,10 @@ pub struct Config {
     /// Validation configuration
     #[serde(default)]
     pub validation: ValidationConfig,
+    
+    /// Frame and shot-change timing configuration
+    #[serde(default)]
+    pub video_timing: VideoTimingConfig,
 
     /// Experimental features (all disabled by default)
     #[serde(default)]
@@ -55,7 +68,7 @@ pub enum TranslationProvider {
     // @provider: LM Studio (OpenAI-compatible local server)
     LMStudio,
     // @provider: vLLM (high-throughput local inference server)
-    VLLM,
+    Vllm,
 }
 
 impl TranslationProvider {
@@ -66,7 +79,7 @@ impl TranslationProvider {
             Self::OpenAI => "OpenAI",
             Self::Anthropic => "Anthropic",
             Self::LMStudio => "LM Studio",
-            Self::VLLM => "vLLM",
+            Self::Vllm => "vLLM",
         }
     }
 
@@ -77,7 +90,7 @@ impl TranslationProvider {
             Self::OpenAI => "openai".to_string(),
             Self::Anthropic => "anthropic".to_string(),
             Self::LMStudio => "lmstudio".to_string(),
-            Self::VLLM => "vllm".to_string(),
+            Self::Vllm => "vllm".to_string(),
         }
     }
 }
@@ -99,7 +112,7 @@ impl std::str::FromStr for TranslationProvider {
             "openai" => Ok(Self::OpenAI),
             "anthropic" => Ok(Self::Anthropic),
             "lmstudio" => Ok(Self::LMStudio),
-            "vllm" => Ok(Self::VLLM),
+            "vllm" => Ok(Self::Vllm),
             _ => Err(anyhow!("Invalid provider type: {}", s)),
         }
     }
@@ -139,6 +152,30 @@ pub struct ProviderConfig {
     // @field: Rate limit (requests per minute)
     #[serde(default)]
     pub rate_limit: Option<u32>,
+
+    // @field: Token prices, used to report the cost of a run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<TokenPricing>,
+}
+
+/// Price of a provider's tokens, in US dollars per million tokens
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct TokenPricing {
+    /// Price of a million prompt tokens
+    #[serde(default)]
+    pub input_per_million: f64,
+
+    /// Price of a million completion tokens
+    #[serde(default)]
+    pub output_per_million: f64,
+}
+
+impl TokenPricing {
+    /// Cost of a number of prompt and completion tokens
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.input_per_million + completion_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
 }
 
 impl ProviderConfig {
@@ -155,6 +192,7 @@ impl ProviderConfig {
                 max_chars_per_request: default_max_chars_per_request(),
                 timeout_secs: default_timeout_secs(),
                 rate_limit: default_ollama_rate_limit(),
+                pricing: None,
             },
             TranslationProvider::OpenAI => Self {
                 provider_type: "openai".to_string(),
@@ -165,6 +203,7 @@ impl ProviderConfig {
                 max_chars_per_request: default_max_chars_per_request(),
                 timeout_secs: default_timeout_secs(),
                 rate_limit: default_openai_rate_limit(),
+                pricing: None,
             },
             TranslationProvider::Anthropic => Self {
                 provider_type: "anthropic".to_string(),
@@ -175,6 +214,7 @@ impl ProviderConfig {
                 max_chars_per_request: default_anthropic_max_chars_per_request(),
                 timeout_secs: default_anthropic_timeout_secs(),
                 rate_limit: default_anthropic_rate_limit(),
+                pricing: None,
             },
             TranslationProvider::LMStudio => Self {
                 provider_type: "lmstudio".to_string(),
@@ -185,8 +225,9 @@ impl ProviderConfig {
                 max_chars_per_request: default_max_chars_per_request(),
                 timeout_secs: default_timeout_secs(),
                 rate_limit: default_lmstudio_rate_limit(),
+                pricing: None,
             },
-            TranslationProvider::VLLM => Self {
+            TranslationProvider::Vllm => Self {
                 provider_type: "vllm".to_string(),
                 model: default_vllm_model(),
                 api_key: String::new(),
@@ -195,6 +236,7 @@ impl ProviderConfig {
                 max_chars_per_request: default_vllm_max_chars_per_request(),
                 timeout_secs: default_vllm_timeout_secs(),
                 rate_limit: default_vllm_rate_limit(),
+                pricing: None,
             },
         }
     }
@@ -399,6 +441,31 @@ pub struct TranslationCommonConfig {
     /// - quality: Use new pipeline with thorough analysis and validation
     #[serde(default = "default_pipeline_mode")]
     pub pipeline_mode: String,
+
+    /// Optional path to a per-show style guide (JSON)
+    /// Used by the pipeline modes to render show conventions into the prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_guide_path: Option<String>,
+
+    /// Optional directory of prompt template overrides
+    /// Files: system.tmpl, user.tmpl, feedback_retry.tmpl, semantic_validation.tmpl, summarization.tmpl, judge.tmpl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_templates_dir: Option<String>,
+
+    /// Optional path to an honorific/cultural-term policy (JSON)
+    /// Replaces the built-in Japanese and Korean policies in the pipeline modes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term_policy_path: Option<String>,
+
+    /// How lyrics, signs, sound effects and speaker labels are translated
+    /// Each class takes "translate" (default), "keep_original", "poetic" or "bilingual"
+    #[serde(default, skip_serializing_if = "ClassPolicy::is_default")]
+    pub class_policy: ClassPolicy,
+
+    /// Optional retiming after translation in the pipeline modes
+    /// Extends, merges and splits cues to keep the reading speed under max_cps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retiming: Option<RetimingConfig>,
 }
 
 fn default_context_entries_count() -> usize {
@@ -421,6 +488,11 @@ impl Default for TranslationCommonConfig {
             entries_per_request: default_entries_per_request(),
             context_entries_count: default_context_entries_count(),
             pipeline_mode: default_pipeline_mode(),
+            style_guide_path: None,
+            prompt_templates_dir: None,
+            term_policy_path: None,
+            class_policy: ClassPolicy::default(),
+            retiming: None,
         }
     }
 }
@@ -462,6 +534,10 @@ impl Default for SubtitleConfig {
 /// Session and persistence configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SessionConfig {
+    /// Storage backend: sqlite or json
+    #[serde(default)]
+    pub storage: StorageBackend,
+
     /// Path to the database file (empty = default location)
     #[serde(default)]
     pub database_path: String,
@@ -477,19 +553,34 @@ pub struct SessionConfig {
     /// Whether session persistence is enabled
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Seconds a session lease lasts without a heartbeat before other processes may take the session over
+    #[serde(default = "default_lease_ttl_secs")]
+    pub lease_ttl_secs: u64,
+
+    /// Series the translated files belong to; episodes of a series share glossary and characters (empty = none)
+    #[serde(default)]
+    pub series: String,
 }
 
 fn default_session_retention_days() -> u32 {
     30
 }
 
+fn default_lease_ttl_secs() -> u64 {
+    120
+}
+
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
+            storage: StorageBackend::default(),
             database_path: String::new(),
             auto_resume: true,
             keep_sessions_days: default_session_retention_days(),
             enabled: true,
+            lease_ttl_secs: default_lease_ttl_secs(),
+            series: String::new(),
         }
     }
 }
@@ -508,18 +599,51 @@ pub struct CacheConfig {
     /// Whether to use cross-session database caching
     #[serde(default = "default_true")]
     pub cross_session: bool,
+
+    /// Context parts (speaker, formality, neighbours) included in cache keys
+    #[serde(default)]
+    pub context_key: CacheKeyFields,
+
+    /// Evict database cache entries unused for this many days (0 = never)
+    #[serde(default = "default_cache_ttl_days")]
+    pub ttl_days: u32,
+
+    /// Maximum number of database cache entries (0 = unlimited)
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
 }
 
 fn default_cache_size_mb() -> u32 {
     50
 }
 
+fn default_cache_ttl_days() -> u32 {
+    180
+}
+
+fn default_cache_max_entries() -> usize {
+    200_000
+}
+
+impl CacheConfig {
+    /// Get the eviction policy for the database cache
+    pub fn eviction_policy(&self) -> CacheEvictionPolicy {
+        CacheEvictionPolicy {
+            max_age_days: self.ttl_days,
+            max_entries: self.max_entries,
+        }
+    }
+}
+
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             enabled: true,
             in_memory_size_mb: default_cache_size_mb(),
             cross_session: true,
+            context_key: CacheKeyFields::default(),
+            ttl_days: default_cache_ttl_days(),
+            max_entries: default_cache_max_entries(),
         }
     }
 }
@@ -562,6 +686,30 @@ pub struct ValidationConfig {
     /// Maximum number of retries for failed validations
     #[serde(default = "default_validation_max_retries")]
     pub max_retries: u32,
+    
+    /// Compliance profile to QC translated files against (built-in name or JSON file path)
+    #[serde(default)]
+    pub compliance_profile: Option<String>,
+    
+    /// Audio sync check of extracted subtitles against the video's audio track
+    #[serde(default)]
+    pub audio_sync: AudioSyncConfig,
+    
+    /// Back-translation check of sampled and flagged entries (pipeline modes)
+    #[serde(default)]
+    pub back_translation: BackTranslationConfig,
+    
+    /// MQM scoring by a judge model, retranslating low-scoring entries (pipeline modes)
+    #[serde(default)]
+    pub judge: JudgeConfig,
+    
+    /// Reflow translated lines to the target language's line-breaking rules (pipeline modes)
+    #[serde(default)]
+    pub reflow_lines: bool,
+    
+    /// Line length limit when reflowing (default: the compliance profile's, else the language's)
+    #[serde(default)]
+    pub max_chars_per_line: Option<usize>,
 }
 
 fn default_length_ratio_min() -> f64 {
@@ -592,6 +740,55 @@ impl Default for ValidationConfig {
             max_chars_per_second: default_max_chars_per_second(),
             retry_on_validation_failure: true,
             max_retries: default_validation_max_retries(),
+            compliance_profile: None,
+            audio_sync: AudioSyncConfig::default(),
+            back_translation: BackTranslationConfig::default(),
+            judge: JudgeConfig::default(),
+            reflow_lines: false,
+            max_chars_per_line: None,
+        }
+    }
+}
+
+/// Frame-accurate timing configuration
+///
+/// Shot changes are read from a `<video>.shots` file next to the input,
+/// holding one timestamp per line or ffmpeg scene-detection output.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VideoTimingConfig {
+    /// Whether to snap translated cues to frames (and nearby shot changes)
+    #[serde(default)]
+    pub snap_to_frames: bool,
+    
+    /// Frame rate override such as "23.976" or "24000/1001" (default: probed with ffprobe)
+    #[serde(default)]
+    pub frame_rate: Option<String>,
+    
+    /// Cue edges within this many frames of a shot change are moved onto it
+    #[serde(default = "default_shot_change_window_frames")]
+    pub shot_change_window_frames: u32,
+    
+    /// Timing transforms applied to the source subtitles before translation
+    #[serde(default)]
+    pub before_translation: Vec<TimingTransform>,
+    
+    /// Timing transforms applied to the translated subtitles before frame snapping
+    #[serde(default)]
+    pub after_translation: Vec<TimingTransform>,
+}
+
+fn default_shot_change_window_frames() -> u32 {
+    12
+}
+
+impl Default for VideoTimingConfig {
+    fn default() -> Self {
+        Self {
+            snap_to_frames: false,
+            frame_rate: None,
+            shot_change_window_frames: default_shot_change_window_frames(),
+            before_translation: Vec::new(),
+            after_translation: Vec::new(),
         }
     }
 }
@@ -815,6 +1012,14 @@ impl Config {
             },
             _ => {}
         }
+
+        if self.session.lease_ttl_secs == 0 {
+            return Err(anyhow!("session.lease_ttl_secs must be greater than 0"));
+        }
+
+        if self.validation.max_chars_per_line == Some(0) {
+            return Err(anyhow!("validation.max_chars_per_line must be greater than 0"));
+        }
         
         Ok(())
     }
@@ -832,6 +1037,7 @@ impl Default for Config {
             session: SessionConfig::default(),
             cache: CacheConfig::default(),
             validation: ValidationConfig::default(),
+            video_timing: VideoTimingConfig::default(),
             experimental: ExperimentalFeatures::default(),
             log_level: LogLevel::default(),
         }
@@ -857,6 +1063,11 @@ impl TranslationConfig {
             .find(|p| p.provider_type == provider_str)
     }
     
+    /// Get the token prices of the active provider, if configured
+    pub fn get_pricing(&self) -> Option<TokenPricing> {
+        self.get_active_provider_config().and_then(|p| p.pricing)
+    }
+
     /// Get the model for the active provider
     pub fn get_model(&self) -> String {
         if let Some(provider_config) = self.get_active_provider_config() {
@@ -871,7 +1082,7 @@ impl TranslationConfig {
             TranslationProvider::OpenAI => default_openai_model(),
             TranslationProvider::Anthropic => default_anthropic_model(),
             TranslationProvider::LMStudio => default_lmstudio_model(),
-            TranslationProvider::VLLM => default_vllm_model(),
+            TranslationProvider::Vllm => default_vllm_model(),
         }
     }
     
@@ -901,7 +1112,7 @@ impl TranslationConfig {
             TranslationProvider::OpenAI => default_openai_endpoint(),
             TranslationProvider::Anthropic => default_anthropic_endpoint(),
             TranslationProvider::LMStudio => default_lmstudio_endpoint(),
-            TranslationProvider::VLLM => default_vllm_endpoint(),
+            TranslationProvider::Vllm => default_vllm_endpoint(),
         }
     }
     
@@ -932,7 +1143,7 @@ impl TranslationConfig {
             TranslationProvider::OpenAI => default_openai_rate_limit(),
             TranslationProvider::Anthropic => default_anthropic_rate_limit(),
             TranslationProvider::LMStudio => default_lmstudio_rate_limit(),
-            TranslationProvider::VLLM => default_vllm_rate_limit(),
+            TranslationProvider::Vllm => default_vllm_rate_limit(),
         }
     }
 }
@@ -950,7 +1161,7 @@ impl Default for TranslationConfig {
         config.available_providers.push(ProviderConfig::new(TranslationProvider::OpenAI));
         config.available_providers.push(ProviderConfig::new(TranslationProvider::Anthropic));
         config.available_providers.push(ProviderConfig::new(TranslationProvider::LMStudio));
-        config.available_providers.push(ProviderConfig::new(TranslationProvider::VLLM));
+        config.available_providers.push(ProviderConfig::new(TranslationProvider::Vllm));
 
         config
     }
@@ -10,11 +10,26 @@ use tokio::sync::Mutex;
 use crate::app_config::Config;
 use crate::file_utils::{self, FileManager, FileType};
 use crate::language_utils;
+use crate::session::analysis::SessionAnalysisStore;
+use crate::session::lease::{LeaseHeartbeat, SessionInUse};
 use crate::session::{PendingEntry, SessionCreateParams, SessionInfo, SessionManager};
 use crate::subtitle_processor::SubtitleCollection;
+use crate::translation::context::{HistorySummary, TermPolicy};
 use crate::translation::core::LogEntry;
-use crate::translation::{BatchTranslator, PipelineAdapter, PipelineMode, TranslationService};
+use crate::translation::document::{EntryRevision, RevisionOrigin};
+use crate::translation::prompts::{PromptTemplates, StyleGuide};
+use crate::translation::quality::reference::{ReferenceEvaluator, ReferenceReport};
+use crate::translation::quality::repair::RepairConfig;
+use crate::translation::telemetry::BatchTelemetry;
+use crate::translation::update::UpdatePlan;
+use crate::translation::{
+    BatchTranslator, PipelineAdapter, PipelineConfig, PipelineMode, TranslationService,
+};
 use crate::subtitle_processor::SubtitleEntry;
+use crate::validation::{
+    AudioSyncChecker, AudioSyncReport, ComplianceChecker, ComplianceProfile, QcReport, SpeechMap,
+};
+use crate::video_timing::{FrameAligner, FrameRate, ShotChanges, TimingTransform};
 
 // @module: Application controller for subtitle processing
 
@@ -37,7 +52,7 @@ impl Controller {
     pub fn with_config(config: Config) -> Result<Self> {
         // Initialize session manager if persistence is enabled
         let session_manager = if config.session.enabled {
-            match SessionManager::new_default() {
+            match SessionManager::from_config(&config.session) {
                 Ok(sm) => {
                     debug!("Session manager initialized");
                     Some(sm)
@@ -71,6 +86,126 @@ impl Controller {
         !self.config.source_language.is_empty() && !self.config.target_language.is_empty()
     }
     
+    /// Build the pipeline configuration for a mode, including the style guide
+    /// and prompt template overrides from the config
+    pub fn build_pipeline_config(config: &Config, mode: PipelineMode) -> Result<PipelineConfig> {
+        let source_language = &config.source_language;
+        let target_language = &config.target_language;
+        let mut pipeline_config = match mode {
+            PipelineMode::Fast => PipelineConfig::fast(source_language, target_language),
+            PipelineMode::Quality => PipelineConfig::quality(source_language, target_language),
+            _ => PipelineConfig::new(source_language, target_language),
+        };
+
+        let common = &config.translation.common;
+        if let Some(ref path) = common.style_guide_path {
+            let guide = StyleGuide::from_file(path)?;
+            info!("Using style guide from {}", path);
+            pipeline_config = pipeline_config.with_style_guide(guide);
+        }
+
+        if let Some(ref path) = common.term_policy_path {
+            let policy = TermPolicy::from_file(path)?;
+            info!("Using term policy from {} ({} rules)", path, policy.rules.len());
+            pipeline_config = pipeline_config.with_term_policy(policy);
+        }
+
+        pipeline_config = pipeline_config.with_class_policy(common.class_policy);
+
+        if let Some(ref retiming) = common.retiming {
+            pipeline_config = pipeline_config.with_retiming(retiming.clone());
+        }
+
+        if config.experimental.enable_semantic_validation {
+            pipeline_config.validation_config.enable_semantic_validation = true;
+        }
+
+        if config.validation.reflow_lines {
+            let mut repair_config = RepairConfig::reflow_only(target_language);
+            let max_chars_per_line = match (config.validation.max_chars_per_line, &config.validation.compliance_profile) {
+                (Some(max_chars), _) => Some(max_chars),
+                (None, Some(profile_name)) => Some(ComplianceProfile::resolve(profile_name)?.max_chars_per_line),
+                (None, None) => None,
+            };
+            if let Some(max_chars) = max_chars_per_line {
+                repair_config = repair_config.with_max_chars_per_line(max_chars);
+            }
+            pipeline_config.validation_config.repair_config = Some(repair_config);
+        }
+
+        let back_translation = &config.validation.back_translation;
+        if back_translation.enabled {
+            pipeline_config = pipeline_config.with_back_translation(back_translation.clone());
+        }
+
+        let judge = &config.validation.judge;
+        if judge.enabled {
+            pipeline_config = pipeline_config.with_judge(judge.clone());
+        }
+
+        if let Some(ref dir) = common.prompt_templates_dir {
+            let templates = PromptTemplates::load_dir(dir)?;
+            info!(
+                "Using prompt templates from {} ({})",
+                dir,
+                templates
+                    .overridden()
+                    .iter()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            pipeline_config = pipeline_config.with_prompt_templates(templates);
+        }
+
+        Ok(pipeline_config)
+    }
+
+    /// Build the translating service
+    ///
+    /// The cache keeps translations in the session storage across runs and is
+    /// keyed by the style guide's formality when the config asks for it.
+    fn translation_service(&self) -> Result<TranslationService> {
+        let storage = self.session_manager.as_ref().map(SessionManager::shared_storage);
+        let mut service = TranslationService::new(self.config.translation.clone())?
+            .with_experimental_features(&self.config.experimental)
+            .with_cache_config(&self.config.cache, storage);
+
+        if let Some(ref path) = self.config.translation.common.style_guide_path
+            && let Some(formality) = StyleGuide::from_file(path)?.formality
+        {
+            service = service.with_formality(&formality);
+        }
+        Ok(service)
+    }
+
+    /// Build a service for a quality check that uses another provider or model
+    ///
+    /// Returns None when neither is overridden, so the translating service is reused.
+    fn quality_check_service(&self, purpose: &str, provider: Option<&str>, model: Option<&str>) -> Result<Option<TranslationService>> {
+        let mut translation_config = self.config.translation.clone();
+        if let Some(provider) = provider {
+            translation_config.provider = provider.parse()?;
+        }
+        if let Some(model) = model {
+            let provider_str = translation_config.provider.to_lowercase_string();
+            if let Some(provider_config) = translation_config.available_providers.iter_mut()
+                .find(|p| p.provider_type == provider_str) {
+                provider_config.model = model.to_string();
+            }
+        }
+
+        let same_provider = translation_config.provider == self.config.translation.provider;
+        if same_provider && model.is_none() {
+            return Ok(None);
+        }
+
+        info!("Using {} for {}", translation_config.provider, purpose);
+        let service = TranslationService::new(translation_config)?
+            .with_experimental_features(&self.config.experimental);
+        Ok(Some(service))
+    }
+
     /// Public method to write logs to a file - used by tests
     #[allow(dead_code)]
     pub fn write_translation_logs(&self, logs: &[LogEntry], file_path: &str, translation_context: &str) -> Result<()> {
@@ -113,7 +248,13 @@ impl Controller {
     /// Run the main workflow with input video file and output directory
     pub async fn run(&self, input_file: PathBuf, output_dir: PathBuf, force_overwrite: bool) -> Result<()> {
         let multi_progress = MultiProgress::new();
-        self.run_with_progress(input_file, output_dir, &multi_progress, force_overwrite).await
+        match self.run_with_progress(input_file, output_dir, &multi_progress, force_overwrite).await {
+            Err(e) if e.is::<SessionInUse>() => {
+                warn!("Skipping file, {}", e);
+                Ok(())
+            }
+            result => result,
+        }
     }
     
     /// Run the controller with progress reporting
@@ -156,19 +297,24 @@ impl Controller {
             
             // Create a new SubtitleCollection
             // Note: We ignore the source language from config since we're processing the subtitle file directly
-            let subtitles = SubtitleCollection {
+            let mut subtitles = SubtitleCollection {
                 source_file,
                 entries,
                 source_language: "auto".to_string(), // Using "auto" to indicate we don't know the actual source language
             };
+            self.apply_timing_transforms(&mut subtitles, &self.config.video_timing.before_translation, "before translation")?;
             
             // Translate the subtitles
-            let (translated_subtitles, translation_duration) = self.translate_subtitles_with_progress(
+            let (mut translated_subtitles, translation_duration) = self.translate_subtitles_with_progress(
                 subtitles, 
                 multi_progress, 
                 &output_dir
             ).await?;
             
+            // Apply post-translation timing transforms, then align to frames and shot changes
+            self.apply_timing_transforms(&mut translated_subtitles, &self.config.video_timing.after_translation, "after translation")?;
+            self.align_to_video(&mut translated_subtitles, &input_file).await?;
+            
             // Save translated subtitles
             self.save_translated_subtitles(translated_subtitles, &input_file, &output_dir)?;
             
@@ -215,14 +361,20 @@ impl Controller {
         // Log the extraction step
         
         // Extract subtitles from the input file
-        let subtitles = self.extract_subtitles_to_memory(&input_file).await?;
+        let mut subtitles = self.extract_subtitles_to_memory(&input_file).await?;
+        self.check_audio_sync(&mut subtitles, &input_file).await?;
+        self.apply_timing_transforms(&mut subtitles, &self.config.video_timing.before_translation, "before translation")?;
         
         // Log the subtitle count
         
         // Start the translation process
         
         // Translate the subtitles
-        let (translated, translation_elapsed) = self.translate_subtitles_with_progress(subtitles, multi_progress, &output_dir).await?;
+        let (mut translated, translation_elapsed) = self.translate_subtitles_with_progress(subtitles, multi_progress, &output_dir).await?;
+        
+        // Apply post-translation timing transforms, then align to frames and shot changes
+        self.apply_timing_transforms(&mut translated, &self.config.video_timing.after_translation, "after translation")?;
+        self.align_to_video(&mut translated, &input_file).await?;
         
         // Save the translated subtitles
         self.save_translated_subtitles(translated, &input_file, &output_dir)?;
@@ -282,7 +434,13 @@ impl Controller {
 
         // Create or resume session if session manager is available
         let (session_info, pending_entries, seq_to_source_id) =
-            self.setup_session(&subtitles).await;
+            self.setup_session(&subtitles).await?;
+
+        // Keep the session leased while this process works on it
+        let lease_heartbeat = match (&self.session_manager, &session_info) {
+            (Some(session_manager), Some(session)) => Some(session_manager.start_heartbeat(&session.id)),
+            _ => None,
+        };
 
         // Determine which entries need translation
         let (entries_to_translate, already_translated) = self
@@ -389,8 +547,25 @@ impl Controller {
         let log_capture_clone = Arc::clone(&log_capture);
 
         // Use the translation service to translate all chunks
-        let translation_service = TranslationService::new(self.config.translation.clone())?
-            .with_experimental_features(&self.config.experimental);
+        let mut translation_service = self.translation_service()?;
+
+        // Save each batch's telemetry as it finishes, so failed runs keep theirs
+        if let (Some(sm), Some(session)) = (&self.session_manager, &session_info) {
+            let sm = sm.clone();
+            let session_id = session.id.clone();
+            let run_id = uuid::Uuid::new_v4().to_string();
+            translation_service = translation_service.with_batch_telemetry_callback(Arc::new(move |batch: &BatchTelemetry| {
+                let sm = sm.clone();
+                let session_id = session_id.clone();
+                let run_id = run_id.clone();
+                let batch = batch.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = sm.record_batch_telemetry(&session_id, &run_id, &[batch]).await {
+                        warn!("Failed to record batch telemetry: {}", e);
+                    }
+                });
+            }));
+        }
 
         // Clone the progress_bar for use in the callback
         let pb = progress_bar.clone();
@@ -400,6 +575,11 @@ impl Controller {
         let session_id_clone = session_info.as_ref().map(|s| s.id.clone());
         let seq_to_source_id_clone = seq_to_source_id.clone();
 
+        // Check pipeline mode configuration
+        let pipeline_mode: PipelineMode = self.config.translation.common.pipeline_mode.parse().unwrap_or_default();
+        // The pipeline reports its own revisions, including repairs and retries
+        let record_revisions = !pipeline_mode.is_pipeline_enabled();
+
         let batch_complete_callback = move |entries: Vec<SubtitleEntry>| {
             // Record translations to database if we have a session
             if let (Some(sm), Some(session_id)) = (&session_manager_clone, &session_id_clone) {
@@ -415,40 +595,103 @@ impl Controller {
                 if !translations.is_empty() {
                     let sm = sm.clone();
                     let session_id = session_id.clone();
+                    let revisions: Vec<(i64, EntryRevision)> = if record_revisions {
+                        entries
+                            .iter()
+                            .filter_map(|e| {
+                                seq_to_source_id_clone.get(&(e.seq_num as i64)).map(|id| {
+                                    (*id, EntryRevision::new(e.seq_num, e.text.clone(), RevisionOrigin::Translation))
+                                })
+                            })
+                            .collect()
+                    } else {
+                        Vec::new()
+                    };
                     // Spawn a task to record translations (fire-and-forget)
                     tokio::spawn(async move {
                         if let Err(e) = sm.record_translations(&session_id, translations).await {
                             debug!("Failed to record translations: {}", e);
                         }
+                        if !revisions.is_empty()
+                            && let Err(e) = sm.record_revisions(&session_id, revisions).await
+                        {
+                            debug!("Failed to record revisions: {}", e);
+                        }
                     });
                 }
             }
         };
 
-        // Check pipeline mode configuration
-        let pipeline_mode: PipelineMode = self.config.translation.common.pipeline_mode.parse().unwrap_or_default();
-
         // Translate using either new pipeline or legacy batch translator
         let (mut new_translated_entries, token_usage) = if pipeline_mode.is_pipeline_enabled() {
             // Use new multi-pass translation pipeline
             info!("Using new translation pipeline (mode: {:?})", pipeline_mode);
 
-            let adapter = match pipeline_mode {
-                PipelineMode::Fast => PipelineAdapter::fast(
-                    translation_service,
-                    &self.config.source_language,
-                    &self.config.target_language,
-                ),
-                PipelineMode::Quality => PipelineAdapter::quality(
-                    translation_service,
-                    &self.config.source_language,
-                    &self.config.target_language,
-                ),
-                _ => PipelineAdapter::with_defaults(
-                    translation_service,
-                    &self.config.source_language,
-                    &self.config.target_language,
-                ),
+            let pipeline_config = Self::build_pipeline_config(&self.config, pipeline_mode)?;
+            let mut adapter = PipelineAdapter::new(translation_service, pipeline_config);
+
+            let back_translation = &self.config.validation.back_translation;
+            if back_translation.enabled
+                && let Some(service) = self.quality_check_service("back-translation", back_translation.provider.as_deref(), None)?
+            {
+                adapter = adapter.with_back_translation_service(service);
+            }
+
+            let judge = &self.config.validation.judge;
+            if judge.enabled
+                && let Some(service) = self.quality_check_service("the quality judge", judge.provider.as_deref(), judge.model.as_deref())?
+            {
+                adapter = adapter.with_judge_service(service);
+            }
+
+            // Keep glossary, characters and summary consistent across resumed runs and episodes
+            if let (Some(sm), Some(session)) = (&self.session_manager, &session_info) {
+                let episode = subtitles.source_file.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+                let store = SessionAnalysisStore::new(sm.clone(), &session.id, episode)
+                    .with_series(&self.config.session.series);
+                adapter = adapter.with_analysis_store(Arc::new(store));
+            }
+
+            // Carry rolling history summaries across resumed runs
+            let adapter = if let (Some(sm), Some(session)) = (&self.session_manager, &session_info) {
+                let previous = self.load_history_summaries(sm, &session.id).await;
+                let revision_sm = sm.clone();
+                let revision_session_id = session.id.clone();
+                let seq_to_source_id = seq_to_source_id.clone();
+                let sm = sm.clone();
+                let session_id = session.id.clone();
+                adapter
+                    .with_revision_callback(move |revisions| {
+                        let revisions: Vec<(i64, EntryRevision)> = revisions
+                            .into_iter()
+                            .filter_map(|r| seq_to_source_id.get(&(r.entry_id as i64)).map(|id| (*id, r)))
+                            .collect();
+                        let sm = revision_sm.clone();
+                        let session_id = revision_session_id.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = sm.record_revisions(&session_id, revisions).await {
+                                debug!("Failed to record revisions: {}", e);
+                            }
+                        });
+                    })
+                    .with_history_summaries(previous)
+                    .with_summary_callback(move |summary| {
+                        let records = vec![(
+                            summary.start_entry_id as i64,
+                            summary.end_entry_id as i64,
+                            summary.entry_count as i64,
+                            summary.text.clone(),
+                        )];
+                        let sm = sm.clone();
+                        let session_id = session_id.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = sm.record_history_summaries(&session_id, records).await {
+                                debug!("Failed to record history summaries: {}", e);
+                            }
+                        });
+                    })
+            } else {
+                adapter
             };
 
             let progress_callback = {
@@ -546,7 +789,10 @@ impl Controller {
         
         // Log the number of entries after translation
         let translated_entries_count = translated_entries.len();
-        if translated_entries_count != total_entries_count {
+        if translated_entries_count != total_entries_count && self.config.translation.common.retiming.is_some() {
+            info!("Retiming changed the number of entries from {} to {}",
+                  total_entries_count, translated_entries_count);
+        } else if translated_entries_count != total_entries_count {
             error!("WARNING: Number of entries changed during translation! Before: {}, After: {}", 
                   total_entries_count, translated_entries_count);
         } else {
@@ -568,6 +814,18 @@ impl Controller {
         // Log translation metrics
         let translation_elapsed = translation_start_time.elapsed();
 
+        // Leave the output to the process that reclaimed the session, if any
+        if let (Some(session_manager), Some(session)) = (&self.session_manager, &session_info) {
+            session_manager.ensure_lease(&session.id).await?;
+            if lease_heartbeat.as_ref().is_some_and(LeaseHeartbeat::is_lost) {
+                return Err(SessionInUse {
+                    session_id: session.id.clone(),
+                    lease: session_manager.storage().get_lease(&session.id).await?,
+                }
+                .into());
+            }
+        }
+
         // Mark session as complete if we have session support
         if let Some(ref session) = session_info {
             if let Some(ref session_manager) = self.session_manager {
@@ -602,9 +860,286 @@ impl Controller {
         
         // Log that we saved the subtitle file
         info!("Success: {}", output_path.display());
+
+        // QC the delivered file when a compliance profile is configured
+        if let Some(report) = self.check_compliance(&subtitles, input_file, &output_path)? {
+            Self::log_qc_report(&report);
+        }
                 
         Ok(output_path)
     }
+
+    /// Translate a file's source subtitles and score them against a human track
+    ///
+    /// The reference is `reference_file` when given, otherwise the video's
+    /// target-language track (the one `run` would reuse instead of translating).
+    pub async fn evaluate_against_reference(&self, input_file: &Path, reference_file: Option<&Path>) -> Result<ReferenceReport> {
+        let file_type = FileManager::detect_file_type(input_file).await?;
+        let subtitles = if file_type == FileType::Subtitle {
+            let content = FileManager::read_to_string(input_file)?;
+            SubtitleCollection {
+                source_file: input_file.to_path_buf(),
+                entries: SubtitleCollection::parse_srt_string(&content).context("Failed to parse subtitle file")?,
+                source_language: self.config.source_language.clone(),
+            }
+        } else {
+            self.extract_subtitles_to_memory(input_file).await?
+        };
+
+        let reference = match reference_file {
+            Some(path) => {
+                let content = FileManager::read_to_string(path)?;
+                SubtitleCollection::parse_srt_string(&content).context("Failed to parse reference subtitles")?
+            }
+            None if file_type == FileType::Subtitle => {
+                return Err(anyhow::anyhow!("A reference subtitle file is required to evaluate a subtitle file"));
+            }
+            None => {
+                let track_id = self.find_target_language_track(input_file).await?.ok_or_else(|| {
+                    anyhow::anyhow!("No {} subtitle track found to use as reference", self.config.target_language)
+                })?;
+                self.extract_target_subtitles_to_memory(input_file, track_id).await?.entries
+            }
+        };
+
+        let multi_progress = MultiProgress::new();
+        let output_dir = input_file.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let (translated, duration) = self.translate_subtitles_with_progress(subtitles, &multi_progress, &output_dir).await?;
+        info!(
+            "Translated {} entries for evaluation in {}",
+            translated.entries.len(),
+            Self::format_duration(duration)
+        );
+
+        let file = input_file.file_name().unwrap_or_default().to_string_lossy();
+        let report = ReferenceEvaluator::new().evaluate(&file, &translated.entries, &reference);
+        info!("{}", report.summary());
+        Ok(report)
+    }
+
+    /// Update an existing translation after its source subtitles were edited
+    ///
+    /// Cues whose text did not change keep their translation and take the new
+    /// timing; only edited and new cues are sent to the provider, with their
+    /// neighbours as context. The previous source is `previous_source` when
+    /// given, otherwise the source of the file's most recent session.
+    pub async fn update_translation(
+        &self,
+        source_file: &Path,
+        translated_file: &Path,
+        previous_source: Option<&Path>,
+        output_file: &Path,
+    ) -> Result<UpdatePlan> {
+        let source = SubtitleCollection::parse_srt_string(&FileManager::read_to_string(source_file)?)
+            .context("Failed to parse source subtitles")?;
+        let translation = SubtitleCollection::parse_srt_string(&FileManager::read_to_string(translated_file)?)
+            .context("Failed to parse translated subtitles")?;
+
+        let previous = match (previous_source, &self.session_manager) {
+            (Some(path), _) => SubtitleCollection::parse_srt_string(&FileManager::read_to_string(path)?)
+                .context("Failed to parse previous source subtitles")?,
+            (None, Some(session_manager)) => match session_manager.latest_source_entries(source_file).await? {
+                Some((session, entries)) => {
+                    info!("Using the source of session {} as the previous version", &session.id[..8]);
+                    entries
+                }
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "No session found for {}; pass the source the translation was made from with --previous",
+                        source_file.display()
+                    ));
+                }
+            },
+            (None, None) => {
+                return Err(anyhow::anyhow!(
+                    "Sessions are disabled; pass the source the translation was made from with --previous"
+                ));
+            }
+        };
+
+        let mut plan = UpdatePlan::new(&previous, &translation, &source);
+        info!("Updating {}: {}", translated_file.display(), plan);
+
+        if plan.pending_count() > 0 {
+            let service = self.translation_service()?;
+            let log_capture = Arc::new(Mutex::new(Vec::new()));
+            let common = &self.config.translation.common;
+            for (entries, context) in plan.work_items(common.entries_per_request, common.context_entries_count) {
+                let translated = service
+                    .translate_entries_with_context(
+                        &entries,
+                        &context,
+                        &self.config.source_language,
+                        &self.config.target_language,
+                        log_capture.clone(),
+                    )
+                    .await?;
+                plan.apply(&translated);
+            }
+        }
+
+        let mut updated = SubtitleCollection::new(output_file.to_path_buf(), self.config.target_language.clone());
+        updated.entries = plan.clone().into_entries()?;
+        updated.write_to_srt(output_file)?;
+        info!("Success: {}", output_file.display());
+
+        // Record the edited source so the next update starts from it
+        if let Some(session_manager) = &self.session_manager
+            && let Err(e) = self.record_updated_session(session_manager, source_file, &source, &updated.entries).await
+        {
+            warn!("Failed to record the updated translation in a session: {}", e);
+        }
+
+        Ok(plan)
+    }
+
+    /// Store an updated translation as a completed session
+    async fn record_updated_session(
+        &self,
+        session_manager: &SessionManager,
+        source_file: &Path,
+        source: &[SubtitleEntry],
+        translated: &[SubtitleEntry],
+    ) -> Result<()> {
+        let params = SessionCreateParams::new(
+            source_file.to_path_buf(),
+            self.config.source_language.clone(),
+            self.config.target_language.clone(),
+            self.config.translation.provider.to_lowercase_string(),
+            self.config.translation.get_model(),
+            source.to_vec(),
+        );
+        let session = session_manager.create_session(params).await?;
+        let pending = session_manager.get_pending_entries(&session.id).await?;
+        let texts: HashMap<i64, &str> = source
+            .iter()
+            .zip(translated)
+            .map(|(s, t)| (s.seq_num as i64, t.text.as_str()))
+            .collect();
+        let translations = pending
+            .iter()
+            .filter_map(|p| texts.get(&p.seq_num).map(|text| (p.source_entry_id, text.to_string())))
+            .collect();
+        session_manager.record_translations(&session.id, translations).await?;
+        session_manager.complete_session(&session.id).await
+    }
+
+    /// Check saved subtitles against the configured compliance profile
+    pub fn check_compliance(&self, subtitles: &SubtitleCollection, input_file: &Path, output_path: &Path) -> Result<Option<QcReport>> {
+        let Some(profile_name) = &self.config.validation.compliance_profile else {
+            return Ok(None);
+        };
+
+        let mut checker = ComplianceChecker::new(ComplianceProfile::resolve(profile_name)?);
+        if let Some(shot_changes) = ShotChanges::for_video(input_file)? {
+            checker = checker.with_shot_changes(&shot_changes);
+        }
+
+        let file_name = output_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| output_path.display().to_string());
+
+        Ok(Some(checker.check(&file_name, &subtitles.entries)))
+    }
+
+    /// Check extracted subtitles against the audio track, applying the correction if configured
+    pub async fn check_audio_sync(&self, subtitles: &mut SubtitleCollection, input_file: &Path) -> Result<Option<AudioSyncReport>> {
+        let config = &self.config.validation.audio_sync;
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        // Sync problems are reported, never fatal
+        let speech = match SpeechMap::detect(input_file, config).await {
+            Ok(speech) if !speech.is_empty() => speech,
+            Ok(_) => {
+                warn!("Audio sync check skipped: no speech detected");
+                return Ok(None);
+            }
+            Err(e) => {
+                warn!("Audio sync check skipped: {}", e);
+                return Ok(None);
+            }
+        };
+
+        let report = AudioSyncChecker::new(config.clone()).check(&subtitles.entries, &speech);
+        if !report.is_significant(config) {
+            info!("Audio sync OK ({})", report.summary());
+            return Ok(Some(report));
+        }
+
+        warn!("Subtitles look out of sync with the audio: {}", report.summary());
+        if config.apply
+            && let Some(transform) = report.suggested_transform()
+        {
+            self.apply_timing_transforms(subtitles, &[transform], "to match the audio")?;
+        }
+        Ok(Some(report))
+    }
+
+    /// Apply configured timing transforms to a subtitle collection
+    fn apply_timing_transforms(&self, subtitles: &mut SubtitleCollection, transforms: &[TimingTransform], stage: &str) -> Result<()> {
+        if transforms.is_empty() {
+            return Ok(());
+        }
+
+        let clamped = subtitles.apply_timing_transforms(transforms)?;
+        let steps: Vec<String> = transforms.iter().map(|t| t.description()).collect();
+        info!("Retimed subtitles {}: {}", stage, steps.join(", "));
+        if clamped > 0 {
+            warn!("{} cues were moved before 00:00:00 and clamped", clamped);
+        }
+        Ok(())
+    }
+
+    /// Snap translated cues to video frames and nearby shot changes if configured
+    pub async fn align_to_video(&self, subtitles: &mut SubtitleCollection, input_file: &Path) -> Result<usize> {
+        let timing = &self.config.video_timing;
+        if !timing.snap_to_frames {
+            return Ok(0);
+        }
+
+        let frame_rate = match &timing.frame_rate {
+            Some(rate) => FrameRate::parse(rate)
+                .ok_or_else(|| anyhow::anyhow!("Invalid frame rate in config: {}", rate))?,
+            None => match SubtitleCollection::probe_frame_rate(input_file).await {
+                Ok(rate) => rate,
+                Err(e) => {
+                    warn!("Skipping frame snapping, frame rate unavailable: {}", e);
+                    return Ok(0);
+                }
+            },
+        };
+
+        let mut aligner = FrameAligner::new(frame_rate);
+        if let Some(shot_changes) = ShotChanges::for_video(input_file)? {
+            debug!("Loaded {} shot changes", shot_changes.len());
+            aligner = aligner.with_shot_changes(shot_changes, timing.shot_change_window_frames);
+        }
+
+        let changed = aligner.align(&mut subtitles.entries);
+        info!("Aligned {} cues to {}", changed, frame_rate);
+        Ok(changed)
+    }
+
+    /// Log a QC report, listing the first violations of a failing file
+    fn log_qc_report(report: &QcReport) {
+        const MAX_LOGGED_VIOLATIONS: usize = 20;
+
+        if report.passed {
+            info!("{}", report.summary());
+            return;
+        }
+
+        warn!("{}", report.summary());
+        for violation in report.violations.iter().take(MAX_LOGGED_VIOLATIONS) {
+            warn!("  #{} [{}] {}", violation.seq_num, violation.check, violation.message);
+        }
+        if report.violations.len() > MAX_LOGGED_VIOLATIONS {
+            warn!("  ... and {} more", report.violations.len() - MAX_LOGGED_VIOLATIONS);
+        }
+    }
     
     // Format duration in a human-readable format (HH:MM:SS)
     fn format_duration(duration: std::time::Duration) -> String {
@@ -695,6 +1230,10 @@ impl Controller {
                 Ok(_) => {
                     success_count += 1;
                 },
+                Err(e) if e.is::<SessionInUse>() => {
+                    warn!("Skipping file {}, {}", file_name, e);
+                    skip_count += 1;
+                }
                 Err(e) => {
                     error!("Error processing file {}: {}", file_name, e);
                     error_count += 1;
@@ -880,14 +1419,17 @@ impl Controller {
     // =========================================================================
 
     /// Setup session for translation, returning session info and pending entries
+    ///
+    /// Fails only with [`SessionInUse`], when another process is translating
+    /// the same subtitles; other session errors continue without persistence.
     async fn setup_session(
         &self,
         subtitles: &SubtitleCollection,
-    ) -> (
+    ) -> Result<(
         Option<SessionInfo>,
         Vec<PendingEntry>,
         HashMap<i64, i64>,
-    ) {
+    )> {
         let total_entries_count = subtitles.entries.len();
 
         if let Some(ref session_manager) = self.session_manager {
@@ -901,7 +1443,19 @@ impl Controller {
             );
 
             match session_manager.resume_or_create(params).await {
-                Ok((session, pending)) => {
+                Ok((session, mut pending)) => {
+                    // Lines approved in review elsewhere are taken from the cache
+                    if self.config.cache.enabled && self.config.cache.cross_session {
+                        match session_manager.reuse_cached_translations(&session.id).await {
+                            Ok(0) => {}
+                            Ok(reused) => {
+                                info!("Reused {} cached translations", reused);
+                                pending = session_manager.get_pending_entries(&session.id).await.unwrap_or(pending);
+                            }
+                            Err(e) => debug!("Failed to reuse cached translations: {}", e),
+                        }
+                    }
+
                     let is_resume = pending.len() < total_entries_count;
 
                     if is_resume {
@@ -921,18 +1475,44 @@ impl Controller {
                         .map(|p| (p.seq_num, p.source_entry_id))
                         .collect();
 
-                    (Some(session), pending, seq_to_source_id)
+                    Ok((Some(session), pending, seq_to_source_id))
                 }
+                Err(e) if e.is::<SessionInUse>() => Err(e),
                 Err(e) => {
                     warn!(
                         "Failed to create/resume session: {}. Continuing without persistence.",
                         e
                     );
-                    (None, vec![], HashMap::new())
+                    Ok((None, vec![], HashMap::new()))
                 }
             }
         } else {
-            (None, vec![], HashMap::new())
+            Ok((None, vec![], HashMap::new()))
+        }
+    }
+
+    /// Load rolling history summaries stored for a session
+    async fn load_history_summaries(
+        &self,
+        session_manager: &SessionManager,
+        session_id: &str,
+    ) -> Vec<HistorySummary> {
+        match session_manager.get_history_summaries(session_id).await {
+            Ok(records) => records
+                .into_iter()
+                .map(|r| {
+                    HistorySummary::new(
+                        r.summary_text,
+                        r.start_seq_num as usize,
+                        r.end_seq_num as usize,
+                        r.entry_count as usize,
+                    )
+                })
+                .collect(),
+            Err(e) => {
+                debug!("Failed to load history summaries: {}", e);
+                Vec::new()
+            }
         }
     }
 
@@ -943,8 +1523,8 @@ impl Controller {
         pending_entries: &[PendingEntry],
         session_info: &Option<SessionInfo>,
     ) -> (Vec<SubtitleEntry>, Vec<SubtitleEntry>) {
-        // If no session, translate everything
-        if session_info.is_none() || pending_entries.is_empty() {
+        // If no session, translate everything; a session without pending entries is done
+        if session_info.is_none() {
             return (all_entries.to_vec(), vec![]);
         }
 
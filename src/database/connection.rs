@@ -10,8 +10,9 @@ use log::{debug, info};
 use rusqlite::Connection;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use super::schema;
+use super::schema::{self, MigrateOptions, MigrationStatus};
 
 /// Default database filename
 const DEFAULT_DB_FILENAME: &str = "yastwai.db";
@@ -19,6 +20,9 @@ const DEFAULT_DB_FILENAME: &str = "yastwai.db";
 /// Default database directory name under user's data directory
 const DEFAULT_DB_DIRNAME: &str = "yastwai";
 
+/// How long a write waits for another process holding the database lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Database connection wrapper with thread-safe access
 #[derive(Clone)]
 pub struct DatabaseConnection {
@@ -36,7 +40,18 @@ impl DatabaseConnection {
     }
 
     /// Create a new database connection at the specified path
+    ///
+    /// Older databases are migrated to the current schema, after a backup.
     pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        let db = Self::open_unmigrated(db_path)?;
+        db.migrate(false)?;
+        Ok(db)
+    }
+
+    /// Open a database without creating or migrating its schema
+    ///
+    /// Used to inspect or explicitly migrate a database.
+    pub fn open_unmigrated<P: AsRef<Path>>(db_path: P) -> Result<Self> {
         let db_path = db_path.as_ref().to_path_buf();
 
         // Ensure parent directory exists
@@ -49,9 +64,8 @@ impl DatabaseConnection {
 
         let conn = Connection::open(&db_path)
             .with_context(|| format!("Failed to open database: {:?}", db_path))?;
-
-        // Initialize schema
-        schema::initialize_schema(&conn)?;
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .context("Failed to set database busy timeout")?;
 
         Ok(Self {
             db_path,
@@ -94,6 +108,35 @@ impl DatabaseConnection {
         &self.db_path
     }
 
+    /// Path of the backup written before migrating from `version` (None in memory)
+    pub fn backup_path(&self, version: i32) -> Option<PathBuf> {
+        if self.db_path.to_string_lossy() == ":memory:" {
+            return None;
+        }
+        let file_name = self.db_path.file_name()?.to_string_lossy();
+        Some(self.db_path.with_file_name(format!("{}.v{}.bak", file_name, version)))
+    }
+
+    /// Get the schema version of the database and its pending migrations
+    pub fn migration_status(&self) -> Result<MigrationStatus> {
+        self.execute(schema::migration_status)
+    }
+
+    /// Migrate the database to the current schema version
+    ///
+    /// The file is backed up first (see `backup_path`). A dry run only
+    /// reports the pending migrations.
+    pub fn migrate(&self, dry_run: bool) -> Result<MigrationStatus> {
+        self.execute(|conn| {
+            let current = schema::migration_status(conn)?.current_version;
+            let options = MigrateOptions {
+                dry_run,
+                backup_path: self.backup_path(current),
+            };
+            schema::migrate(conn, &options)
+        })
+    }
+
     /// Execute a database operation with the connection
     ///
     /// This method acquires the mutex lock and executes the provided closure
@@ -0,0 +1,1538 @@
+/*!
+ * JSON file storage backend.
+ *
+ * Keeps every table in memory and rewrites a single JSON file after each
+ * change. It needs no database engine, which makes it a convenient stand-in
+ * for tests and for sharing a small amount of session data, but it is not
+ * meant for large translation memories.
+ *
+ * Several processes may share a store file. A change takes a lock file next
+ * to the store, re-reads the store, applies the change and writes the file
+ * back before releasing the lock; reads reload the file when another process
+ * changed it. The holder keeps the lock fresh, so a lock that goes stale was
+ * left behind by a crashed process and is taken over. File access runs on the
+ * blocking thread pool.
+ */
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
+
+use super::connection::DatabaseConnection;
+use super::models::{
+    AnalysisScope, AnalysisSummaryRecord, BatchTelemetryRecord, CacheRecord, CharacterProfileRecord, GlossaryTermRecord,
+    HistorySummaryRecord, SessionLeaseRecord, SessionRecord, SessionStatus,
+    SourceEntryRecord, TranslatedEntryRecord, TranslationRevisionRecord, TranslationStatus,
+    ValidationResultRecord,
+};
+use super::repository::{
+    CacheEvictionPolicy, CacheEvictionResult, CacheQuery, CacheStats, Repository, ResumableSessionQuery,
+};
+use super::storage::Storage;
+
+/// Default store filename, next to the default SQLite database
+const DEFAULT_STORE_FILENAME: &str = "yastwai.json";
+
+/// Age after which a lock file is considered left behind by a crashed process
+const LOCK_STALE_AFTER: Duration = Duration::from_secs(30);
+
+/// How often the holder of a lock refreshes it
+const LOCK_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for another process to release the lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Delay between attempts to take the lock
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// Contents of a JSON store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreData {
+    /// Last assigned record ID
+    #[serde(default)]
+    last_id: i64,
+    #[serde(default)]
+    sessions: Vec<SessionRecord>,
+    #[serde(default)]
+    source_entries: Vec<SourceEntryRecord>,
+    #[serde(default)]
+    translated_entries: Vec<TranslatedEntryRecord>,
+    #[serde(default)]
+    revisions: Vec<TranslationRevisionRecord>,
+    #[serde(default)]
+    history_summaries: Vec<HistorySummaryRecord>,
+    #[serde(default, deserialize_with = "super::models::deserialize_glossary_terms")]
+    glossary_terms: Vec<GlossaryTermRecord>,
+    #[serde(default)]
+    cache_entries: Vec<CacheRecord>,
+    #[serde(default)]
+    validation_results: Vec<ValidationResultRecord>,
+    #[serde(default)]
+    batch_telemetry: Vec<BatchTelemetryRecord>,
+    #[serde(default)]
+    leases: Vec<SessionLeaseRecord>,
+    #[serde(default)]
+    character_profiles: Vec<CharacterProfileRecord>,
+    #[serde(default)]
+    analysis_summaries: Vec<AnalysisSummaryRecord>,
+    /// Modification time and size of the file when it was last read or written
+    #[serde(skip)]
+    file_stamp: Option<(SystemTime, u64)>,
+}
+
+impl StoreData {
+    /// Assign the next record ID
+    fn next_id(&mut self) -> i64 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    /// Remove a session and everything that belongs to it
+    fn remove_session(&mut self, session_id: &str) -> bool {
+        let before = self.sessions.len();
+        self.sessions.retain(|s| s.id != session_id);
+
+        let source_ids: HashSet<i64> = self
+            .source_entries
+            .iter()
+            .filter(|e| e.session_id == session_id)
+            .map(|e| e.id)
+            .collect();
+        let translated_ids: HashSet<i64> = self
+            .translated_entries
+            .iter()
+            .filter(|e| source_ids.contains(&e.source_entry_id))
+            .map(|e| e.id)
+            .collect();
+
+        self.source_entries.retain(|e| e.session_id != session_id);
+        self.translated_entries.retain(|e| !source_ids.contains(&e.source_entry_id));
+        self.revisions.retain(|r| !source_ids.contains(&r.source_entry_id));
+        self.validation_results.retain(|r| !translated_ids.contains(&r.translated_entry_id));
+        self.history_summaries.retain(|s| s.session_id != session_id);
+        self.batch_telemetry.retain(|b| b.session_id != session_id);
+        self.leases.retain(|l| l.session_id != session_id);
+
+        // Series-scoped analysis results outlive the session
+        let scope = AnalysisScope::Session(session_id.to_string());
+        self.glossary_terms.retain(|t| t.scope != scope);
+        self.character_profiles.retain(|p| p.scope != scope);
+        self.analysis_summaries.retain(|s| s.scope != scope);
+
+        self.sessions.len() != before
+    }
+
+    /// Find the cache entry with the same key as a record
+    fn cache_entry_mut(&mut self, record: &CacheRecord) -> Option<&mut CacheRecord> {
+        self.cache_entries.iter_mut().find(|c| {
+            c.source_text_hash == record.source_text_hash
+                && c.source_language == record.source_language
+                && c.target_language == record.target_language
+                && c.provider == record.provider
+                && c.model == record.model
+                && c.context_key == record.context_key
+        })
+    }
+}
+
+/// Storage backend keeping all data in one JSON file
+#[derive(Clone)]
+pub struct JsonFileStore {
+    /// Store file (None = in memory only)
+    path: Option<PathBuf>,
+    /// Store contents
+    data: Arc<Mutex<StoreData>>,
+}
+
+impl JsonFileStore {
+    /// Open a store file, starting empty if it does not exist yet
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        if !path.exists()
+            && let Some(parent) = path.parent()
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create store directory: {:?}", parent))?;
+        }
+        let data = Self::load(&path)?;
+
+        Ok(Self {
+            path: Some(path),
+            data: Arc::new(Mutex::new(data)),
+        })
+    }
+
+    /// Create a store that is never written to disk (for testing)
+    pub fn new_in_memory() -> Self {
+        Self {
+            path: None,
+            data: Arc::new(Mutex::new(StoreData::default())),
+        }
+    }
+
+    /// Get the default store path, next to the default database
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(DatabaseConnection::default_database_path()?.with_file_name(DEFAULT_STORE_FILENAME))
+    }
+
+    /// Get the store file path
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Run a read-only operation on the store contents, reloading them if the file changed
+    ///
+    /// File access runs on the blocking thread pool, like `DatabaseConnection::execute_async`.
+    async fn read<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&StoreData) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut data = store.data.lock().map_err(|_| anyhow!("JSON store lock poisoned"))?;
+            if let Some(path) = &store.path
+                && file_stamp(path) != data.file_stamp
+            {
+                *data = Self::load(path)?;
+            }
+            Ok(f(&data))
+        })
+        .await
+        .context("JSON store task panicked")?
+    }
+
+    /// Run an operation that changes the store contents, then save the file
+    ///
+    /// The change is made on a fresh copy of the file, read under the store's
+    /// lock file, so changes made by other processes are kept. Waiting for the
+    /// lock and file access run on the blocking thread pool.
+    async fn write<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut StoreData) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut data = store.data.lock().map_err(|_| anyhow!("JSON store lock poisoned"))?;
+            let Some(path) = &store.path else {
+                return f(&mut data);
+            };
+
+            let _lock = StoreLock::acquire(path)?;
+            *data = Self::load(path)?;
+            let result = f(&mut data)?;
+            Self::save(path, &mut data)?;
+            Ok(result)
+        })
+        .await
+        .context("JSON store task panicked")?
+    }
+
+    /// Read a store file, or start empty if it does not exist
+    fn load(path: &Path) -> Result<StoreData> {
+        let file_stamp = file_stamp(path);
+        if file_stamp.is_none() {
+            return Ok(StoreData::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read JSON store: {}", path.display()))?;
+        let data: StoreData = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON store: {}", path.display()))?;
+        Ok(StoreData { file_stamp, ..data })
+    }
+
+    /// Write the store contents to its file, replacing it atomically
+    fn save(path: &Path, data: &mut StoreData) -> Result<()> {
+        let content = serde_json::to_string(data)?;
+        let temp_path = sibling_path(path, &format!(".{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, content)
+            .with_context(|| format!("Failed to write JSON store: {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to replace JSON store: {}", path.display()))?;
+        data.file_stamp = file_stamp(path);
+        Ok(())
+    }
+}
+
+/// Modification time and size of a file (None if it does not exist)
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Path of a file next to `path`, named after it with a suffix
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Exclusive lock on a store file, held while `<store>.lock` holds this lock's token
+///
+/// A background thread keeps the lock file's modification time current, so a
+/// long write is never taken for a crashed one.
+struct StoreLock {
+    path: PathBuf,
+    token: String,
+    refresh: Option<(mpsc::Sender<()>, JoinHandle<()>)>,
+}
+
+impl StoreLock {
+    /// Create the lock file, waiting for other processes to remove theirs
+    fn acquire(store_path: &Path) -> Result<Self> {
+        let path = sibling_path(store_path, ".lock");
+        let token = format!("{}-{}", std::process::id(), Uuid::new_v4());
+        let started = Instant::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(token.as_bytes())
+                        .with_context(|| format!("Failed to write JSON store lock: {}", path.display()))?;
+                    return Ok(Self::held(path, token));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if Self::take_over_if_stale(&path, &token)? {
+                        return Ok(Self::held(path, token));
+                    }
+                    if started.elapsed() > LOCK_TIMEOUT {
+                        return Err(anyhow!("Timed out waiting for JSON store lock: {}", path.display()));
+                    }
+                    std::thread::sleep(LOCK_RETRY_DELAY);
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create JSON store lock: {}", path.display()));
+                }
+            }
+        }
+    }
+
+    /// Replace a lock left behind by a crashed process with this lock's token
+    ///
+    /// Only the waiter that creates the breaker file for the stale token may
+    /// replace it, and it renames its own lock file over the stale one after
+    /// checking the stale token is still there, so two waiters never both win.
+    fn take_over_if_stale(path: &Path, token: &str) -> Result<bool> {
+        if !is_stale(path) {
+            return Ok(false);
+        }
+        let Ok(stale_token) = std::fs::read_to_string(path) else {
+            return Ok(false);
+        };
+
+        let safe_token: String = stale_token.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+        let breaker = sibling_path(path, &format!(".break.{}", safe_token));
+        match OpenOptions::new().write(true).create_new(true).open(&breaker) {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                // A waiter that crashed while breaking the lock leaves its breaker behind
+                if is_stale(&breaker) {
+                    let _ = std::fs::remove_file(&breaker);
+                }
+                return Ok(false);
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create JSON store lock: {}", breaker.display()));
+            }
+        }
+
+        let taken = (|| -> Result<bool> {
+            if !is_stale(path) || std::fs::read_to_string(path).ok().as_deref() != Some(stale_token.as_str()) {
+                return Ok(false);
+            }
+            let temp_path = sibling_path(path, &format!(".{}", token));
+            std::fs::write(&temp_path, token)
+                .with_context(|| format!("Failed to write JSON store lock: {}", temp_path.display()))?;
+            std::fs::rename(&temp_path, path)
+                .with_context(|| format!("Failed to replace JSON store lock: {}", path.display()))?;
+            warn!("Took over stale JSON store lock: {}", path.display());
+            Ok(true)
+        })();
+        let _ = std::fs::remove_file(&breaker);
+        taken
+    }
+
+    /// Start refreshing a lock this process now holds
+    fn held(path: PathBuf, token: String) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let lock_path = path.clone();
+        let task = std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(LOCK_REFRESH_INTERVAL) {
+                if let Ok(file) = OpenOptions::new().write(true).open(&lock_path) {
+                    let _ = file.set_modified(SystemTime::now());
+                }
+            }
+        });
+
+        Self {
+            path,
+            token,
+            refresh: Some((stop, task)),
+        }
+    }
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        if let Some((stop, task)) = self.refresh.take() {
+            drop(stop);
+            let _ = task.join();
+        }
+        // Leave the lock alone if another process took it over
+        if std::fs::read_to_string(&self.path).ok().as_deref() == Some(self.token.as_str()) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Whether a lock file was last refreshed longer ago than `LOCK_STALE_AFTER`
+fn is_stale(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > LOCK_STALE_AFTER)
+}
+
+#[async_trait]
+impl Storage for JsonFileStore {
+    async fn create_session(&self, session: &SessionRecord) -> Result<()> {
+        let session = session.clone();
+        self.write(move |data| {
+            if data.sessions.iter().any(|s| s.id == session.id) {
+                return Err(anyhow!("Session already exists: {}", session.id));
+            }
+            data.sessions.push(session.clone());
+            Ok(())
+        })
+        .await
+    }
+
+    async fn create_leased_session(
+        &self,
+        session: &SessionRecord,
+        lease: &SessionLeaseRecord,
+    ) -> Result<Option<SessionRecord>> {
+        let session = session.clone();
+        let lease = lease.clone();
+        self.write(move |data| {
+            let same_subtitles = |s: &SessionRecord| {
+                ((!session.source_content_hash.is_empty() && s.source_content_hash == session.source_content_hash)
+                    || s.source_file_hash == session.source_file_hash)
+                    && s.source_language == session.source_language
+                    && s.target_language == session.target_language
+                    && s.provider == session.provider
+                    && s.model == session.model
+                    && s.is_resumable()
+            };
+
+            let existing = data
+                .sessions
+                .iter()
+                .filter(|s| same_subtitles(s))
+                .filter(|s| {
+                    data.leases.iter().any(|l| {
+                        l.session_id == s.id && l.owner_id != lease.owner_id && l.expires_at >= lease.heartbeat_at
+                    })
+                })
+                .max_by(|a, b| a.updated_at.cmp(&b.updated_at))
+                .cloned();
+            if existing.is_some() {
+                return Ok(existing);
+            }
+
+            if data.sessions.iter().any(|s| s.id == session.id) {
+                return Err(anyhow!("Session already exists: {}", session.id));
+            }
+            data.sessions.push(session.clone());
+            data.leases.retain(|l| l.session_id != lease.session_id);
+            data.leases.push(lease.clone());
+            Ok(None)
+        })
+        .await
+    }
+
+    async fn get_session(&self, session_id: &str) -> Result<Option<SessionRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| data.sessions.iter().find(|s| s.id == session_id).cloned()).await
+    }
+
+    async fn find_resumable_session(&self, query: &ResumableSessionQuery) -> Result<Option<SessionRecord>> {
+        let query = query.clone();
+        self.read(move |data| {
+            let same_content =
+                |s: &SessionRecord| !query.source_content_hash.is_empty() && s.source_content_hash == query.source_content_hash;
+
+            data.sessions
+                .iter()
+                .filter(|s| {
+                    (same_content(s) || s.source_file_hash == query.source_file_hash || s.source_file_path == query.source_file_path)
+                        && s.source_language == query.source_language
+                        && s.target_language == query.target_language
+                        && s.provider == query.provider
+                        && s.model == query.model
+                        && s.is_resumable()
+                })
+                .max_by(|a, b| {
+                    (same_content(a), a.source_file_hash == query.source_file_hash, &a.updated_at).cmp(&(
+                        same_content(b),
+                        b.source_file_hash == query.source_file_hash,
+                        &b.updated_at,
+                    ))
+                })
+                .cloned()
+        })
+        .await
+    }
+
+    async fn update_session_status(&self, session_id: &str, status: SessionStatus) -> Result<()> {
+        let session_id = session_id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.write(move |data| {
+            if let Some(session) = data.sessions.iter_mut().find(|s| s.id == session_id) {
+                session.status = status;
+                if status == SessionStatus::Completed {
+                    session.completed_at = Some(now.clone());
+                }
+                session.updated_at = now;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update_session_progress(&self, session_id: &str, completed_entries: i64) -> Result<()> {
+        let session_id = session_id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.write(move |data| {
+            if let Some(session) = data.sessions.iter_mut().find(|s| s.id == session_id) {
+                session.completed_entries = completed_entries;
+                session.updated_at = now;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn list_sessions(&self, status_filter: Option<SessionStatus>) -> Result<Vec<SessionRecord>> {
+        self.read(move |data| {
+            let mut sessions: Vec<SessionRecord> = data
+                .sessions
+                .iter()
+                .filter(|s| status_filter.is_none_or(|status| s.status == status))
+                .cloned()
+                .collect();
+            sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+            sessions
+        })
+        .await
+    }
+
+    async fn delete_session(&self, session_id: &str) -> Result<()> {
+        let session_id = session_id.to_string();
+        self.write(move |data| {
+            data.remove_session(&session_id);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete_old_sessions(&self, days: i64) -> Result<i64> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+
+        self.write(move |data| {
+            let old: Vec<String> = data
+                .sessions
+                .iter()
+                .filter(|s| {
+                    chrono::DateTime::parse_from_rfc3339(&s.created_at)
+                        .map(|created| created < cutoff)
+                        .unwrap_or(false)
+                })
+                .map(|s| s.id.clone())
+                .collect();
+            for session_id in &old {
+                data.remove_session(session_id);
+            }
+            Ok(old.len() as i64)
+        })
+        .await
+    }
+
+    async fn acquire_lease(&self, lease: &SessionLeaseRecord) -> Result<bool> {
+        let lease = lease.clone();
+        self.write(move |data| {
+            match data.leases.iter_mut().find(|l| l.session_id == lease.session_id) {
+                Some(current) if current.owner_id == lease.owner_id => {
+                    current.heartbeat_at = lease.heartbeat_at.clone();
+                    current.expires_at = lease.expires_at.clone();
+                }
+                Some(current) if current.expires_at < lease.heartbeat_at => *current = lease.clone(),
+                Some(_) => return Ok(false),
+                None => data.leases.push(lease.clone()),
+            }
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn renew_lease(&self, session_id: &str, owner_id: &str, heartbeat_at: &str, expires_at: &str) -> Result<bool> {
+        let session_id = session_id.to_string();
+        let owner_id = owner_id.to_string();
+        let heartbeat_at = heartbeat_at.to_string();
+        let expires_at = expires_at.to_string();
+        self.write(move |data| {
+            let Some(lease) = data.leases.iter_mut().find(|l| l.session_id == session_id && l.owner_id == owner_id) else {
+                return Ok(false);
+            };
+            lease.heartbeat_at = heartbeat_at.to_string();
+            lease.expires_at = expires_at.to_string();
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn release_lease(&self, session_id: &str, owner_id: &str) -> Result<()> {
+        let session_id = session_id.to_string();
+        let owner_id = owner_id.to_string();
+        self.write(move |data| {
+            data.leases.retain(|l| !(l.session_id == session_id && l.owner_id == owner_id));
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_lease(&self, session_id: &str) -> Result<Option<SessionLeaseRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| data.leases.iter().find(|l| l.session_id == session_id).cloned()).await
+    }
+
+    async fn insert_source_entries(&self, entries: Vec<SourceEntryRecord>) -> Result<()> {
+        self.write(move |data| {
+            for entry in entries {
+                if data
+                    .source_entries
+                    .iter()
+                    .any(|e| e.session_id == entry.session_id && e.seq_num == entry.seq_num)
+                {
+                    return Err(anyhow!("Duplicate source entry {} in session {}", entry.seq_num, entry.session_id));
+                }
+                let id = data.next_id();
+                data.source_entries.push(SourceEntryRecord { id, ..entry });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_source_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| {
+            let mut entries: Vec<SourceEntryRecord> = data
+                .source_entries
+                .iter()
+                .filter(|e| e.session_id == session_id)
+                .cloned()
+                .collect();
+            entries.sort_by_key(|e| e.seq_num);
+            entries
+        })
+        .await
+    }
+
+    async fn get_pending_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>> {
+        let session_id = session_id.to_string();
+        let entries = self.get_source_entries(&session_id).await?;
+
+        self.read(move |data| {
+            entries
+                .into_iter()
+                .filter(|source| {
+                    data.translated_entries
+                        .iter()
+                        .find(|t| t.source_entry_id == source.id)
+                        .is_none_or(|t| {
+                            matches!(
+                                t.translation_status,
+                                TranslationStatus::Pending | TranslationStatus::Retry | TranslationStatus::Failed
+                            )
+                        })
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn insert_translated_entries(&self, entries: Vec<TranslatedEntryRecord>) -> Result<()> {
+        self.write(move |data| {
+            for entry in entries {
+                match data
+                    .translated_entries
+                    .iter_mut()
+                    .find(|t| t.source_entry_id == entry.source_entry_id)
+                {
+                    Some(existing) if existing.translation_status == TranslationStatus::Approved => {}
+                    Some(existing) => {
+                        existing.translated_text = entry.translated_text;
+                        existing.translation_status = entry.translation_status;
+                        existing.quality_score = entry.quality_score;
+                        existing.validation_errors = entry.validation_errors;
+                        existing.attempt_count += 1;
+                        existing.updated_at = entry.updated_at;
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.translated_entries.push(TranslatedEntryRecord { id, ..entry });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_translated_entries(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<(SourceEntryRecord, TranslatedEntryRecord)>> {
+        let session_id = session_id.to_string();
+        let sources = self.get_source_entries(&session_id).await?;
+
+        self.read(move |data| {
+            sources
+                .into_iter()
+                .filter_map(|source| {
+                    let translated = data.translated_entries.iter().find(|t| t.source_entry_id == source.id)?;
+                    Some((source, translated.clone()))
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn approve_translations(&self, approved: Vec<(i64, String)>) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.write(move |data| {
+            for (source_entry_id, translated_text) in approved {
+                match data.translated_entries.iter_mut().find(|t| t.source_entry_id == source_entry_id) {
+                    Some(existing) => {
+                        existing.translated_text = translated_text;
+                        existing.translation_status = TranslationStatus::Approved;
+                        existing.updated_at = now.clone();
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.translated_entries.push(TranslatedEntryRecord {
+                            id,
+                            source_entry_id,
+                            translated_text,
+                            translation_status: TranslationStatus::Approved,
+                            quality_score: None,
+                            validation_errors: None,
+                            attempt_count: 0,
+                            created_at: now.clone(),
+                            updated_at: now.clone(),
+                        });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn add_revisions(&self, revisions: Vec<TranslationRevisionRecord>) -> Result<()> {
+        self.write(move |data| {
+            for revision in revisions {
+                let latest = data
+                    .revisions
+                    .iter()
+                    .filter(|r| r.source_entry_id == revision.source_entry_id)
+                    .map(|r| r.revision)
+                    .max()
+                    .unwrap_or(0);
+                let id = data.next_id();
+                data.revisions.push(TranslationRevisionRecord {
+                    id,
+                    revision: latest + 1,
+                    ..revision
+                });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_revisions(&self, source_entry_id: i64) -> Result<Vec<TranslationRevisionRecord>> {
+        self.read(move |data| {
+            let mut revisions: Vec<TranslationRevisionRecord> = data
+                .revisions
+                .iter()
+                .filter(|r| r.source_entry_id == source_entry_id)
+                .cloned()
+                .collect();
+            revisions.sort_by_key(|r| r.revision);
+            revisions
+        })
+        .await
+    }
+
+    async fn save_history_summaries(&self, summaries: Vec<HistorySummaryRecord>) -> Result<()> {
+        self.write(move |data| {
+            for summary in summaries {
+                match data
+                    .history_summaries
+                    .iter_mut()
+                    .find(|s| s.session_id == summary.session_id && s.start_seq_num == summary.start_seq_num)
+                {
+                    Some(existing) => {
+                        existing.end_seq_num = summary.end_seq_num;
+                        existing.entry_count = summary.entry_count;
+                        existing.summary_text = summary.summary_text;
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.history_summaries.push(HistorySummaryRecord { id, ..summary });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_history_summaries(&self, session_id: &str) -> Result<Vec<HistorySummaryRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| {
+            let mut summaries: Vec<HistorySummaryRecord> = data
+                .history_summaries
+                .iter()
+                .filter(|s| s.session_id == session_id)
+                .cloned()
+                .collect();
+            summaries.sort_by_key(|s| s.start_seq_num);
+            summaries
+        })
+        .await
+    }
+
+    async fn save_glossary_terms(&self, terms: Vec<GlossaryTermRecord>) -> Result<()> {
+        self.write(move |data| {
+            for term in terms {
+                match data
+                    .glossary_terms
+                    .iter_mut()
+                    .find(|t| t.scope == term.scope && t.kind == term.kind && t.source_term == term.source_term)
+                {
+                    Some(existing) => {
+                        existing.target_term = term.target_term;
+                        if term.context.is_some() {
+                            existing.context = term.context;
+                        }
+                        existing.updated_at = term.updated_at;
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.glossary_terms.push(GlossaryTermRecord { id, ..term });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_glossary_terms(&self, scope: &AnalysisScope) -> Result<Vec<GlossaryTermRecord>> {
+        let scope = scope.clone();
+        self.read(move |data| {
+            let mut terms: Vec<GlossaryTermRecord> =
+                data.glossary_terms.iter().filter(|t| t.scope == scope).cloned().collect();
+            terms.sort_by(|a, b| (a.kind.as_str(), &a.source_term).cmp(&(b.kind.as_str(), &b.source_term)));
+            terms
+        })
+        .await
+    }
+
+    async fn save_character_profiles(&self, profiles: Vec<CharacterProfileRecord>) -> Result<()> {
+        self.write(move |data| {
+            for profile in profiles {
+                match data
+                    .character_profiles
+                    .iter_mut()
+                    .find(|p| p.scope == profile.scope && p.name == profile.name)
+                {
+                    Some(existing) => {
+                        existing.mentions = profile.mentions;
+                        existing.first_seen = existing.first_seen.take().or(profile.first_seen);
+                        existing.notes = existing.notes.take().or(profile.notes);
+                        existing.updated_at = profile.updated_at;
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.character_profiles.push(CharacterProfileRecord { id, ..profile });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_character_profiles(&self, scope: &AnalysisScope) -> Result<Vec<CharacterProfileRecord>> {
+        let scope = scope.clone();
+        self.read(move |data| {
+            let mut profiles: Vec<CharacterProfileRecord> =
+                data.character_profiles.iter().filter(|p| p.scope == scope).cloned().collect();
+            profiles.sort_by(|a, b| a.name.cmp(&b.name));
+            profiles
+        })
+        .await
+    }
+
+    async fn save_analysis_summary(&self, summary: &AnalysisSummaryRecord) -> Result<()> {
+        let summary = summary.clone();
+        self.write(move |data| {
+            match data
+                .analysis_summaries
+                .iter_mut()
+                .find(|s| s.scope == summary.scope && s.label == summary.label)
+            {
+                Some(existing) => {
+                    existing.summary_text = summary.summary_text.clone();
+                    existing.updated_at = summary.updated_at.clone();
+                }
+                None => {
+                    let id = data.next_id();
+                    data.analysis_summaries.push(AnalysisSummaryRecord { id, ..summary.clone() });
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_analysis_summaries(&self, scope: &AnalysisScope) -> Result<Vec<AnalysisSummaryRecord>> {
+        let scope = scope.clone();
+        self.read(move |data| {
+            let mut summaries: Vec<AnalysisSummaryRecord> =
+                data.analysis_summaries.iter().filter(|s| s.scope == scope).cloned().collect();
+            summaries.sort_by(|a, b| a.label.cmp(&b.label));
+            summaries
+        })
+        .await
+    }
+
+    async fn insert_batch_telemetry(&self, records: Vec<BatchTelemetryRecord>) -> Result<()> {
+        self.write(move |data| {
+            for record in records {
+                let id = data.next_id();
+                data.batch_telemetry.push(BatchTelemetryRecord { id, ..record });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_batch_telemetry(&self, session_id: &str) -> Result<Vec<BatchTelemetryRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| {
+            data.batch_telemetry
+                .iter()
+                .filter(|b| b.session_id == session_id)
+                .cloned()
+                .collect()
+        })
+        .await
+    }
+
+    async fn get_cached_translation_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        context_key: &str,
+    ) -> Result<Option<String>> {
+        let source_text = source_text.to_string();
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        let provider = provider.to_string();
+        let model = model.to_string();
+        let context_key = context_key.to_string();
+        let source_text_hash = Repository::hash_text(&source_text);
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.write(move |data| {
+            let entry = data.cache_entries.iter_mut().find(|c| {
+                c.source_text_hash == source_text_hash
+                    && c.source_language == source_language
+                    && c.target_language == target_language
+                    && c.provider == provider
+                    && c.model == model
+                    && c.context_key == context_key
+            });
+            Ok(entry.map(|entry| {
+                entry.hit_count += 1;
+                entry.last_used_at = now;
+                entry.translated_text.clone()
+            }))
+        })
+        .await
+    }
+
+    async fn cache_translation(&self, record: &CacheRecord) -> Result<()> {
+        let record = record.clone();
+        self.write(move |data| {
+            match data.cache_entry_mut(&record) {
+                Some(existing) => {
+                    existing.hit_count += 1;
+                    existing.last_used_at = record.last_used_at.clone();
+                }
+                None => {
+                    let id = data.next_id();
+                    data.cache_entries.push(CacheRecord { id, ..record.clone() });
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn import_cache_entries(&self, records: Vec<CacheRecord>) -> Result<usize> {
+        self.write(move |data| {
+            let count = records.len();
+            for record in records {
+                match data.cache_entry_mut(&record) {
+                    Some(existing) => {
+                        existing.translated_text = record.translated_text;
+                        if record.last_used_at > existing.last_used_at {
+                            existing.last_used_at = record.last_used_at;
+                        }
+                    }
+                    None => {
+                        let id = data.next_id();
+                        data.cache_entries.push(CacheRecord { id, ..record });
+                    }
+                }
+            }
+            Ok(count)
+        })
+        .await
+    }
+
+    async fn get_cache_stats(&self) -> Result<CacheStats> {
+        self.read(move |data| CacheStats {
+            total_entries: data.cache_entries.len() as i64,
+            total_hits: data.cache_entries.iter().map(|c| c.hit_count).sum(),
+            contextual_entries: data.cache_entries.iter().filter(|c| !c.context_key.is_empty()).count() as i64,
+            oldest_use: data.cache_entries.iter().map(|c| c.last_used_at.clone()).min(),
+            newest_use: data.cache_entries.iter().map(|c| c.last_used_at.clone()).max(),
+        })
+        .await
+    }
+
+    async fn clear_cache(&self) -> Result<i64> {
+        self.write(move |data| {
+            let deleted = data.cache_entries.len() as i64;
+            data.cache_entries.clear();
+            Ok(deleted)
+        })
+        .await
+    }
+
+    async fn prune_cache(&self, policy: &CacheEvictionPolicy) -> Result<CacheEvictionResult> {
+        let policy = *policy;
+        let cutoff = (policy.max_age_days > 0)
+            .then(|| (chrono::Utc::now() - chrono::Duration::days(policy.max_age_days as i64)).to_rfc3339());
+
+        self.write(move |data| {
+            let before = data.cache_entries.len();
+            if let Some(cutoff) = cutoff {
+                data.cache_entries.retain(|c| c.last_used_at >= cutoff);
+            }
+            let expired = before - data.cache_entries.len();
+
+            let mut evicted = 0;
+            if policy.max_entries > 0 && data.cache_entries.len() > policy.max_entries {
+                data.cache_entries.sort_by(|a, b| {
+                    b.last_used_at
+                        .cmp(&a.last_used_at)
+                        .then_with(|| b.hit_count.cmp(&a.hit_count))
+                });
+                evicted = data.cache_entries.len() - policy.max_entries;
+                data.cache_entries.truncate(policy.max_entries);
+            }
+
+            Ok(CacheEvictionResult {
+                expired: expired as i64,
+                evicted: evicted as i64,
+            })
+        })
+        .await
+    }
+
+    async fn list_cache_entries(&self, query: &CacheQuery) -> Result<Vec<CacheRecord>> {
+        let query = query.clone();
+        self.read(move |data| {
+            let mut entries: Vec<CacheRecord> = data
+                .cache_entries
+                .iter()
+                .filter(|c| {
+                    query.source_language.as_ref().is_none_or(|l| &c.source_language == l)
+                        && query.target_language.as_ref().is_none_or(|l| &c.target_language == l)
+                        && query
+                            .search
+                            .as_ref()
+                            .is_none_or(|s| c.source_text.contains(s.as_str()) || c.translated_text.contains(s.as_str()))
+                })
+                .cloned()
+                .collect();
+            entries.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at).then_with(|| b.id.cmp(&a.id)));
+            if let Some(limit) = query.limit {
+                entries.truncate(limit);
+            }
+            entries
+        })
+        .await
+    }
+
+    async fn get_session_cache_entries(&self, session_id: &str) -> Result<Vec<CacheRecord>> {
+        let session_id = session_id.to_string();
+        self.read(move |data| {
+            let Some(session) = data.sessions.iter().find(|s| s.id == session_id) else {
+                return Vec::new();
+            };
+            let texts: HashSet<&str> = data
+                .source_entries
+                .iter()
+                .filter(|e| e.session_id == session_id)
+                .map(|e| e.source_text.as_str())
+                .collect();
+
+            let mut entries: Vec<CacheRecord> = data
+                .cache_entries
+                .iter()
+                .filter(|c| {
+                    c.source_language == session.source_language
+                        && c.target_language == session.target_language
+                        && texts.contains(c.source_text.as_str())
+                })
+                .cloned()
+                .collect();
+            entries.sort_by_key(|c| c.id);
+            entries
+        })
+        .await
+    }
+
+    async fn get_recent_cache_entries(
+        &self,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<CacheRecord>> {
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        let provider = provider.to_string();
+        let model = model.to_string();
+        self.read(move |data| {
+            let mut entries: Vec<CacheRecord> = data
+                .cache_entries
+                .iter()
+                .filter(|c| {
+                    c.source_language == source_language
+                        && c.target_language == target_language
+                        && c.provider == provider
+                        && c.model == model
+                        && c.context_key.is_empty()
+                })
+                .cloned()
+                .collect();
+            entries.sort_by(|a, b| b.hit_count.cmp(&a.hit_count).then_with(|| b.created_at.cmp(&a.created_at)));
+            entries.truncate(limit);
+            entries
+        })
+        .await
+    }
+
+    async fn insert_validation_results(&self, results: Vec<ValidationResultRecord>) -> Result<()> {
+        self.write(move |data| {
+            for result in results {
+                let id = data.next_id();
+                data.validation_results.push(ValidationResultRecord { id, ..result });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_validation_results(&self, translated_entry_id: i64) -> Result<Vec<ValidationResultRecord>> {
+        self.read(move |data| {
+            data.validation_results
+                .iter()
+                .filter(|r| r.translated_entry_id == translated_entry_id)
+                .cloned()
+                .collect()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{BatchTelemetry, GlossaryTermKind, RevisionOrigin, ValidationType};
+    use tempfile::TempDir;
+
+    fn create_test_session(id: &str) -> SessionRecord {
+        SessionRecord::new(
+            id.to_string(),
+            "/tmp/movie.srt".to_string(),
+            "abc123".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            2,
+        )
+    }
+
+    async fn create_store_with_entries(store: &JsonFileStore) -> Vec<SourceEntryRecord> {
+        store.create_session(&create_test_session("session-1")).await.unwrap();
+        store
+            .insert_source_entries(vec![
+                SourceEntryRecord::new("session-1".to_string(), 1, 0, 1000, "Hello".to_string()),
+                SourceEntryRecord::new("session-1".to_string(), 2, 1000, 2000, "World".to_string()),
+            ])
+            .await
+            .unwrap();
+        store.get_source_entries("session-1").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_open_shouldReloadSavedData() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+
+        let store = JsonFileStore::open(&path).unwrap();
+        let sources = create_store_with_entries(&store).await;
+        store
+            .insert_translated_entries(vec![TranslatedEntryRecord::new(sources[0].id, "Bonjour".to_string())])
+            .await
+            .unwrap();
+        store
+            .cache_translation(&CacheRecord::new(
+                Repository::hash_text("Hello"),
+                "Hello".to_string(),
+                "en".to_string(),
+                "fr".to_string(),
+                "Bonjour".to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+            ))
+            .await
+            .unwrap();
+        drop(store);
+
+        let reopened = JsonFileStore::open(&path).unwrap();
+        let translated = reopened.get_translated_entries("session-1").await.unwrap();
+        assert_eq!(translated.len(), 1);
+        assert_eq!(translated[0].1.translated_text, "Bonjour");
+        let pending = reopened.get_pending_entries("session-1").await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].seq_num, 2);
+        let cached = reopened
+            .get_cached_translation("Hello", "en", "fr", "ollama", "llama2")
+            .await
+            .unwrap();
+        assert_eq!(cached, Some("Bonjour".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_write_withTwoStoresOnSameFile_shouldKeepBothChanges() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+        let first = JsonFileStore::open(&path).unwrap();
+        let second = JsonFileStore::open(&path).unwrap();
+
+        first.create_session(&create_test_session("session-1")).await.unwrap();
+        second.create_session(&create_test_session("session-2")).await.unwrap();
+
+        assert!(first.get_session("session-2").await.unwrap().is_some());
+        let reopened = JsonFileStore::open(&path).unwrap();
+        assert!(reopened.get_session("session-1").await.unwrap().is_some());
+        assert!(reopened.get_session("session-2").await.unwrap().is_some());
+        assert!(!sibling_path(&path, ".lock").exists());
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_createLeasedSession_withSameSubtitlesLeased_shouldReturnExisting() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+        let first = JsonFileStore::open(&path).unwrap();
+        let second = JsonFileStore::open(&path).unwrap();
+        let lease = |session_id: &str, owner: &str| {
+            SessionLeaseRecord::new(
+                session_id.to_string(),
+                owner.to_string(),
+                42,
+                "host".to_string(),
+                chrono::Duration::seconds(60),
+            )
+        };
+
+        let created = first
+            .create_leased_session(&create_test_session("session-1"), &lease("session-1", "first"))
+            .await
+            .unwrap();
+        let existing = second
+            .create_leased_session(&create_test_session("session-2"), &lease("session-2", "second"))
+            .await
+            .unwrap();
+
+        assert!(created.is_none());
+        assert_eq!(existing.map(|s| s.id), Some("session-1".to_string()));
+        assert!(second.get_session("session-2").await.unwrap().is_none());
+        assert_eq!(second.get_lease("session-1").await.unwrap().unwrap().owner_id, "first");
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_write_withStaleLockFile_shouldBreakIt() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+        let lock = std::fs::File::create(sibling_path(&path, ".lock")).unwrap();
+        lock.set_modified(SystemTime::now() - LOCK_STALE_AFTER * 2).unwrap();
+        drop(lock);
+
+        let store = JsonFileStore::open(&path).unwrap();
+        store.create_session(&create_test_session("session-1")).await.unwrap();
+
+        assert!(store.get_session("session-1").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_storeLock_takeOverIfStale_shouldLetOnlyOneWaiterWin() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json.lock");
+        std::fs::write(&path, "1234-crashed").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - LOCK_STALE_AFTER * 2)
+            .unwrap();
+
+        assert!(StoreLock::take_over_if_stale(&path, "1-first").unwrap());
+        assert!(!StoreLock::take_over_if_stale(&path, "2-second").unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1-first");
+
+        // A holder whose lock was taken over leaves the new lock in place
+        drop(StoreLock::held(path.clone(), "1234-crashed".to_string()));
+        assert!(path.exists());
+        drop(StoreLock::held(path.clone(), "1-first".to_string()));
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_insertTranslatedEntries_shouldKeepApprovedEntries() {
+        let store = JsonFileStore::new_in_memory();
+        let sources = create_store_with_entries(&store).await;
+
+        store.approve_translations(vec![(sources[0].id, "Salut".to_string())]).await.unwrap();
+        store
+            .insert_translated_entries(vec![
+                TranslatedEntryRecord::new(sources[0].id, "Bonjour".to_string()),
+                TranslatedEntryRecord::new(sources[1].id, "Monde".to_string()),
+            ])
+            .await
+            .unwrap();
+        store
+            .insert_translated_entries(vec![TranslatedEntryRecord::new(sources[1].id, "Le monde".to_string())])
+            .await
+            .unwrap();
+
+        let translated = store.get_translated_entries("session-1").await.unwrap();
+        assert_eq!(translated[0].1.translated_text, "Salut");
+        assert_eq!(translated[0].1.translation_status, TranslationStatus::Approved);
+        assert_eq!(translated[1].1.translated_text, "Le monde");
+        assert_eq!(translated[1].1.attempt_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_analysisMemory_shouldReloadAndKeepSeriesRowsOnSessionDelete() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+        let session = AnalysisScope::Session("session-1".to_string());
+        let series = AnalysisScope::Series("Show".to_string());
+
+        let store = JsonFileStore::open(&path).unwrap();
+        store.create_session(&create_test_session("session-1")).await.unwrap();
+        for scope in [&session, &series] {
+            store
+                .save_glossary_terms(vec![GlossaryTermRecord::new(
+                    scope.clone(),
+                    GlossaryTermKind::Term,
+                    "Ring".to_string(),
+                    Some("Anneau".to_string()),
+                )])
+                .await
+                .unwrap();
+            store
+                .save_character_profiles(vec![CharacterProfileRecord::new(scope.clone(), "Frodo".to_string(), 3, Some("e01".to_string()))])
+                .await
+                .unwrap();
+            store
+                .save_analysis_summary(&AnalysisSummaryRecord::new(scope.clone(), String::new(), "A journey".to_string()))
+                .await
+                .unwrap();
+        }
+        store
+            .save_character_profiles(vec![CharacterProfileRecord::new(series.clone(), "Frodo".to_string(), 5, Some("e02".to_string()))])
+            .await
+            .unwrap();
+
+        let store = JsonFileStore::open(&path).unwrap();
+        let profiles = store.get_character_profiles(&series).await.unwrap();
+        assert_eq!(profiles[0].mentions, 5);
+        assert_eq!(profiles[0].first_seen.as_deref(), Some("e01"));
+        assert_eq!(store.get_glossary_terms(&session).await.unwrap()[0].target_term.as_deref(), Some("Anneau"));
+
+        store.delete_session("session-1").await.unwrap();
+
+        assert!(store.get_glossary_terms(&session).await.unwrap().is_empty());
+        assert!(store.get_character_profiles(&session).await.unwrap().is_empty());
+        assert!(store.get_analysis_summaries(&session).await.unwrap().is_empty());
+        assert_eq!(store.get_glossary_terms(&series).await.unwrap().len(), 1);
+        assert_eq!(store.get_analysis_summaries(&series).await.unwrap()[0].summary_text, "A journey");
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_open_withUnscopedGlossaryTerms_shouldReadThemAsSessionTerms() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("store.json");
+        std::fs::write(
+            &path,
+            r#"{"last_id": 2, "glossary_terms": [
+                {"id": 1, "session_id": "session-1", "source_term": "Frodo", "target_term": null, "created_at": "t"},
+                {"id": 2, "session_id": "session-1", "source_term": "Ring", "target_term": "Anneau", "created_at": "t"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let store = JsonFileStore::open(&path).unwrap();
+        let terms = store.get_glossary_terms(&AnalysisScope::Session("session-1".to_string())).await.unwrap();
+
+        assert_eq!(terms.len(), 2);
+        assert_eq!((terms[0].kind, terms[0].source_term.as_str()), (GlossaryTermKind::Character, "Frodo"));
+        assert_eq!((terms[1].kind, terms[1].target_term.as_deref()), (GlossaryTermKind::Term, Some("Anneau")));
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_deleteSession_shouldRemoveRelatedData() {
+        let store = JsonFileStore::new_in_memory();
+        let sources = create_store_with_entries(&store).await;
+        store
+            .insert_translated_entries(vec![TranslatedEntryRecord::new(sources[0].id, "Bonjour".to_string())])
+            .await
+            .unwrap();
+        let translated_id = store.get_translated_entries("session-1").await.unwrap()[0].1.id;
+        store
+            .insert_validation_results(vec![ValidationResultRecord::passed(translated_id, ValidationType::FormatCheck)])
+            .await
+            .unwrap();
+        store
+            .add_revisions(vec![
+                TranslationRevisionRecord::new(sources[0].id, "Bonjour".to_string(), RevisionOrigin::Translation),
+                TranslationRevisionRecord::new(sources[0].id, "Salut".to_string(), RevisionOrigin::Human),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(store.get_revisions(sources[0].id).await.unwrap()[1].revision, 2);
+        let batch = BatchTelemetry {
+            phase: "translation".to_string(),
+            batch_index: 0,
+            entry_count: 1,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            duration_ms: 10,
+            requests: 1,
+            cache_hits: 0,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            cost_usd: None,
+            latencies_ms: vec![10],
+            retries: Default::default(),
+            issues: Default::default(),
+        };
+        store
+            .insert_batch_telemetry(vec![BatchTelemetryRecord::new("session-1".to_string(), "run".to_string(), batch)])
+            .await
+            .unwrap();
+        assert_eq!(store.get_batch_telemetry("session-1").await.unwrap().len(), 1);
+
+        store.delete_session("session-1").await.unwrap();
+
+        assert!(store.get_session("session-1").await.unwrap().is_none());
+        assert!(store.get_source_entries("session-1").await.unwrap().is_empty());
+        assert!(store.get_revisions(sources[0].id).await.unwrap().is_empty());
+        assert!(store.get_validation_results(translated_id).await.unwrap().is_empty());
+        assert!(store.get_batch_telemetry("session-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_jsonFileStore_pruneCache_shouldEvictExpiredThenLeastRecentlyUsed() {
+        let store = JsonFileStore::new_in_memory();
+        let days_ago = |days: i64| (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        let records = [("Old", 400), ("A", 3), ("B", 2), ("C", 1)]
+            .into_iter()
+            .map(|(text, age)| CacheRecord {
+                last_used_at: days_ago(age),
+                ..CacheRecord::new(
+                    Repository::hash_text(text),
+                    text.to_string(),
+                    "en".to_string(),
+                    "fr".to_string(),
+                    text.to_string(),
+                    "ollama".to_string(),
+                    "llama2".to_string(),
+                )
+            })
+            .collect();
+        store.import_cache_entries(records).await.unwrap();
+
+        let policy = CacheEvictionPolicy {
+            max_age_days: 365,
+            max_entries: 2,
+        };
+        let result = store.prune_cache(&policy).await.unwrap();
+
+        assert_eq!((result.expired, result.evicted), (1, 1));
+        let remaining: Vec<String> = store
+            .list_cache_entries(&CacheQuery::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.source_text)
+            .collect();
+        assert_eq!(remaining, vec!["C".to_string(), "B".to_string()]);
+    }
+}
@@ -5,6 +5,9 @@
  * - Translation sessions with resume capability
  * - Translation cache for cross-session deduplication
  * - Quality validation results
+ *
+ * Callers go through the `Storage` trait; SQLite (`Repository`) is the
+ * default backend and `JsonFileStore` keeps everything in one JSON file.
  */
 
 // Allow dead code and unused imports - database types are for library consumers
@@ -15,7 +18,11 @@ pub mod schema;
 pub mod connection;
 pub mod repository;
 pub mod models;
+pub mod storage;
+pub mod json_store;
 
 // Re-export main types
 pub use connection::DatabaseConnection;
 pub use repository::Repository;
+pub use storage::{Storage, StorageBackend};
+pub use json_store::JsonFileStore;
@@ -5,9 +5,12 @@
  * type-safe access to persisted data.
  */
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 
+pub use crate::translation::document::RevisionOrigin;
+pub use crate::translation::telemetry::BatchTelemetry;
+
 /// Session status enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -61,6 +64,8 @@ pub enum TranslationStatus {
     Failed,
     /// Entry marked for retry
     Retry,
+    /// Entry reviewed and approved by a human editor
+    Approved,
 }
 
 impl fmt::Display for TranslationStatus {
@@ -71,6 +76,7 @@ impl fmt::Display for TranslationStatus {
             TranslationStatus::Validated => write!(f, "validated"),
             TranslationStatus::Failed => write!(f, "failed"),
             TranslationStatus::Retry => write!(f, "retry"),
+            TranslationStatus::Approved => write!(f, "approved"),
         }
     }
 }
@@ -85,6 +91,7 @@ impl std::str::FromStr for TranslationStatus {
             "validated" => Ok(TranslationStatus::Validated),
             "failed" => Ok(TranslationStatus::Failed),
             "retry" => Ok(TranslationStatus::Retry),
+            "approved" => Ok(TranslationStatus::Approved),
             _ => Err(anyhow::anyhow!("Invalid translation status: {}", s)),
         }
     }
@@ -170,6 +177,9 @@ pub struct SessionRecord {
     pub source_file_path: String,
     /// SHA256 hash of the source file for change detection
     pub source_file_hash: String,
+    /// SHA256 hash of the extracted subtitle entries (empty for older sessions)
+    #[serde(default)]
+    pub source_content_hash: String,
     /// Source language code
     pub source_language: String,
     /// Target language code
@@ -209,6 +219,7 @@ impl SessionRecord {
             id,
             source_file_path,
             source_file_hash,
+            source_content_hash: String::new(),
             source_language,
             target_language,
             provider,
@@ -222,6 +233,12 @@ impl SessionRecord {
         }
     }
 
+    /// Set the hash of the extracted subtitle entries
+    pub fn with_content_hash(mut self, source_content_hash: String) -> Self {
+        self.source_content_hash = source_content_hash;
+        self
+    }
+
     /// Check if session is resumable
     pub fn is_resumable(&self) -> bool {
         matches!(self.status, SessionStatus::InProgress | SessionStatus::Paused)
@@ -333,8 +350,14 @@ pub struct CacheRecord {
     pub provider: String,
     /// Model used for translation
     pub model: String,
+    /// Translation context (speaker, formality, neighbours); empty = any context
+    #[serde(default)]
+    pub context_key: String,
     /// Creation timestamp
     pub created_at: String,
+    /// Last time the entry was stored or hit
+    #[serde(default)]
+    pub last_used_at: String,
     /// Number of cache hits
     pub hit_count: i64,
 }
@@ -350,6 +373,7 @@ impl CacheRecord {
         provider: String,
         model: String,
     ) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
         Self {
             id: 0, // Will be assigned by database
             source_text_hash,
@@ -359,12 +383,385 @@ impl CacheRecord {
             translated_text,
             provider,
             model,
-            created_at: chrono::Utc::now().to_rfc3339(),
+            context_key: String::new(),
+            created_at: now.clone(),
+            last_used_at: now,
             hit_count: 1,
         }
     }
 }
 
+/// Rolling history summary record for a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySummaryRecord {
+    /// Database ID
+    pub id: i64,
+    /// Session ID this summary belongs to
+    pub session_id: String,
+    /// First subtitle sequence number covered by the summary
+    pub start_seq_num: i64,
+    /// Last subtitle sequence number covered by the summary
+    pub end_seq_num: i64,
+    /// Number of entries summarized
+    pub entry_count: i64,
+    /// Summary text
+    pub summary_text: String,
+    /// Creation timestamp
+    pub created_at: String,
+}
+
+impl HistorySummaryRecord {
+    /// Create a new history summary record (without database ID)
+    pub fn new(
+        session_id: String,
+        start_seq_num: i64,
+        end_seq_num: i64,
+        entry_count: i64,
+        summary_text: String,
+    ) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            session_id,
+            start_seq_num,
+            end_seq_num,
+            entry_count,
+            summary_text,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// What analysis results are shared with: one session, or every episode of a series
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "scope", content = "scope_key", rename_all = "lowercase")]
+pub enum AnalysisScope {
+    /// A single session, by ID
+    Session(String),
+    /// A named series
+    Series(String),
+}
+
+impl AnalysisScope {
+    /// Scope name stored in the database
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AnalysisScope::Session(_) => "session",
+            AnalysisScope::Series(_) => "series",
+        }
+    }
+
+    /// Session ID or series name
+    pub fn key(&self) -> &str {
+        match self {
+            AnalysisScope::Session(key) | AnalysisScope::Series(key) => key,
+        }
+    }
+}
+
+impl fmt::Display for AnalysisScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}'", self.kind(), self.key())
+    }
+}
+
+/// Kind of a glossary term
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlossaryTermKind {
+    /// Term with a required translation
+    Term,
+    /// Technical term with a specific translation
+    Technical,
+    /// Character name, kept untranslated
+    Character,
+}
+
+impl GlossaryTermKind {
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GlossaryTermKind::Term => "term",
+            GlossaryTermKind::Technical => "technical",
+            GlossaryTermKind::Character => "character",
+        }
+    }
+}
+
+impl std::str::FromStr for GlossaryTermKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "term" => Ok(GlossaryTermKind::Term),
+            "technical" => Ok(GlossaryTermKind::Technical),
+            "character" => Ok(GlossaryTermKind::Character),
+            _ => Err(anyhow::anyhow!("Invalid glossary term kind: {}", s)),
+        }
+    }
+}
+
+/// Glossary term kept for a session or a series, found by analysis or chosen in review
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryTermRecord {
+    /// Database ID
+    pub id: i64,
+    /// Session or series the term belongs to
+    #[serde(flatten)]
+    pub scope: AnalysisScope,
+    /// Kind of term
+    pub kind: GlossaryTermKind,
+    /// Term as it appears in the source text
+    pub source_term: String,
+    /// Required translation (None = keep the term untranslated, e.g. a character name)
+    pub target_term: Option<String>,
+    /// When to use this translation
+    pub context: Option<String>,
+    /// Last update timestamp
+    pub updated_at: String,
+}
+
+impl GlossaryTermRecord {
+    /// Create a new glossary term record (without database ID)
+    pub fn new(scope: AnalysisScope, kind: GlossaryTermKind, source_term: String, target_term: Option<String>) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            scope,
+            kind,
+            source_term,
+            target_term,
+            context: None,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Glossary term as stored before terms had a scope and a kind
+#[derive(Deserialize)]
+struct LegacyGlossaryTerm {
+    id: i64,
+    session_id: String,
+    source_term: String,
+    target_term: Option<String>,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredGlossaryTerm {
+    Scoped(GlossaryTermRecord),
+    Legacy(LegacyGlossaryTerm),
+}
+
+/// Read glossary terms, turning session terms stored before v9 into scoped ones
+pub(crate) fn deserialize_glossary_terms<'de, D>(deserializer: D) -> std::result::Result<Vec<GlossaryTermRecord>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let terms = Vec::<StoredGlossaryTerm>::deserialize(deserializer)?;
+    Ok(terms
+        .into_iter()
+        .map(|term| match term {
+            StoredGlossaryTerm::Scoped(term) => term,
+            StoredGlossaryTerm::Legacy(term) => GlossaryTermRecord {
+                id: term.id,
+                scope: AnalysisScope::Session(term.session_id),
+                kind: if term.target_term.is_some() { GlossaryTermKind::Term } else { GlossaryTermKind::Character },
+                source_term: term.source_term,
+                target_term: term.target_term,
+                context: None,
+                updated_at: term.created_at,
+            },
+        })
+        .collect())
+}
+
+/// Character seen in a session or a series
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterProfileRecord {
+    /// Database ID
+    pub id: i64,
+    /// Session or series the character belongs to
+    #[serde(flatten)]
+    pub scope: AnalysisScope,
+    /// Character name
+    pub name: String,
+    /// Entries mentioning the character in the latest analysis
+    pub mentions: i64,
+    /// Where the character was first seen (episode or session), kept on later updates
+    pub first_seen: Option<String>,
+    /// Free-form notes (gender, register, relationships), kept on later updates
+    pub notes: Option<String>,
+    /// Last update timestamp
+    pub updated_at: String,
+}
+
+impl CharacterProfileRecord {
+    /// Create a new character profile record (without database ID)
+    pub fn new(scope: AnalysisScope, name: String, mentions: i64, first_seen: Option<String>) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            scope,
+            name,
+            mentions,
+            first_seen,
+            notes: None,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Content summary made by analysis, kept for a session or for an episode of a series
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisSummaryRecord {
+    /// Database ID
+    pub id: i64,
+    /// Session or series the summary belongs to
+    #[serde(flatten)]
+    pub scope: AnalysisScope,
+    /// Episode the summary is of (empty for session summaries)
+    pub label: String,
+    /// Summary text
+    pub summary_text: String,
+    /// Last update timestamp
+    pub updated_at: String,
+}
+
+impl AnalysisSummaryRecord {
+    /// Create a new analysis summary record (without database ID)
+    pub fn new(scope: AnalysisScope, label: String, summary_text: String) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            scope,
+            label,
+            summary_text,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Candidate translation recorded for an entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationRevisionRecord {
+    /// Database ID
+    pub id: i64,
+    /// Reference to source entry
+    pub source_entry_id: i64,
+    /// Revision number, starting at 1 for each entry (assigned on insert)
+    pub revision: i64,
+    /// Candidate text
+    pub translated_text: String,
+    /// Where the text came from
+    pub origin: RevisionOrigin,
+    /// Provider that produced the text (None for repairs and human edits)
+    pub provider: Option<String>,
+    /// Model that produced the text
+    pub model: Option<String>,
+    /// Repair strategy or retry feedback that produced the text
+    pub detail: Option<String>,
+    /// Quality score (0.0 to 1.0)
+    pub quality_score: Option<f64>,
+    /// Whether the text passed validation (None = not validated)
+    pub validation_passed: Option<bool>,
+    /// Validation issues, or the feedback the text was rejected with
+    pub validation_message: Option<String>,
+    /// Creation timestamp
+    pub created_at: String,
+}
+
+impl TranslationRevisionRecord {
+    /// Create a new revision record (without database ID or revision number)
+    pub fn new(source_entry_id: i64, translated_text: String, origin: RevisionOrigin) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            source_entry_id,
+            revision: 0,
+            translated_text,
+            origin,
+            provider: None,
+            model: None,
+            detail: None,
+            quality_score: None,
+            validation_passed: None,
+            validation_message: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Telemetry of one batch of a translation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTelemetryRecord {
+    /// Database ID
+    pub id: i64,
+    /// Session ID this batch belongs to
+    pub session_id: String,
+    /// Run of the session the batch belongs to
+    pub run_id: String,
+    /// Measurements of the batch
+    #[serde(flatten)]
+    pub batch: BatchTelemetry,
+}
+
+impl BatchTelemetryRecord {
+    /// Create a new batch telemetry record (without database ID)
+    pub fn new(session_id: String, run_id: String, batch: BatchTelemetry) -> Self {
+        Self {
+            id: 0, // Will be assigned by database
+            session_id,
+            run_id,
+            batch,
+        }
+    }
+}
+
+/// Lease a process holds on a session while it translates it
+///
+/// Timestamps are UTC RFC 3339 with millisecond precision, so they compare
+/// correctly as strings in SQL.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionLeaseRecord {
+    /// Leased session
+    pub session_id: String,
+    /// Unique ID of the holder
+    pub owner_id: String,
+    /// Process ID of the holder
+    pub pid: i64,
+    /// Host the holder runs on
+    pub hostname: String,
+    /// When the holder first took the lease
+    pub acquired_at: String,
+    /// Last heartbeat of the holder
+    pub heartbeat_at: String,
+    /// When the lease lapses without another heartbeat
+    pub expires_at: String,
+}
+
+impl SessionLeaseRecord {
+    /// Create a lease starting now and lasting `ttl`
+    pub fn new(session_id: String, owner_id: String, pid: i64, hostname: String, ttl: chrono::Duration) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            session_id,
+            owner_id,
+            pid,
+            hostname,
+            acquired_at: lease_timestamp(now),
+            heartbeat_at: lease_timestamp(now),
+            expires_at: lease_timestamp(now + ttl),
+        }
+    }
+
+    /// Whether the lease has lapsed
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < lease_timestamp(chrono::Utc::now())
+    }
+}
+
+/// Format a lease timestamp
+pub fn lease_timestamp(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
 /// Validation result record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResultRecord {
@@ -516,6 +913,8 @@ mod tests {
         assert_eq!(TranslationStatus::Pending.to_string(), "pending");
         assert_eq!(TranslationStatus::Translated.to_string(), "translated");
         assert_eq!(TranslationStatus::Validated.to_string(), "validated");
+        assert_eq!(TranslationStatus::Approved.to_string(), "approved");
+        assert_eq!("approved".parse::<TranslationStatus>().unwrap(), TranslationStatus::Approved);
     }
 
     #[test]
@@ -7,13 +7,16 @@
 
 use anyhow::Result;
 use log::debug;
-use rusqlite::{params, Connection, OptionalExtension};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
 use sha2::{Digest, Sha256};
 
 use super::connection::DatabaseConnection;
+use crate::translation::document::RevisionOrigin;
 use super::models::{
-    CacheRecord, SessionRecord, SessionStatus, SourceEntryRecord, TranslatedEntryRecord,
-    TranslationStatus, ValidationResultRecord, ValidationType,
+    AnalysisScope, AnalysisSummaryRecord, BatchTelemetry, BatchTelemetryRecord, CacheRecord, CharacterProfileRecord,
+    GlossaryTermKind, GlossaryTermRecord, HistorySummaryRecord, SessionLeaseRecord,
+    SessionRecord, SessionStatus, SourceEntryRecord, TranslatedEntryRecord, TranslationRevisionRecord, TranslationStatus,
+    ValidationResultRecord, ValidationType,
 };
 
 /// Repository for database operations
@@ -50,36 +53,114 @@ impl Repository {
         let session = session.clone();
 
         self.db
-            .execute_async(move |conn| {
-                conn.execute(
+            .execute_async(move |conn| Self::insert_session_sync(conn, &session))
+            .await
+    }
+
+    /// Create a session together with its lease, unless another owner is already working on the same subtitles
+    ///
+    /// The check and both inserts run in one immediate transaction, so two
+    /// processes starting on the same file cannot both create a session.
+    /// Returns the session found in use instead, if any.
+    pub async fn create_leased_session(
+        &self,
+        session: &SessionRecord,
+        lease: &SessionLeaseRecord,
+    ) -> Result<Option<SessionRecord>> {
+        let session = session.clone();
+        let lease = lease.clone();
+
+        self.db
+            .execute_mut_async(move |conn| {
+                let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+                let existing = tx
+                    .query_row(
+                        r#"
+                        SELECT s.id, s.source_file_path, s.source_file_hash, s.source_language, s.target_language,
+                               s.provider, s.model, s.total_entries, s.completed_entries, s.status,
+                               s.created_at, s.updated_at, s.completed_at, s.source_content_hash
+                        FROM sessions s
+                        JOIN session_leases l ON l.session_id = s.id
+                        WHERE ((?1 != '' AND s.source_content_hash = ?1) OR s.source_file_hash = ?2)
+                          AND s.source_language = ?3
+                          AND s.target_language = ?4
+                          AND s.provider = ?5
+                          AND s.model = ?6
+                          AND s.status IN ('in_progress', 'paused')
+                          AND l.owner_id != ?7
+                          AND l.expires_at >= ?8
+                        ORDER BY s.updated_at DESC
+                        LIMIT 1
+                        "#,
+                        params![
+                            session.source_content_hash,
+                            session.source_file_hash,
+                            session.source_language,
+                            session.target_language,
+                            session.provider,
+                            session.model,
+                            lease.owner_id,
+                            lease.heartbeat_at,
+                        ],
+                        session_record_from_row,
+                    )
+                    .optional()?;
+                if existing.is_some() {
+                    return Ok(existing);
+                }
+
+                Self::insert_session_sync(&tx, &session)?;
+                tx.execute(
                     r#"
-                    INSERT INTO sessions (
-                        id, source_file_path, source_file_hash, source_language, target_language,
-                        provider, model, total_entries, completed_entries, status,
-                        created_at, updated_at, completed_at
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                    INSERT INTO session_leases (session_id, owner_id, pid, hostname, acquired_at, heartbeat_at, expires_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
                     "#,
                     params![
-                        session.id,
-                        session.source_file_path,
-                        session.source_file_hash,
-                        session.source_language,
-                        session.target_language,
-                        session.provider,
-                        session.model,
-                        session.total_entries,
-                        session.completed_entries,
-                        session.status.to_string(),
-                        session.created_at,
-                        session.updated_at,
-                        session.completed_at,
+                        lease.session_id,
+                        lease.owner_id,
+                        lease.pid,
+                        lease.hostname,
+                        lease.acquired_at,
+                        lease.heartbeat_at,
+                        lease.expires_at,
                     ],
                 )?;
-                Ok(())
+                tx.commit()?;
+                Ok(None)
             })
             .await
     }
 
+    /// Insert a session row (synchronous version for use within transactions)
+    fn insert_session_sync(conn: &Connection, session: &SessionRecord) -> Result<()> {
+        conn.execute(
+            r#"
+            INSERT INTO sessions (
+                id, source_file_path, source_file_hash, source_language, target_language,
+                provider, model, total_entries, completed_entries, status,
+                created_at, updated_at, completed_at, source_content_hash
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            "#,
+            params![
+                session.id,
+                session.source_file_path,
+                session.source_file_hash,
+                session.source_language,
+                session.target_language,
+                session.provider,
+                session.model,
+                session.total_entries,
+                session.completed_entries,
+                session.status.to_string(),
+                session.created_at,
+                session.updated_at,
+                session.completed_at,
+                session.source_content_hash,
+            ],
+        )?;
+        Ok(())
+    }
+
     /// Get a session by ID
     pub async fn get_session(&self, session_id: &str) -> Result<Option<SessionRecord>> {
         let session_id = session_id.to_string();
@@ -98,50 +179,23 @@ impl Repository {
                 r#"
                 SELECT id, source_file_path, source_file_hash, source_language, target_language,
                        provider, model, total_entries, completed_entries, status,
-                       created_at, updated_at, completed_at
+                       created_at, updated_at, completed_at, source_content_hash
                 FROM sessions WHERE id = ?1
                 "#,
                 [session_id],
-                |row| {
-                    Ok(SessionRecord {
-                        id: row.get(0)?,
-                        source_file_path: row.get(1)?,
-                        source_file_hash: row.get(2)?,
-                        source_language: row.get(3)?,
-                        target_language: row.get(4)?,
-                        provider: row.get(5)?,
-                        model: row.get(6)?,
-                        total_entries: row.get(7)?,
-                        completed_entries: row.get(8)?,
-                        status: row
-                            .get::<_, String>(9)?
-                            .parse()
-                            .unwrap_or(SessionStatus::InProgress),
-                        created_at: row.get(10)?,
-                        updated_at: row.get(11)?,
-                        completed_at: row.get(12)?,
-                    })
-                },
+                session_record_from_row,
             )
             .optional()?;
 
         Ok(result)
     }
 
-    /// Find a resumable session for the given file and language pair
-    pub async fn find_resumable_session(
-        &self,
-        source_file_hash: &str,
-        source_language: &str,
-        target_language: &str,
-        provider: &str,
-        model: &str,
-    ) -> Result<Option<SessionRecord>> {
-        let source_file_hash = source_file_hash.to_string();
-        let source_language = source_language.to_string();
-        let target_language = target_language.to_string();
-        let provider = provider.to_string();
-        let model = model.to_string();
+    /// Find a resumable session for the given source and language pair
+    ///
+    /// Sessions with the same subtitle content are preferred, then sessions
+    /// of the same file, then sessions of the same path whose source changed.
+    pub async fn find_resumable_session(&self, query: &ResumableSessionQuery) -> Result<Option<SessionRecord>> {
+        let query = query.clone();
 
         self.db
             .execute_async(move |conn| {
@@ -150,38 +204,31 @@ impl Repository {
                         r#"
                         SELECT id, source_file_path, source_file_hash, source_language, target_language,
                                provider, model, total_entries, completed_entries, status,
-                               created_at, updated_at, completed_at
+                               created_at, updated_at, completed_at, source_content_hash
                         FROM sessions
-                        WHERE source_file_hash = ?1
-                          AND source_language = ?2
-                          AND target_language = ?3
-                          AND provider = ?4
-                          AND model = ?5
+                        WHERE ((?1 != '' AND source_content_hash = ?1)
+                               OR source_file_hash = ?2
+                               OR source_file_path = ?3)
+                          AND source_language = ?4
+                          AND target_language = ?5
+                          AND provider = ?6
+                          AND model = ?7
                           AND status IN ('in_progress', 'paused')
-                        ORDER BY updated_at DESC
+                        ORDER BY (?1 != '' AND source_content_hash = ?1) DESC,
+                                 source_file_hash = ?2 DESC,
+                                 updated_at DESC
                         LIMIT 1
                         "#,
-                        params![source_file_hash, source_language, target_language, provider, model],
-                        |row| {
-                            Ok(SessionRecord {
-                                id: row.get(0)?,
-                                source_file_path: row.get(1)?,
-                                source_file_hash: row.get(2)?,
-                                source_language: row.get(3)?,
-                                target_language: row.get(4)?,
-                                provider: row.get(5)?,
-                                model: row.get(6)?,
-                                total_entries: row.get(7)?,
-                                completed_entries: row.get(8)?,
-                                status: row
-                                    .get::<_, String>(9)?
-                                    .parse()
-                                    .unwrap_or(SessionStatus::InProgress),
-                                created_at: row.get(10)?,
-                                updated_at: row.get(11)?,
-                                completed_at: row.get(12)?,
-                            })
-                        },
+                        params![
+                            query.source_content_hash,
+                            query.source_file_hash,
+                            query.source_file_path,
+                            query.source_language,
+                            query.target_language,
+                            query.provider,
+                            query.model,
+                        ],
+                        session_record_from_row,
                     )
                     .optional()?;
 
@@ -247,40 +294,18 @@ impl Repository {
     ) -> Result<Vec<SessionRecord>> {
         self.db
             .execute_async(move |conn| {
-                // Helper function to parse a session row
-                fn parse_session_row(row: &rusqlite::Row) -> rusqlite::Result<SessionRecord> {
-                    Ok(SessionRecord {
-                        id: row.get(0)?,
-                        source_file_path: row.get(1)?,
-                        source_file_hash: row.get(2)?,
-                        source_language: row.get(3)?,
-                        target_language: row.get(4)?,
-                        provider: row.get(5)?,
-                        model: row.get(6)?,
-                        total_entries: row.get(7)?,
-                        completed_entries: row.get(8)?,
-                        status: row
-                            .get::<_, String>(9)?
-                            .parse()
-                            .unwrap_or(SessionStatus::InProgress),
-                        created_at: row.get(10)?,
-                        updated_at: row.get(11)?,
-                        completed_at: row.get(12)?,
-                    })
-                }
-
                 let sessions: Vec<SessionRecord> = if let Some(status) = status_filter {
                     let mut stmt = conn.prepare(
                         r#"
                         SELECT id, source_file_path, source_file_hash, source_language, target_language,
                                provider, model, total_entries, completed_entries, status,
-                               created_at, updated_at, completed_at
+                               created_at, updated_at, completed_at, source_content_hash
                         FROM sessions
                         WHERE status = ?1
                         ORDER BY updated_at DESC
                         "#,
                     )?;
-                    stmt.query_map([status.to_string()], parse_session_row)?
+                    stmt.query_map([status.to_string()], session_record_from_row)?
                         .filter_map(|r| r.ok())
                         .collect()
                 } else {
@@ -288,12 +313,12 @@ impl Repository {
                         r#"
                         SELECT id, source_file_path, source_file_hash, source_language, target_language,
                                provider, model, total_entries, completed_entries, status,
-                               created_at, updated_at, completed_at
+                               created_at, updated_at, completed_at, source_content_hash
                         FROM sessions
                         ORDER BY updated_at DESC
                         "#,
                     )?;
-                    stmt.query_map([], parse_session_row)?
+                    stmt.query_map([], session_record_from_row)?
                         .filter_map(|r| r.ok())
                         .collect()
                 };
@@ -332,6 +357,120 @@ impl Repository {
             .await
     }
 
+    // =========================================================================
+    // Session Lease Operations
+    // =========================================================================
+
+    /// Take the lease of a session if it is free, expired or already held by the same owner
+    ///
+    /// Returns whether the lease was taken. The check and the write are a
+    /// single statement, so two processes can never both win.
+    pub async fn acquire_lease(&self, lease: &SessionLeaseRecord) -> Result<bool> {
+        let lease = lease.clone();
+        self.db
+            .execute_async(move |conn| {
+                let changed = conn.execute(
+                    r#"
+                    INSERT INTO session_leases (session_id, owner_id, pid, hostname, acquired_at, heartbeat_at, expires_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT(session_id) DO UPDATE SET
+                        owner_id = excluded.owner_id,
+                        pid = excluded.pid,
+                        hostname = excluded.hostname,
+                        acquired_at = CASE
+                            WHEN session_leases.owner_id = excluded.owner_id THEN session_leases.acquired_at
+                            ELSE excluded.acquired_at
+                        END,
+                        heartbeat_at = excluded.heartbeat_at,
+                        expires_at = excluded.expires_at
+                    WHERE session_leases.owner_id = excluded.owner_id
+                       OR session_leases.expires_at < excluded.heartbeat_at
+                    "#,
+                    params![
+                        lease.session_id,
+                        lease.owner_id,
+                        lease.pid,
+                        lease.hostname,
+                        lease.acquired_at,
+                        lease.heartbeat_at,
+                        lease.expires_at,
+                    ],
+                )?;
+                Ok(changed > 0)
+            })
+            .await
+    }
+
+    /// Extend a lease still held by the owner, returning false if it was lost
+    pub async fn renew_lease(&self, session_id: &str, owner_id: &str, heartbeat_at: &str, expires_at: &str) -> Result<bool> {
+        let session_id = session_id.to_string();
+        let owner_id = owner_id.to_string();
+        let heartbeat_at = heartbeat_at.to_string();
+        let expires_at = expires_at.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                let changed = conn.execute(
+                    r#"
+                    UPDATE session_leases
+                    SET heartbeat_at = ?3, expires_at = ?4
+                    WHERE session_id = ?1 AND owner_id = ?2
+                    "#,
+                    params![session_id, owner_id, heartbeat_at, expires_at],
+                )?;
+                Ok(changed > 0)
+            })
+            .await
+    }
+
+    /// Release a lease held by the owner
+    pub async fn release_lease(&self, session_id: &str, owner_id: &str) -> Result<()> {
+        let session_id = session_id.to_string();
+        let owner_id = owner_id.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                conn.execute(
+                    "DELETE FROM session_leases WHERE session_id = ?1 AND owner_id = ?2",
+                    params![session_id, owner_id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Get the current lease of a session, expired or not
+    pub async fn get_lease(&self, session_id: &str) -> Result<Option<SessionLeaseRecord>> {
+        let session_id = session_id.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                let lease = conn
+                    .query_row(
+                        r#"
+                        SELECT session_id, owner_id, pid, hostname, acquired_at, heartbeat_at, expires_at
+                        FROM session_leases
+                        WHERE session_id = ?1
+                        "#,
+                        [&session_id],
+                        |row| {
+                            Ok(SessionLeaseRecord {
+                                session_id: row.get(0)?,
+                                owner_id: row.get(1)?,
+                                pid: row.get(2)?,
+                                hostname: row.get(3)?,
+                                acquired_at: row.get(4)?,
+                                heartbeat_at: row.get(5)?,
+                                expires_at: row.get(6)?,
+                            })
+                        },
+                    )
+                    .optional()?;
+                Ok(lease)
+            })
+            .await
+    }
+
     // =========================================================================
     // Source Entry Operations
     // =========================================================================
@@ -431,6 +570,8 @@ impl Repository {
     // =========================================================================
 
     /// Insert or update a translated entry
+    ///
+    /// Entries approved during human review are left untouched.
     pub async fn upsert_translated_entry(&self, entry: &TranslatedEntryRecord) -> Result<i64> {
         let entry = entry.clone();
 
@@ -449,6 +590,7 @@ impl Repository {
                         validation_errors = excluded.validation_errors,
                         attempt_count = translated_entries.attempt_count + 1,
                         updated_at = excluded.updated_at
+                    WHERE translated_entries.translation_status != 'approved'
                     "#,
                     params![
                         entry.source_entry_id,
@@ -467,7 +609,7 @@ impl Repository {
             .await
     }
 
-    /// Batch insert translated entries
+    /// Batch insert translated entries, leaving approved entries untouched
     pub async fn insert_translated_entries(&self, entries: Vec<TranslatedEntryRecord>) -> Result<()> {
         self.db
             .transaction_async(move |tx| {
@@ -485,6 +627,7 @@ impl Repository {
                             validation_errors = excluded.validation_errors,
                             attempt_count = translated_entries.attempt_count + 1,
                             updated_at = excluded.updated_at
+                        WHERE translated_entries.translation_status != 'approved'
                         "#,
                         params![
                             entry.source_entry_id,
@@ -558,6 +701,40 @@ impl Repository {
             .await
     }
 
+    /// Store human-approved translations, keyed by source entry ID
+    ///
+    /// Overwrites any machine translation and marks the entries approved,
+    /// keeping their quality score and attempt count.
+    pub async fn approve_translations(&self, approved: Vec<(i64, String)>) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.db
+            .transaction_async(move |tx| {
+                for (source_entry_id, translated_text) in approved {
+                    tx.execute(
+                        r#"
+                        INSERT INTO translated_entries (
+                            source_entry_id, translated_text, translation_status,
+                            attempt_count, created_at, updated_at
+                        ) VALUES (?1, ?2, ?3, 0, ?4, ?4)
+                        ON CONFLICT(source_entry_id) DO UPDATE SET
+                            translated_text = excluded.translated_text,
+                            translation_status = excluded.translation_status,
+                            updated_at = excluded.updated_at
+                        "#,
+                        params![
+                            source_entry_id,
+                            translated_text,
+                            TranslationStatus::Approved.to_string(),
+                            now,
+                        ],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
     /// Update translation status for an entry
     pub async fn update_translation_status(
         &self,
@@ -578,158 +755,765 @@ impl Repository {
     }
 
     // =========================================================================
-    // Cache Operations
+    // Revision Operations
     // =========================================================================
 
-    /// Compute SHA256 hash of text
-    pub fn hash_text(text: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(text.as_bytes());
-        format!("{:x}", hasher.finalize())
-    }
-
-    /// Get a cached translation
-    pub async fn get_cached_translation(
-        &self,
-        source_text: &str,
-        source_language: &str,
-        target_language: &str,
-        provider: &str,
-        model: &str,
-    ) -> Result<Option<String>> {
-        let source_text_hash = Self::hash_text(source_text);
-        let source_language = source_language.to_string();
-        let target_language = target_language.to_string();
-        let provider = provider.to_string();
-        let model = model.to_string();
-
+    /// Append translation revisions, numbering each after its entry's latest revision
+    pub async fn add_revisions(&self, revisions: Vec<TranslationRevisionRecord>) -> Result<()> {
         self.db
-            .execute_async(move |conn| {
-                let result: Option<(i64, String)> = conn
-                    .query_row(
+            .transaction_async(move |tx| {
+                for revision in revisions {
+                    tx.execute(
                         r#"
-                        SELECT id, translated_text
-                        FROM translation_cache
-                        WHERE source_text_hash = ?1
-                          AND source_language = ?2
-                          AND target_language = ?3
-                          AND provider = ?4
-                          AND model = ?5
+                        INSERT INTO translation_revisions (
+                            source_entry_id, revision, translated_text, origin, provider, model, detail,
+                            quality_score, validation_passed, validation_message, created_at
+                        )
+                        SELECT ?1, COALESCE(MAX(revision), 0) + 1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10
+                        FROM translation_revisions
+                        WHERE source_entry_id = ?1
                         "#,
-                        params![source_text_hash, source_language, target_language, provider, model],
-                        |row| Ok((row.get(0)?, row.get(1)?)),
-                    )
-                    .optional()?;
-
-                if let Some((id, translated_text)) = result {
-                    // Increment hit count
-                    conn.execute(
-                        "UPDATE translation_cache SET hit_count = hit_count + 1 WHERE id = ?1",
-                        [id],
+                        params![
+                            revision.source_entry_id,
+                            revision.translated_text,
+                            revision.origin.to_string(),
+                            revision.provider,
+                            revision.model,
+                            revision.detail,
+                            revision.quality_score,
+                            revision.validation_passed,
+                            revision.validation_message,
+                            revision.created_at,
+                        ],
                     )?;
-                    debug!("Cache hit for translation");
-                    Ok(Some(translated_text))
-                } else {
-                    Ok(None)
                 }
+                Ok(())
             })
             .await
     }
 
-    /// Store a translation in the cache
-    pub async fn cache_translation(&self, record: &CacheRecord) -> Result<()> {
-        let record = record.clone();
-
+    /// Get the revisions of an entry, oldest first
+    pub async fn get_revisions(&self, source_entry_id: i64) -> Result<Vec<TranslationRevisionRecord>> {
         self.db
             .execute_async(move |conn| {
-                conn.execute(
+                let mut stmt = conn.prepare(
                     r#"
-                    INSERT INTO translation_cache (
-                        source_text_hash, source_text, source_language, target_language,
-                        translated_text, provider, model, created_at, hit_count
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
-                    ON CONFLICT(source_text_hash, source_language, target_language, provider, model)
-                    DO UPDATE SET hit_count = translation_cache.hit_count + 1
+                    SELECT id, source_entry_id, revision, translated_text, origin, provider, model, detail,
+                           quality_score, validation_passed, validation_message, created_at
+                    FROM translation_revisions
+                    WHERE source_entry_id = ?1
+                    ORDER BY revision
                     "#,
-                    params![
-                        record.source_text_hash,
-                        record.source_text,
-                        record.source_language,
-                        record.target_language,
-                        record.translated_text,
-                        record.provider,
-                        record.model,
-                        record.created_at,
-                        record.hit_count,
-                    ],
                 )?;
-                Ok(())
-            })
-            .await
-    }
-
-    /// Get cache statistics
-    pub async fn get_cache_stats(&self) -> Result<CacheStats> {
-        self.db
-            .execute_async(|conn| {
-                let total_entries: i64 = conn
-                    .query_row("SELECT COUNT(*) FROM translation_cache", [], |row| row.get(0))
-                    .unwrap_or(0);
 
-                let total_hits: i64 = conn
-                    .query_row(
-                        "SELECT COALESCE(SUM(hit_count), 0) FROM translation_cache",
-                        [],
-                        |row| row.get(0),
-                    )
-                    .unwrap_or(0);
+                let rows = stmt.query_map([source_entry_id], |row| {
+                    Ok(TranslationRevisionRecord {
+                        id: row.get(0)?,
+                        source_entry_id: row.get(1)?,
+                        revision: row.get(2)?,
+                        translated_text: row.get(3)?,
+                        origin: row
+                            .get::<_, String>(4)?
+                            .parse()
+                            .unwrap_or(RevisionOrigin::Translation),
+                        provider: row.get(5)?,
+                        model: row.get(6)?,
+                        detail: row.get(7)?,
+                        quality_score: row.get(8)?,
+                        validation_passed: row.get(9)?,
+                        validation_message: row.get(10)?,
+                        created_at: row.get(11)?,
+                    })
+                })?;
 
-                Ok(CacheStats {
-                    total_entries,
-                    total_hits,
-                })
+                let revisions: Vec<TranslationRevisionRecord> = rows.filter_map(|r| r.ok()).collect();
+                Ok(revisions)
             })
             .await
     }
 
-    /// Clear the translation cache
-    pub async fn clear_cache(&self) -> Result<i64> {
+    // =========================================================================
+    // History Summary Operations
+    // =========================================================================
+
+    /// Save rolling history summaries for a session (batch upsert)
+    pub async fn save_history_summaries(&self, summaries: Vec<HistorySummaryRecord>) -> Result<()> {
         self.db
-            .execute_async(|conn| {
-                let deleted = conn.execute("DELETE FROM translation_cache", [])?;
-                Ok(deleted as i64)
+            .transaction_async(move |tx| {
+                for summary in summaries {
+                    tx.execute(
+                        r#"
+                        INSERT INTO history_summaries (
+                            session_id, start_seq_num, end_seq_num, entry_count, summary_text, created_at
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                        ON CONFLICT(session_id, start_seq_num) DO UPDATE SET
+                            end_seq_num = excluded.end_seq_num,
+                            entry_count = excluded.entry_count,
+                            summary_text = excluded.summary_text
+                        "#,
+                        params![
+                            summary.session_id,
+                            summary.start_seq_num,
+                            summary.end_seq_num,
+                            summary.entry_count,
+                            summary.summary_text,
+                            summary.created_at,
+                        ],
+                    )?;
+                }
+                Ok(())
             })
             .await
     }
 
-    /// Get recent cache entries for a language pair (for cache warming)
-    ///
-    /// Returns the most frequently used entries for the given language pair,
-    /// limited to the specified count.
-    pub async fn get_recent_cache_entries(
-        &self,
-        source_language: &str,
-        target_language: &str,
-        provider: &str,
-        model: &str,
-        limit: usize,
-    ) -> Result<Vec<CacheRecord>> {
-        let source_language = source_language.to_string();
-        let target_language = target_language.to_string();
-        let provider = provider.to_string();
-        let model = model.to_string();
+    /// Get rolling history summaries for a session, oldest first
+    pub async fn get_history_summaries(&self, session_id: &str) -> Result<Vec<HistorySummaryRecord>> {
+        let session_id = session_id.to_string();
 
         self.db
             .execute_async(move |conn| {
                 let mut stmt = conn.prepare(
                     r#"
-                    SELECT id, source_text_hash, source_text, source_language, target_language,
-                           translated_text, provider, model, created_at, hit_count
+                    SELECT id, session_id, start_seq_num, end_seq_num, entry_count, summary_text, created_at
+                    FROM history_summaries
+                    WHERE session_id = ?1
+                    ORDER BY start_seq_num
+                    "#,
+                )?;
+
+                let rows = stmt.query_map([&session_id], |row| {
+                    Ok(HistorySummaryRecord {
+                        id: row.get(0)?,
+                        session_id: row.get(1)?,
+                        start_seq_num: row.get(2)?,
+                        end_seq_num: row.get(3)?,
+                        entry_count: row.get(4)?,
+                        summary_text: row.get(5)?,
+                        created_at: row.get(6)?,
+                    })
+                })?;
+
+                let summaries: Vec<HistorySummaryRecord> = rows.filter_map(|r| r.ok()).collect();
+                Ok(summaries)
+            })
+            .await
+    }
+
+    // =========================================================================
+    // Glossary and Analysis Memory Operations
+    // =========================================================================
+
+    /// Save glossary terms of a session or series (batch upsert)
+    pub async fn save_glossary_terms(&self, terms: Vec<GlossaryTermRecord>) -> Result<()> {
+        self.db
+            .transaction_async(move |tx| {
+                for term in terms {
+                    tx.execute(
+                        r#"
+                        INSERT INTO glossary_terms (scope, scope_key, kind, source_term, target_term, context, updated_at)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                        ON CONFLICT(scope, scope_key, kind, source_term) DO UPDATE SET
+                            target_term = excluded.target_term,
+                            context = COALESCE(excluded.context, glossary_terms.context),
+                            updated_at = excluded.updated_at
+                        "#,
+                        params![
+                            term.scope.kind(),
+                            term.scope.key(),
+                            term.kind.as_str(),
+                            term.source_term,
+                            term.target_term,
+                            term.context,
+                            term.updated_at,
+                        ],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Get the glossary terms of a session or series, sorted by kind and source term
+    pub async fn get_glossary_terms(&self, scope: &AnalysisScope) -> Result<Vec<GlossaryTermRecord>> {
+        let scope = scope.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, kind, source_term, target_term, context, updated_at
+                    FROM glossary_terms
+                    WHERE scope = ?1 AND scope_key = ?2
+                    ORDER BY kind, source_term
+                    "#,
+                )?;
+
+                let rows = stmt.query_map(params![scope.kind(), scope.key()], |row| {
+                    Ok(GlossaryTermRecord {
+                        id: row.get(0)?,
+                        scope: scope.clone(),
+                        kind: row
+                            .get::<_, String>(1)?
+                            .parse()
+                            .unwrap_or(GlossaryTermKind::Term),
+                        source_term: row.get(2)?,
+                        target_term: row.get(3)?,
+                        context: row.get(4)?,
+                        updated_at: row.get(5)?,
+                    })
+                })?;
+
+                let terms: Vec<GlossaryTermRecord> = rows.filter_map(|r| r.ok()).collect();
+                Ok(terms)
+            })
+            .await
+    }
+
+    /// Save character profiles (batch upsert)
+    ///
+    /// The first sighting and notes of a known character are kept.
+    pub async fn save_character_profiles(&self, profiles: Vec<CharacterProfileRecord>) -> Result<()> {
+        self.db
+            .transaction_async(move |tx| {
+                for profile in profiles {
+                    tx.execute(
+                        r#"
+                        INSERT INTO character_profiles (scope, scope_key, name, mentions, first_seen, notes, updated_at)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                        ON CONFLICT(scope, scope_key, name) DO UPDATE SET
+                            mentions = excluded.mentions,
+                            first_seen = COALESCE(character_profiles.first_seen, excluded.first_seen),
+                            notes = COALESCE(character_profiles.notes, excluded.notes),
+                            updated_at = excluded.updated_at
+                        "#,
+                        params![
+                            profile.scope.kind(),
+                            profile.scope.key(),
+                            profile.name,
+                            profile.mentions,
+                            profile.first_seen,
+                            profile.notes,
+                            profile.updated_at,
+                        ],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Get the character profiles of a session or series, sorted by name
+    pub async fn get_character_profiles(&self, scope: &AnalysisScope) -> Result<Vec<CharacterProfileRecord>> {
+        let scope = scope.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, name, mentions, first_seen, notes, updated_at
+                    FROM character_profiles
+                    WHERE scope = ?1 AND scope_key = ?2
+                    ORDER BY name
+                    "#,
+                )?;
+
+                let rows = stmt.query_map(params![scope.kind(), scope.key()], |row| {
+                    Ok(CharacterProfileRecord {
+                        id: row.get(0)?,
+                        scope: scope.clone(),
+                        name: row.get(1)?,
+                        mentions: row.get(2)?,
+                        first_seen: row.get(3)?,
+                        notes: row.get(4)?,
+                        updated_at: row.get(5)?,
+                    })
+                })?;
+
+                let profiles: Vec<CharacterProfileRecord> = rows.filter_map(|r| r.ok()).collect();
+                Ok(profiles)
+            })
+            .await
+    }
+
+    /// Save an analysis summary, replacing the one with the same scope and label
+    pub async fn save_analysis_summary(&self, summary: &AnalysisSummaryRecord) -> Result<()> {
+        let summary = summary.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                conn.execute(
+                    r#"
+                    INSERT INTO analysis_summaries (scope, scope_key, label, summary_text, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT(scope, scope_key, label) DO UPDATE SET
+                        summary_text = excluded.summary_text,
+                        updated_at = excluded.updated_at
+                    "#,
+                    params![
+                        summary.scope.kind(),
+                        summary.scope.key(),
+                        summary.label,
+                        summary.summary_text,
+                        summary.updated_at,
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Get the analysis summaries of a session or series, sorted by label
+    pub async fn get_analysis_summaries(&self, scope: &AnalysisScope) -> Result<Vec<AnalysisSummaryRecord>> {
+        let scope = scope.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, label, summary_text, updated_at
+                    FROM analysis_summaries
+                    WHERE scope = ?1 AND scope_key = ?2
+                    ORDER BY label
+                    "#,
+                )?;
+
+                let rows = stmt.query_map(params![scope.kind(), scope.key()], |row| {
+                    Ok(AnalysisSummaryRecord {
+                        id: row.get(0)?,
+                        scope: scope.clone(),
+                        label: row.get(1)?,
+                        summary_text: row.get(2)?,
+                        updated_at: row.get(3)?,
+                    })
+                })?;
+
+                let summaries: Vec<AnalysisSummaryRecord> = rows.filter_map(|r| r.ok()).collect();
+                Ok(summaries)
+            })
+            .await
+    }
+
+    // =========================================================================
+    // Run Telemetry Operations
+    // =========================================================================
+
+    /// Insert the telemetry of translated batches
+    pub async fn insert_batch_telemetry(&self, records: Vec<BatchTelemetryRecord>) -> Result<()> {
+        self.db
+            .transaction_async(move |tx| {
+                for record in records {
+                    let batch = &record.batch;
+                    tx.execute(
+                        r#"
+                        INSERT INTO batch_telemetry (
+                            session_id, run_id, phase, batch_index, entry_count, started_at,
+                            duration_ms, requests, cache_hits, prompt_tokens, completion_tokens,
+                            cost_usd, latencies_ms, retries, issues
+                        )
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                        "#,
+                        params![
+                            record.session_id,
+                            record.run_id,
+                            batch.phase,
+                            batch.batch_index as i64,
+                            batch.entry_count as i64,
+                            batch.started_at,
+                            batch.duration_ms as i64,
+                            batch.requests as i64,
+                            batch.cache_hits as i64,
+                            batch.prompt_tokens as i64,
+                            batch.completion_tokens as i64,
+                            batch.cost_usd,
+                            serde_json::to_string(&batch.latencies_ms)?,
+                            serde_json::to_string(&batch.retries)?,
+                            serde_json::to_string(&batch.issues)?,
+                        ],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Get the telemetry of a session's batches, in the order they were recorded
+    pub async fn get_batch_telemetry(&self, session_id: &str) -> Result<Vec<BatchTelemetryRecord>> {
+        let session_id = session_id.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, session_id, run_id, phase, batch_index, entry_count, started_at,
+                           duration_ms, requests, cache_hits, prompt_tokens, completion_tokens,
+                           cost_usd, latencies_ms, retries, issues
+                    FROM batch_telemetry
+                    WHERE session_id = ?1
+                    ORDER BY id
+                    "#,
+                )?;
+
+                let rows = stmt.query_map([&session_id], |row| {
+                    let record = BatchTelemetryRecord {
+                        id: row.get(0)?,
+                        session_id: row.get(1)?,
+                        run_id: row.get(2)?,
+                        batch: BatchTelemetry {
+                            phase: row.get(3)?,
+                            batch_index: row.get::<_, i64>(4)? as usize,
+                            entry_count: row.get::<_, i64>(5)? as usize,
+                            started_at: row.get(6)?,
+                            duration_ms: row.get::<_, i64>(7)? as u64,
+                            requests: row.get::<_, i64>(8)? as usize,
+                            cache_hits: row.get::<_, i64>(9)? as usize,
+                            prompt_tokens: row.get::<_, i64>(10)? as u64,
+                            completion_tokens: row.get::<_, i64>(11)? as u64,
+                            cost_usd: row.get(12)?,
+                            latencies_ms: Vec::new(),
+                            retries: Default::default(),
+                            issues: Default::default(),
+                        },
+                    };
+                    let json_columns: (String, String, String) = (row.get(13)?, row.get(14)?, row.get(15)?);
+                    Ok((record, json_columns))
+                })?;
+
+                let mut records = Vec::new();
+                for row in rows {
+                    let (mut record, (latencies, retries, issues)) = row?;
+                    record.batch.latencies_ms = serde_json::from_str(&latencies).unwrap_or_default();
+                    record.batch.retries = serde_json::from_str(&retries).unwrap_or_default();
+                    record.batch.issues = serde_json::from_str(&issues).unwrap_or_default();
+                    records.push(record);
+                }
+                Ok(records)
+            })
+            .await
+    }
+
+    // =========================================================================
+    // Cache Operations
+    // =========================================================================
+
+    /// Compute SHA256 hash of text
+    pub fn hash_text(text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(text.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Get a cached translation made without any context
+    pub async fn get_cached_translation(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+    ) -> Result<Option<String>> {
+        self.get_cached_translation_in_context(source_text, source_language, target_language, provider, model, "")
+            .await
+    }
+
+    /// Get a cached translation made in the given context (empty = any context)
+    pub async fn get_cached_translation_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        context_key: &str,
+    ) -> Result<Option<String>> {
+        let source_text_hash = Self::hash_text(source_text);
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        let provider = provider.to_string();
+        let model = model.to_string();
+        let context_key = context_key.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.db
+            .execute_async(move |conn| {
+                let result: Option<(i64, String)> = conn
+                    .query_row(
+                        r#"
+                        SELECT id, translated_text
+                        FROM translation_cache
+                        WHERE source_text_hash = ?1
+                          AND source_language = ?2
+                          AND target_language = ?3
+                          AND provider = ?4
+                          AND model = ?5
+                          AND context_key = ?6
+                        "#,
+                        params![source_text_hash, source_language, target_language, provider, model, context_key],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+
+                if let Some((id, translated_text)) = result {
+                    // Increment hit count
+                    conn.execute(
+                        "UPDATE translation_cache SET hit_count = hit_count + 1, last_used_at = ?1 WHERE id = ?2",
+                        params![now, id],
+                    )?;
+                    debug!("Cache hit for translation");
+                    Ok(Some(translated_text))
+                } else {
+                    Ok(None)
+                }
+            })
+            .await
+    }
+
+    /// Store a translation in the cache
+    pub async fn cache_translation(&self, record: &CacheRecord) -> Result<()> {
+        let record = record.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                conn.execute(
+                    r#"
+                    INSERT INTO translation_cache (
+                        source_text_hash, source_text, source_language, target_language,
+                        translated_text, provider, model, context_key, created_at, last_used_at, hit_count
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                    ON CONFLICT(source_text_hash, source_language, target_language, provider, model, context_key)
+                    DO UPDATE SET
+                        hit_count = translation_cache.hit_count + 1,
+                        last_used_at = excluded.last_used_at
+                    "#,
+                    params![
+                        record.source_text_hash,
+                        record.source_text,
+                        record.source_language,
+                        record.target_language,
+                        record.translated_text,
+                        record.provider,
+                        record.model,
+                        record.context_key,
+                        record.created_at,
+                        record.last_used_at,
+                        record.hit_count,
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Store a translation in the cache, replacing any cached translation of the same text
+    ///
+    /// Used for human corrections, which must win over earlier machine output.
+    pub async fn replace_cached_translation(&self, record: &CacheRecord) -> Result<()> {
+        self.import_cache_entries(vec![record.clone()]).await?;
+        Ok(())
+    }
+
+    /// Store cache entries, replacing the translation of entries that already exist
+    ///
+    /// Returns the number of entries written.
+    pub async fn import_cache_entries(&self, records: Vec<CacheRecord>) -> Result<usize> {
+        self.db
+            .transaction_async(move |tx| {
+                for record in &records {
+                    tx.execute(
+                        r#"
+                        INSERT INTO translation_cache (
+                            source_text_hash, source_text, source_language, target_language,
+                            translated_text, provider, model, context_key, created_at, last_used_at, hit_count
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                        ON CONFLICT(source_text_hash, source_language, target_language, provider, model, context_key)
+                        DO UPDATE SET
+                            translated_text = excluded.translated_text,
+                            last_used_at = MAX(translation_cache.last_used_at, excluded.last_used_at)
+                        "#,
+                        params![
+                            record.source_text_hash,
+                            record.source_text,
+                            record.source_language,
+                            record.target_language,
+                            record.translated_text,
+                            record.provider,
+                            record.model,
+                            record.context_key,
+                            record.created_at,
+                            record.last_used_at,
+                            record.hit_count,
+                        ],
+                    )?;
+                }
+                Ok(records.len())
+            })
+            .await
+    }
+
+    /// Get cache statistics
+    pub async fn get_cache_stats(&self) -> Result<CacheStats> {
+        self.db
+            .execute_async(|conn| {
+                let (total_entries, total_hits, contextual_entries, oldest_use, newest_use) = conn.query_row(
+                    r#"
+                    SELECT COUNT(*), COALESCE(SUM(hit_count), 0),
+                           COALESCE(SUM(context_key != ''), 0),
+                           MIN(last_used_at), MAX(last_used_at)
+                    FROM translation_cache
+                    "#,
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+                )?;
+
+                Ok(CacheStats {
+                    total_entries,
+                    total_hits,
+                    contextual_entries,
+                    oldest_use,
+                    newest_use,
+                })
+            })
+            .await
+    }
+
+    /// Clear the translation cache
+    pub async fn clear_cache(&self) -> Result<i64> {
+        self.db
+            .execute_async(|conn| {
+                let deleted = conn.execute("DELETE FROM translation_cache", [])?;
+                Ok(deleted as i64)
+            })
+            .await
+    }
+
+    /// Evict expired entries, then the least recently used entries above the size limit
+    pub async fn prune_cache(&self, policy: &CacheEvictionPolicy) -> Result<CacheEvictionResult> {
+        let cutoff = (policy.max_age_days > 0)
+            .then(|| (chrono::Utc::now() - chrono::Duration::days(policy.max_age_days as i64)).to_rfc3339());
+        let max_entries = policy.max_entries as i64;
+
+        self.db
+            .transaction_async(move |tx| {
+                let expired = match cutoff {
+                    Some(cutoff) => tx.execute("DELETE FROM translation_cache WHERE last_used_at < ?1", [cutoff])?,
+                    None => 0,
+                };
+
+                let evicted = if max_entries > 0 {
+                    tx.execute(
+                        r#"
+                        DELETE FROM translation_cache
+                        WHERE id IN (
+                            SELECT id FROM translation_cache
+                            ORDER BY last_used_at DESC, hit_count DESC
+                            LIMIT -1 OFFSET ?1
+                        )
+                        "#,
+                        [max_entries],
+                    )?
+                } else {
+                    0
+                };
+
+                Ok(CacheEvictionResult {
+                    expired: expired as i64,
+                    evicted: evicted as i64,
+                })
+            })
+            .await
+    }
+
+    /// List cache entries matching a query, most recently used first
+    pub async fn list_cache_entries(&self, query: &CacheQuery) -> Result<Vec<CacheRecord>> {
+        let query = query.clone();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, source_text_hash, source_text, source_language, target_language,
+                           translated_text, provider, model, context_key, created_at, last_used_at, hit_count
+                    FROM translation_cache
+                    WHERE (?1 IS NULL OR source_language = ?1)
+                      AND (?2 IS NULL OR target_language = ?2)
+                      AND (?3 IS NULL OR source_text LIKE '%' || ?3 || '%' OR translated_text LIKE '%' || ?3 || '%')
+                    ORDER BY last_used_at DESC, id DESC
+                    LIMIT ?4
+                    "#,
+                )?;
+
+                let limit = query.limit.map(|l| l as i64).unwrap_or(-1);
+                let records = stmt
+                    .query_map(
+                        params![query.source_language, query.target_language, query.search, limit],
+                        cache_record_from_row,
+                    )?
+                    .filter_map(|r| r.ok())
+                    .collect();
+
+                Ok(records)
+            })
+            .await
+    }
+
+    /// Get the cache entries for the source lines of a session, in any context
+    pub async fn get_session_cache_entries(&self, session_id: &str) -> Result<Vec<CacheRecord>> {
+        let session_id = session_id.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT c.id, c.source_text_hash, c.source_text, c.source_language, c.target_language,
+                           c.translated_text, c.provider, c.model, c.context_key, c.created_at,
+                           c.last_used_at, c.hit_count
+                    FROM translation_cache c
+                    JOIN sessions s
+                      ON s.source_language = c.source_language AND s.target_language = c.target_language
+                    WHERE s.id = ?1
+                      AND c.source_text IN (SELECT source_text FROM source_entries WHERE session_id = ?1)
+                    ORDER BY c.id
+                    "#,
+                )?;
+
+                let records = stmt
+                    .query_map([&session_id], cache_record_from_row)?
+                    .filter_map(|r| r.ok())
+                    .collect();
+
+                Ok(records)
+            })
+            .await
+    }
+
+    /// Get recent cache entries for a language pair (for cache warming)
+    ///
+    /// Returns the most frequently used context-free entries for the given
+    /// language pair, limited to the specified count.
+    pub async fn get_recent_cache_entries(
+        &self,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<CacheRecord>> {
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        let provider = provider.to_string();
+        let model = model.to_string();
+
+        self.db
+            .execute_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT id, source_text_hash, source_text, source_language, target_language,
+                           translated_text, provider, model, context_key, created_at, last_used_at, hit_count
                     FROM translation_cache
                     WHERE source_language = ?1
                       AND target_language = ?2
                       AND provider = ?3
                       AND model = ?4
+                      AND context_key = ''
                     ORDER BY hit_count DESC, created_at DESC
                     LIMIT ?5
                     "#,
@@ -738,20 +1522,7 @@ impl Repository {
                 let records = stmt
                     .query_map(
                         params![source_language, target_language, provider, model, limit as i64],
-                        |row| {
-                            Ok(CacheRecord {
-                                id: row.get(0)?,
-                                source_text_hash: row.get(1)?,
-                                source_text: row.get(2)?,
-                                source_language: row.get(3)?,
-                                target_language: row.get(4)?,
-                                translated_text: row.get(5)?,
-                                provider: row.get(6)?,
-                                model: row.get(7)?,
-                                created_at: row.get(8)?,
-                                hit_count: row.get(9)?,
-                            })
-                        },
+                        cache_record_from_row,
                     )?
                     .filter_map(|r| r.ok())
                     .collect();
@@ -837,11 +1608,136 @@ pub struct CacheStats {
     pub total_entries: i64,
     /// Total number of cache hits
     pub total_hits: i64,
+    /// Entries keyed by a translation context
+    pub contextual_entries: i64,
+    /// Oldest last use (None if the cache is empty)
+    pub oldest_use: Option<String>,
+    /// Most recent last use
+    pub newest_use: Option<String>,
+}
+
+/// Limits applied when pruning the translation cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheEvictionPolicy {
+    /// Evict entries not used for this many days (0 = never expire)
+    pub max_age_days: u32,
+    /// Keep at most this many entries, evicting the least recently used (0 = unlimited)
+    pub max_entries: usize,
+}
+
+impl CacheEvictionPolicy {
+    /// Check whether the policy never evicts anything
+    pub fn is_disabled(&self) -> bool {
+        self.max_age_days == 0 && self.max_entries == 0
+    }
+}
+
+/// Number of entries removed by a prune
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheEvictionResult {
+    /// Entries removed because they expired
+    pub expired: i64,
+    /// Entries removed to respect the size limit
+    pub evicted: i64,
+}
+
+impl CacheEvictionResult {
+    /// Total number of entries removed
+    pub fn total(&self) -> i64 {
+        self.expired + self.evicted
+    }
+}
+
+/// Filter for listing cache entries
+#[derive(Debug, Clone, Default)]
+pub struct CacheQuery {
+    /// Only entries from this source language
+    pub source_language: Option<String>,
+    /// Only entries into this target language
+    pub target_language: Option<String>,
+    /// Only entries whose source or translated text contains this string
+    pub search: Option<String>,
+    /// Maximum number of entries (None = all)
+    pub limit: Option<usize>,
+}
+
+/// What a resumable session is matched on
+#[derive(Debug, Clone, Default)]
+pub struct ResumableSessionQuery {
+    /// Path of the source file
+    pub source_file_path: String,
+    /// SHA256 hash of the source file
+    pub source_file_hash: String,
+    /// SHA256 hash of the extracted subtitle entries (empty = not matched on)
+    pub source_content_hash: String,
+    /// Source language code
+    pub source_language: String,
+    /// Target language code
+    pub target_language: String,
+    /// Translation provider
+    pub provider: String,
+    /// Model
+    pub model: String,
+}
+
+impl ResumableSessionQuery {
+    /// Check whether a session has the subtitle content this query is for
+    ///
+    /// Sessions from before content hashing are compared on the file hash.
+    pub fn matches_content(&self, session: &SessionRecord) -> bool {
+        if session.source_content_hash.is_empty() || self.source_content_hash.is_empty() {
+            session.source_file_hash == self.source_file_hash
+        } else {
+            session.source_content_hash == self.source_content_hash
+        }
+    }
+}
+
+/// Map a `sessions` row selected with all columns to a record
+fn session_record_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionRecord> {
+    Ok(SessionRecord {
+        id: row.get(0)?,
+        source_file_path: row.get(1)?,
+        source_file_hash: row.get(2)?,
+        source_language: row.get(3)?,
+        target_language: row.get(4)?,
+        provider: row.get(5)?,
+        model: row.get(6)?,
+        total_entries: row.get(7)?,
+        completed_entries: row.get(8)?,
+        status: row
+            .get::<_, String>(9)?
+            .parse()
+            .unwrap_or(SessionStatus::InProgress),
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        completed_at: row.get(12)?,
+        source_content_hash: row.get(13)?,
+    })
+}
+
+/// Map a `translation_cache` row selected with all columns to a record
+fn cache_record_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CacheRecord> {
+    Ok(CacheRecord {
+        id: row.get(0)?,
+        source_text_hash: row.get(1)?,
+        source_text: row.get(2)?,
+        source_language: row.get(3)?,
+        target_language: row.get(4)?,
+        translated_text: row.get(5)?,
+        provider: row.get(6)?,
+        model: row.get(7)?,
+        context_key: row.get(8)?,
+        created_at: row.get(9)?,
+        last_used_at: row.get(10)?,
+        hit_count: row.get(11)?,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::models::lease_timestamp;
 
     async fn create_test_repo() -> Repository {
         Repository::new_in_memory().expect("Failed to create test repository")
@@ -895,7 +1791,7 @@ mod tests {
         repo.create_session(&session).await.expect("Failed to create session");
 
         let found = repo
-            .find_resumable_session("file-hash-123", "en", "fr", "ollama", "llama2")
+            .find_resumable_session(&resumable_query("/other/path.mkv", "file-hash-123", ""))
             .await
             .expect("Failed to find session");
 
@@ -903,6 +1799,58 @@ mod tests {
         assert_eq!(found.unwrap().id, "resumable-session");
     }
 
+    fn resumable_query(path: &str, file_hash: &str, content_hash: &str) -> ResumableSessionQuery {
+        ResumableSessionQuery {
+            source_file_path: path.to_string(),
+            source_file_hash: file_hash.to_string(),
+            source_content_hash: content_hash.to_string(),
+            source_language: "en".to_string(),
+            target_language: "fr".to_string(),
+            provider: "ollama".to_string(),
+            model: "llama2".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_findResumableSession_shouldPreferSameContentOverSamePath() {
+        let repo = create_test_repo().await;
+        let new_session = |id: &str, path: &str, content_hash: &str| {
+            SessionRecord::new(
+                id.to_string(),
+                path.to_string(),
+                format!("file-{}", id),
+                "en".to_string(),
+                "fr".to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+                10,
+            )
+            .with_content_hash(content_hash.to_string())
+        };
+        repo.create_session(&new_session("edited", "/movies/a.srt", "content-1")).await.unwrap();
+        repo.create_session(&new_session("remuxed", "/movies/a.mkv", "content-2")).await.unwrap();
+
+        let same_content = repo
+            .find_resumable_session(&resumable_query("/movies/a.srt", "file-new", "content-2"))
+            .await
+            .unwrap();
+        assert_eq!(same_content.unwrap().id, "remuxed");
+
+        let same_path = repo
+            .find_resumable_session(&resumable_query("/movies/a.srt", "file-new", "content-3"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(same_path.id, "edited");
+        assert!(!resumable_query("/movies/a.srt", "file-new", "content-3").matches_content(&same_path));
+
+        let unrelated = repo
+            .find_resumable_session(&resumable_query("/movies/b.srt", "file-new", "content-3"))
+            .await
+            .unwrap();
+        assert!(unrelated.is_none());
+    }
+
     #[tokio::test]
     async fn test_insertSourceEntries_shouldInsertAll() {
         let repo = create_test_repo().await;
@@ -1086,6 +2034,413 @@ mod tests {
         assert_eq!(pending[1].source_text, "Three");
     }
 
+    #[tokio::test]
+    async fn test_saveHistorySummaries_shouldUpsertAndReturnInOrder() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "summary-test".to_string(),
+            "/path/to/video.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            100,
+        );
+        repo.create_session(&session).await.unwrap();
+
+        let summaries = vec![
+            HistorySummaryRecord::new("summary-test".to_string(), 51, 100, 50, "Second.".to_string()),
+            HistorySummaryRecord::new("summary-test".to_string(), 1, 50, 50, "First.".to_string()),
+        ];
+        repo.save_history_summaries(summaries).await.unwrap();
+
+        // Saving the same range again replaces the text
+        let updated = vec![HistorySummaryRecord::new(
+            "summary-test".to_string(),
+            1,
+            50,
+            50,
+            "First, revised.".to_string(),
+        )];
+        repo.save_history_summaries(updated).await.unwrap();
+
+        let retrieved = repo.get_history_summaries("summary-test").await.unwrap();
+        assert_eq!(retrieved.len(), 2);
+        assert_eq!(retrieved[0].summary_text, "First, revised.");
+        assert_eq!(retrieved[1].start_seq_num, 51);
+    }
+
+    #[tokio::test]
+    async fn test_insertBatchTelemetry_shouldRoundTripRunsInOrder() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "telemetry-test".to_string(),
+            "/path/to/video.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "openai".to_string(),
+            "gpt-4o-mini".to_string(),
+            10,
+        );
+        repo.create_session(&session).await.unwrap();
+
+        let batch = BatchTelemetry {
+            phase: "translation".to_string(),
+            batch_index: 0,
+            entry_count: 10,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            duration_ms: 1200,
+            requests: 2,
+            cache_hits: 1,
+            prompt_tokens: 300,
+            completion_tokens: 120,
+            cost_usd: Some(0.0002),
+            latencies_ms: vec![500, 650],
+            retries: [("rate_limit".to_string(), 1)].into_iter().collect(),
+            issues: Default::default(),
+        };
+        let validation = BatchTelemetry {
+            phase: "validation".to_string(),
+            cost_usd: None,
+            latencies_ms: Vec::new(),
+            retries: Default::default(),
+            issues: [("length_too_long".to_string(), 2)].into_iter().collect(),
+            ..batch.clone()
+        };
+        repo.insert_batch_telemetry(vec![
+            BatchTelemetryRecord::new("telemetry-test".to_string(), "run-1".to_string(), batch.clone()),
+            BatchTelemetryRecord::new("telemetry-test".to_string(), "run-1".to_string(), validation.clone()),
+        ])
+        .await
+        .unwrap();
+
+        let retrieved = repo.get_batch_telemetry("telemetry-test").await.unwrap();
+        assert_eq!(retrieved.len(), 2);
+        assert_eq!(retrieved[0].run_id, "run-1");
+        assert_eq!(retrieved[0].batch, batch);
+        assert_eq!(retrieved[1].batch, validation);
+    }
+
+    #[tokio::test]
+    async fn test_acquireLease_shouldExcludeOtherOwnersUntilExpired() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "lease-test".to_string(),
+            "/path/to/video.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            10,
+        );
+        repo.create_session(&session).await.unwrap();
+
+        let lease = |owner: &str, ttl_ms: i64| {
+            SessionLeaseRecord::new(
+                "lease-test".to_string(),
+                owner.to_string(),
+                42,
+                "host".to_string(),
+                chrono::Duration::milliseconds(ttl_ms),
+            )
+        };
+
+        assert!(repo.acquire_lease(&lease("first", 60_000)).await.unwrap());
+        assert!(repo.acquire_lease(&lease("first", 60_000)).await.unwrap());
+        assert!(!repo.acquire_lease(&lease("second", 60_000)).await.unwrap());
+        assert!(!repo.renew_lease("lease-test", "second", "2000-01-01T00:00:00.000Z", "2999-01-01T00:00:00.000Z").await.unwrap());
+
+        // An expired lease is taken over by the next owner
+        let past = lease_timestamp(chrono::Utc::now() - chrono::Duration::seconds(1));
+        assert!(repo.renew_lease("lease-test", "first", &past, &past).await.unwrap());
+        assert!(repo.acquire_lease(&lease("second", 60_000)).await.unwrap());
+        assert_eq!(repo.get_lease("lease-test").await.unwrap().unwrap().owner_id, "second");
+
+        // Releasing only removes the owner's own lease
+        repo.release_lease("lease-test", "first").await.unwrap();
+        assert!(repo.get_lease("lease-test").await.unwrap().is_some());
+        repo.release_lease("lease-test", "second").await.unwrap();
+        assert!(repo.get_lease("lease-test").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_analysisMemory_shouldIsolateScopesAndDropSessionRowsWithSession() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "memory-test".to_string(),
+            "/path/to/episode01.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            10,
+        );
+        repo.create_session(&session).await.unwrap();
+        let session_scope = AnalysisScope::Session("memory-test".to_string());
+        let series_scope = AnalysisScope::Series("Show".to_string());
+
+        for scope in [&session_scope, &series_scope] {
+            repo.save_glossary_terms(vec![
+                GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Term, "Ring".to_string(), Some("Anneau".to_string())),
+                GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Character, "Frodo".to_string(), None),
+            ])
+            .await
+            .unwrap();
+            repo.save_character_profiles(vec![CharacterProfileRecord::new(scope.clone(), "Frodo".to_string(), 3, Some("episode01".to_string()))])
+                .await
+                .unwrap();
+            repo.save_analysis_summary(&AnalysisSummaryRecord::new(scope.clone(), "episode01".to_string(), "A journey".to_string()))
+                .await
+                .unwrap();
+        }
+
+        // Updates keep the first sighting and replace the summary
+        repo.save_character_profiles(vec![CharacterProfileRecord::new(series_scope.clone(), "Frodo".to_string(), 7, Some("episode02".to_string()))])
+            .await
+            .unwrap();
+        repo.save_analysis_summary(&AnalysisSummaryRecord::new(series_scope.clone(), "episode01".to_string(), "A long journey".to_string()))
+            .await
+            .unwrap();
+
+        let glossary = repo.get_glossary_terms(&series_scope).await.unwrap();
+        assert_eq!(glossary.len(), 2);
+        assert_eq!(glossary[0].kind, GlossaryTermKind::Character);
+        assert_eq!(glossary[1].target_term.as_deref(), Some("Anneau"));
+        let profiles = repo.get_character_profiles(&series_scope).await.unwrap();
+        assert_eq!(profiles[0].mentions, 7);
+        assert_eq!(profiles[0].first_seen.as_deref(), Some("episode01"));
+        assert_eq!(repo.get_analysis_summaries(&series_scope).await.unwrap()[0].summary_text, "A long journey");
+        assert_eq!(repo.get_character_profiles(&session_scope).await.unwrap()[0].mentions, 3);
+
+        repo.delete_session("memory-test").await.unwrap();
+
+        assert!(repo.get_glossary_terms(&session_scope).await.unwrap().is_empty());
+        assert!(repo.get_character_profiles(&session_scope).await.unwrap().is_empty());
+        assert!(repo.get_analysis_summaries(&session_scope).await.unwrap().is_empty());
+        assert_eq!(repo.get_glossary_terms(&series_scope).await.unwrap().len(), 2);
+        assert_eq!(repo.get_analysis_summaries(&series_scope).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_approveTranslations_shouldSurviveLaterMachineTranslations() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "approve-test".to_string(),
+            "/path/to/video.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            2,
+        );
+        repo.create_session(&session).await.unwrap();
+        repo.insert_source_entries(vec![
+            SourceEntryRecord::new("approve-test".to_string(), 1, 0, 1000, "Hello".to_string()),
+            SourceEntryRecord::new("approve-test".to_string(), 2, 1000, 2000, "World".to_string()),
+        ])
+        .await
+        .unwrap();
+        let source_entries = repo.get_source_entries("approve-test").await.unwrap();
+
+        repo.insert_translated_entries(vec![TranslatedEntryRecord::new(source_entries[0].id, "Allo".to_string())])
+            .await
+            .unwrap();
+        repo.approve_translations(vec![
+            (source_entries[0].id, "Bonjour".to_string()),
+            (source_entries[1].id, "Monde".to_string()),
+        ])
+        .await
+        .unwrap();
+
+        // A later machine run must not overwrite the approved text
+        repo.insert_translated_entries(vec![TranslatedEntryRecord::new(source_entries[0].id, "Salut".to_string())])
+            .await
+            .unwrap();
+
+        let translated = repo.get_translated_entries("approve-test").await.unwrap();
+        assert_eq!(translated.len(), 2);
+        assert_eq!(translated[0].1.translated_text, "Bonjour");
+        assert_eq!(translated[0].1.translation_status, TranslationStatus::Approved);
+        assert_eq!(translated[1].1.translated_text, "Monde");
+        assert!(repo.get_pending_entries("approve-test").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_addRevisions_shouldNumberRevisionsPerEntry() {
+        let repo = create_test_repo().await;
+
+        let session = SessionRecord::new(
+            "revision-test".to_string(),
+            "/path/to/video.mkv".to_string(),
+            "hash".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            2,
+        );
+        repo.create_session(&session).await.unwrap();
+        repo.insert_source_entries(vec![
+            SourceEntryRecord::new("revision-test".to_string(), 1, 0, 1000, "Hello".to_string()),
+            SourceEntryRecord::new("revision-test".to_string(), 2, 1000, 2000, "World".to_string()),
+        ])
+        .await
+        .unwrap();
+        let source_entries = repo.get_source_entries("revision-test").await.unwrap();
+
+        let mut first = TranslationRevisionRecord::new(source_entries[0].id, "Allo".to_string(), RevisionOrigin::Translation);
+        first.provider = Some("ollama".to_string());
+        first.quality_score = Some(0.4);
+        first.validation_passed = Some(false);
+        first.validation_message = Some("Low confidence".to_string());
+        let other = TranslationRevisionRecord::new(source_entries[1].id, "Monde".to_string(), RevisionOrigin::Translation);
+        repo.add_revisions(vec![first, other]).await.unwrap();
+
+        let mut retry = TranslationRevisionRecord::new(source_entries[0].id, "Bonjour".to_string(), RevisionOrigin::FeedbackRetry);
+        retry.detail = Some("Provide a more confident translation".to_string());
+        repo.add_revisions(vec![retry]).await.unwrap();
+
+        let revisions = repo.get_revisions(source_entries[0].id).await.unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!((revisions[0].revision, revisions[1].revision), (1, 2));
+        assert_eq!(revisions[0].provider.as_deref(), Some("ollama"));
+        assert_eq!(revisions[0].validation_passed, Some(false));
+        assert_eq!(revisions[1].origin, RevisionOrigin::FeedbackRetry);
+        assert_eq!(revisions[1].translated_text, "Bonjour");
+        assert!(revisions[1].validation_passed.is_none());
+
+        let revisions = repo.get_revisions(source_entries[1].id).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].revision, 1);
+    }
+
+    #[tokio::test]
+    async fn test_replaceCachedTranslation_shouldOverrideCachedText() {
+        let repo = create_test_repo().await;
+        let record = |text: &str| {
+            CacheRecord::new(
+                Repository::hash_text("Hello"),
+                "Hello".to_string(),
+                "en".to_string(),
+                "fr".to_string(),
+                text.to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+            )
+        };
+
+        repo.cache_translation(&record("Allo")).await.unwrap();
+        repo.replace_cached_translation(&record("Bonjour")).await.unwrap();
+
+        let cached = repo
+            .get_cached_translation("Hello", "en", "fr", "ollama", "llama2")
+            .await
+            .unwrap();
+        assert_eq!(cached, Some("Bonjour".to_string()));
+    }
+
+    fn cache_record(source: &str, translated: &str, context_key: &str, last_used_at: &str) -> CacheRecord {
+        let mut record = CacheRecord::new(
+            Repository::hash_text(source),
+            source.to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            translated.to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+        );
+        record.context_key = context_key.to_string();
+        record.last_used_at = last_used_at.to_string();
+        record
+    }
+
+    #[tokio::test]
+    async fn test_getCachedTranslationInContext_shouldSeparateContexts() {
+        let repo = create_test_repo().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        repo.cache_translation(&cache_record("I'm ready.", "Je suis prête.", "speaker=anna", &now))
+            .await
+            .unwrap();
+        repo.cache_translation(&cache_record("I'm ready.", "Je suis prêt.", "speaker=tom", &now))
+            .await
+            .unwrap();
+
+        let lookup = |context: &'static str| {
+            repo.get_cached_translation_in_context("I'm ready.", "en", "fr", "ollama", "llama2", context)
+        };
+        assert_eq!(lookup("speaker=anna").await.unwrap(), Some("Je suis prête.".to_string()));
+        assert_eq!(lookup("speaker=tom").await.unwrap(), Some("Je suis prêt.".to_string()));
+        assert_eq!(lookup("").await.unwrap(), None);
+
+        let stats = repo.get_cache_stats().await.unwrap();
+        assert_eq!(stats.total_entries, 2);
+        assert_eq!(stats.contextual_entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pruneCache_shouldEvictExpiredThenLeastRecentlyUsed() {
+        let repo = create_test_repo().await;
+        let days_ago = |days: i64| (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        repo.import_cache_entries(vec![
+            cache_record("Old", "Vieux", "", &days_ago(400)),
+            cache_record("A", "A", "", &days_ago(3)),
+            cache_record("B", "B", "", &days_ago(2)),
+            cache_record("C", "C", "", &days_ago(1)),
+        ])
+        .await
+        .unwrap();
+
+        let policy = CacheEvictionPolicy {
+            max_age_days: 365,
+            max_entries: 2,
+        };
+        let result = repo.prune_cache(&policy).await.unwrap();
+
+        assert_eq!((result.expired, result.evicted), (1, 1));
+        let remaining: Vec<String> = repo
+            .list_cache_entries(&CacheQuery::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.source_text)
+            .collect();
+        assert_eq!(remaining, vec!["C".to_string(), "B".to_string()]);
+
+        let unchanged = repo.prune_cache(&CacheEvictionPolicy::default()).await.unwrap();
+        assert_eq!(unchanged.total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_listCacheEntries_withSearch_shouldFilterEntries() {
+        let repo = create_test_repo().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        repo.import_cache_entries(vec![
+            cache_record("Hello", "Bonjour", "", &now),
+            cache_record("Goodbye", "Au revoir", "", &now),
+        ])
+        .await
+        .unwrap();
+
+        let query = CacheQuery {
+            search: Some("revoir".to_string()),
+            ..Default::default()
+        };
+        let entries = repo.list_cache_entries(&query).await.unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].source_text, "Goodbye");
+    }
+
     #[tokio::test]
     async fn test_updateSessionStatus_shouldChangeStatus() {
         let repo = create_test_repo().await;
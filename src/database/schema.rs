@@ -3,36 +3,211 @@
  *
  * This module contains the SQL schema for all database tables
  * and handles schema migrations for version upgrades.
+ *
+ * Fresh databases are created at the current version directly. Existing
+ * databases are upgraded through the ordered `MIGRATIONS` registry: each
+ * migration runs in its own transaction together with the version bump,
+ * so an interrupted upgrade leaves the database at the last completed
+ * version. To add a schema change, bump `SCHEMA_VERSION`, update
+ * `create_all_tables` and append a migration.
  */
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
 use log::{debug, info};
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 /// Current schema version
-pub const SCHEMA_VERSION: i32 = 1;
+pub const SCHEMA_VERSION: i32 = 9;
+
+/// A schema migration from `version - 1` to `version`
+pub struct Migration {
+    /// Version the migration upgrades to
+    pub version: i32,
+    /// What the migration changes
+    pub description: &'static str,
+    /// Apply the migration (runs inside a transaction)
+    apply: fn(&Connection) -> Result<()>,
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}: {}", self.version, self.description)
+    }
+}
+
+/// All migrations, in version order
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        description: "rolling history summaries per session",
+        apply: migrate_v1_to_v2,
+    },
+    Migration {
+        version: 3,
+        description: "per-session glossary terms",
+        apply: migrate_v2_to_v3,
+    },
+    Migration {
+        version: 4,
+        description: "per-entry translation revisions",
+        apply: migrate_v3_to_v4,
+    },
+    Migration {
+        version: 5,
+        description: "context-aware translation cache keys and last-use tracking",
+        apply: migrate_v4_to_v5,
+    },
+    Migration {
+        version: 6,
+        description: "subtitle content hash on sessions",
+        apply: migrate_v5_to_v6,
+    },
+    Migration {
+        version: 7,
+        description: "per-batch run telemetry",
+        apply: migrate_v6_to_v7,
+    },
+    Migration {
+        version: 8,
+        description: "session leases for concurrent processes",
+        apply: migrate_v7_to_v8,
+    },
+    Migration {
+        version: 9,
+        description: "scoped glossary, characters and summaries per session or series",
+        apply: migrate_v8_to_v9,
+    },
+];
+
+/// Options for running migrations
+#[derive(Debug, Clone, Default)]
+pub struct MigrateOptions {
+    /// Report the pending migrations without applying them
+    pub dry_run: bool,
+    /// Copy the database file here before applying migrations
+    pub backup_path: Option<PathBuf>,
+}
 
-/// Initialize the database schema
+/// Schema version of a database and the migrations it still needs
+#[derive(Debug)]
+pub struct MigrationStatus {
+    /// Version the database is at (0 = empty database)
+    pub current_version: i32,
+    /// Version this build expects
+    pub target_version: i32,
+    /// Migrations not yet applied, in order
+    pub pending: Vec<&'static Migration>,
+}
+
+impl MigrationStatus {
+    /// Whether the database has no schema yet
+    pub fn is_fresh(&self) -> bool {
+        self.current_version == 0
+    }
+
+    /// Whether the database is at the current version
+    pub fn is_up_to_date(&self) -> bool {
+        self.current_version == self.target_version
+    }
+}
+
+/// Initialize the database schema, migrating older databases without a backup
 pub fn initialize_schema(conn: &Connection) -> Result<()> {
-    // Check current schema version
+    migrate(conn, &MigrateOptions::default())?;
+    Ok(())
+}
+
+/// Get the schema version of a database and the migrations it needs
+pub fn migration_status(conn: &Connection) -> Result<MigrationStatus> {
     let current_version = get_schema_version(conn)?;
 
-    if current_version == 0 {
+    if current_version > SCHEMA_VERSION {
+        return Err(anyhow::anyhow!(
+            "Database schema v{} is newer than this version of yastwai supports (v{})",
+            current_version,
+            SCHEMA_VERSION
+        ));
+    }
+
+    let pending: Vec<&'static Migration> = if current_version == 0 {
+        Vec::new()
+    } else {
+        MIGRATIONS.iter().filter(|m| m.version > current_version).collect()
+    };
+
+    // Migrations must continue the database's version without gaps
+    for (expected, migration) in (current_version + 1..).zip(&pending) {
+        if migration.version != expected {
+            return Err(anyhow::anyhow!(
+                "Unsupported schema version: {}. Expected version {}. Please recreate the database.",
+                current_version,
+                SCHEMA_VERSION
+            ));
+        }
+    }
+
+    Ok(MigrationStatus {
+        current_version,
+        target_version: SCHEMA_VERSION,
+        pending,
+    })
+}
+
+/// Bring a database to the current schema version
+///
+/// Returns the status before migrating, which lists the migrations that were
+/// applied (or, for a dry run, would be).
+pub fn migrate(conn: &Connection, options: &MigrateOptions) -> Result<MigrationStatus> {
+    let status = migration_status(conn)?;
+
+    if options.dry_run {
+        return Ok(status);
+    }
+
+    if status.is_fresh() {
         // Fresh database - create all tables
         info!("Initializing database schema v{}", SCHEMA_VERSION);
         create_all_tables(conn)?;
         set_schema_version(conn, SCHEMA_VERSION)?;
-    } else if current_version < SCHEMA_VERSION {
-        // Need to migrate
-        info!(
-            "Migrating database schema from v{} to v{}",
-            current_version, SCHEMA_VERSION
-        );
-        migrate_schema(conn, current_version)?;
-    } else {
-        debug!("Database schema is up to date (v{})", current_version);
+        return Ok(status);
     }
 
+    if status.pending.is_empty() {
+        debug!("Database schema is up to date (v{})", status.current_version);
+        return Ok(status);
+    }
+
+    if let Some(ref backup_path) = options.backup_path {
+        backup_database(conn, backup_path)?;
+    }
+
+    info!(
+        "Migrating database schema from v{} to v{}",
+        status.current_version, SCHEMA_VERSION
+    );
+    for migration in &status.pending {
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(&tx)
+            .with_context(|| format!("Schema migration to v{} failed", migration.version))?;
+        set_schema_version(&tx, migration.version)?;
+        tx.commit()?;
+        info!("Applied schema migration {:?}", migration);
+    }
+
+    Ok(status)
+}
+
+/// Write a consistent copy of the database to `path`, replacing any previous backup
+pub fn backup_database(conn: &Connection, path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to replace database backup: {:?}", path))?;
+    }
+    conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])
+        .with_context(|| format!("Failed to back up database to {:?}", path))?;
+    info!("Backed up database to {:?}", path);
     Ok(())
 }
 
@@ -95,6 +270,7 @@ fn create_all_tables(conn: &Connection) -> Result<()> {
             id TEXT PRIMARY KEY,
             source_file_path TEXT NOT NULL,
             source_file_hash TEXT NOT NULL,
+            source_content_hash TEXT NOT NULL DEFAULT '',
             source_language TEXT NOT NULL,
             target_language TEXT NOT NULL,
             provider TEXT NOT NULL,
@@ -109,6 +285,7 @@ fn create_all_tables(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_sessions_status ON sessions(status);
         CREATE INDEX IF NOT EXISTS idx_sessions_source_hash ON sessions(source_file_hash);
+        CREATE INDEX IF NOT EXISTS idx_sessions_content_hash ON sessions(source_content_hash);
         CREATE INDEX IF NOT EXISTS idx_sessions_languages ON sessions(source_language, target_language);
         "#,
     )?;
@@ -151,7 +328,38 @@ fn create_all_tables(conn: &Connection) -> Result<()> {
         "#,
     )?;
 
-    // Create translation_cache table
+    create_translation_cache_table(conn)?;
+
+    // Create validation_results table
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS validation_results (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            translated_entry_id INTEGER NOT NULL REFERENCES translated_entries(id) ON DELETE CASCADE,
+            validation_type TEXT NOT NULL,
+            passed INTEGER NOT NULL,
+            severity TEXT,
+            message TEXT,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_validation_entry ON validation_results(translated_entry_id);
+        CREATE INDEX IF NOT EXISTS idx_validation_type ON validation_results(validation_type);
+        "#,
+    )?;
+
+    create_history_summaries_table(conn)?;
+    create_translation_revisions_table(conn)?;
+    create_batch_telemetry_table(conn)?;
+    create_session_leases_table(conn)?;
+    create_analysis_memory_tables(conn)?;
+
+    info!("Database schema created successfully");
+    Ok(())
+}
+
+/// Create the translation_cache table (context keys and last use added in v5)
+fn create_translation_cache_table(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         r#"
         CREATE TABLE IF NOT EXISTS translation_cache (
@@ -163,55 +371,296 @@ fn create_all_tables(conn: &Connection) -> Result<()> {
             translated_text TEXT NOT NULL,
             provider TEXT NOT NULL,
             model TEXT NOT NULL,
+            context_key TEXT NOT NULL DEFAULT '',
             created_at TEXT NOT NULL,
+            last_used_at TEXT NOT NULL,
             hit_count INTEGER DEFAULT 1,
-            UNIQUE(source_text_hash, source_language, target_language, provider, model)
+            UNIQUE(source_text_hash, source_language, target_language, provider, model, context_key)
         );
 
         CREATE INDEX IF NOT EXISTS idx_cache_lookup ON translation_cache(source_text_hash, source_language, target_language);
         CREATE INDEX IF NOT EXISTS idx_cache_provider ON translation_cache(provider, model);
+        CREATE INDEX IF NOT EXISTS idx_cache_last_used ON translation_cache(last_used_at);
         "#,
     )?;
+    Ok(())
+}
 
-    // Create validation_results table
+/// Create the history_summaries table (added in v2)
+fn create_history_summaries_table(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         r#"
-        CREATE TABLE IF NOT EXISTS validation_results (
+        CREATE TABLE IF NOT EXISTS history_summaries (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            translated_entry_id INTEGER NOT NULL REFERENCES translated_entries(id) ON DELETE CASCADE,
-            validation_type TEXT NOT NULL,
-            passed INTEGER NOT NULL,
-            severity TEXT,
-            message TEXT,
-            created_at TEXT NOT NULL
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            start_seq_num INTEGER NOT NULL,
+            end_seq_num INTEGER NOT NULL,
+            entry_count INTEGER NOT NULL,
+            summary_text TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE(session_id, start_seq_num)
         );
 
-        CREATE INDEX IF NOT EXISTS idx_validation_entry ON validation_results(translated_entry_id);
-        CREATE INDEX IF NOT EXISTS idx_validation_type ON validation_results(validation_type);
+        CREATE INDEX IF NOT EXISTS idx_history_summaries_session ON history_summaries(session_id);
         "#,
     )?;
+    Ok(())
+}
 
-    info!("Database schema created successfully");
+/// Create the translation_revisions table (added in v4)
+fn create_translation_revisions_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS translation_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_entry_id INTEGER NOT NULL REFERENCES source_entries(id) ON DELETE CASCADE,
+            revision INTEGER NOT NULL,
+            translated_text TEXT NOT NULL,
+            origin TEXT NOT NULL,
+            provider TEXT,
+            model TEXT,
+            detail TEXT,
+            quality_score REAL,
+            validation_passed INTEGER,
+            validation_message TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE(source_entry_id, revision)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_translation_revisions_entry ON translation_revisions(source_entry_id);
+        "#,
+    )?;
+    Ok(())
+}
+
+/// Create the batch_telemetry table (added in v7)
+fn create_batch_telemetry_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS batch_telemetry (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            run_id TEXT NOT NULL,
+            phase TEXT NOT NULL,
+            batch_index INTEGER NOT NULL,
+            entry_count INTEGER NOT NULL,
+            started_at TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            requests INTEGER NOT NULL,
+            cache_hits INTEGER NOT NULL,
+            prompt_tokens INTEGER NOT NULL,
+            completion_tokens INTEGER NOT NULL,
+            cost_usd REAL,
+            latencies_ms TEXT NOT NULL DEFAULT '[]',
+            retries TEXT NOT NULL DEFAULT '{}',
+            issues TEXT NOT NULL DEFAULT '{}'
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_batch_telemetry_session ON batch_telemetry(session_id, run_id);
+        "#,
+    )?;
+    Ok(())
+}
+
+/// Create the session_leases table (added in v8)
+fn create_session_leases_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS session_leases (
+            session_id TEXT PRIMARY KEY REFERENCES sessions(id) ON DELETE CASCADE,
+            owner_id TEXT NOT NULL,
+            pid INTEGER NOT NULL,
+            hostname TEXT NOT NULL,
+            acquired_at TEXT NOT NULL,
+            heartbeat_at TEXT NOT NULL,
+            expires_at TEXT NOT NULL
+        );
+        "#,
+    )?;
+    Ok(())
+}
+
+/// Create the glossary_terms, character_profiles and analysis_summaries tables (scoped in v9)
+///
+/// Rows are scoped to a session (`scope = 'session'`, keyed by session ID) or
+/// to a named series. Series rows outlive sessions, so session rows are
+/// removed by triggers instead of foreign keys.
+fn create_analysis_memory_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS glossary_terms (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scope TEXT NOT NULL,
+            scope_key TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            source_term TEXT NOT NULL,
+            target_term TEXT,
+            context TEXT,
+            updated_at TEXT NOT NULL,
+            UNIQUE(scope, scope_key, kind, source_term)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_glossary_terms_scope ON glossary_terms(scope, scope_key);
+
+        CREATE TABLE IF NOT EXISTS character_profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scope TEXT NOT NULL,
+            scope_key TEXT NOT NULL,
+            name TEXT NOT NULL,
+            mentions INTEGER NOT NULL DEFAULT 0,
+            first_seen TEXT,
+            notes TEXT,
+            updated_at TEXT NOT NULL,
+            UNIQUE(scope, scope_key, name)
+        );
+
+        CREATE TABLE IF NOT EXISTS analysis_summaries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scope TEXT NOT NULL,
+            scope_key TEXT NOT NULL,
+            label TEXT NOT NULL DEFAULT '',
+            summary_text TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE(scope, scope_key, label)
+        );
+
+        CREATE TRIGGER IF NOT EXISTS trg_sessions_delete_analysis_memory
+        AFTER DELETE ON sessions
+        BEGIN
+            DELETE FROM glossary_terms WHERE scope = 'session' AND scope_key = OLD.id;
+            DELETE FROM character_profiles WHERE scope = 'session' AND scope_key = OLD.id;
+            DELETE FROM analysis_summaries WHERE scope = 'session' AND scope_key = OLD.id;
+        END;
+        "#,
+    )?;
+    Ok(())
+}
+
+/// v2: rolling history summaries per session
+fn migrate_v1_to_v2(conn: &Connection) -> Result<()> {
+    create_history_summaries_table(conn)
+}
+
+/// v3: per-session glossary terms (e.g. names restored during review)
+///
+/// This is the pre-v9 layout; v9 rebuilds the table with a scope.
+fn migrate_v2_to_v3(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS glossary_terms (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            source_term TEXT NOT NULL,
+            target_term TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE(session_id, source_term)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_glossary_terms_session ON glossary_terms(session_id);
+        "#,
+    )?;
+    Ok(())
+}
+
+/// v4: per-entry translation revisions, seeded with the current translations
+fn migrate_v3_to_v4(conn: &Connection) -> Result<()> {
+    create_translation_revisions_table(conn)?;
+    conn.execute_batch(
+        r#"
+        INSERT INTO translation_revisions (
+            source_entry_id, revision, translated_text, origin, provider, model,
+            quality_score, created_at
+        )
+        SELECT t.source_entry_id, 1, t.translated_text,
+            CASE t.translation_status WHEN 'approved' THEN 'human' ELSE 'translation' END,
+            CASE t.translation_status WHEN 'approved' THEN NULL ELSE s.provider END,
+            CASE t.translation_status WHEN 'approved' THEN NULL ELSE s.model END,
+            t.quality_score, t.updated_at
+        FROM translated_entries t
+        JOIN source_entries e ON e.id = t.source_entry_id
+        JOIN sessions s ON s.id = e.session_id;
+        "#,
+    )?;
+    Ok(())
+}
+
+/// v5: context keys and last use on cache entries
+///
+/// The unique key changes, so the table is rebuilt; existing entries get an
+/// empty context key and their creation time as last use.
+fn migrate_v4_to_v5(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE translation_cache RENAME TO translation_cache_v4;
+        DROP INDEX IF EXISTS idx_cache_lookup;
+        DROP INDEX IF EXISTS idx_cache_provider;
+        "#,
+    )?;
+    create_translation_cache_table(conn)?;
+    conn.execute_batch(
+        r#"
+        INSERT INTO translation_cache (
+            source_text_hash, source_text, source_language, target_language,
+            translated_text, provider, model, context_key, created_at, last_used_at, hit_count
+        )
+        SELECT source_text_hash, source_text, source_language, target_language,
+            translated_text, provider, model, '', created_at, created_at, hit_count
+        FROM translation_cache_v4;
+
+        DROP TABLE translation_cache_v4;
+        "#,
+    )?;
+    Ok(())
+}
+
+/// v6: hash of the extracted subtitle entries, so sessions survive re-muxed or moved files
+///
+/// Existing sessions get an empty hash and keep matching on the file hash.
+fn migrate_v5_to_v6(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE sessions ADD COLUMN source_content_hash TEXT NOT NULL DEFAULT '';
+        CREATE INDEX IF NOT EXISTS idx_sessions_content_hash ON sessions(source_content_hash);
+        "#,
+    )?;
     Ok(())
 }
 
-/// Migrate the schema from one version to another
-fn migrate_schema(_conn: &Connection, from_version: i32) -> Result<()> {
-    // No migrations defined yet - SCHEMA_VERSION is 1, so any older version
-    // is unsupported. When migrations are needed, add match arms here:
-    //
-    // let mut current = from_version;
-    // while current < SCHEMA_VERSION {
-    //     match current {
-    //         1 => { migrate_v1_to_v2(conn)?; current = 2; }
-    //         _ => return Err(...)
-    //     }
-    // }
-    Err(anyhow::anyhow!(
-        "Unsupported schema version: {}. Expected version {}. Please recreate the database.",
-        from_version,
-        SCHEMA_VERSION
-    ))
+/// v7: per-batch telemetry of translation runs
+fn migrate_v6_to_v7(conn: &Connection) -> Result<()> {
+    create_batch_telemetry_table(conn)
+}
+
+/// v8: leases that keep parallel processes off the same session
+fn migrate_v7_to_v8(conn: &Connection) -> Result<()> {
+    create_session_leases_table(conn)
+}
+
+/// v9: analysis results kept per session or series, so reruns stay consistent
+///
+/// The glossary gains a scope and a kind, so the table is rebuilt; existing
+/// terms become session terms, and terms without a translation become
+/// character names.
+fn migrate_v8_to_v9(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE glossary_terms RENAME TO glossary_terms_v8;
+        DROP INDEX IF EXISTS idx_glossary_terms_session;
+        "#,
+    )?;
+    create_analysis_memory_tables(conn)?;
+    conn.execute_batch(
+        r#"
+        INSERT INTO glossary_terms (scope, scope_key, kind, source_term, target_term, context, updated_at)
+        SELECT 'session', session_id,
+            CASE WHEN target_term IS NULL THEN 'character' ELSE 'term' END,
+            source_term, target_term, NULL, created_at
+        FROM glossary_terms_v8;
+
+        DROP TABLE glossary_terms_v8;
+        "#,
+    )?;
+    Ok(())
 }
 
 /// Drop all tables (for testing purposes only)
@@ -219,6 +668,14 @@ fn migrate_schema(_conn: &Connection, from_version: i32) -> Result<()> {
 pub fn drop_all_tables(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         r#"
+        DROP TRIGGER IF EXISTS trg_sessions_delete_analysis_memory;
+        DROP TABLE IF EXISTS analysis_summaries;
+        DROP TABLE IF EXISTS character_profiles;
+        DROP TABLE IF EXISTS session_leases;
+        DROP TABLE IF EXISTS batch_telemetry;
+        DROP TABLE IF EXISTS translation_revisions;
+        DROP TABLE IF EXISTS glossary_terms;
+        DROP TABLE IF EXISTS history_summaries;
         DROP TABLE IF EXISTS validation_results;
         DROP TABLE IF EXISTS translated_entries;
         DROP TABLE IF EXISTS source_entries;
@@ -235,6 +692,20 @@ mod tests {
     use super::*;
     use rusqlite::Connection;
 
+    /// Undo every migration after v1 on a freshly created schema
+    const V1_ROLLBACK: &str = r#"
+        DROP TRIGGER trg_sessions_delete_analysis_memory;
+        DROP TABLE analysis_summaries;
+        DROP TABLE character_profiles;
+        DROP TABLE glossary_terms;
+        DROP TABLE session_leases;
+        DROP TABLE batch_telemetry;
+        DROP TABLE translation_revisions;
+        DROP TABLE history_summaries;
+        DROP INDEX idx_sessions_content_hash;
+        ALTER TABLE sessions DROP COLUMN source_content_hash;
+    "#;
+
     fn create_test_connection() -> Connection {
         Connection::open_in_memory().expect("Failed to create in-memory database")
     }
@@ -260,6 +731,119 @@ mod tests {
         assert!(tables.contains(&"translation_cache".to_string()));
         assert!(tables.contains(&"validation_results".to_string()));
         assert!(tables.contains(&"schema_version".to_string()));
+        assert!(tables.contains(&"history_summaries".to_string()));
+        assert!(tables.contains(&"glossary_terms".to_string()));
+        assert!(tables.contains(&"translation_revisions".to_string()));
+        assert!(tables.contains(&"session_leases".to_string()));
+        assert!(tables.contains(&"character_profiles".to_string()));
+        assert!(tables.contains(&"analysis_summaries".to_string()));
+    }
+
+    #[test]
+    fn test_initializeSchema_withV1Database_shouldMigrateToCurrent() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).expect("Failed to initialize schema");
+
+        // Roll back to a v1 layout
+        conn.execute_batch(V1_ROLLBACK).unwrap();
+        set_schema_version(&conn, 1).unwrap();
+
+        initialize_schema(&conn).expect("Failed to migrate schema");
+
+        let version = get_schema_version(&conn).expect("Failed to get version");
+        assert_eq!(version, SCHEMA_VERSION);
+
+        let table_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='history_summaries'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(table_count, 1);
+
+        let table_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='glossary_terms'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(table_count, 1);
+    }
+
+    #[test]
+    fn test_migrateV8ToV9_shouldKeepGlossaryTermsAsSessionTerms() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).expect("Failed to initialize schema");
+        conn.execute_batch(V1_ROLLBACK).unwrap();
+        set_schema_version(&conn, 1).unwrap();
+        for migration in MIGRATIONS.iter().filter(|m| m.version <= 8) {
+            (migration.apply)(&conn).unwrap();
+        }
+        conn.execute_batch(
+            r#"
+            INSERT INTO sessions (id, source_file_path, source_file_hash, source_language, target_language,
+                provider, model, status, total_entries, completed_entries, created_at, updated_at)
+            VALUES ('s1', '/tmp/a.srt', 'h', 'en', 'fr', 'ollama', 'm', 'in_progress', 0, 0, 't', 't');
+            INSERT INTO glossary_terms (session_id, source_term, target_term, created_at)
+            VALUES ('s1', 'Frodo', NULL, 't'), ('s1', 'Ring', 'Anneau', 't');
+            "#,
+        )
+        .unwrap();
+
+        migrate_v8_to_v9(&conn).unwrap();
+
+        let rows: Vec<(String, String, String, String)> = conn
+            .prepare("SELECT scope, scope_key, kind, source_term FROM glossary_terms ORDER BY source_term")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("session".to_string(), "s1".to_string(), "character".to_string(), "Frodo".to_string()),
+                ("session".to_string(), "s1".to_string(), "term".to_string(), "Ring".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrations_shouldBeContiguousAndEndAtSchemaVersion() {
+        for pair in MIGRATIONS.windows(2) {
+            assert_eq!(pair[1].version, pair[0].version + 1);
+        }
+        assert_eq!(MIGRATIONS.last().map(|m| m.version), Some(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn test_migrate_withDryRun_shouldOnlyReportPendingMigrations() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).expect("Failed to initialize schema");
+        conn.execute_batch(V1_ROLLBACK).unwrap();
+        set_schema_version(&conn, 1).unwrap();
+
+        let options = MigrateOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let status = migrate(&conn, &options).expect("Dry run failed");
+
+        assert_eq!(status.current_version, 1);
+        assert_eq!(status.pending.iter().map(|m| m.version).collect::<Vec<_>>(), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(get_schema_version(&conn).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_migrationStatus_withNewerDatabase_shouldFail() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).expect("Failed to initialize schema");
+        set_schema_version(&conn, SCHEMA_VERSION + 1).unwrap();
+
+        assert!(migration_status(&conn).is_err());
+        assert!(initialize_schema(&conn).is_err());
     }
 
     #[test]
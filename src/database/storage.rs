@@ -0,0 +1,453 @@
+/*!
+ * Storage backends for sessions and the translation cache.
+ *
+ * `Storage` is the interface the session manager and the L2 translation
+ * cache use for persistence. Two backends implement it:
+ * - `Repository`: SQLite, the default
+ * - `JsonFileStore`: a single JSON file, for tests and small setups
+ *
+ * A shared database can be added as another implementation without
+ * changing the code that uses the trait.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::json_store::JsonFileStore;
+use super::models::{
+    AnalysisScope, AnalysisSummaryRecord, BatchTelemetryRecord, CacheRecord, CharacterProfileRecord, GlossaryTermRecord,
+    HistorySummaryRecord, SessionLeaseRecord, SessionRecord, SessionStatus, SourceEntryRecord, TranslatedEntryRecord, TranslationRevisionRecord, ValidationResultRecord,
+};
+use super::repository::{
+    CacheEvictionPolicy, CacheEvictionResult, CacheQuery, CacheStats, Repository, ResumableSessionQuery,
+};
+use super::DatabaseConnection;
+
+/// Persistence operations for sessions, entries, cache and validation results
+///
+/// Implementations must keep the semantics of the SQLite backend: deleting a
+/// session removes its entries, approved translations are never overwritten
+/// by machine translations, and revisions are numbered per entry.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    // Sessions
+
+    /// Create a new translation session
+    async fn create_session(&self, session: &SessionRecord) -> Result<()>;
+
+    /// Create a session and its lease in one step, unless another owner holds
+    /// a live lease on a resumable session with the same subtitles, languages,
+    /// provider and model; that session is returned instead
+    async fn create_leased_session(
+        &self,
+        session: &SessionRecord,
+        lease: &SessionLeaseRecord,
+    ) -> Result<Option<SessionRecord>>;
+
+    /// Get a session by ID
+    async fn get_session(&self, session_id: &str) -> Result<Option<SessionRecord>>;
+
+    /// Find a resumable session, preferring the same content, then the same file, then the same path
+    async fn find_resumable_session(&self, query: &ResumableSessionQuery) -> Result<Option<SessionRecord>>;
+
+    /// Update session status
+    async fn update_session_status(&self, session_id: &str, status: SessionStatus) -> Result<()>;
+
+    /// Update session progress
+    async fn update_session_progress(&self, session_id: &str, completed_entries: i64) -> Result<()>;
+
+    /// List sessions, most recently updated first
+    async fn list_sessions(&self, status_filter: Option<SessionStatus>) -> Result<Vec<SessionRecord>>;
+
+    /// Delete a session and all related data
+    async fn delete_session(&self, session_id: &str) -> Result<()>;
+
+    /// Delete sessions older than the specified number of days
+    async fn delete_old_sessions(&self, days: i64) -> Result<i64>;
+
+    // Session leases
+
+    /// Take the lease of a session if it is free, expired or already held by the same owner
+    async fn acquire_lease(&self, lease: &SessionLeaseRecord) -> Result<bool>;
+
+    /// Extend a lease still held by the owner, returning false if it was lost
+    async fn renew_lease(&self, session_id: &str, owner_id: &str, heartbeat_at: &str, expires_at: &str) -> Result<bool>;
+
+    /// Release a lease held by the owner
+    async fn release_lease(&self, session_id: &str, owner_id: &str) -> Result<()>;
+
+    /// Get the current lease of a session, expired or not
+    async fn get_lease(&self, session_id: &str) -> Result<Option<SessionLeaseRecord>>;
+
+    // Entries
+
+    /// Insert source entries for a session
+    async fn insert_source_entries(&self, entries: Vec<SourceEntryRecord>) -> Result<()>;
+
+    /// Get all source entries for a session, in sequence order
+    async fn get_source_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>>;
+
+    /// Get source entries not yet translated or marked for retry
+    async fn get_pending_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>>;
+
+    /// Insert or update translated entries, leaving approved entries untouched
+    async fn insert_translated_entries(&self, entries: Vec<TranslatedEntryRecord>) -> Result<()>;
+
+    /// Get the translated entries of a session with their source entries
+    async fn get_translated_entries(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<(SourceEntryRecord, TranslatedEntryRecord)>>;
+
+    /// Store human-approved translations, keyed by source entry ID
+    async fn approve_translations(&self, approved: Vec<(i64, String)>) -> Result<()>;
+
+    /// Append translation revisions, numbering each after its entry's latest revision
+    async fn add_revisions(&self, revisions: Vec<TranslationRevisionRecord>) -> Result<()>;
+
+    /// Get the revisions of an entry, oldest first
+    async fn get_revisions(&self, source_entry_id: i64) -> Result<Vec<TranslationRevisionRecord>>;
+
+    /// Save rolling history summaries for a session
+    async fn save_history_summaries(&self, summaries: Vec<HistorySummaryRecord>) -> Result<()>;
+
+    /// Get rolling history summaries for a session, oldest first
+    async fn get_history_summaries(&self, session_id: &str) -> Result<Vec<HistorySummaryRecord>>;
+
+    // Glossary and analysis memory (per session or series)
+
+    /// Save glossary terms, replacing the translation of known terms
+    async fn save_glossary_terms(&self, terms: Vec<GlossaryTermRecord>) -> Result<()>;
+
+    /// Get the glossary terms of a session or series, sorted by kind and source term
+    async fn get_glossary_terms(&self, scope: &AnalysisScope) -> Result<Vec<GlossaryTermRecord>>;
+
+    /// Save character profiles, keeping the first sighting and notes of known characters
+    async fn save_character_profiles(&self, profiles: Vec<CharacterProfileRecord>) -> Result<()>;
+
+    /// Get the character profiles of a session or series, sorted by name
+    async fn get_character_profiles(&self, scope: &AnalysisScope) -> Result<Vec<CharacterProfileRecord>>;
+
+    /// Save an analysis summary, replacing the one with the same scope and label
+    async fn save_analysis_summary(&self, summary: &AnalysisSummaryRecord) -> Result<()>;
+
+    /// Get the analysis summaries of a session or series, sorted by label
+    async fn get_analysis_summaries(&self, scope: &AnalysisScope) -> Result<Vec<AnalysisSummaryRecord>>;
+
+    // Run telemetry
+
+    /// Insert the telemetry of translated batches
+    async fn insert_batch_telemetry(&self, records: Vec<BatchTelemetryRecord>) -> Result<()>;
+
+    /// Get the telemetry of a session's batches, in the order they were recorded
+    async fn get_batch_telemetry(&self, session_id: &str) -> Result<Vec<BatchTelemetryRecord>>;
+
+    // Cache
+
+    /// Get a cached translation made in the given context, counting the hit
+    async fn get_cached_translation_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        context_key: &str,
+    ) -> Result<Option<String>>;
+
+    /// Store a translation in the cache, keeping an existing translation
+    async fn cache_translation(&self, record: &CacheRecord) -> Result<()>;
+
+    /// Store cache entries, replacing the translation of entries that already exist
+    async fn import_cache_entries(&self, records: Vec<CacheRecord>) -> Result<usize>;
+
+    /// Get cache statistics
+    async fn get_cache_stats(&self) -> Result<CacheStats>;
+
+    /// Clear the translation cache
+    async fn clear_cache(&self) -> Result<i64>;
+
+    /// Evict expired entries, then the least recently used entries above the size limit
+    async fn prune_cache(&self, policy: &CacheEvictionPolicy) -> Result<CacheEvictionResult>;
+
+    /// List cache entries matching a query, most recently used first
+    async fn list_cache_entries(&self, query: &CacheQuery) -> Result<Vec<CacheRecord>>;
+
+    /// Get the cache entries for the source lines of a session, in any context
+    async fn get_session_cache_entries(&self, session_id: &str) -> Result<Vec<CacheRecord>>;
+
+    /// Get the most used context-free entries of a language pair (for cache warming)
+    async fn get_recent_cache_entries(
+        &self,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<CacheRecord>>;
+
+    // Validation results
+
+    /// Insert validation results
+    async fn insert_validation_results(&self, results: Vec<ValidationResultRecord>) -> Result<()>;
+
+    /// Get validation results for a translated entry
+    async fn get_validation_results(&self, translated_entry_id: i64) -> Result<Vec<ValidationResultRecord>>;
+
+    /// Get a cached translation made without any context
+    async fn get_cached_translation(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+    ) -> Result<Option<String>> {
+        self.get_cached_translation_in_context(source_text, source_language, target_language, provider, model, "")
+            .await
+    }
+
+    /// Store a translation in the cache, replacing any cached translation of the same text
+    async fn replace_cached_translation(&self, record: &CacheRecord) -> Result<()> {
+        self.import_cache_entries(vec![record.clone()]).await?;
+        Ok(())
+    }
+}
+
+/// Storage backend selected in the configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// SQLite database (default)
+    #[default]
+    Sqlite,
+    /// Single JSON file
+    Json,
+}
+
+impl StorageBackend {
+    /// Open the backend at a path, or at its default location
+    pub fn open(&self, path: Option<&Path>) -> Result<Arc<dyn Storage>> {
+        Ok(match (self, path) {
+            (StorageBackend::Sqlite, Some(path)) => Arc::new(Repository::new(DatabaseConnection::new(path)?)),
+            (StorageBackend::Sqlite, None) => Arc::new(Repository::new_default()?),
+            (StorageBackend::Json, Some(path)) => Arc::new(JsonFileStore::open(path)?),
+            (StorageBackend::Json, None) => Arc::new(JsonFileStore::open(JsonFileStore::default_path()?)?),
+        })
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+            StorageBackend::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for Repository {
+    async fn create_session(&self, session: &SessionRecord) -> Result<()> {
+        Repository::create_session(self, session).await
+    }
+
+    async fn create_leased_session(
+        &self,
+        session: &SessionRecord,
+        lease: &SessionLeaseRecord,
+    ) -> Result<Option<SessionRecord>> {
+        Repository::create_leased_session(self, session, lease).await
+    }
+
+    async fn get_session(&self, session_id: &str) -> Result<Option<SessionRecord>> {
+        Repository::get_session(self, session_id).await
+    }
+
+    async fn find_resumable_session(&self, query: &ResumableSessionQuery) -> Result<Option<SessionRecord>> {
+        Repository::find_resumable_session(self, query).await
+    }
+
+    async fn update_session_status(&self, session_id: &str, status: SessionStatus) -> Result<()> {
+        Repository::update_session_status(self, session_id, status).await
+    }
+
+    async fn update_session_progress(&self, session_id: &str, completed_entries: i64) -> Result<()> {
+        Repository::update_session_progress(self, session_id, completed_entries).await
+    }
+
+    async fn list_sessions(&self, status_filter: Option<SessionStatus>) -> Result<Vec<SessionRecord>> {
+        Repository::list_sessions(self, status_filter).await
+    }
+
+    async fn delete_session(&self, session_id: &str) -> Result<()> {
+        Repository::delete_session(self, session_id).await
+    }
+
+    async fn delete_old_sessions(&self, days: i64) -> Result<i64> {
+        Repository::delete_old_sessions(self, days).await
+    }
+
+    async fn acquire_lease(&self, lease: &SessionLeaseRecord) -> Result<bool> {
+        Repository::acquire_lease(self, lease).await
+    }
+
+    async fn renew_lease(&self, session_id: &str, owner_id: &str, heartbeat_at: &str, expires_at: &str) -> Result<bool> {
+        Repository::renew_lease(self, session_id, owner_id, heartbeat_at, expires_at).await
+    }
+
+    async fn release_lease(&self, session_id: &str, owner_id: &str) -> Result<()> {
+        Repository::release_lease(self, session_id, owner_id).await
+    }
+
+    async fn get_lease(&self, session_id: &str) -> Result<Option<SessionLeaseRecord>> {
+        Repository::get_lease(self, session_id).await
+    }
+
+    async fn insert_source_entries(&self, entries: Vec<SourceEntryRecord>) -> Result<()> {
+        Repository::insert_source_entries(self, entries).await
+    }
+
+    async fn get_source_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>> {
+        Repository::get_source_entries(self, session_id).await
+    }
+
+    async fn get_pending_entries(&self, session_id: &str) -> Result<Vec<SourceEntryRecord>> {
+        Repository::get_pending_entries(self, session_id).await
+    }
+
+    async fn insert_translated_entries(&self, entries: Vec<TranslatedEntryRecord>) -> Result<()> {
+        Repository::insert_translated_entries(self, entries).await
+    }
+
+    async fn get_translated_entries(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<(SourceEntryRecord, TranslatedEntryRecord)>> {
+        Repository::get_translated_entries(self, session_id).await
+    }
+
+    async fn approve_translations(&self, approved: Vec<(i64, String)>) -> Result<()> {
+        Repository::approve_translations(self, approved).await
+    }
+
+    async fn add_revisions(&self, revisions: Vec<TranslationRevisionRecord>) -> Result<()> {
+        Repository::add_revisions(self, revisions).await
+    }
+
+    async fn get_revisions(&self, source_entry_id: i64) -> Result<Vec<TranslationRevisionRecord>> {
+        Repository::get_revisions(self, source_entry_id).await
+    }
+
+    async fn save_history_summaries(&self, summaries: Vec<HistorySummaryRecord>) -> Result<()> {
+        Repository::save_history_summaries(self, summaries).await
+    }
+
+    async fn get_history_summaries(&self, session_id: &str) -> Result<Vec<HistorySummaryRecord>> {
+        Repository::get_history_summaries(self, session_id).await
+    }
+
+    async fn save_glossary_terms(&self, terms: Vec<GlossaryTermRecord>) -> Result<()> {
+        Repository::save_glossary_terms(self, terms).await
+    }
+
+    async fn get_glossary_terms(&self, scope: &AnalysisScope) -> Result<Vec<GlossaryTermRecord>> {
+        Repository::get_glossary_terms(self, scope).await
+    }
+
+    async fn save_character_profiles(&self, profiles: Vec<CharacterProfileRecord>) -> Result<()> {
+        Repository::save_character_profiles(self, profiles).await
+    }
+
+    async fn get_character_profiles(&self, scope: &AnalysisScope) -> Result<Vec<CharacterProfileRecord>> {
+        Repository::get_character_profiles(self, scope).await
+    }
+
+    async fn save_analysis_summary(&self, summary: &AnalysisSummaryRecord) -> Result<()> {
+        Repository::save_analysis_summary(self, summary).await
+    }
+
+    async fn get_analysis_summaries(&self, scope: &AnalysisScope) -> Result<Vec<AnalysisSummaryRecord>> {
+        Repository::get_analysis_summaries(self, scope).await
+    }
+
+    async fn insert_batch_telemetry(&self, records: Vec<BatchTelemetryRecord>) -> Result<()> {
+        Repository::insert_batch_telemetry(self, records).await
+    }
+
+    async fn get_batch_telemetry(&self, session_id: &str) -> Result<Vec<BatchTelemetryRecord>> {
+        Repository::get_batch_telemetry(self, session_id).await
+    }
+
+    async fn get_cached_translation_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        context_key: &str,
+    ) -> Result<Option<String>> {
+        Repository::get_cached_translation_in_context(
+            self,
+            source_text,
+            source_language,
+            target_language,
+            provider,
+            model,
+            context_key,
+        )
+        .await
+    }
+
+    async fn cache_translation(&self, record: &CacheRecord) -> Result<()> {
+        Repository::cache_translation(self, record).await
+    }
+
+    async fn import_cache_entries(&self, records: Vec<CacheRecord>) -> Result<usize> {
+        Repository::import_cache_entries(self, records).await
+    }
+
+    async fn get_cache_stats(&self) -> Result<CacheStats> {
+        Repository::get_cache_stats(self).await
+    }
+
+    async fn clear_cache(&self) -> Result<i64> {
+        Repository::clear_cache(self).await
+    }
+
+    async fn prune_cache(&self, policy: &CacheEvictionPolicy) -> Result<CacheEvictionResult> {
+        Repository::prune_cache(self, policy).await
+    }
+
+    async fn list_cache_entries(&self, query: &CacheQuery) -> Result<Vec<CacheRecord>> {
+        Repository::list_cache_entries(self, query).await
+    }
+
+    async fn get_session_cache_entries(&self, session_id: &str) -> Result<Vec<CacheRecord>> {
+        Repository::get_session_cache_entries(self, session_id).await
+    }
+
+    async fn get_recent_cache_entries(
+        &self,
+        source_language: &str,
+        target_language: &str,
+        provider: &str,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<CacheRecord>> {
+        Repository::get_recent_cache_entries(self, source_language, target_language, provider, model, limit).await
+    }
+
+    async fn insert_validation_results(&self, results: Vec<ValidationResultRecord>) -> Result<()> {
+        Repository::insert_validation_results(self, results).await
+    }
+
+    async fn get_validation_results(&self, translated_entry_id: i64) -> Result<Vec<ValidationResultRecord>> {
+        Repository::get_validation_results(self, translated_entry_id).await
+    }
+}
@@ -28,6 +28,7 @@
  * - `file_utils`: File system operations
  * - `app_controller`: Main application controller
  * - `language_utils`: ISO language code utilities
+ * - `video_timing`: Frame rates, shot changes and frame-accurate cue timing
  * - `providers`: Client implementations for various LLM providers:
  *   - `providers::ollama`: Ollama API client
  *   - `providers::openai`: OpenAI API client
@@ -42,6 +43,8 @@
 // Global lints configuration
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::redundant_closure_for_method_calls)]
+// Test names follow the test_functionName_withCondition_shouldBehavior convention
+#![cfg_attr(test, allow(non_snake_case))]
 // Public API re-exports may not be used internally
 #![allow(unused_imports)]
 
@@ -57,6 +60,7 @@ pub mod errors;
 pub mod database;
 pub mod session;
 pub mod validation;
+pub mod video_timing;
 
 // Re-export main types for easier usage
 pub use app_config::{Config, SessionConfig, CacheConfig, ValidationConfig};
@@ -1,6 +1,8 @@
 // Module-specific lints configuration
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::too_many_arguments)]
+// Test names follow the test_functionName_withCondition_shouldBehavior convention
+#![cfg_attr(test, allow(non_snake_case))]
 // Add other lints specific to this module that you want to allow but not auto-fix
 
 use anyhow::{Result, anyhow, Context};
@@ -26,6 +28,7 @@ mod errors;
 mod database;
 mod session;
 mod validation;
+mod video_timing;
 
 /// CLI Wrapper for TranslationProvider to implement ValueEnum
 #[derive(Debug, Clone, ValueEnum)]
@@ -34,7 +37,7 @@ enum CliTranslationProvider {
     OpenAI,
     Anthropic,
     LMStudio,
-    VLLM,
+    Vllm,
 }
 
 impl From<CliTranslationProvider> for TranslationProvider {
@@ -44,7 +47,7 @@ impl From<CliTranslationProvider> for TranslationProvider {
             CliTranslationProvider::OpenAI => TranslationProvider::OpenAI,
             CliTranslationProvider::Anthropic => TranslationProvider::Anthropic,
             CliTranslationProvider::LMStudio => TranslationProvider::LMStudio,
-            CliTranslationProvider::VLLM => TranslationProvider::VLLM,
+            CliTranslationProvider::Vllm => TranslationProvider::Vllm,
         }
     }
 }
@@ -81,6 +84,151 @@ enum Commands {
     #[command(subcommand)]
     Sessions(SessionCommands),
 
+    /// Inspect translation prompts
+    #[command(subcommand)]
+    Prompt(PromptCommands),
+
+    /// Export translations for human review and re-import the edits
+    #[command(subcommand)]
+    Review(ReviewCommands),
+
+    /// Inspect and migrate the session database schema
+    #[command(subcommand)]
+    Db(DbCommands),
+
+    /// Inspect, prune, export and import the cross-session translation cache
+    #[command(subcommand)]
+    Cache(CacheCommands),
+
+    /// Shift, rescale or resync the timings of a subtitle file
+    Retime {
+        /// Subtitle file (SRT) to retime
+        file: PathBuf,
+
+        /// Output file (default: <file>.retimed.srt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Frame rate the subtitles were timed for (e.g. 23.976), used with --to-fps
+        #[arg(long, requires = "to_fps")]
+        from_fps: Option<String>,
+
+        /// Frame rate of the target release (e.g. 25)
+        #[arg(long, requires = "from_fps")]
+        to_fps: Option<String>,
+
+        /// Map a reference cue to a new start time as CUE=HH:MM:SS,mmm (give exactly two)
+        #[arg(long, num_args = 1)]
+        sync: Vec<String>,
+
+        /// Constant offset in milliseconds, applied last (negative = earlier)
+        #[arg(long, allow_hyphen_values = true)]
+        offset: Option<i64>,
+    },
+
+    /// Check a subtitle file's sync against the video's audio track (requires ffmpeg)
+    SyncCheck {
+        /// Video file providing the audio track
+        video: PathBuf,
+
+        /// Subtitle file (SRT) to check
+        subtitles: PathBuf,
+
+        /// Write the corrected subtitles when a correction is suggested
+        #[arg(long)]
+        apply: bool,
+
+        /// Output file for --apply (default: <subtitles>.synced.srt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Largest offset to search in either direction, in milliseconds
+        #[arg(long, default_value = "5000")]
+        max_offset: i64,
+    },
+
+    /// Check subtitle files against a broadcaster compliance profile
+    Qc {
+        /// Subtitle files (SRT) to check
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Built-in profile (netflix, teletext, generic) or path to a JSON profile (overrides the config)
+        #[arg(short, long)]
+        profile: Option<String>,
+
+        /// Shot-change list (timestamps or ffmpeg scene-detection output) to check cue edges against
+        #[arg(long)]
+        shot_changes: Option<PathBuf>,
+
+        /// Print the reports as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Configuration file path
+        #[arg(short, long, default_value = "conf.json")]
+        config_path: String,
+    },
+
+    /// Update a translation after its source subtitles were edited, re-translating only changed cues
+    Update {
+        /// Edited source subtitles (SRT)
+        source: PathBuf,
+
+        /// Existing translation to update (SRT)
+        translated: PathBuf,
+
+        /// Source the translation was made from (default: the source of the file's latest session)
+        #[arg(long)]
+        previous: Option<PathBuf>,
+
+        /// Output file (default: overwrite the translation)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Translation provider to use
+        #[arg(short, long, value_enum)]
+        provider: Option<CliTranslationProvider>,
+
+        /// Model name to use for translation
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Configuration file path
+        #[arg(short, long, default_value = "conf.json")]
+        config_path: String,
+    },
+
+    /// Translate a file anyway and score it against a human subtitle track (chrF, BLEU, TER)
+    Evaluate {
+        /// Video or subtitle file to translate
+        input: PathBuf,
+
+        /// Human reference subtitles (default: the video's target-language track)
+        #[arg(short, long)]
+        reference: Option<PathBuf>,
+
+        /// Translation provider to use
+        #[arg(short, long, value_enum)]
+        provider: Option<CliTranslationProvider>,
+
+        /// Model name to use for translation
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Number of lowest-scoring entries to list
+        #[arg(long, default_value = "10")]
+        worst: usize,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Configuration file path
+        #[arg(short, long, default_value = "conf.json")]
+        config_path: String,
+    },
+
     /// Generate shell completions for yastwai
     Completions {
         /// Shell to generate completions for
@@ -110,6 +258,44 @@ enum SessionCommands {
         session_id: String,
     },
 
+    /// Show every candidate translation recorded for an entry
+    History {
+        /// Session ID (or ID prefix)
+        session_id: String,
+
+        /// Entry sequence number
+        seq: i64,
+    },
+
+    /// Show per-run timing, token, cost, retry and latency statistics of a session
+    Report {
+        /// Session ID (or ID prefix)
+        session_id: String,
+
+        /// Report format: text, json or html (default: from the output extension, else text)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Write the report to a file instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write a session, its entries and related cache rows to a portable JSON bundle
+    Export {
+        /// Session ID (or ID prefix) to export
+        session_id: String,
+
+        /// Bundle file to write
+        file: PathBuf,
+    },
+
+    /// Import a session bundle written by `sessions export`
+    Import {
+        /// Bundle file to read
+        file: PathBuf,
+    },
+
     /// Delete a session and its data
     Delete {
         /// Session ID to delete
@@ -131,6 +317,173 @@ enum SessionCommands {
     Stats,
 }
 
+#[derive(Subcommand, Debug)]
+enum ReviewCommands {
+    /// Write a review sheet (CSV or TSV) with an empty "final" column for editors
+    Export {
+        /// Session ID (or ID prefix) to export
+        session_id: String,
+
+        /// Output file (default: review-<session>.<format>)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Sheet format: csv or tsv (default: from the output extension, else csv)
+        #[arg(long)]
+        format: Option<String>,
+    },
+
+    /// Apply the "final" and "terms" columns of a review sheet to a session
+    Import {
+        /// Session ID (or ID prefix) to update
+        session_id: String,
+
+        /// Edited review sheet (.csv or .tsv)
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DbCommands {
+    /// Show the schema version of the database and any pending migrations
+    Status {
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// Back up the database and apply pending schema migrations
+    Migrate {
+        /// List the migrations that would run without applying them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommands {
+    /// Show the number of entries, hits and last use
+    Stats {
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// List cache entries, most recently used first
+    List {
+        /// Only entries whose source or translated text contains this string
+        #[arg(long)]
+        search: Option<String>,
+
+        /// Only entries from this source language
+        #[arg(short, long)]
+        source_language: Option<String>,
+
+        /// Only entries into this target language
+        #[arg(short, long)]
+        target_language: Option<String>,
+
+        /// Maximum number of entries to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// Evict expired entries and the least recently used entries above the size limit
+    Prune {
+        /// Evict entries unused for this many days (default: cache.ttl_days, 0 = never)
+        #[arg(long)]
+        max_age_days: Option<u32>,
+
+        /// Keep at most this many entries (default: cache.max_entries, 0 = unlimited)
+        #[arg(long)]
+        max_entries: Option<usize>,
+
+        /// Configuration file path
+        #[arg(short, long, default_value = "conf.json")]
+        config_path: String,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// Write cache entries to a JSON file
+    Export {
+        /// Output file
+        file: PathBuf,
+
+        /// Only entries from this source language
+        #[arg(short, long)]
+        source_language: Option<String>,
+
+        /// Only entries into this target language
+        #[arg(short, long)]
+        target_language: Option<String>,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// Load cache entries from a JSON file written by `cache export`
+    Import {
+        /// Input file
+        file: PathBuf,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+
+    /// Remove every cache entry
+    Clear {
+        /// Clear without confirmation
+        #[arg(short, long)]
+        force: bool,
+
+        /// Database file (default: the user data directory's yastwai.db)
+        #[arg(long)]
+        database: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PromptCommands {
+    /// Print the exact prompts for one batch of a subtitle file without calling a provider
+    Preview {
+        /// Subtitle file (SRT) to build the prompt from
+        file: PathBuf,
+
+        /// Batch number to render (1-based)
+        #[arg(short, long, default_value = "1")]
+        batch: usize,
+
+        /// Prompt template directory (overrides the config)
+        #[arg(long)]
+        templates: Option<PathBuf>,
+
+        /// Source language code (e.g., 'en', 'es', 'fr')
+        #[arg(short, long)]
+        source_language: Option<String>,
+
+        /// Target language code (e.g., 'en', 'es', 'fr')
+        #[arg(short, long)]
+        target_language: Option<String>,
+
+        /// Configuration file path
+        #[arg(short, long, default_value = "conf.json")]
+        config_path: String,
+    },
+}
+
 #[derive(Parser, Debug)]
 struct TranslateArgs {
     /// Input video file or directory to process
@@ -149,6 +502,10 @@ struct TranslateArgs {
     #[arg(long)]
     force_new_session: bool,
 
+    /// Series the input belongs to; episodes of a series share glossary and characters
+    #[arg(long, value_name = "NAME")]
+    series: Option<String>,
+
     /// Translation provider to use
     #[arg(short, long, value_enum)]
     provider: Option<CliTranslationProvider>,
@@ -202,6 +559,14 @@ EXAMPLES:
     yastwai -e --extract-language en movie.mkv # Extract English subtitles only
     yastwai --log-level debug /movies/         # Process entire directory with debug logging
     yastwai completions bash > yastwai.bash    # Generate bash completions
+    yastwai prompt preview movie.en.srt        # Show the prompts for the first batch
+    yastwai review export 1a2b3c4d             # Write a review sheet for a session
+    yastwai sessions history 1a2b3c4d 42       # Show how entry 42 got its translation
+    yastwai sessions export 1a2b3c4d job.json  # Bundle a session to continue it elsewhere
+    yastwai sessions report 1a2b3c4d -o r.html # Export timing, token and cost history of a session
+    yastwai translate --series dark s01e02.mkv # Reuse glossary and characters of earlier episodes
+    yastwai update movie.en.srt movie.fr.srt   # Re-translate only the cues edited in the source
+    yastwai cache prune --max-age-days 90      # Evict cache entries unused for 90 days
 
 CONFIGURATION:
     Configuration is stored in conf.json by default. You can specify a different
@@ -373,6 +738,33 @@ async fn main() -> Result<()> {
         Some(Commands::Sessions(session_cmd)) => {
             return run_session_command(session_cmd).await;
         }
+        Some(Commands::Prompt(prompt_cmd)) => {
+            return run_prompt_command(prompt_cmd);
+        }
+        Some(Commands::Review(review_cmd)) => {
+            return run_review_command(review_cmd).await;
+        }
+        Some(Commands::Db(db_cmd)) => {
+            return run_db_command(db_cmd);
+        }
+        Some(Commands::Cache(cache_cmd)) => {
+            return run_cache_command(cache_cmd).await;
+        }
+        Some(Commands::Retime { file, output, from_fps, to_fps, sync, offset }) => {
+            return run_retime_command(file, output, from_fps, to_fps, sync, offset);
+        }
+        Some(Commands::SyncCheck { video, subtitles, apply, output, max_offset }) => {
+            return run_sync_check_command(video, subtitles, apply, output, max_offset).await;
+        }
+        Some(Commands::Qc { files, profile, shot_changes, json, config_path }) => {
+            return run_qc_command(files, profile, shot_changes, json, &config_path);
+        }
+        Some(Commands::Update { source, translated, previous, output, provider, model, config_path }) => {
+            return run_update_command(source, translated, previous, output, provider, model, &config_path).await;
+        }
+        Some(Commands::Evaluate { input, reference, provider, model, worst, json, config_path }) => {
+            return run_evaluate_command(input, reference, provider, model, worst, json, &config_path).await;
+        }
         None => {
             // Default behavior - use top-level args for backwards compatibility
             let input_path = cli.input_path.ok_or_else(|| {
@@ -384,6 +776,7 @@ async fn main() -> Result<()> {
                 force_overwrite: cli.force_overwrite,
                 resume: false,
                 force_new_session: false,
+                series: None,
                 provider: cli.provider,
                 model: cli.model,
                 source_language: cli.source_language,
@@ -460,6 +853,104 @@ async fn run_session_command(cmd: SessionCommands) -> Result<()> {
             }
         }
 
+        SessionCommands::History { session_id, seq } => {
+            let sessions = session_manager.list_sessions(None).await?;
+            let session = sessions
+                .iter()
+                .find(|s| s.id.starts_with(&session_id))
+                .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+            let (source, revisions) = session_manager
+                .get_entry_history(&session.id, seq)
+                .await?
+                .ok_or_else(|| anyhow!("Entry {} not found in session {}", seq, &session.id[..8]))?;
+
+            println!("Entry {} of session {}:", seq, &session.id[..8]);
+            println!("Source: {}", source.source_text);
+            println!("{:-<80}", "");
+            if revisions.is_empty() {
+                println!("No revisions recorded.");
+            }
+            for revision in &revisions {
+                let mut origin = revision.origin.to_string();
+                if let (Some(provider), Some(model)) = (&revision.provider, &revision.model) {
+                    origin.push_str(&format!(" ({} / {})", provider, model));
+                }
+                let score = revision
+                    .quality_score
+                    .map(|s| format!(" | score {:.2}", s))
+                    .unwrap_or_default();
+                let outcome = match (revision.validation_passed, &revision.validation_message) {
+                    (Some(true), _) => " | passed".to_string(),
+                    (Some(false), Some(message)) => format!(" | failed: {}", message),
+                    (Some(false), None) => " | failed".to_string(),
+                    (None, _) => String::new(),
+                };
+                println!("#{} {}{}{} | {}", revision.revision, origin, score, outcome, revision.created_at);
+                if let Some(ref detail) = revision.detail {
+                    println!("   {}", detail);
+                }
+                println!("   > {}", revision.translated_text.replace('\n', "\n   > "));
+            }
+        }
+
+        SessionCommands::Report { session_id, format, output } => {
+            use session::report::ReportFormat;
+
+            let sessions = session_manager.list_sessions(None).await?;
+            let session = sessions
+                .iter()
+                .find(|s| s.id.starts_with(&session_id))
+                .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+            let format = match (format, &output) {
+                (Some(format), _) => format.parse::<ReportFormat>()?,
+                (None, Some(path)) => ReportFormat::from_path(path),
+                (None, None) => ReportFormat::Text,
+            };
+
+            let report = session_manager.session_report(&session.id).await?;
+            let rendered = report.render(format)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write report to {}", path.display()))?;
+                    println!("Wrote report of {} run(s) to {}", report.runs.len(), path.display());
+                }
+                None => print!("{}", rendered),
+            }
+        }
+
+        SessionCommands::Export { session_id, file } => {
+            let sessions = session_manager.list_sessions(None).await?;
+            let session = sessions
+                .iter()
+                .find(|s| s.id.starts_with(&session_id))
+                .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+
+            let bundle = session_manager.export_bundle(&session.id).await?;
+            bundle.write_to_file(&file)?;
+            println!(
+                "Exported session {} ({} entries, {} translated, {} cache entries) to {}",
+                &session.id[..8],
+                bundle.source_entries.len(),
+                bundle.translated_entries.len(),
+                bundle.cache_entries.len(),
+                file.display()
+            );
+        }
+
+        SessionCommands::Import { file } => {
+            let bundle = session::bundle::SessionBundle::read_from_file(&file)?;
+            let summary = session_manager.import_bundle(&bundle).await?;
+
+            println!("{}", summary);
+            if summary.renamed {
+                println!(
+                    "Session {} already exists here; the import was given a new ID.",
+                    &bundle.session.id[..8.min(bundle.session.id.len())]
+                );
+            }
+        }
+
         SessionCommands::Resume { session_id } => {
             info!("Resume functionality is integrated into the translate command.");
             info!("Use: yastwai -R <input_file> to auto-resume sessions");
@@ -505,6 +996,563 @@ async fn run_session_command(cmd: SessionCommands) -> Result<()> {
     Ok(())
 }
 
+/// Handle human review commands
+async fn run_review_command(cmd: ReviewCommands) -> Result<()> {
+    use session::SessionManager;
+    use session::review::{ReviewFormat, ReviewSheet};
+
+    let session_manager = SessionManager::new_default()
+        .context("Failed to initialize session manager")?;
+
+    // Find the full session ID from a prefix
+    let session_id = match &cmd {
+        ReviewCommands::Export { session_id, .. } | ReviewCommands::Import { session_id, .. } => session_id,
+    };
+    let sessions = session_manager.list_sessions(None).await?;
+    let session = sessions
+        .iter()
+        .find(|s| s.id.starts_with(session_id.as_str()))
+        .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+
+    match cmd {
+        ReviewCommands::Export { output, format, .. } => {
+            let format = match (format, &output) {
+                (Some(format), _) => format.parse::<ReviewFormat>()?,
+                (None, Some(path)) => ReviewFormat::from_path(path),
+                (None, None) => ReviewFormat::Csv,
+            };
+            let output = output
+                .unwrap_or_else(|| PathBuf::from(format!("review-{}.{}", &session.id[..8], format.extension())));
+
+            let sheet = session_manager.export_review(&session.id).await?;
+            sheet.write_to_file(&output, format)?;
+
+            let flagged = sheet.rows.iter().filter(|r| !r.issues.is_empty()).count();
+            println!(
+                "Wrote {} entries ({} with validation issues) to {}",
+                sheet.rows.len(),
+                flagged,
+                output.display()
+            );
+        }
+
+        ReviewCommands::Import { file, .. } => {
+            let sheet = ReviewSheet::read_from_file(&file)?;
+            let summary = session_manager.import_review(&session.id, &sheet).await?;
+
+            println!("{}", summary);
+            if !summary.outdated.is_empty() {
+                println!(
+                    "Warning: {} entries changed since the sheet was exported: {:?}",
+                    summary.outdated.len(),
+                    summary.outdated
+                );
+            }
+            if !summary.unknown.is_empty() {
+                println!("Warning: unknown entry IDs skipped: {:?}", summary.unknown);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle database schema commands
+fn run_db_command(cmd: DbCommands) -> Result<()> {
+    use database::DatabaseConnection;
+
+    let (database, dry_run) = match cmd {
+        DbCommands::Status { database } => (database, None),
+        DbCommands::Migrate { dry_run, database } => (database, Some(dry_run)),
+    };
+    let path = match database {
+        Some(path) => path,
+        None => DatabaseConnection::default_database_path()?,
+    };
+    let db = DatabaseConnection::open_unmigrated(&path)?;
+
+    let status = match dry_run {
+        None => db.migration_status()?,
+        Some(dry_run) => db.migrate(dry_run)?,
+    };
+
+    let applied = dry_run == Some(false);
+
+    println!("Database:         {}", path.display());
+    if status.is_fresh() && !applied {
+        println!("Schema version:   none (empty database)");
+    } else if applied {
+        println!("Schema version:   v{}", status.target_version);
+    } else {
+        println!("Schema version:   v{}", status.current_version);
+    }
+    println!("Current version:  v{}", status.target_version);
+
+    if status.is_fresh() {
+        if applied {
+            println!("Created schema v{}.", status.target_version);
+        } else {
+            println!("The schema will be created on first use.");
+        }
+    } else if status.pending.is_empty() {
+        println!("Up to date.");
+    } else {
+        if applied {
+            println!("Applied migrations (from v{}):", status.current_version);
+        } else {
+            println!("Pending migrations:");
+        }
+        for migration in &status.pending {
+            println!("  v{}  {}", migration.version, migration.description);
+        }
+        if applied && let Some(backup) = db.backup_path(status.current_version) {
+            println!("Backup:           {}", backup.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle translation cache commands
+async fn run_cache_command(cmd: CacheCommands) -> Result<()> {
+    use database::models::CacheRecord;
+    use database::repository::CacheQuery;
+    use database::{DatabaseConnection, Repository};
+
+    let database = match &cmd {
+        CacheCommands::Stats { database }
+        | CacheCommands::List { database, .. }
+        | CacheCommands::Prune { database, .. }
+        | CacheCommands::Export { database, .. }
+        | CacheCommands::Import { database, .. }
+        | CacheCommands::Clear { database, .. } => database.clone(),
+    };
+    let path = match database {
+        Some(path) => path,
+        None => DatabaseConnection::default_database_path()?,
+    };
+    let repo = Repository::new(DatabaseConnection::new(&path)?);
+
+    match cmd {
+        CacheCommands::Stats { .. } => {
+            let stats = repo.get_cache_stats().await?;
+            println!("Database:         {}", path.display());
+            println!("Entries:          {}", stats.total_entries);
+            println!("With context:     {}", stats.contextual_entries);
+            println!("Hits:             {}", stats.total_hits);
+            if let (Some(oldest), Some(newest)) = (stats.oldest_use, stats.newest_use) {
+                println!("Last used:        {} .. {}", oldest, newest);
+            }
+        }
+
+        CacheCommands::List { search, source_language, target_language, limit, .. } => {
+            let query = CacheQuery {
+                source_language,
+                target_language,
+                search,
+                limit: Some(limit),
+            };
+            let entries = repo.list_cache_entries(&query).await?;
+            if entries.is_empty() {
+                println!("No cache entries found.");
+            }
+            for entry in &entries {
+                let context = if entry.context_key.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", entry.context_key)
+                };
+                println!(
+                    "{} -> {} | {} / {} | {} hit(s) | {}{}",
+                    entry.source_language,
+                    entry.target_language,
+                    entry.provider,
+                    entry.model,
+                    entry.hit_count,
+                    entry.last_used_at,
+                    context
+                );
+                println!("   < {}", entry.source_text.replace('\n', " / "));
+                println!("   > {}", entry.translated_text.replace('\n', " / "));
+            }
+        }
+
+        CacheCommands::Prune { max_age_days, max_entries, config_path, .. } => {
+            let config = if Path::new(&config_path).exists() {
+                let file = File::open(&config_path)
+                    .context(format!("Failed to open config file: {}", config_path))?;
+                serde_json::from_reader::<_, Config>(BufReader::new(file))
+                    .context(format!("Failed to parse config file: {}", config_path))?
+            } else {
+                Config::default()
+            };
+            let mut policy = config.cache.eviction_policy();
+            if let Some(days) = max_age_days {
+                policy.max_age_days = days;
+            }
+            if let Some(entries) = max_entries {
+                policy.max_entries = entries;
+            }
+
+            let result = repo.prune_cache(&policy).await?;
+            println!(
+                "Removed {} expired and {} least recently used cache entries.",
+                result.expired, result.evicted
+            );
+        }
+
+        CacheCommands::Export { file, source_language, target_language, .. } => {
+            let query = CacheQuery {
+                source_language,
+                target_language,
+                ..Default::default()
+            };
+            let entries = repo.list_cache_entries(&query).await?;
+            let output = File::create(&file)
+                .context(format!("Failed to create export file: {}", file.display()))?;
+            serde_json::to_writer_pretty(output, &entries)?;
+            println!("Exported {} cache entries to {}", entries.len(), file.display());
+        }
+
+        CacheCommands::Import { file, .. } => {
+            let input = File::open(&file)
+                .context(format!("Failed to open import file: {}", file.display()))?;
+            let mut entries: Vec<CacheRecord> = serde_json::from_reader(BufReader::new(input))
+                .context(format!("Failed to parse import file: {}", file.display()))?;
+            for entry in &mut entries {
+                // Recompute the hash so hand-edited files still match lookups
+                entry.source_text_hash = Repository::hash_text(&entry.source_text);
+                if entry.last_used_at.is_empty() {
+                    entry.last_used_at = entry.created_at.clone();
+                }
+            }
+            let imported = repo.import_cache_entries(entries).await?;
+            println!("Imported {} cache entries from {}", imported, file.display());
+        }
+
+        CacheCommands::Clear { force, .. } => {
+            if !force {
+                println!("Are you sure you want to clear the translation cache? Use --force to confirm.");
+                return Ok(());
+            }
+            let deleted = repo.clear_cache().await?;
+            println!("Removed {} cache entries.", deleted);
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle prompt inspection commands
+fn run_prompt_command(cmd: PromptCommands) -> Result<()> {
+    use crate::subtitle_processor::SubtitleCollection;
+    use crate::translation::document::SubtitleDocument;
+    use crate::translation::pipeline::TranslationPipeline;
+    use crate::translation::PipelineMode;
+
+    match cmd {
+        PromptCommands::Preview { file, batch, templates, source_language, target_language, config_path } => {
+            // Read the config without creating one: previews must not touch the disk
+            let mut config = if Path::new(&config_path).exists() {
+                let file = File::open(&config_path)
+                    .context(format!("Failed to open config file: {}", config_path))?;
+                serde_json::from_reader::<_, Config>(BufReader::new(file))
+                    .context(format!("Failed to parse config file: {}", config_path))?
+            } else {
+                Config::default()
+            };
+
+            if let Some(source_lang) = source_language {
+                config.source_language = source_lang;
+            }
+            if let Some(target_lang) = target_language {
+                config.target_language = target_lang;
+            }
+            if let Some(dir) = templates {
+                config.translation.common.prompt_templates_dir = Some(dir.to_string_lossy().to_string());
+            }
+
+            // Legacy mode has no pipeline prompts, preview the standard pipeline instead
+            let mode = match config.translation.common.pipeline_mode.parse().unwrap_or_default() {
+                PipelineMode::Legacy => PipelineMode::Standard,
+                mode => mode,
+            };
+            let pipeline_config = Controller::build_pipeline_config(&config, mode)?;
+
+            let content = std::fs::read_to_string(&file)
+                .context(format!("Failed to read subtitle file: {:?}", file))?;
+            let entries = SubtitleCollection::parse_srt_string(&content)?;
+            let mut doc = SubtitleDocument::from_entries(entries, &config.source_language)
+                .with_target_language(&config.target_language);
+
+            let batch_size = pipeline_config.translation_config.window_config.batch_size.max(1);
+            let total_batches = doc.entries.len().div_ceil(batch_size);
+            let pipeline = TranslationPipeline::new(pipeline_config);
+
+            let (system_prompt, user_prompt) = batch
+                .checked_sub(1)
+                .and_then(|idx| pipeline.preview_prompts(&mut doc, idx))
+                .ok_or_else(|| anyhow!("Batch {} is out of range (1-{})", batch, total_batches))?;
+
+            println!("=== System prompt (batch {}/{}) ===", batch, total_batches);
+            println!("{}", system_prompt);
+            println!();
+            println!("=== User prompt (batch {}/{}) ===", batch, total_batches);
+            println!("{}", user_prompt);
+        }
+    }
+
+    Ok(())
+}
+
+/// Retime a subtitle file: frame-rate conversion, then two-point sync, then offset
+fn run_retime_command(
+    file: PathBuf,
+    output: Option<PathBuf>,
+    from_fps: Option<String>,
+    to_fps: Option<String>,
+    sync: Vec<String>,
+    offset: Option<i64>,
+) -> Result<()> {
+    use crate::subtitle_processor::SubtitleCollection;
+    use crate::video_timing::{FrameRate, SyncPoint, TimingTransform};
+
+    let mut transforms = Vec::new();
+    if let (Some(from), Some(to)) = (from_fps, to_fps) {
+        transforms.push(TimingTransform::FrameRate {
+            from: FrameRate::try_from(from)?,
+            to: FrameRate::try_from(to)?,
+        });
+    }
+    match sync.as_slice() {
+        [] => {}
+        [first, second] => transforms.push(TimingTransform::Sync {
+            first: SyncPoint::parse(first)?,
+            second: SyncPoint::parse(second)?,
+        }),
+        _ => return Err(anyhow!("--sync needs exactly two reference cues")),
+    }
+    if let Some(offset_ms) = offset {
+        transforms.push(TimingTransform::Offset { offset_ms });
+    }
+    if transforms.is_empty() {
+        return Err(anyhow!("Nothing to do: give --offset, --from-fps/--to-fps or --sync"));
+    }
+
+    let content = std::fs::read_to_string(&file)
+        .context(format!("Failed to read subtitle file: {:?}", file))?;
+    let mut subtitles = SubtitleCollection::new(file.clone(), "auto".to_string());
+    subtitles.entries = SubtitleCollection::parse_srt_string(&content)?;
+
+    let clamped = subtitles.apply_timing_transforms(&transforms)?;
+    let output = output.unwrap_or_else(|| file.with_extension("retimed.srt"));
+    subtitles.write_to_srt(&output)?;
+
+    for transform in &transforms {
+        println!("Applied {}", transform.description());
+    }
+    if clamped > 0 {
+        println!("Warning: {} cues were moved before 00:00:00 and clamped", clamped);
+    }
+    println!("Wrote {} cues to {}", subtitles.entries.len(), output.display());
+
+    Ok(())
+}
+
+/// Correlate a subtitle file with the audio's speech map and optionally write the correction
+async fn run_sync_check_command(
+    video: PathBuf,
+    subtitles: PathBuf,
+    apply: bool,
+    output: Option<PathBuf>,
+    max_offset: i64,
+) -> Result<()> {
+    use crate::subtitle_processor::{SubtitleCollection, SubtitleEntry};
+    use crate::validation::{AudioSyncChecker, AudioSyncConfig, SpeechMap};
+
+    let config = AudioSyncConfig {
+        enabled: true,
+        apply,
+        max_offset_ms: max_offset,
+        ..Default::default()
+    };
+
+    let content = std::fs::read_to_string(&subtitles)
+        .context(format!("Failed to read subtitle file: {:?}", subtitles))?;
+    let mut collection = SubtitleCollection::new(subtitles.clone(), "auto".to_string());
+    collection.entries = SubtitleCollection::parse_srt_string(&content)?;
+
+    let speech = SpeechMap::detect(&video, &config).await?;
+    if speech.is_empty() {
+        return Err(anyhow!("No speech detected in the audio of {:?}", video));
+    }
+
+    let report = AudioSyncChecker::new(config.clone()).check(&collection.entries, &speech);
+    println!("Speech: {} ms in {} intervals", speech.speech_ms(), speech.intervals().len());
+    for segment in &report.segments {
+        println!(
+            "  {} - {}: offset {:+}ms ({:.0}% speech overlap)",
+            SubtitleEntry::format_timestamp(segment.start_ms),
+            SubtitleEntry::format_timestamp(segment.end_ms),
+            segment.offset_ms,
+            segment.score * 100.0
+        );
+    }
+    println!("Suggested correction: {}", report.summary());
+
+    let transform = report.suggested_transform().filter(|_| report.is_significant(&config));
+    match (transform, apply) {
+        (None, _) => println!("Subtitles are in sync, no correction needed"),
+        (Some(transform), false) => println!("Run with --apply to write the {} correction", transform.description()),
+        (Some(transform), true) => {
+            collection.apply_timing_transforms(&[transform])?;
+            let output = output.unwrap_or_else(|| subtitles.with_extension("synced.srt"));
+            collection.write_to_srt(&output)?;
+            println!("Wrote corrected subtitles to {}", output.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check subtitle files against a compliance profile, failing if any file fails QC
+fn run_qc_command(
+    files: Vec<PathBuf>,
+    profile: Option<String>,
+    shot_changes: Option<PathBuf>,
+    json: bool,
+    config_path: &str,
+) -> Result<()> {
+    use crate::subtitle_processor::SubtitleCollection;
+    use crate::validation::{ComplianceChecker, ComplianceProfile};
+    use crate::video_timing::ShotChanges;
+
+    // Fall back to the configured profile, then the generic one
+    let profile_name = match profile {
+        Some(name) => name,
+        None if Path::new(config_path).exists() => {
+            let file = File::open(config_path)
+                .context(format!("Failed to open config file: {}", config_path))?;
+            serde_json::from_reader::<_, Config>(BufReader::new(file))
+                .context(format!("Failed to parse config file: {}", config_path))?
+                .validation
+                .compliance_profile
+                .unwrap_or_else(|| "generic".to_string())
+        }
+        None => "generic".to_string(),
+    };
+    let mut checker = ComplianceChecker::new(ComplianceProfile::resolve(&profile_name)?);
+    if let Some(path) = shot_changes {
+        checker = checker.with_shot_changes(&ShotChanges::from_file(path)?);
+    }
+
+    let mut reports = Vec::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .context(format!("Failed to read subtitle file: {:?}", file))?;
+        let entries = SubtitleCollection::parse_srt_string(&content)?;
+        reports.push(checker.check(&file.display().to_string(), &entries));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!("{}", report.format_report());
+        }
+    }
+
+    let failed = reports.iter().filter(|r| !r.passed).count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} files failed QC ({})", failed, reports.len(), checker.profile().name));
+    }
+
+    Ok(())
+}
+
+async fn run_evaluate_command(
+    input: PathBuf,
+    reference: Option<PathBuf>,
+    provider: Option<CliTranslationProvider>,
+    model: Option<String>,
+    worst: usize,
+    json: bool,
+    config_path: &str,
+) -> Result<()> {
+    let mut config = if Path::new(config_path).exists() {
+        let file = File::open(config_path)
+            .context(format!("Failed to open config file: {}", config_path))?;
+        serde_json::from_reader::<_, Config>(BufReader::new(file))
+            .context(format!("Failed to parse config file: {}", config_path))?
+    } else {
+        Config::default()
+    };
+
+    if let Some(provider) = provider {
+        config.translation.provider = provider.into();
+    }
+    if let Some(model) = model {
+        let provider_str = config.translation.provider.to_lowercase_string();
+        if let Some(provider_config) = config.translation.available_providers.iter_mut()
+            .find(|p| p.provider_type == provider_str) {
+            provider_config.model = model;
+        }
+    }
+
+    // A resumed session would reuse earlier output; every run must translate from scratch
+    config.session.enabled = false;
+    config.validate().context("Configuration validation failed")?;
+
+    let controller = Controller::with_config(config)?;
+    let report = controller.evaluate_against_reference(&input, reference.as_deref()).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.format_report(worst));
+    }
+
+    Ok(())
+}
+
+async fn run_update_command(
+    source: PathBuf,
+    translated: PathBuf,
+    previous: Option<PathBuf>,
+    output: Option<PathBuf>,
+    provider: Option<CliTranslationProvider>,
+    model: Option<String>,
+    config_path: &str,
+) -> Result<()> {
+    let mut config = if Path::new(config_path).exists() {
+        let file = File::open(config_path)
+            .context(format!("Failed to open config file: {}", config_path))?;
+        serde_json::from_reader::<_, Config>(BufReader::new(file))
+            .context(format!("Failed to parse config file: {}", config_path))?
+    } else {
+        Config::default()
+    };
+
+    if let Some(provider) = provider {
+        config.translation.provider = provider.into();
+    }
+    if let Some(model) = model {
+        let provider_str = config.translation.provider.to_lowercase_string();
+        if let Some(provider_config) = config.translation.available_providers.iter_mut()
+            .find(|p| p.provider_type == provider_str) {
+            provider_config.model = model;
+        }
+    }
+    config.validate().context("Configuration validation failed")?;
+
+    let output = output.unwrap_or_else(|| translated.clone());
+    let controller = Controller::with_config(config)?;
+    let plan = controller.update_translation(&source, &translated, previous.as_deref(), &output).await?;
+
+    println!("Updated {}: {}", output.display(), plan);
+    Ok(())
+}
+
 async fn run_translate(options: TranslateArgs) -> Result<()> {
     // If log level is set via command line, apply it immediately
     if let Some(cmd_log_level) = &options.log_level {
@@ -551,6 +1599,10 @@ async fn run_translate(options: TranslateArgs) -> Result<()> {
         if let Some(target_lang) = &options.target_language {
             config.target_language = target_lang.clone();
         }
+
+        if let Some(series) = &options.series {
+            config.session.series = series.clone();
+        }
         
         // Update log level in config if specified via command line
         if let Some(log_level) = &options.log_level {
@@ -3,6 +3,7 @@ use serde::{de::DeserializeOwned, Serialize, Deserialize};
 use anyhow::{Result, Context, anyhow};
 use reqwest::Client;
 use async_trait::async_trait;
+use log::debug;
 use tokio::time::sleep;
 use tokio::sync::Mutex;
 
@@ -335,6 +336,7 @@ impl Anthropic {
                 Ok(response) => return Ok(response),
                 Err(err) => {
                     // Only retry on connection errors, rate limit errors, and server errors
+                    #[allow(clippy::collapsible_match)]
                     match &err {
                         ProviderError::ConnectionError(_) => {
                             last_error = Some(err);
@@ -426,8 +428,20 @@ impl Anthropic {
             };
         }
         
-        response.json::<AnthropicResponse>().await
-            .map_err(|e| ProviderError::ParseError(e.to_string()))
+        let response = response.json::<AnthropicResponse>().await
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        debug!(
+            "Anthropic {} {} from {}: stop reason {}, cache write/read tokens {}/{}",
+            response.response_type.as_deref().unwrap_or("response"),
+            response.id.as_deref().unwrap_or("-"),
+            response.model.as_deref().unwrap_or("unknown model"),
+            response.stop_reason.as_deref().unwrap_or("-"),
+            response.usage.cache_creation_input_tokens.unwrap_or(0),
+            response.usage.cache_read_input_tokens.unwrap_or(0),
+        );
+
+        Ok(response)
     }
 }
 
@@ -261,6 +261,38 @@ mod tests {
         let response = provider.complete(request).await.unwrap();
         assert!(response.text.contains("TRANSLATED"));
         assert!(response.text.contains("fr"));
+        assert_eq!(response.prompt_tokens, Some(11));
+        assert_eq!(response.completion_tokens, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_partialMarkersProvider_shouldMissMiddleMarkers() {
+        let provider = MockProvider::partial_markers();
+        let request = MockRequest {
+            text: "Hello".to_string(),
+            source_language: "en".to_string(),
+            target_language: "fr".to_string(),
+        };
+
+        let response = provider.complete(request).await.unwrap();
+        assert!(response.text.contains("<<ENTRY_0>>"));
+        assert!(!response.text.contains("<<ENTRY_1>>"));
+        assert!(response.text.contains("<<END>>"));
+    }
+
+    #[tokio::test]
+    async fn test_slowProvider_shouldAnswerAfterDelay() {
+        let provider = MockProvider::new(MockBehavior::Slow { delay_ms: 20 });
+        let request = MockRequest {
+            text: "Hello".to_string(),
+            source_language: "en".to_string(),
+            target_language: "fr".to_string(),
+        };
+
+        let start = std::time::Instant::now();
+        let response = provider.complete(request).await.unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+        assert_eq!(response.text, "[TRANSLATED] Hello");
     }
 
     #[tokio::test]
@@ -10,7 +10,7 @@ use log::{error, debug};
 /// - Continuous batching (PagedAttention)
 /// - High concurrency support
 /// - No rate limiting (local deployment)
-pub struct VLLM {
+pub struct Vllm {
     /// HTTP client for API requests
     client: Client,
     /// API key for authentication (optional for some deployments)
@@ -212,7 +212,7 @@ impl VLLMRequest {
 
 /// vLLM client implementation
 #[allow(dead_code)]
-impl VLLM {
+impl Vllm {
     /// Create a new vLLM client with default settings
     pub fn new(endpoint: impl Into<String>) -> Self {
         Self {
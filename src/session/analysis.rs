@@ -0,0 +1,120 @@
+/*!
+ * Analysis results kept per session and per series.
+ *
+ * The glossary, characters and summary found by the analysis pass are
+ * saved for the session, so a resumed session sees the same results. When
+ * the session belongs to a named series, they are saved for the series as
+ * well and loaded for every later episode; the session's own results win
+ * over the series'.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::database::models::AnalysisScope;
+use crate::translation::pipeline::analysis_pass::{AnalysisMemory, AnalysisStore};
+
+use super::manager::SessionManager;
+
+/// Analysis store backed by the session database
+#[derive(Clone)]
+pub struct SessionAnalysisStore {
+    manager: SessionManager,
+    session_id: String,
+    /// Series the session belongs to (None = session only)
+    series: Option<String>,
+    /// Episode label for series summaries and first sightings
+    episode: String,
+}
+
+impl SessionAnalysisStore {
+    /// Create a store for a session
+    pub fn new(manager: SessionManager, session_id: &str, episode: &str) -> Self {
+        Self {
+            manager,
+            session_id: session_id.to_string(),
+            series: None,
+            episode: episode.to_string(),
+        }
+    }
+
+    /// Share results with the other episodes of a series (empty = none)
+    pub fn with_series(mut self, series: &str) -> Self {
+        self.series = (!series.trim().is_empty()).then(|| series.trim().to_string());
+        self
+    }
+
+    fn session_scope(&self) -> AnalysisScope {
+        AnalysisScope::Session(self.session_id.clone())
+    }
+}
+
+#[async_trait]
+impl AnalysisStore for SessionAnalysisStore {
+    async fn load(&self) -> Result<AnalysisMemory> {
+        let mut memory = match self.series {
+            Some(ref series) => {
+                self.manager
+                    .load_analysis_memory(&AnalysisScope::Series(series.clone()), &self.episode)
+                    .await?
+            }
+            None => AnalysisMemory::default(),
+        };
+        let session = self.manager.load_analysis_memory(&self.session_scope(), "").await?;
+        memory.merge(&session);
+        Ok(memory)
+    }
+
+    async fn save(&self, memory: &AnalysisMemory) -> Result<()> {
+        self.manager.save_analysis_memory(&self.session_scope(), "", memory).await?;
+        if let Some(ref series) = self.series {
+            self.manager
+                .save_analysis_memory(&AnalysisScope::Series(series.clone()), &self.episode, memory)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::pipeline::analysis_pass::CharacterProfile;
+
+    #[tokio::test]
+    async fn test_sessionAnalysisStore_load_shouldMergeSeriesAndSessionResults() {
+        let manager = SessionManager::new_in_memory().unwrap();
+        let first_id = "session-e01".to_string();
+        let second_id = "session-e02".to_string();
+
+        let mut memory = AnalysisMemory::default();
+        memory.glossary.add_term("Ring", "Anneau", None);
+        memory.characters.push(CharacterProfile { name: "Frodo".to_string(), mentions: 4 });
+        memory.summary = Some("Episode one".to_string());
+        let first = SessionAnalysisStore::new(manager.clone(), &first_id, "e01").with_series("Show");
+        first.save(&memory).await.unwrap();
+
+        // A later episode sees the series glossary and characters, not the episode summary
+        let second = SessionAnalysisStore::new(manager.clone(), &second_id, "e02").with_series("Show");
+        let loaded = second.load().await.unwrap();
+        assert_eq!(loaded.glossary.get_translation("Ring"), Some("Anneau"));
+        assert!(loaded.glossary.character_names.contains("Frodo"));
+        assert!(loaded.summary.is_none());
+
+        // The session's own results win over the series'
+        let mut own = AnalysisMemory::default();
+        own.glossary.add_term("Ring", "Bague", None);
+        own.summary = Some("Episode two".to_string());
+        manager
+            .save_analysis_memory(&AnalysisScope::Session(second_id.clone()), "", &own)
+            .await
+            .unwrap();
+        let loaded = second.load().await.unwrap();
+        assert_eq!(loaded.glossary.get_translation("Ring"), Some("Bague"));
+        assert_eq!(loaded.summary.as_deref(), Some("Episode two"));
+
+        // Without a series, nothing is shared
+        let alone = SessionAnalysisStore::new(manager.clone(), &second_id, "e02").with_series(" ");
+        assert!(!alone.load().await.unwrap().glossary.character_names.contains("Frodo"));
+    }
+}
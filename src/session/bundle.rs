@@ -0,0 +1,190 @@
+/*!
+ * Portable session bundles.
+ *
+ * A bundle is a versioned JSON file holding everything needed to continue a
+ * session on another machine: the session record, its source and translated
+ * entries, revisions, validation results, history summaries, the session's
+ * glossary, characters and analysis summaries, the telemetry of its runs and
+ * the cache rows for its source lines. Database IDs in a bundle are the
+ * exporting machine's; they are remapped when the bundle is imported.
+ */
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+use crate::database::models::{
+    AnalysisSummaryRecord, BatchTelemetryRecord, CacheRecord, CharacterProfileRecord, GlossaryTermRecord, HistorySummaryRecord,
+    SessionRecord, SourceEntryRecord, TranslatedEntryRecord, TranslationRevisionRecord, ValidationResultRecord,
+};
+use crate::database::schema::SCHEMA_VERSION;
+
+/// Current bundle format version (v2: scoped glossary terms, characters and analysis summaries; v3: batch telemetry)
+pub const BUNDLE_FORMAT_VERSION: u32 = 3;
+
+/// A session and all its data, ready to be moved to another database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    /// Bundle format version
+    pub format_version: u32,
+    /// Schema version of the exporting database
+    pub schema_version: i32,
+    /// Export timestamp (ISO 8601)
+    pub exported_at: String,
+    /// The session record
+    pub session: SessionRecord,
+    /// Source entries
+    pub source_entries: Vec<SourceEntryRecord>,
+    /// Translated entries, keyed to source entries by their exported IDs
+    #[serde(default)]
+    pub translated_entries: Vec<TranslatedEntryRecord>,
+    /// Translation revisions, oldest first per entry
+    #[serde(default)]
+    pub revisions: Vec<TranslationRevisionRecord>,
+    /// Validation results, keyed to translated entries by their exported IDs
+    #[serde(default)]
+    pub validation_results: Vec<ValidationResultRecord>,
+    /// Rolling history summaries
+    #[serde(default)]
+    pub history_summaries: Vec<HistorySummaryRecord>,
+    /// Session glossary terms
+    #[serde(default, deserialize_with = "crate::database::models::deserialize_glossary_terms")]
+    pub glossary_terms: Vec<GlossaryTermRecord>,
+    /// Characters seen in the session
+    #[serde(default)]
+    pub characters: Vec<CharacterProfileRecord>,
+    /// Analysis summaries of the session
+    #[serde(default)]
+    pub analysis_summaries: Vec<AnalysisSummaryRecord>,
+    /// Telemetry of the session's translated batches, in the order they were recorded
+    #[serde(default)]
+    pub batch_telemetry: Vec<BatchTelemetryRecord>,
+    /// Cached translations of the session's source lines
+    #[serde(default)]
+    pub cache_entries: Vec<CacheRecord>,
+}
+
+impl SessionBundle {
+    /// Create a bundle for a session with no entries yet
+    pub fn new(session: SessionRecord) -> Self {
+        Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            schema_version: SCHEMA_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            session,
+            source_entries: Vec::new(),
+            translated_entries: Vec::new(),
+            revisions: Vec::new(),
+            validation_results: Vec::new(),
+            history_summaries: Vec::new(),
+            glossary_terms: Vec::new(),
+            characters: Vec::new(),
+            analysis_summaries: Vec::new(),
+            batch_telemetry: Vec::new(),
+            cache_entries: Vec::new(),
+        }
+    }
+
+    /// Parse a bundle, rejecting formats newer than this build understands
+    pub fn parse(content: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(content).context("Invalid session bundle")?;
+        if bundle.format_version == 0 || bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(anyhow!(
+                "Unsupported session bundle format v{} (this version reads up to v{})",
+                bundle.format_version,
+                BUNDLE_FORMAT_VERSION
+            ));
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle to a file as pretty-printed JSON.
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write session bundle: {}", path.display()))
+    }
+
+    /// Read a bundle from a file.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read session bundle: {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Failed to load session bundle: {}", path.display()))
+    }
+}
+
+/// Outcome of importing a session bundle
+#[derive(Debug, Clone, Default)]
+pub struct BundleImportSummary {
+    /// ID of the imported session
+    pub session_id: String,
+    /// Whether the bundle's session ID was taken and a new one assigned
+    pub renamed: bool,
+    /// Source entries imported
+    pub source_entries: usize,
+    /// Translated entries imported
+    pub translated_entries: usize,
+    /// Revisions imported
+    pub revisions: usize,
+    /// Validation results imported
+    pub validation_results: usize,
+    /// Cache rows added or updated
+    pub cache_entries: usize,
+}
+
+impl fmt::Display for BundleImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Imported session {} ({} entries, {} translated, {} revisions, {} validation results, {} cache entries)",
+            &self.session_id[..8.min(self.session_id.len())],
+            self.source_entries,
+            self.translated_entries,
+            self.revisions,
+            self.validation_results,
+            self.cache_entries
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_bundle() -> SessionBundle {
+        SessionBundle::new(SessionRecord::new(
+            "session-1".to_string(),
+            "/tmp/movie.srt".to_string(),
+            "abc123".to_string(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            0,
+        ))
+    }
+
+    #[test]
+    fn test_sessionBundle_parse_shouldRoundTrip() {
+        let bundle = create_test_bundle();
+        let content = serde_json::to_string(&bundle).unwrap();
+
+        let parsed = SessionBundle::parse(&content).unwrap();
+
+        assert_eq!(parsed.format_version, BUNDLE_FORMAT_VERSION);
+        assert_eq!(parsed.session.id, "session-1");
+        assert!(parsed.source_entries.is_empty());
+    }
+
+    #[test]
+    fn test_sessionBundle_parse_withNewerFormat_shouldFail() {
+        let mut bundle = create_test_bundle();
+        bundle.format_version = BUNDLE_FORMAT_VERSION + 1;
+        let content = serde_json::to_string(&bundle).unwrap();
+
+        let err = SessionBundle::parse(&content).unwrap_err();
+
+        assert!(err.to_string().contains("Unsupported session bundle format"));
+    }
+}
@@ -0,0 +1,165 @@
+/*!
+ * Diffing of source entries between two versions of a subtitle file.
+ *
+ * When the subtitles of a session change (an edited SRT, a new cut of the
+ * video), entries are aligned on their normalised text with a longest
+ * common subsequence, so translations of unchanged lines can be kept even
+ * when lines were inserted or removed around them. Unmatched lines between
+ * two aligned ones count as edited, the rest as added or removed.
+ */
+
+use std::fmt;
+
+/// Alignment of old and new entries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryDiff {
+    /// Indices of unchanged entries, as (old index, new index)
+    pub unchanged: Vec<(usize, usize)>,
+    /// New entries that replace an old entry at the same place
+    pub edited: usize,
+    /// New entries without an old counterpart
+    pub added: usize,
+    /// Old entries without a new counterpart
+    pub removed: usize,
+}
+
+impl EntryDiff {
+    /// Align two lists of entry texts
+    pub fn compute<S: AsRef<str>>(old: &[S], new: &[S]) -> Self {
+        let old: Vec<String> = old.iter().map(|s| normalize(s.as_ref())).collect();
+        let new: Vec<String> = new.iter().map(|s| normalize(s.as_ref())).collect();
+
+        // Common prefix and suffix are matched directly, the LCS only runs on the middle
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut unchanged: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+        let old_mid = &old[prefix..old.len() - suffix];
+        let new_mid = &new[prefix..new.len() - suffix];
+        unchanged.extend(
+            longest_common_subsequence(old_mid, new_mid)
+                .into_iter()
+                .map(|(o, n)| (o + prefix, n + prefix)),
+        );
+        unchanged.extend((0..suffix).map(|i| (old.len() - suffix + i, new.len() - suffix + i)));
+
+        let mut diff = Self {
+            unchanged,
+            ..Default::default()
+        };
+
+        // Count the unmatched entries in each gap between aligned entries
+        let mut previous = (0, 0);
+        let anchors = diff.unchanged.iter().copied().chain(std::iter::once((old.len(), new.len())));
+        for (o, n) in anchors {
+            let old_gap = o - previous.0;
+            let new_gap = n - previous.1;
+            let edited = old_gap.min(new_gap);
+            diff.edited += edited;
+            diff.added += new_gap - edited;
+            diff.removed += old_gap - edited;
+            previous = (o + 1, n + 1);
+        }
+
+        diff
+    }
+
+    /// Check whether both versions have the same entries
+    pub fn is_identical(&self) -> bool {
+        self.edited == 0 && self.added == 0 && self.removed == 0
+    }
+}
+
+impl fmt::Display for EntryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} unchanged, {} edited, {} added, {} removed",
+            self.unchanged.len(),
+            self.edited,
+            self.added,
+            self.removed
+        )
+    }
+}
+
+/// Collapse whitespace so re-wrapped lines still match
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Matched index pairs of a longest common subsequence, in order
+fn longest_common_subsequence(old: &[String], new: &[String]) -> Vec<(usize, usize)> {
+    if old.is_empty() || new.is_empty() {
+        return Vec::new();
+    }
+
+    // lengths[i][j] = LCS length of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entryDiff_compute_withSameEntries_shouldBeIdentical() {
+        let diff = EntryDiff::compute(&["Hello", "World"], &["Hello", "World  "]);
+
+        assert!(diff.is_identical());
+        assert_eq!(diff.unchanged, vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_entryDiff_compute_shouldClassifyEditedAddedAndRemoved() {
+        let old = ["Hi.", "Where is he?", "Yes.", "Run!", "The end."];
+        let new = ["Hi.", "Where did he go?", "Yes.", "Over here.", "No.", "The end."];
+
+        let diff = EntryDiff::compute(&old, &new);
+
+        assert_eq!(diff.unchanged, vec![(0, 0), (2, 2), (4, 5)]);
+        assert_eq!((diff.edited, diff.added, diff.removed), (2, 1, 0));
+        assert_eq!(diff.to_string(), "3 unchanged, 2 edited, 1 added, 0 removed");
+    }
+
+    #[test]
+    fn test_entryDiff_compute_withInsertedLine_shouldKeepRepeatedLinesAligned() {
+        let old = ["Yes.", "No.", "Yes."];
+        let new = ["Intro", "Yes.", "No.", "Yes."];
+
+        let diff = EntryDiff::compute(&old, &new);
+
+        assert_eq!(diff.unchanged, vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!((diff.edited, diff.added, diff.removed), (0, 1, 0));
+    }
+}
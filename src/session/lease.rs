@@ -0,0 +1,156 @@
+/*!
+ * Session leases for parallel yastwai processes.
+ *
+ * A process that translates a session holds a lease on it: a database row
+ * naming the owner (a unique ID, the PID and the host), the time of the
+ * last heartbeat and an expiry. Other processes that find the session for
+ * a file leased fail with [`SessionInUse`] and skip the file instead of
+ * translating it a second time. The owner renews the lease in the background
+ * while it works; a crashed process stops renewing, so its lease expires
+ * and the session can be reclaimed. A process whose lease was reclaimed
+ * stops writing to the session: the session manager refuses its writes.
+ */
+
+use log::{debug, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::database::models::{lease_timestamp, SessionLeaseRecord};
+use crate::database::storage::Storage;
+
+/// Default lease duration without a heartbeat
+pub const DEFAULT_LEASE_TTL: Duration = Duration::from_secs(120);
+
+/// Identity of the process holding leases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaseOwner {
+    /// Unique ID of this holder
+    pub owner_id: String,
+    /// Process ID
+    pub pid: u32,
+    /// Host name
+    pub hostname: String,
+}
+
+impl LeaseOwner {
+    /// Identify the current process, with a fresh owner ID
+    pub fn current() -> Self {
+        Self {
+            owner_id: Uuid::new_v4().to_string(),
+            pid: std::process::id(),
+            hostname: hostname(),
+        }
+    }
+}
+
+impl std::fmt::Display for LeaseOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pid {} on {}", self.pid, self.hostname)
+    }
+}
+
+/// Error returned when another process holds the lease of the session for a file
+#[derive(Debug, Clone)]
+pub struct SessionInUse {
+    /// ID of the leased session
+    pub session_id: String,
+    /// The lease held on it, if it is still there
+    pub lease: Option<SessionLeaseRecord>,
+}
+
+impl std::fmt::Display for SessionInUse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = &self.session_id[..8.min(self.session_id.len())];
+        match &self.lease {
+            Some(lease) => write!(
+                f,
+                "session {} is in use by pid {} on {} (lease expires {})",
+                id, lease.pid, lease.hostname, lease.expires_at
+            ),
+            None => write!(f, "session {} is in use by another process", id),
+        }
+    }
+}
+
+impl std::error::Error for SessionInUse {}
+
+/// Host name from the environment, falling back to /etc/hostname
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Background renewal of a lease, stopped when dropped
+#[derive(Debug)]
+pub struct LeaseHeartbeat {
+    task: JoinHandle<()>,
+    lost: Arc<AtomicBool>,
+}
+
+impl LeaseHeartbeat {
+    /// Renew a lease every third of its duration until dropped or lost
+    pub fn start(storage: Arc<dyn Storage>, session_id: String, owner_id: String, ttl: Duration) -> Self {
+        let interval = (ttl / 3).max(Duration::from_millis(100));
+        let ttl = chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::seconds(120));
+        let lost = Arc::new(AtomicBool::new(false));
+        let lost_flag = Arc::clone(&lost);
+
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let now = chrono::Utc::now();
+                match storage
+                    .renew_lease(&session_id, &owner_id, &lease_timestamp(now), &lease_timestamp(now + ttl))
+                    .await
+                {
+                    Ok(true) => debug!("Renewed lease on session {}", &session_id[..8.min(session_id.len())]),
+                    Ok(false) => {
+                        warn!(
+                            "Lost the lease on session {}; stopping work on it",
+                            &session_id[..8.min(session_id.len())]
+                        );
+                        lost_flag.store(true, Ordering::SeqCst);
+                        return;
+                    }
+                    Err(e) => warn!("Failed to renew lease on session {}: {}", &session_id[..8.min(session_id.len())], e),
+                }
+            }
+        });
+
+        Self { task, lost }
+    }
+
+    /// Whether the lease was lost, so the work done under it must be dropped
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for LeaseHeartbeat {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaseOwner_current_shouldBeUniquePerCall() {
+        let first = LeaseOwner::current();
+        let second = LeaseOwner::current();
+
+        assert_eq!(first.pid, std::process::id());
+        assert!(!first.hostname.is_empty());
+        assert_ne!(first.owner_id, second.owner_id);
+    }
+}
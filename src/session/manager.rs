@@ -11,28 +11,63 @@
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::database::models::{
-    SessionRecord, SessionStatus, SourceEntryRecord, TranslatedEntryRecord, TranslationStatus,
+    AnalysisScope, AnalysisSummaryRecord, BatchTelemetry, BatchTelemetryRecord, CacheRecord, CharacterProfileRecord,
+    GlossaryTermKind, GlossaryTermRecord, HistorySummaryRecord, SessionLeaseRecord,
+    SessionRecord, SessionStatus, SourceEntryRecord, TranslatedEntryRecord, TranslationRevisionRecord, TranslationStatus,
+    ValidationResultRecord,
 };
-use crate::database::repository::Repository;
+use crate::app_config::SessionConfig;
+use crate::database::repository::{Repository, ResumableSessionQuery};
+use crate::database::storage::Storage;
 use crate::subtitle_processor::SubtitleEntry;
+use crate::translation::document::{EntryRevision, RevisionOrigin, SubtitleDocument};
+use crate::translation::pipeline::analysis_pass::{AnalysisMemory, CharacterProfile};
+use crate::translation::pipeline::validation_pass::ValidationPass;
 
+use super::bundle::{BundleImportSummary, SessionBundle};
+use super::diff::EntryDiff;
+use super::lease::{LeaseHeartbeat, LeaseOwner, SessionInUse, DEFAULT_LEASE_TTL};
 use super::models::{PendingEntry, ResumeResult, SessionCreateParams, SessionInfo, SessionStats};
+use super::report::SessionReport;
+use super::review::{ReviewImportSummary, ReviewRow, ReviewSheet};
 
 /// Session manager for handling translation session lifecycle
 pub struct SessionManager {
-    /// Repository for database operations
-    repo: Repository,
+    /// Storage backend for sessions, entries and cache
+    storage: Arc<dyn Storage>,
+    /// Identity used for the leases this manager takes
+    owner: LeaseOwner,
+    /// How long a lease lasts without a heartbeat
+    lease_ttl: Duration,
 }
 
 impl SessionManager {
-    /// Create a new session manager with the given repository
-    pub fn new(repo: Repository) -> Self {
-        Self { repo }
+    /// Create a new session manager with the given storage backend
+    pub fn new<S: Storage + 'static>(storage: S) -> Self {
+        Self::with_storage(Arc::new(storage))
+    }
+
+    /// Create a new session manager sharing a storage backend
+    pub fn with_storage(storage: Arc<dyn Storage>) -> Self {
+        Self {
+            storage,
+            owner: LeaseOwner::current(),
+            lease_ttl: DEFAULT_LEASE_TTL,
+        }
+    }
+
+    /// Set how long a session lease lasts without a heartbeat
+    pub fn with_lease_ttl(mut self, lease_ttl: Duration) -> Self {
+        self.lease_ttl = lease_ttl;
+        self
     }
 
     /// Create a new session manager with the default database
@@ -41,15 +76,26 @@ impl SessionManager {
         Ok(Self::new(repo))
     }
 
+    /// Create a new session manager with the configured storage backend
+    pub fn from_config(config: &SessionConfig) -> Result<Self> {
+        let path = (!config.database_path.is_empty()).then(|| Path::new(&config.database_path));
+        Ok(Self::with_storage(config.storage.open(path)?).with_lease_ttl(Duration::from_secs(config.lease_ttl_secs)))
+    }
+
     /// Create a new session manager with an in-memory database (for testing)
     pub fn new_in_memory() -> Result<Self> {
         let repo = Repository::new_in_memory()?;
         Ok(Self::new(repo))
     }
 
-    /// Get the underlying repository
-    pub fn repository(&self) -> &Repository {
-        &self.repo
+    /// Get the underlying storage backend
+    pub fn storage(&self) -> &dyn Storage {
+        self.storage.as_ref()
+    }
+
+    /// Get a shared handle to the storage backend (e.g. for the translation cache)
+    pub fn shared_storage(&self) -> Arc<dyn Storage> {
+        Arc::clone(&self.storage)
     }
 
     // =========================================================================
@@ -58,33 +104,48 @@ impl SessionManager {
 
     /// Create a new translation session
     pub async fn create_session(&self, params: SessionCreateParams) -> Result<SessionInfo> {
+        let session_record = self.new_session_record(&params).await?;
+
+        // Save session to database
+        self.storage.create_session(&session_record).await?;
+
+        self.insert_session_entries(&session_record, &params).await
+    }
+
+    /// Build the record of a new session for the given subtitles
+    async fn new_session_record(&self, params: &SessionCreateParams) -> Result<SessionRecord> {
         // Generate session ID
         let session_id = Uuid::new_v4().to_string();
 
         // Compute file hash
         let file_hash = self.hash_file(&params.source_file_path).await?;
 
-        info!(
-            "Creating new session {} for {} ({} entries)",
-            &session_id[..8],
-            params.source_file_path.display(),
-            params.entries.len()
-        );
-
         // Create session record
         let session_record = SessionRecord::new(
             session_id.clone(),
             params.source_file_path.to_string_lossy().to_string(),
             file_hash,
-            params.source_language,
-            params.target_language,
-            params.provider,
-            params.model,
+            params.source_language.clone(),
+            params.target_language.clone(),
+            params.provider.clone(),
+            params.model.clone(),
             params.entries.len() as i64,
-        );
+        )
+        .with_content_hash(params.content_hash());
 
-        // Save session to database
-        self.repo.create_session(&session_record).await?;
+        Ok(session_record)
+    }
+
+    /// Insert the source entries of a newly created session
+    async fn insert_session_entries(&self, session_record: &SessionRecord, params: &SessionCreateParams) -> Result<SessionInfo> {
+        let session_id = session_record.id.clone();
+
+        info!(
+            "Creating new session {} for {} ({} entries)",
+            &session_id[..8],
+            params.source_file_path.display(),
+            params.entries.len()
+        );
 
         // Create source entry records
         let source_entries: Vec<SourceEntryRecord> = params
@@ -102,14 +163,17 @@ impl SessionManager {
             .collect();
 
         // Insert source entries
-        self.repo.insert_source_entries(source_entries).await?;
+        self.storage.insert_source_entries(source_entries).await?;
 
         debug!("Session {} created successfully", &session_id[..8]);
 
-        Ok(SessionInfo::from_record(&session_record))
+        Ok(SessionInfo::from_record(session_record))
     }
 
     /// Try to resume an existing session or create a new one
+    ///
+    /// Fails with [`SessionInUse`] when another process holds the session for
+    /// these subtitles, so the caller can skip the file.
     pub async fn resume_or_create(
         &self,
         params: SessionCreateParams,
@@ -131,13 +195,18 @@ impl SessionManager {
             }
             ResumeResult::NotFound => {
                 // Create new session
-                let session = self.create_session(params.clone()).await?;
+                let session = self.create_leased_session(params.clone()).await?;
 
                 // Get pending entries (all entries for a new session)
                 let pending = self.get_pending_entries(&session.id).await?;
 
                 Ok((session, pending))
             }
+            ResumeResult::Leased { session, lease } => Err(SessionInUse {
+                session_id: session.id,
+                lease,
+            }
+            .into()),
             ResumeResult::SourceFileChanged {
                 session,
                 old_hash,
@@ -146,17 +215,27 @@ impl SessionManager {
                 warn!(
                     "Source file changed since session {} was created (old: {}, new: {})",
                     &session.id[..8],
-                    &old_hash[..8],
-                    &new_hash[..8]
+                    &old_hash[..8.min(old_hash.len())],
+                    &new_hash[..8.min(new_hash.len())]
                 );
 
-                // Mark old session as failed
-                self.repo
-                    .update_session_status(&session.id, SessionStatus::Failed)
-                    .await?;
+                // Mark old session as failed, unless another process is still working on it
+                if self.acquire_lease(&session.id).await? {
+                    self.storage
+                        .update_session_status(&session.id, SessionStatus::Failed)
+                        .await?;
+                    self.release_lease(&session.id).await?;
+                }
 
-                // Create new session
-                let new_session = self.create_session(params.clone()).await?;
+                // Create new session, keeping the translations of unchanged entries
+                let new_session = self.create_leased_session(params.clone()).await?;
+                let diff = self.carry_over_translations(&session.id, &new_session.id).await?;
+                info!(
+                    "Reusing translations from session {}: {}",
+                    &session.id[..8],
+                    diff
+                );
+                let new_session = self.get_session(&new_session.id).await?.unwrap_or(new_session);
                 let pending = self.get_pending_entries(&new_session.id).await?;
 
                 Ok((new_session, pending))
@@ -170,7 +249,7 @@ impl SessionManager {
                 warn!("Previous session {} failed, creating new session", &session.id[..8]);
 
                 // Create new session
-                let new_session = self.create_session(params.clone()).await?;
+                let new_session = self.create_leased_session(params.clone()).await?;
                 let pending = self.get_pending_entries(&new_session.id).await?;
 
                 Ok((new_session, pending))
@@ -180,29 +259,31 @@ impl SessionManager {
 
     /// Try to resume an existing session
     pub async fn try_resume(&self, params: &SessionCreateParams) -> Result<ResumeResult> {
-        // Compute current file hash
-        let current_hash = self.hash_file(&params.source_file_path).await?;
-
-        // Look for a matching session
-        let existing_session = self
-            .repo
-            .find_resumable_session(
-                &current_hash,
-                &params.source_language,
-                &params.target_language,
-                &params.provider,
-                &params.model,
-            )
-            .await?;
+        // Look for a session with the same subtitles, the same file or the same path
+        let query = ResumableSessionQuery {
+            source_file_path: params.source_file_path.to_string_lossy().to_string(),
+            source_file_hash: self.hash_file(&params.source_file_path).await?,
+            source_content_hash: params.content_hash(),
+            source_language: params.source_language.clone(),
+            target_language: params.target_language.clone(),
+            provider: params.provider.clone(),
+            model: params.model.clone(),
+        };
+        let existing_session = self.storage.find_resumable_session(&query).await?;
 
         match existing_session {
             Some(session) => {
-                // Verify file hash matches
-                if session.source_file_hash != current_hash {
+                // Verify the subtitles are unchanged
+                if !query.matches_content(&session) {
+                    let (old_hash, new_hash) = if session.source_content_hash.is_empty() {
+                        (session.source_file_hash.clone(), query.source_file_hash)
+                    } else {
+                        (session.source_content_hash.clone(), query.source_content_hash)
+                    };
                     return Ok(ResumeResult::SourceFileChanged {
                         session: SessionInfo::from_record(&session),
-                        old_hash: session.source_file_hash.clone(),
-                        new_hash: current_hash,
+                        old_hash,
+                        new_hash,
                     });
                 }
 
@@ -219,15 +300,23 @@ impl SessionManager {
                         })
                     }
                     SessionStatus::InProgress | SessionStatus::Paused => {
+                        // Skip sessions another process is working on
+                        if !self.acquire_lease(&session.id).await? {
+                            return Ok(ResumeResult::Leased {
+                                session: SessionInfo::from_record(&session),
+                                lease: self.storage.get_lease(&session.id).await?,
+                            });
+                        }
+
                         // Get pending entries
-                        let pending_records = self.repo.get_pending_entries(&session.id).await?;
+                        let pending_records = self.storage.get_pending_entries(&session.id).await?;
                         let pending_entries: Vec<PendingEntry> = pending_records
                             .iter()
                             .map(PendingEntry::from_source_record)
                             .collect();
 
                         // Update session status to in_progress
-                        self.repo
+                        self.storage
                             .update_session_status(&session.id, SessionStatus::InProgress)
                             .await?;
 
@@ -242,19 +331,73 @@ impl SessionManager {
         }
     }
 
+    /// Copy the translations of unchanged entries from an older session of the same source
+    ///
+    /// Entries are aligned on their text, so lines inserted or removed around
+    /// an entry do not lose its translation. Revisions, glossary terms and
+    /// analysis results are carried over as well.
+    pub async fn carry_over_translations(&self, old_session_id: &str, new_session_id: &str) -> Result<EntryDiff> {
+        let old_sources = self.storage.get_source_entries(old_session_id).await?;
+        let new_sources = self.storage.get_source_entries(new_session_id).await?;
+        let diff = EntryDiff::compute(
+            &old_sources.iter().map(|s| s.source_text.as_str()).collect::<Vec<_>>(),
+            &new_sources.iter().map(|s| s.source_text.as_str()).collect::<Vec<_>>(),
+        );
+
+        let translated: HashMap<i64, TranslatedEntryRecord> = self
+            .storage
+            .get_translated_entries(old_session_id)
+            .await?
+            .into_iter()
+            .map(|(source, translated)| (source.id, translated))
+            .collect();
+
+        let mut entries = Vec::new();
+        let mut revisions = Vec::new();
+        for &(old_index, new_index) in &diff.unchanged {
+            let old_id = old_sources[old_index].id;
+            let new_id = new_sources[new_index].id;
+            let Some(entry) = translated.get(&old_id) else {
+                continue;
+            };
+            entries.push(TranslatedEntryRecord { source_entry_id: new_id, ..entry.clone() });
+            revisions.extend(
+                self.storage
+                    .get_revisions(old_id)
+                    .await?
+                    .into_iter()
+                    .map(|r| TranslationRevisionRecord { source_entry_id: new_id, ..r }),
+            );
+        }
+        let old_scope = AnalysisScope::Session(old_session_id.to_string());
+
+        self.storage.insert_translated_entries(entries).await?;
+        self.storage.add_revisions(revisions).await?;
+        self.save_scoped_analysis(
+            &AnalysisScope::Session(new_session_id.to_string()),
+            self.storage.get_glossary_terms(&old_scope).await?,
+            self.storage.get_character_profiles(&old_scope).await?,
+            self.storage.get_analysis_summaries(&old_scope).await?,
+        )
+        .await?;
+        self.refresh_progress(new_session_id).await?;
+
+        Ok(diff)
+    }
+
     // =========================================================================
     // Session State Management
     // =========================================================================
 
     /// Get session by ID
     pub async fn get_session(&self, session_id: &str) -> Result<Option<SessionInfo>> {
-        let record = self.repo.get_session(session_id).await?;
+        let record = self.storage.get_session(session_id).await?;
         Ok(record.map(|r| SessionInfo::from_record(&r)))
     }
 
     /// Get pending entries for a session
     pub async fn get_pending_entries(&self, session_id: &str) -> Result<Vec<PendingEntry>> {
-        let records = self.repo.get_pending_entries(session_id).await?;
+        let records = self.storage.get_pending_entries(session_id).await?;
         Ok(records.iter().map(PendingEntry::from_source_record).collect())
     }
 
@@ -264,6 +407,8 @@ impl SessionManager {
         session_id: &str,
         translations: Vec<(i64, String)>, // (source_entry_id, translated_text)
     ) -> Result<()> {
+        self.ensure_lease(session_id).await?;
+
         let entries: Vec<TranslatedEntryRecord> = translations
             .into_iter()
             .map(|(source_entry_id, translated_text)| {
@@ -271,19 +416,77 @@ impl SessionManager {
             })
             .collect();
 
-        self.repo.insert_translated_entries(entries).await?;
+        self.storage.insert_translated_entries(entries).await?;
+        self.refresh_progress(session_id).await
+    }
+
+    /// Record candidate translations, keyed by source entry ID
+    ///
+    /// Model-produced revisions are attributed to the session's provider and model.
+    pub async fn record_revisions(&self, session_id: &str, revisions: Vec<(i64, EntryRevision)>) -> Result<()> {
+        self.ensure_lease(session_id).await?;
+
+        let session = self
+            .storage
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+
+        let records = revisions
+            .into_iter()
+            .map(|(source_entry_id, revision)| {
+                let mut record = TranslationRevisionRecord::new(source_entry_id, revision.text, revision.origin);
+                if matches!(revision.origin, RevisionOrigin::Translation | RevisionOrigin::FeedbackRetry) {
+                    record.provider = Some(session.provider.clone());
+                    record.model = Some(session.model.clone());
+                }
+                record.detail = revision.detail;
+                record.quality_score = revision.confidence.map(f64::from);
+                record.validation_passed = revision.validation_passed;
+                record.validation_message = revision.validation_message;
+                record
+            })
+            .collect();
+
+        self.storage.add_revisions(records).await
+    }
+
+    /// Get an entry (by sequence number) and its revisions, oldest first
+    pub async fn get_entry_history(
+        &self,
+        session_id: &str,
+        seq_num: i64,
+    ) -> Result<Option<(SourceEntryRecord, Vec<TranslationRevisionRecord>)>> {
+        let source = self
+            .storage
+            .get_source_entries(session_id)
+            .await?
+            .into_iter()
+            .find(|s| s.seq_num == seq_num);
 
-        // Update session progress
-        let session = self.repo.get_session(session_id).await?;
+        match source {
+            Some(source) => {
+                let revisions = self.storage.get_revisions(source.id).await?;
+                Ok(Some((source, revisions)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Recount the completed entries of a session
+    async fn refresh_progress(&self, session_id: &str) -> Result<()> {
+        let session = self.storage.get_session(session_id).await?;
         if let Some(_session) = session {
-            let translated = self.repo.get_translated_entries(session_id).await?;
+            let translated = self.storage.get_translated_entries(session_id).await?;
             let completed_count = translated
                 .iter()
-                .filter(|(_, t)| t.translation_status == TranslationStatus::Validated || 
-                                 t.translation_status == TranslationStatus::Translated)
+                .filter(|(_, t)| matches!(
+                    t.translation_status,
+                    TranslationStatus::Validated | TranslationStatus::Translated | TranslationStatus::Approved
+                ))
                 .count() as i64;
 
-            self.repo
+            self.storage
                 .update_session_progress(session_id, completed_count)
                 .await?;
         }
@@ -291,28 +494,176 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Record rolling history summaries for a session
+    pub async fn record_history_summaries(
+        &self,
+        session_id: &str,
+        summaries: Vec<(i64, i64, i64, String)>, // (start_seq_num, end_seq_num, entry_count, text)
+    ) -> Result<()> {
+        self.ensure_lease(session_id).await?;
+
+        let records: Vec<HistorySummaryRecord> = summaries
+            .into_iter()
+            .map(|(start_seq_num, end_seq_num, entry_count, text)| {
+                HistorySummaryRecord::new(
+                    session_id.to_string(),
+                    start_seq_num,
+                    end_seq_num,
+                    entry_count,
+                    text,
+                )
+            })
+            .collect();
+
+        self.storage.save_history_summaries(records).await
+    }
+
+    /// Get rolling history summaries stored for a session, oldest first
+    pub async fn get_history_summaries(&self, session_id: &str) -> Result<Vec<HistorySummaryRecord>> {
+        self.storage.get_history_summaries(session_id).await
+    }
+
+    /// Record the per-batch telemetry of one translation run
+    pub async fn record_batch_telemetry(&self, session_id: &str, run_id: &str, batches: &[BatchTelemetry]) -> Result<()> {
+        let records: Vec<BatchTelemetryRecord> = batches
+            .iter()
+            .map(|batch| BatchTelemetryRecord::new(session_id.to_string(), run_id.to_string(), batch.clone()))
+            .collect();
+
+        self.storage.insert_batch_telemetry(records).await
+    }
+
+    /// Build the run history report of a session
+    pub async fn session_report(&self, session_id: &str) -> Result<SessionReport> {
+        let session = self
+            .get_session(session_id)
+            .await?
+            .with_context(|| format!("Session not found: {}", session_id))?;
+        let stats = self.get_session_stats(session_id).await?;
+        let telemetry = self.storage.get_batch_telemetry(session_id).await?;
+
+        Ok(SessionReport::new(session, stats, telemetry))
+    }
+
     /// Mark session as paused (for graceful interruption)
     pub async fn pause_session(&self, session_id: &str) -> Result<()> {
+        self.ensure_lease(session_id).await?;
         info!("Pausing session {}", &session_id[..8.min(session_id.len())]);
-        self.repo
+        self.storage
             .update_session_status(session_id, SessionStatus::Paused)
-            .await
+            .await?;
+        self.release_lease(session_id).await
     }
 
     /// Mark session as completed
     pub async fn complete_session(&self, session_id: &str) -> Result<()> {
+        self.ensure_lease(session_id).await?;
         info!("Completing session {}", &session_id[..8.min(session_id.len())]);
-        self.repo
+        self.storage
             .update_session_status(session_id, SessionStatus::Completed)
-            .await
+            .await?;
+        self.release_lease(session_id).await
     }
 
     /// Mark session as failed
     pub async fn fail_session(&self, session_id: &str) -> Result<()> {
+        self.ensure_lease(session_id).await?;
         warn!("Marking session {} as failed", &session_id[..8.min(session_id.len())]);
-        self.repo
+        self.storage
             .update_session_status(session_id, SessionStatus::Failed)
-            .await
+            .await?;
+        self.release_lease(session_id).await
+    }
+
+    // =========================================================================
+    // Session Leases
+    // =========================================================================
+
+    /// Take the lease of a session for this manager
+    ///
+    /// Succeeds if the session is not leased, its lease expired (its holder
+    /// crashed or hung) or this manager already holds it.
+    pub async fn acquire_lease(&self, session_id: &str) -> Result<bool> {
+        let previous = self.storage.get_lease(session_id).await?;
+        let lease = self.new_lease(session_id)?;
+        if !self.storage.acquire_lease(&lease).await? {
+            return Ok(false);
+        }
+
+        if let Some(previous) = previous
+            && previous.owner_id != self.owner.owner_id
+        {
+            info!(
+                "Reclaimed session {} from pid {} on {} (lease expired {})",
+                &session_id[..8.min(session_id.len())],
+                previous.pid,
+                previous.hostname,
+                previous.expires_at
+            );
+        }
+        Ok(true)
+    }
+
+    /// Fail with [`SessionInUse`] if another manager has taken over the lease of a session
+    ///
+    /// Every write on behalf of a session checks this first, so a process whose
+    /// lease expired and was reclaimed stops writing to the session.
+    pub async fn ensure_lease(&self, session_id: &str) -> Result<()> {
+        match self.storage.get_lease(session_id).await? {
+            Some(lease) if lease.owner_id != self.owner.owner_id => Err(SessionInUse {
+                session_id: session_id.to_string(),
+                lease: Some(lease),
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Release this manager's lease on a session
+    pub async fn release_lease(&self, session_id: &str) -> Result<()> {
+        self.storage.release_lease(session_id, &self.owner.owner_id).await
+    }
+
+    /// Keep this manager's lease on a session alive until the returned heartbeat is dropped
+    pub fn start_heartbeat(&self, session_id: &str) -> LeaseHeartbeat {
+        LeaseHeartbeat::start(
+            Arc::clone(&self.storage),
+            session_id.to_string(),
+            self.owner.owner_id.clone(),
+            self.lease_ttl,
+        )
+    }
+
+    /// Create a new session leased to this manager
+    ///
+    /// The session and its lease are created in one step, so of two processes
+    /// starting on the same subtitles only one creates a session and the other
+    /// fails with [`SessionInUse`].
+    async fn create_leased_session(&self, params: SessionCreateParams) -> Result<SessionInfo> {
+        let session_record = self.new_session_record(&params).await?;
+        let lease = self.new_lease(&session_record.id)?;
+        if let Some(existing) = self.storage.create_leased_session(&session_record, &lease).await? {
+            return Err(SessionInUse {
+                lease: self.storage.get_lease(&existing.id).await?,
+                session_id: existing.id,
+            }
+            .into());
+        }
+
+        let session = self.insert_session_entries(&session_record, &params).await?;
+        self.ensure_lease(&session.id).await?;
+        Ok(session)
+    }
+
+    /// Build a lease on a session for this manager, starting now
+    fn new_lease(&self, session_id: &str) -> Result<SessionLeaseRecord> {
+        Ok(SessionLeaseRecord::new(
+            session_id.to_string(),
+            self.owner.owner_id.clone(),
+            self.owner.pid as i64,
+            self.owner.hostname.clone(),
+            chrono::Duration::from_std(self.lease_ttl)?,
+        ))
     }
 
     // =========================================================================
@@ -321,26 +672,49 @@ impl SessionManager {
 
     /// List all sessions
     pub async fn list_sessions(&self, status: Option<SessionStatus>) -> Result<Vec<SessionInfo>> {
-        let records = self.repo.list_sessions(status).await?;
+        let records = self.storage.list_sessions(status).await?;
         Ok(records.iter().map(SessionInfo::from_record).collect())
     }
 
+    /// Get the source entries of the most recent session for a file
+    ///
+    /// This is the source a translation of the file was last made from.
+    pub async fn latest_source_entries(&self, source_file_path: &Path) -> Result<Option<(SessionInfo, Vec<SubtitleEntry>)>> {
+        let canonical = source_file_path.canonicalize().ok();
+        let sessions = self.storage.list_sessions(None).await?;
+        let Some(session) = sessions.iter().find(|s| {
+            let path = Path::new(&s.source_file_path);
+            path == source_file_path || (canonical.is_some() && path.canonicalize().ok() == canonical)
+        }) else {
+            return Ok(None);
+        };
+
+        let entries = self
+            .storage
+            .get_source_entries(&session.id)
+            .await?
+            .into_iter()
+            .map(|e| SubtitleEntry::new(e.seq_num as usize, e.start_time_ms as u64, e.end_time_ms as u64, e.source_text))
+            .collect();
+        Ok(Some((SessionInfo::from_record(session), entries)))
+    }
+
     /// Delete a session
     pub async fn delete_session(&self, session_id: &str) -> Result<()> {
         info!("Deleting session {}", session_id);
-        self.repo.delete_session(session_id).await
+        self.storage.delete_session(session_id).await
     }
 
     /// Clean up old sessions
     pub async fn cleanup_old_sessions(&self, days: i64) -> Result<i64> {
         info!("Cleaning up sessions older than {} days", days);
-        self.repo.delete_old_sessions(days).await
+        self.storage.delete_old_sessions(days).await
     }
 
     /// Get session statistics
     pub async fn get_session_stats(&self, session_id: &str) -> Result<SessionStats> {
-        let entries = self.repo.get_source_entries(session_id).await?;
-        let translated = self.repo.get_translated_entries(session_id).await?;
+        let entries = self.storage.get_source_entries(session_id).await?;
+        let translated = self.storage.get_translated_entries(session_id).await?;
 
         let mut stats = SessionStats::new();
         stats.total_entries = entries.len() as i64;
@@ -358,6 +732,11 @@ impl SessionManager {
             .filter(|(_, t)| t.translation_status == TranslationStatus::Validated)
             .count() as i64;
 
+        for batch in self.storage.get_batch_telemetry(session_id).await? {
+            stats.cache_hits += batch.batch.cache_hits as i64;
+            stats.cache_misses += batch.batch.requests as i64;
+        }
+
         Ok(stats)
     }
 
@@ -366,7 +745,7 @@ impl SessionManager {
         &self,
         session_id: &str,
     ) -> Result<Vec<SubtitleEntry>> {
-        let entries = self.repo.get_translated_entries(session_id).await?;
+        let entries = self.storage.get_translated_entries(session_id).await?;
 
         let subtitles: Vec<SubtitleEntry> = entries
             .into_iter()
@@ -383,6 +762,501 @@ impl SessionManager {
         Ok(subtitles)
     }
 
+    // =========================================================================
+    // Human Review
+    // =========================================================================
+
+    /// Build a review sheet for a session
+    ///
+    /// Validation issues are recomputed from the stored translations, so the
+    /// sheet reflects the session as it is now.
+    pub async fn export_review(&self, session_id: &str) -> Result<ReviewSheet> {
+        let session = self
+            .storage
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+        let sources = self.storage.get_source_entries(session_id).await?;
+        let translated: HashMap<i64, String> = self
+            .storage
+            .get_translated_entries(session_id)
+            .await?
+            .into_iter()
+            .map(|(source, translated)| (source.seq_num, translated.translated_text))
+            .collect();
+
+        let entries: Vec<SubtitleEntry> = sources
+            .iter()
+            .map(|s| {
+                SubtitleEntry::new(
+                    s.seq_num as usize,
+                    s.start_time_ms as u64,
+                    s.end_time_ms as u64,
+                    s.source_text.clone(),
+                )
+            })
+            .collect();
+        let mut doc = SubtitleDocument::from_entries(entries, &session.source_language);
+        for entry in doc.entries.iter_mut() {
+            if let Some(text) = translated.get(&(entry.id as i64)) {
+                entry.set_translation(text.clone(), None);
+            }
+        }
+
+        let mut issues: HashMap<usize, Vec<String>> = HashMap::new();
+        for issue in &ValidationPass::with_defaults().validate(&doc).issues {
+            issues.entry(issue.entry_id()).or_default().push(issue.description());
+        }
+
+        let rows = doc
+            .entries
+            .iter()
+            .map(|entry| ReviewRow {
+                entry_id: entry.id,
+                start_time_ms: entry.timecode.start_ms,
+                end_time_ms: entry.timecode.end_ms,
+                source: entry.original_text.clone(),
+                machine: entry.translated_text.clone().unwrap_or_default(),
+                issues: issues.remove(&entry.id).unwrap_or_default(),
+                final_text: String::new(),
+                terms: Vec::new(),
+            })
+            .collect();
+
+        Ok(ReviewSheet::new(rows))
+    }
+
+    /// Apply the reviewed rows of a sheet to a session
+    ///
+    /// Reviewed entries are stored as human-approved, which later machine runs
+    /// never overwrite. Their final text also replaces the cached translation
+    /// of the source line (see [`Self::reuse_cached_translations`]). Names the
+    /// editor restored and the term translations of the terms column are added
+    /// to the session glossary, which the analysis pass of later runs loads.
+    pub async fn import_review(&self, session_id: &str, sheet: &ReviewSheet) -> Result<ReviewImportSummary> {
+        let session = self
+            .storage
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+        let sources: HashMap<i64, SourceEntryRecord> = self
+            .storage
+            .get_source_entries(session_id)
+            .await?
+            .into_iter()
+            .map(|s| (s.seq_num, s))
+            .collect();
+        let translated: HashMap<i64, String> = self
+            .storage
+            .get_translated_entries(session_id)
+            .await?
+            .into_iter()
+            .map(|(source, translated)| (source.seq_num, translated.translated_text))
+            .collect();
+
+        let mut summary = ReviewImportSummary::default();
+        let mut approved = Vec::new();
+        let mut revisions = Vec::new();
+        let mut cache_records = Vec::new();
+
+        for row in sheet.reviewed() {
+            let Some(source) = sources.get(&(row.entry_id as i64)) else {
+                warn!("Review sheet entry {} is not in session {}", row.entry_id, &session_id[..8.min(session_id.len())]);
+                summary.unknown.push(row.entry_id);
+                continue;
+            };
+
+            let current = translated.get(&source.seq_num).map(|t| t.trim()).unwrap_or_default();
+            if current != row.machine.trim() {
+                warn!("Entry {} changed since the review sheet was exported", row.entry_id);
+                summary.outdated.push(row.entry_id);
+            }
+
+            let final_text = row.final_text.trim().to_string();
+            summary.approved += 1;
+            if row.is_changed() {
+                summary.changed += 1;
+            }
+            for name in row.restored_names() {
+                if !summary.names.contains(&name) {
+                    summary.names.push(name);
+                }
+            }
+
+            cache_records.push(CacheRecord::new(
+                Repository::hash_text(&source.source_text),
+                source.source_text.clone(),
+                session.source_language.clone(),
+                session.target_language.clone(),
+                final_text.clone(),
+                session.provider.clone(),
+                session.model.clone(),
+            ));
+            let mut revision = TranslationRevisionRecord::new(source.id, final_text.clone(), RevisionOrigin::Human);
+            revision.detail = Some(if row.is_changed() { "edited in review" } else { "approved in review" }.to_string());
+            revisions.push(revision);
+            approved.push((source.id, final_text));
+        }
+
+        self.storage.approve_translations(approved).await?;
+        self.storage.add_revisions(revisions).await?;
+        for record in &cache_records {
+            self.storage.replace_cached_translation(record).await?;
+        }
+        let scope = AnalysisScope::Session(session_id.to_string());
+        let mut terms: Vec<GlossaryTermRecord> = summary
+            .names
+            .iter()
+            .map(|name| GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Character, name.clone(), None))
+            .collect();
+        for (source, target) in sheet.rows.iter().flat_map(|r| &r.terms) {
+            terms.retain(|t| !(t.kind == GlossaryTermKind::Term && &t.source_term == source));
+            terms.push(GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Term, source.clone(), Some(target.clone())));
+        }
+        summary.terms = terms.iter().filter(|t| t.kind == GlossaryTermKind::Term).count();
+        self.storage.save_glossary_terms(terms).await?;
+        self.refresh_progress(session_id).await?;
+
+        info!("Imported review for session {}: {}", &session_id[..8.min(session_id.len())], summary);
+        Ok(summary)
+    }
+
+    /// Translate pending entries of a session from cached single-line translations
+    ///
+    /// Lines approved in review, or brought in with a bundle, are cached
+    /// without context. Reusing them keeps the editor's text in later
+    /// sessions that contain the same lines. Returns the number of entries
+    /// filled in.
+    pub async fn reuse_cached_translations(&self, session_id: &str) -> Result<usize> {
+        let session = self
+            .storage
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+
+        let mut entries = Vec::new();
+        let mut revisions = Vec::new();
+        for source in self.storage.get_pending_entries(session_id).await? {
+            let Some(text) = self
+                .storage
+                .get_cached_translation(
+                    &source.source_text,
+                    &session.source_language,
+                    &session.target_language,
+                    &session.provider,
+                    &session.model,
+                )
+                .await?
+            else {
+                continue;
+            };
+            let mut revision = TranslationRevisionRecord::new(source.id, text.clone(), RevisionOrigin::Translation);
+            revision.detail = Some("reused from the translation cache".to_string());
+            revisions.push(revision);
+            entries.push(TranslatedEntryRecord::new(source.id, text));
+        }
+
+        let reused = entries.len();
+        if reused > 0 {
+            self.storage.insert_translated_entries(entries).await?;
+            self.storage.add_revisions(revisions).await?;
+            self.refresh_progress(session_id).await?;
+            debug!("Reused {} cached translations for session {}", reused, &session_id[..8.min(session_id.len())]);
+        }
+        Ok(reused)
+    }
+
+    /// Get the glossary terms recorded for a session
+    pub async fn get_glossary_terms(&self, session_id: &str) -> Result<Vec<GlossaryTermRecord>> {
+        self.storage
+            .get_glossary_terms(&AnalysisScope::Session(session_id.to_string()))
+            .await
+    }
+
+    // =========================================================================
+    // Analysis Memory
+    // =========================================================================
+
+    /// Load the analysis results kept for a session or series
+    ///
+    /// Only the summary with the given label (the episode, or "" for a
+    /// session) is loaded.
+    pub async fn load_analysis_memory(&self, scope: &AnalysisScope, label: &str) -> Result<AnalysisMemory> {
+        let mut memory = AnalysisMemory::default();
+
+        for term in self.storage.get_glossary_terms(scope).await? {
+            match (term.kind, term.target_term) {
+                (GlossaryTermKind::Term, Some(target)) => {
+                    memory.glossary.add_term(&term.source_term, &target, term.context.as_deref())
+                }
+                (GlossaryTermKind::Technical, Some(target)) => memory.glossary.add_technical_term(&term.source_term, &target),
+                (GlossaryTermKind::Character, _) => memory.glossary.add_character(&term.source_term),
+                _ => {}
+            }
+        }
+        for profile in self.storage.get_character_profiles(scope).await? {
+            memory.glossary.add_character(&profile.name);
+            memory.characters.push(CharacterProfile {
+                name: profile.name,
+                mentions: profile.mentions.max(0) as usize,
+            });
+        }
+        memory.summary = self
+            .storage
+            .get_analysis_summaries(scope)
+            .await?
+            .into_iter()
+            .find(|s| s.label == label)
+            .map(|s| s.summary_text);
+
+        Ok(memory)
+    }
+
+    /// Save analysis results for a session or series
+    ///
+    /// Characters seen for the first time are recorded as first seen in `label`.
+    pub async fn save_analysis_memory(&self, scope: &AnalysisScope, label: &str, memory: &AnalysisMemory) -> Result<()> {
+        let glossary = &memory.glossary;
+        let mut terms: Vec<GlossaryTermRecord> = glossary
+            .terms
+            .values()
+            .map(|t| GlossaryTermRecord {
+                context: t.context.clone(),
+                ..GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Term, t.source.clone(), Some(t.target.clone()))
+            })
+            .collect();
+        terms.extend(glossary.technical_terms.iter().map(|(source, target)| {
+            GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Technical, source.clone(), Some(target.clone()))
+        }));
+        terms.extend(
+            glossary
+                .character_names
+                .iter()
+                .map(|name| GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Character, name.clone(), None)),
+        );
+        let first_seen = (!label.is_empty()).then(|| label.to_string());
+        let profiles = memory
+            .characters
+            .iter()
+            .map(|p| CharacterProfileRecord::new(scope.clone(), p.name.clone(), p.mentions as i64, first_seen.clone()))
+            .collect();
+
+        self.storage.save_glossary_terms(terms).await?;
+        self.storage.save_character_profiles(profiles).await?;
+        if let Some(ref summary) = memory.summary {
+            self.storage
+                .save_analysis_summary(&AnalysisSummaryRecord::new(scope.clone(), label.to_string(), summary.clone()))
+                .await?;
+        }
+
+        debug!(
+            "Saved analysis for {}: {} glossary entries, {} characters",
+            scope,
+            glossary.terms.len() + glossary.technical_terms.len() + glossary.character_names.len(),
+            memory.characters.len()
+        );
+        Ok(())
+    }
+
+    /// Save glossary terms, characters and summaries taken from another scope under `scope`
+    async fn save_scoped_analysis(
+        &self,
+        scope: &AnalysisScope,
+        terms: Vec<GlossaryTermRecord>,
+        characters: Vec<CharacterProfileRecord>,
+        summaries: Vec<AnalysisSummaryRecord>,
+    ) -> Result<()> {
+        self.storage
+            .save_glossary_terms(terms.into_iter().map(|t| GlossaryTermRecord { scope: scope.clone(), ..t }).collect())
+            .await?;
+        self.storage
+            .save_character_profiles(
+                characters
+                    .into_iter()
+                    .map(|p| CharacterProfileRecord { scope: scope.clone(), ..p })
+                    .collect(),
+            )
+            .await?;
+        for summary in summaries {
+            self.storage
+                .save_analysis_summary(&AnalysisSummaryRecord { scope: scope.clone(), ..summary })
+                .await?;
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // Bundles
+    // =========================================================================
+
+    /// Collect a session and all its data into a portable bundle
+    pub async fn export_bundle(&self, session_id: &str) -> Result<SessionBundle> {
+        let session = self
+            .storage
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+
+        let mut bundle = SessionBundle::new(session);
+        bundle.source_entries = self.storage.get_source_entries(session_id).await?;
+        for source in &bundle.source_entries {
+            bundle.revisions.extend(self.storage.get_revisions(source.id).await?);
+        }
+        for (_, translated) in self.storage.get_translated_entries(session_id).await? {
+            bundle
+                .validation_results
+                .extend(self.storage.get_validation_results(translated.id).await?);
+            bundle.translated_entries.push(translated);
+        }
+        bundle.history_summaries = self.storage.get_history_summaries(session_id).await?;
+        let scope = AnalysisScope::Session(session_id.to_string());
+        bundle.glossary_terms = self.storage.get_glossary_terms(&scope).await?;
+        bundle.characters = self.storage.get_character_profiles(&scope).await?;
+        bundle.analysis_summaries = self.storage.get_analysis_summaries(&scope).await?;
+        bundle.batch_telemetry = self.storage.get_batch_telemetry(session_id).await?;
+        bundle.cache_entries = self.storage.get_session_cache_entries(session_id).await?;
+
+        Ok(bundle)
+    }
+
+    /// Import a bundle as a new session
+    ///
+    /// The bundle keeps its session ID unless that ID is already taken, in
+    /// which case a new one is assigned. Entry IDs are always remapped, and a
+    /// failed import removes the partly imported session.
+    pub async fn import_bundle(&self, bundle: &SessionBundle) -> Result<BundleImportSummary> {
+        let mut session = bundle.session.clone();
+        let renamed = self.storage.get_session(&session.id).await?.is_some();
+        if renamed {
+            session.id = Uuid::new_v4().to_string();
+        }
+
+        self.storage.create_session(&session).await?;
+        match self.import_bundle_entries(&session.id, bundle).await {
+            Ok(mut summary) => {
+                summary.renamed = renamed;
+                info!("{}", summary);
+                Ok(summary)
+            }
+            Err(e) => {
+                if let Err(cleanup) = self.storage.delete_session(&session.id).await {
+                    warn!("Failed to remove partly imported session {}: {}", &session.id[..8], cleanup);
+                }
+                Err(e.context("Failed to import session bundle"))
+            }
+        }
+    }
+
+    /// Insert the entries of a bundle into an already created session
+    async fn import_bundle_entries(&self, session_id: &str, bundle: &SessionBundle) -> Result<BundleImportSummary> {
+        let sources = bundle
+            .source_entries
+            .iter()
+            .map(|s| SourceEntryRecord { session_id: session_id.to_string(), ..s.clone() })
+            .collect();
+        self.storage.insert_source_entries(sources).await?;
+
+        // Exported source IDs -> new source IDs, matched by sequence number
+        let new_ids: HashMap<i64, i64> = self
+            .storage
+            .get_source_entries(session_id)
+            .await?
+            .into_iter()
+            .map(|s| (s.seq_num, s.id))
+            .collect();
+        let source_ids: HashMap<i64, i64> = bundle
+            .source_entries
+            .iter()
+            .filter_map(|s| new_ids.get(&s.seq_num).map(|id| (s.id, *id)))
+            .collect();
+        let remap_source = |old: i64| {
+            source_ids
+                .get(&old)
+                .copied()
+                .ok_or_else(|| anyhow::anyhow!("Bundle references unknown source entry {}", old))
+        };
+
+        let translated = bundle
+            .translated_entries
+            .iter()
+            .map(|t| Ok(TranslatedEntryRecord { source_entry_id: remap_source(t.source_entry_id)?, ..t.clone() }))
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.insert_translated_entries(translated).await?;
+
+        // Exported translated IDs -> new translated IDs, through their source entries
+        let new_translated_ids: HashMap<i64, i64> = self
+            .storage
+            .get_translated_entries(session_id)
+            .await?
+            .into_iter()
+            .map(|(source, translated)| (source.id, translated.id))
+            .collect();
+        let translated_ids: HashMap<i64, i64> = bundle
+            .translated_entries
+            .iter()
+            .filter_map(|t| {
+                let source_id = source_ids.get(&t.source_entry_id)?;
+                new_translated_ids.get(source_id).map(|id| (t.id, *id))
+            })
+            .collect();
+
+        let validation_results = bundle
+            .validation_results
+            .iter()
+            .map(|r| {
+                let translated_entry_id = translated_ids.get(&r.translated_entry_id).copied().ok_or_else(|| {
+                    anyhow::anyhow!("Bundle references unknown translated entry {}", r.translated_entry_id)
+                })?;
+                Ok(ValidationResultRecord { translated_entry_id, ..r.clone() })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut revisions = bundle.revisions.clone();
+        revisions.sort_by_key(|r| (r.source_entry_id, r.revision));
+        let revisions = revisions
+            .into_iter()
+            .map(|r| Ok(TranslationRevisionRecord { source_entry_id: remap_source(r.source_entry_id)?, ..r }))
+            .collect::<Result<Vec<_>>>()?;
+
+        let summaries = bundle
+            .history_summaries
+            .iter()
+            .map(|s| HistorySummaryRecord { session_id: session_id.to_string(), ..s.clone() })
+            .collect();
+
+        let telemetry = bundle
+            .batch_telemetry
+            .iter()
+            .map(|t| BatchTelemetryRecord::new(session_id.to_string(), t.run_id.clone(), t.batch.clone()))
+            .collect();
+
+        let summary = BundleImportSummary {
+            session_id: session_id.to_string(),
+            renamed: false,
+            source_entries: bundle.source_entries.len(),
+            translated_entries: bundle.translated_entries.len(),
+            revisions: revisions.len(),
+            validation_results: validation_results.len(),
+            cache_entries: 0,
+        };
+
+        self.storage.insert_validation_results(validation_results).await?;
+        self.storage.add_revisions(revisions).await?;
+        self.storage.save_history_summaries(summaries).await?;
+        self.storage.insert_batch_telemetry(telemetry).await?;
+        self.save_scoped_analysis(
+            &AnalysisScope::Session(session_id.to_string()),
+            bundle.glossary_terms.clone(),
+            bundle.characters.clone(),
+            bundle.analysis_summaries.clone(),
+        )
+        .await?;
+        let cache_entries = self.storage.import_cache_entries(bundle.cache_entries.clone()).await?;
+        self.refresh_progress(session_id).await?;
+
+        Ok(BundleImportSummary { cache_entries, ..summary })
+    }
+
     // =========================================================================
     // Utility Methods
     // =========================================================================
@@ -416,7 +1290,9 @@ impl SessionManager {
 impl Clone for SessionManager {
     fn clone(&self) -> Self {
         Self {
-            repo: self.repo.clone(),
+            storage: Arc::clone(&self.storage),
+            owner: self.owner.clone(),
+            lease_ttl: self.lease_ttl,
         }
     }
 }
@@ -513,6 +1389,123 @@ mod tests {
         assert_eq!(pending.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_resumeOrCreate_withSessionLeasedByOtherProcess_shouldFailWithSessionInUse() {
+        let manager = create_test_manager().await;
+        let other = SessionManager::with_storage(Arc::clone(&manager.storage));
+        let temp_file = create_temp_file_with_content("leased content");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "it".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        let (session1, _) = manager.resume_or_create(params.clone()).await.unwrap();
+        let error = other.resume_or_create(params).await.unwrap_err();
+
+        let in_use = error.downcast_ref::<SessionInUse>().expect("should report the session in use");
+        assert_eq!(in_use.session_id, session1.id);
+        assert_eq!(in_use.lease.as_ref().map(|l| l.pid), Some(std::process::id() as i64));
+        assert_eq!(manager.list_sessions(None).await.unwrap().len(), 1);
+        let lease = manager.storage().get_lease(&session1.id).await.unwrap().unwrap();
+        assert_eq!(lease.owner_id, manager.owner.owner_id);
+
+        // Once released, another process can take the session
+        assert!(!other.acquire_lease(&session1.id).await.unwrap());
+        manager.pause_session(&session1.id).await.unwrap();
+        assert!(other.acquire_lease(&session1.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_resumeOrCreate_withConcurrentCreate_shouldCreateOneSession() {
+        let manager = create_test_manager().await;
+        let other = SessionManager::with_storage(Arc::clone(&manager.storage));
+        let temp_file = create_temp_file_with_content("raced content");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "nl".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        // Both processes find no session before either creates one
+        assert!(matches!(manager.try_resume(&params).await.unwrap(), ResumeResult::NotFound));
+        assert!(matches!(other.try_resume(&params).await.unwrap(), ResumeResult::NotFound));
+        let session = manager.create_leased_session(params.clone()).await.unwrap();
+        let error = other.create_leased_session(params).await.unwrap_err();
+
+        let in_use = error.downcast_ref::<SessionInUse>().expect("should report the session in use");
+        assert_eq!(in_use.session_id, session.id);
+        assert_eq!(manager.list_sessions(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recordTranslations_afterLeaseReclaimed_shouldFailWithSessionInUse() {
+        let stalled = create_test_manager().await.with_lease_ttl(Duration::from_millis(1));
+        let manager = SessionManager::with_storage(Arc::clone(&stalled.storage));
+        let temp_file = create_temp_file_with_content("stalled content");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "sv".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        let (session, pending) = stalled.resume_or_create(params.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        manager.resume_or_create(params).await.unwrap();
+
+        let error = stalled
+            .record_translations(&session.id, vec![(pending[0].source_entry_id, "Hej".to_string())])
+            .await
+            .unwrap_err();
+
+        assert!(error.is::<SessionInUse>());
+        assert!(stalled.complete_session(&session.id).await.is_err());
+        assert_eq!(manager.get_session_stats(&session.id).await.unwrap().translated_entries, 0);
+        manager
+            .record_translations(&session.id, vec![(pending[0].source_entry_id, "Hej".to_string())])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resumeOrCreate_withExpiredLease_shouldReclaimSession() {
+        let crashed = create_test_manager().await.with_lease_ttl(Duration::from_millis(1));
+        let manager = SessionManager::with_storage(Arc::clone(&crashed.storage));
+        let temp_file = create_temp_file_with_content("crashed content");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "pt".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        // The first process takes the session and dies without releasing it
+        let (session1, _) = crashed.resume_or_create(params.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let (session2, pending) = manager.resume_or_create(params).await.unwrap();
+
+        assert_eq!(session1.id, session2.id);
+        assert_eq!(pending.len(), 3);
+        let lease = manager.storage().get_lease(&session2.id).await.unwrap().unwrap();
+        assert_eq!(lease.owner_id, manager.owner.owner_id);
+    }
+
     #[tokio::test]
     async fn test_resumeOrCreate_withExisting_shouldResume() {
         let manager = create_test_manager().await;
@@ -540,6 +1533,161 @@ mod tests {
         assert_eq!(pending.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_resumeOrCreate_withJsonStore_shouldResume() {
+        let manager = SessionManager::new(crate::database::JsonFileStore::new_in_memory());
+        let temp_file = create_temp_file_with_content("json store resume");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "de".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        let (session1, pending) = manager.resume_or_create(params.clone()).await.unwrap();
+        manager
+            .record_translations(&session1.id, vec![(pending[0].source_entry_id, "Hallo".to_string())])
+            .await
+            .unwrap();
+        manager.pause_session(&session1.id).await.unwrap();
+
+        let (session2, pending) = manager.resume_or_create(params).await.unwrap();
+
+        assert_eq!(session1.id, session2.id);
+        assert_eq!(session2.completed_entries, 1);
+        assert_eq!(pending.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resumeOrCreate_withMovedFile_shouldResumeByContent() {
+        let manager = create_test_manager().await;
+        let original = create_temp_file_with_content("video container v1");
+        let remuxed = create_temp_file_with_content("video container v2");
+
+        let params = |path: &Path| {
+            SessionCreateParams::new(
+                path.to_path_buf(),
+                "en".to_string(),
+                "it".to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+                create_test_entries(),
+            )
+        };
+
+        let (session1, _) = manager.resume_or_create(params(original.path())).await.unwrap();
+        manager.pause_session(&session1.id).await.unwrap();
+
+        let (session2, pending) = manager.resume_or_create(params(remuxed.path())).await.unwrap();
+
+        assert_eq!(session1.id, session2.id);
+        assert_eq!(pending.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_latestSourceEntries_shouldReturnSourceOfMatchingSession() {
+        let manager = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("subtitles");
+        let other_file = create_temp_file_with_content("other subtitles");
+
+        assert!(manager.latest_source_entries(temp_file.path()).await.unwrap().is_none());
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+        let session = manager.create_session(params).await.unwrap();
+
+        let (found, entries) = manager.latest_source_entries(temp_file.path()).await.unwrap().unwrap();
+        assert_eq!(found.id, session.id);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].text, create_test_entries()[0].text);
+        assert!(manager.latest_source_entries(other_file.path()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resumeOrCreate_withEditedSource_shouldReuseUnchangedTranslations() {
+        let manager = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("original subtitles");
+
+        let params = |entries: Vec<SubtitleEntry>| {
+            SessionCreateParams::new(
+                temp_file.path().to_path_buf(),
+                "en".to_string(),
+                "fr".to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+                entries,
+            )
+        };
+
+        let (old_session, pending) = manager.resume_or_create(params(create_test_entries())).await.unwrap();
+        let translations = pending
+            .iter()
+            .zip(["Bonjour", "Monde", "Essai"])
+            .map(|(p, text)| (p.source_entry_id, text.to_string()))
+            .collect();
+        manager.record_translations(&old_session.id, translations).await.unwrap();
+        manager.pause_session(&old_session.id).await.unwrap();
+
+        // The editor rewrote the second line and appended one
+        std::fs::write(temp_file.path(), "edited subtitles").unwrap();
+        let edited = vec![
+            SubtitleEntry::new(1, 0, 1000, "Hello".to_string()),
+            SubtitleEntry::new(2, 1000, 2000, "Brave new world".to_string()),
+            SubtitleEntry::new(3, 2000, 3000, "Test".to_string()),
+            SubtitleEntry::new(4, 3000, 4000, "Bye".to_string()),
+        ];
+
+        let (new_session, pending) = manager.resume_or_create(params(edited)).await.unwrap();
+
+        assert_ne!(new_session.id, old_session.id);
+        assert_eq!(new_session.completed_entries, 2);
+        assert_eq!(pending.iter().map(|p| p.seq_num).collect::<Vec<_>>(), vec![2, 4]);
+
+        let reused = manager.get_translated_entries(&new_session.id).await.unwrap();
+        assert_eq!(reused.iter().map(|e| e.text.as_str()).collect::<Vec<_>>(), vec!["Bonjour", "Essai"]);
+
+        let old = manager.get_session(&old_session.id).await.unwrap().unwrap();
+        assert_eq!(old.status, SessionStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_recordHistorySummaries_shouldBeAvailableOnResume() {
+        let manager = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("summary resume test");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "ja".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+
+        let (session, _) = manager.resume_or_create(params.clone()).await.unwrap();
+        manager
+            .record_history_summaries(&session.id, vec![(1, 2, 2, "A greeting.".to_string())])
+            .await
+            .unwrap();
+        manager.pause_session(&session.id).await.unwrap();
+
+        let (resumed, _) = manager.resume_or_create(params).await.unwrap();
+        let summaries = manager.get_history_summaries(&resumed.id).await.unwrap();
+
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].summary_text, "A greeting.");
+        assert_eq!(summaries[0].end_seq_num, 2);
+    }
+
     #[tokio::test]
     async fn test_completeSession_shouldMarkAsCompleted() {
         let manager = create_test_manager().await;
@@ -594,6 +1742,268 @@ mod tests {
         assert_eq!(sessions.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_importReview_shouldApproveEditsAndUpdateCacheAndGlossary() {
+        let manager = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("review test");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            vec![
+                SubtitleEntry::new(1, 0, 1000, "Hello Frodo".to_string()),
+                SubtitleEntry::new(2, 1000, 2000, "World".to_string()),
+                SubtitleEntry::new(3, 2000, 3000, "Test".to_string()),
+            ],
+        );
+        let session = manager.create_session(params).await.unwrap();
+        let pending = manager.get_pending_entries(&session.id).await.unwrap();
+        manager
+            .record_translations(
+                &session.id,
+                vec![
+                    (pending[0].source_entry_id, "Bonjour Fredon".to_string()),
+                    (pending[1].source_entry_id, "Monde".to_string()),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let mut sheet = manager.export_review(&session.id).await.unwrap();
+        assert_eq!(sheet.rows.len(), 3);
+        assert_eq!(sheet.rows[0].machine, "Bonjour Fredon");
+        assert!(!sheet.rows[2].issues.is_empty(), "untranslated entry should be flagged");
+
+        sheet.rows[0].final_text = "Bonjour Frodo".to_string();
+        sheet.rows[1].final_text = "Monde".to_string();
+        sheet.rows[1].terms = vec![("World".to_string(), "Monde".to_string())];
+        sheet.rows.push(ReviewRow { entry_id: 99, final_text: "?".to_string(), ..sheet.rows[2].clone() });
+
+        let summary = manager.import_review(&session.id, &sheet).await.unwrap();
+
+        assert_eq!(summary.approved, 2);
+        assert_eq!(summary.changed, 1);
+        assert_eq!(summary.unknown, vec![99]);
+        assert_eq!(summary.names, vec!["Frodo".to_string()]);
+        assert_eq!(summary.terms, 1);
+
+        let entries = manager.storage().get_translated_entries(&session.id).await.unwrap();
+        assert_eq!(entries[0].1.translated_text, "Bonjour Frodo");
+        assert_eq!(entries[0].1.translation_status, TranslationStatus::Approved);
+
+        let cached = manager
+            .storage()
+            .get_cached_translation("Hello Frodo", "en", "fr", "ollama", "llama2")
+            .await
+            .unwrap();
+        assert_eq!(cached, Some("Bonjour Frodo".to_string()));
+
+        let terms = manager.get_glossary_terms(&session.id).await.unwrap();
+        assert_eq!(terms.len(), 2);
+        assert_eq!(terms[0].source_term, "Frodo");
+        assert!(terms[0].target_term.is_none());
+        assert_eq!(terms[1].target_term.as_deref(), Some("Monde"));
+
+        // Later runs load the editor's choices into the document glossary
+        let memory = manager
+            .load_analysis_memory(&AnalysisScope::Session(session.id.clone()), "")
+            .await
+            .unwrap();
+        assert!(memory.glossary.character_names.contains("Frodo"));
+        assert_eq!(memory.glossary.terms.values().next().map(|t| t.target.as_str()), Some("Monde"));
+
+        let (_, revisions) = manager.get_entry_history(&session.id, 1).await.unwrap().unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].origin, RevisionOrigin::Human);
+        assert_eq!(revisions[0].detail.as_deref(), Some("edited in review"));
+    }
+
+    #[tokio::test]
+    async fn test_reuseCachedTranslations_shouldFillPendingEntriesFromReviewedLines() {
+        let manager = create_test_manager().await;
+        let entries = vec![
+            SubtitleEntry::new(1, 0, 1000, "Hello".to_string()),
+            SubtitleEntry::new(2, 1000, 2000, "World".to_string()),
+        ];
+        let params = |file: &NamedTempFile| {
+            SessionCreateParams::new(
+                file.path().to_path_buf(),
+                "en".to_string(),
+                "fr".to_string(),
+                "ollama".to_string(),
+                "llama2".to_string(),
+                entries.clone(),
+            )
+        };
+        let first_file = create_temp_file_with_content("episode one");
+        let first = manager.create_session(params(&first_file)).await.unwrap();
+        let mut sheet = manager.export_review(&first.id).await.unwrap();
+        sheet.rows[1].final_text = "Le monde".to_string();
+        manager.import_review(&first.id, &sheet).await.unwrap();
+
+        let second_file = create_temp_file_with_content("episode two");
+        let second = manager.create_session(params(&second_file)).await.unwrap();
+        let reused = manager.reuse_cached_translations(&second.id).await.unwrap();
+
+        assert_eq!(reused, 1);
+        let pending = manager.get_pending_entries(&second.id).await.unwrap();
+        assert_eq!(pending.iter().map(|p| p.seq_num).collect::<Vec<_>>(), vec![1]);
+        let translated = manager.storage().get_translated_entries(&second.id).await.unwrap();
+        assert_eq!(translated[0].1.translated_text, "Le monde");
+        assert_eq!(translated[0].1.translation_status, TranslationStatus::Translated);
+    }
+
+    #[tokio::test]
+    async fn test_recordRevisions_shouldAttributeModelRevisionsToSessionProvider() {
+        let manager = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("revision test");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            vec![SubtitleEntry::new(1, 0, 1000, "<i>Hello</i>".to_string())],
+        );
+        let session = manager.create_session(params).await.unwrap();
+        let pending = manager.get_pending_entries(&session.id).await.unwrap();
+
+        let mut translation = EntryRevision::new(1, "Bonjour".to_string(), RevisionOrigin::Translation);
+        translation.confidence = Some(0.5);
+        translation.set_outcome(&["Missing Italic formatting".to_string()]);
+        let mut repair = EntryRevision::new(1, "<i>Bonjour</i>".to_string(), RevisionOrigin::Repair);
+        repair.detail = Some("added Italic formatting".to_string());
+        repair.set_outcome(&[]);
+        let source_id = pending[0].source_entry_id;
+        manager
+            .record_revisions(&session.id, vec![(source_id, translation), (source_id, repair)])
+            .await
+            .unwrap();
+
+        let (source, revisions) = manager.get_entry_history(&session.id, 1).await.unwrap().unwrap();
+        assert_eq!(source.source_text, "<i>Hello</i>");
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].provider.as_deref(), Some("ollama"));
+        assert_eq!(revisions[0].model.as_deref(), Some("llama2"));
+        assert_eq!(revisions[0].quality_score, Some(0.5));
+        assert_eq!(revisions[0].validation_message.as_deref(), Some("Missing Italic formatting"));
+        assert!(revisions[1].provider.is_none());
+        assert_eq!(revisions[1].validation_passed, Some(true));
+
+        assert!(manager.get_entry_history(&session.id, 2).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_importBundle_shouldRemapIdsAndKeepTranslations() {
+        let source = create_test_manager().await;
+        let temp_file = create_temp_file_with_content("bundle test");
+
+        let params = SessionCreateParams::new(
+            temp_file.path().to_path_buf(),
+            "en".to_string(),
+            "fr".to_string(),
+            "ollama".to_string(),
+            "llama2".to_string(),
+            create_test_entries(),
+        );
+        let session = source.create_session(params).await.unwrap();
+        let pending = source.get_pending_entries(&session.id).await.unwrap();
+        source
+            .record_translations(&session.id, vec![(pending[1].source_entry_id, "Monde".to_string())])
+            .await
+            .unwrap();
+        let mut sheet = source.export_review(&session.id).await.unwrap();
+        sheet.rows[1].final_text = "Le monde".to_string();
+        source.import_review(&session.id, &sheet).await.unwrap();
+        let translated_id = source.storage().get_translated_entries(&session.id).await.unwrap()[0].1.id;
+        source
+            .storage()
+            .insert_validation_results(vec![ValidationResultRecord::passed(
+                translated_id,
+                crate::database::models::ValidationType::FormatCheck,
+            )])
+            .await
+            .unwrap();
+        let mut memory = AnalysisMemory::default();
+        memory.glossary.add_term("Ring", "Anneau", None);
+        memory.characters.push(CharacterProfile { name: "Frodo".to_string(), mentions: 2 });
+        memory.summary = Some("A journey".to_string());
+        source
+            .save_analysis_memory(&AnalysisScope::Session(session.id.clone()), "", &memory)
+            .await
+            .unwrap();
+        let batch = BatchTelemetry::new(
+            crate::translation::pipeline::PipelinePhase::Translation,
+            0,
+            3,
+            chrono::Utc::now(),
+            std::time::Duration::from_secs(1),
+            Default::default(),
+            None,
+        );
+        source.record_batch_telemetry(&session.id, "run-1", &[batch]).await.unwrap();
+
+        let bundle = source.export_bundle(&session.id).await.unwrap();
+        assert_eq!(bundle.source_entries.len(), 3);
+        assert_eq!(bundle.translated_entries.len(), 1);
+        assert_eq!(bundle.revisions.len(), 1);
+        assert_eq!(bundle.validation_results.len(), 1);
+        assert_eq!(bundle.cache_entries.len(), 1);
+        assert_eq!(bundle.characters.len(), 1);
+        assert_eq!(bundle.analysis_summaries.len(), 1);
+        assert_eq!(bundle.batch_telemetry.len(), 1);
+
+        // A database that already holds the session gets a copy under a new ID
+        let summary = source.import_bundle(&bundle).await.unwrap();
+        assert!(summary.renamed);
+        assert_ne!(summary.session_id, session.id);
+        assert_eq!(summary.translated_entries, 1);
+
+        let entries = source.storage().get_translated_entries(&summary.session_id).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.seq_num, 2);
+        assert_ne!(entries[0].0.id, pending[1].source_entry_id);
+        assert_eq!(entries[0].1.translated_text, "Le monde");
+        assert_eq!(entries[0].1.translation_status, TranslationStatus::Approved);
+        assert_eq!(source.storage().get_validation_results(entries[0].1.id).await.unwrap().len(), 1);
+
+        let (_, revisions) = source.get_entry_history(&summary.session_id, 2).await.unwrap().unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].origin, RevisionOrigin::Human);
+
+        let imported = source.get_session(&summary.session_id).await.unwrap().unwrap();
+        assert_eq!(imported.completed_entries, 1);
+
+        // A fresh database keeps the session ID and receives the cache rows
+        let target = create_test_manager().await;
+        let summary = target.import_bundle(&bundle).await.unwrap();
+        assert!(!summary.renamed);
+        assert_eq!(summary.session_id, session.id);
+        assert_eq!(summary.cache_entries, 1);
+        let cached = target
+            .storage()
+            .get_cached_translation("World", "en", "fr", "ollama", "llama2")
+            .await
+            .unwrap();
+        assert_eq!(cached, Some("Le monde".to_string()));
+
+        let memory = target
+            .load_analysis_memory(&AnalysisScope::Session(session.id.clone()), "")
+            .await
+            .unwrap();
+        assert_eq!(memory.glossary.terms.len(), 1);
+        assert!(memory.glossary.character_names.contains("Frodo"));
+        assert_eq!(memory.characters.len(), 1);
+        assert_eq!(memory.summary.as_deref(), Some("A journey"));
+
+        let report = target.session_report(&session.id).await.unwrap();
+        assert_eq!(report.runs.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_deleteSession_shouldRemoveSession() {
         let manager = create_test_manager().await;
@@ -3,15 +3,27 @@
  *
  * This module provides:
  * - Session creation and tracking
- * - Resume capability for interrupted translations
+ * - Resume capability for interrupted translations, keyed on subtitle content
+ * - Reuse of translations for unchanged entries when the source changed
  * - Progress tracking and state management
+ * - Human review sheets (export, edit, re-import)
+ * - Portable session bundles for moving work between machines
+ * - Run history reports built from per-batch telemetry
+ * - Leases that keep parallel processes off the same session
+ * - Analysis results (glossary, characters, summary) kept per session and series
  */
 
 // Allow dead code - session types have extra methods for future use
 #![allow(dead_code)]
 
+pub mod analysis;
+pub mod bundle;
+pub mod diff;
+pub mod lease;
 pub mod manager;
 pub mod models;
+pub mod report;
+pub mod review;
 
 // Re-export main types
 pub use manager::SessionManager;
@@ -6,9 +6,10 @@
  */
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::PathBuf;
 
-use crate::database::models::{SessionRecord, SessionStatus, SourceEntryRecord};
+use crate::database::models::{SessionLeaseRecord, SessionRecord, SessionStatus, SourceEntryRecord};
 use crate::subtitle_processor::SubtitleEntry;
 
 /// Parameters for creating a new session
@@ -47,6 +48,18 @@ impl SessionCreateParams {
             entries,
         }
     }
+
+    /// SHA256 hash of the entries' timings and text
+    ///
+    /// Identical subtitles hash the same whichever file they were extracted
+    /// from, so a moved or re-muxed video still finds its session.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for entry in &self.entries {
+            hasher.update(format!("{}\t{}\t{}\n", entry.start_time_ms, entry.end_time_ms, entry.text));
+        }
+        format!("{:x}", hasher.finalize())
+    }
 }
 
 /// High-level session information for display and tracking
@@ -150,13 +163,13 @@ pub enum ResumeResult {
     },
     /// No matching session found, need to create new
     NotFound,
-    /// Source file has changed since session was created
+    /// Subtitles have changed since session was created
     SourceFileChanged {
         /// The existing session info
         session: SessionInfo,
-        /// Old file hash
+        /// Old content hash (file hash for older sessions)
         old_hash: String,
-        /// New file hash
+        /// New content hash (file hash for older sessions)
         new_hash: String,
     },
     /// Session exists but is already completed
@@ -169,6 +182,13 @@ pub enum ResumeResult {
         /// The failed session info
         session: SessionInfo,
     },
+    /// Session is being translated by another process
+    Leased {
+        /// The leased session info
+        session: SessionInfo,
+        /// The lease held on it, if it is still there
+        lease: Option<SessionLeaseRecord>,
+    },
 }
 
 impl ResumeResult {
@@ -184,6 +204,7 @@ impl ResumeResult {
             ResumeResult::SourceFileChanged { session, .. } => Some(session),
             ResumeResult::AlreadyCompleted { session } => Some(session),
             ResumeResult::Failed { session } => Some(session),
+            ResumeResult::Leased { session, .. } => Some(session),
             ResumeResult::NotFound => None,
         }
     }
@@ -0,0 +1,582 @@
+/*!
+ * Run history reports for translation sessions.
+ *
+ * Every translation run stores one telemetry row per batch and phase. A
+ * report groups those rows by run and summarises each run: wall time per
+ * pipeline phase, requests, cache hit rate, tokens and cost, retries by
+ * error kind, validation issues by type and provider latency percentiles.
+ * Reports are printed as text or exported as JSON or a standalone HTML page.
+ */
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, FixedOffset};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::database::models::BatchTelemetryRecord;
+use crate::translation::telemetry::{percentile, BatchTelemetry};
+
+use super::models::{SessionInfo, SessionStats};
+
+/// Report output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Plain text for the terminal
+    Text,
+    /// JSON for other tools
+    Json,
+    /// Standalone HTML page
+    Html,
+}
+
+impl ReportFormat {
+    /// Pick the format from a file extension (`.json`, `.html`/`.htm`, anything else is text).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()) {
+            Some(ext) if ext == "json" => ReportFormat::Json,
+            Some(ext) if ext == "html" || ext == "htm" => ReportFormat::Html,
+            _ => ReportFormat::Text,
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(anyhow!("Invalid report format: {} (expected text, json or html)", s)),
+        }
+    }
+}
+
+/// Totals of one pipeline phase of a run
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PhaseSummary {
+    /// Phase name
+    pub phase: String,
+    /// Batches in the phase
+    pub batches: usize,
+    /// Wall time from the first batch start to the last batch end
+    pub wall_time_ms: u64,
+    /// Requests sent to the provider
+    pub requests: usize,
+    /// Translations served from the cache
+    pub cache_hits: usize,
+    /// Prompt tokens
+    pub prompt_tokens: u64,
+    /// Completion tokens
+    pub completion_tokens: u64,
+    /// Cost in US dollars, when the provider has a price
+    pub cost_usd: Option<f64>,
+}
+
+/// Provider latency percentiles
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LatencySummary {
+    /// Successful requests measured
+    pub samples: usize,
+    /// Median latency
+    pub p50_ms: u64,
+    /// 90th percentile latency
+    pub p90_ms: u64,
+    /// 99th percentile latency
+    pub p99_ms: u64,
+    /// Slowest request
+    pub max_ms: u64,
+}
+
+impl LatencySummary {
+    /// Summarise latencies, `None` when there are none
+    pub fn from_latencies(latencies: &[u64]) -> Option<Self> {
+        let mut sorted = latencies.to_vec();
+        sorted.sort_unstable();
+        Some(Self {
+            samples: sorted.len(),
+            p50_ms: percentile(&sorted, 50.0)?,
+            p90_ms: percentile(&sorted, 90.0)?,
+            p99_ms: percentile(&sorted, 99.0)?,
+            max_ms: *sorted.last()?,
+        })
+    }
+}
+
+/// Summary of one translation run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    /// Run ID
+    pub run_id: String,
+    /// Start time of the first batch (RFC 3339)
+    pub started_at: String,
+    /// Wall time from the first batch start to the last batch end
+    pub wall_time_ms: u64,
+    /// Totals per phase, in pipeline order
+    pub phases: Vec<PhaseSummary>,
+    /// Requests sent to the provider
+    pub requests: usize,
+    /// Translations served from the cache
+    pub cache_hits: usize,
+    /// Share of translations served from the cache (percent)
+    pub cache_hit_rate: f64,
+    /// Prompt tokens
+    pub prompt_tokens: u64,
+    /// Completion tokens
+    pub completion_tokens: u64,
+    /// Cost in US dollars, when the provider has a price
+    pub cost_usd: Option<f64>,
+    /// Failed attempts by error kind
+    pub retries: BTreeMap<String, usize>,
+    /// Validation issues by type
+    pub issues: BTreeMap<String, usize>,
+    /// Provider latency percentiles
+    pub latency: Option<LatencySummary>,
+    /// Telemetry of every batch
+    pub batches: Vec<BatchTelemetry>,
+}
+
+impl RunReport {
+    /// Summarise the batches of one run
+    pub fn new(run_id: String, batches: Vec<BatchTelemetry>) -> Self {
+        // Pipeline phases in pipeline order, then any other phase in recorded order
+        let mut order: Vec<&str> = vec!["analysis", "translation", "validation"];
+        for batch in &batches {
+            if !order.contains(&batch.phase.as_str()) {
+                order.push(&batch.phase);
+            }
+        }
+        let phases = order
+            .into_iter()
+            .filter_map(|phase| {
+                let rows: Vec<&BatchTelemetry> = batches.iter().filter(|b| b.phase == phase).collect();
+                (!rows.is_empty()).then(|| Self::summarise_phase(phase, &rows))
+            })
+            .collect();
+
+        let all: Vec<&BatchTelemetry> = batches.iter().collect();
+        let requests = batches.iter().map(|b| b.requests).sum();
+        let cache_hits = batches.iter().map(|b| b.cache_hits).sum();
+        let lookups = requests + cache_hits;
+        let latencies: Vec<u64> = batches.iter().flat_map(|b| b.latencies_ms.iter().copied()).collect();
+
+        let mut retries = BTreeMap::new();
+        let mut issues = BTreeMap::new();
+        for batch in &batches {
+            for (kind, count) in &batch.retries {
+                *retries.entry(kind.clone()).or_default() += count;
+            }
+            for (kind, count) in &batch.issues {
+                *issues.entry(kind.clone()).or_default() += count;
+            }
+        }
+
+        Self {
+            run_id,
+            started_at: batches.iter().map(|b| b.started_at.clone()).min().unwrap_or_default(),
+            wall_time_ms: wall_time_ms(&all),
+            phases,
+            requests,
+            cache_hits,
+            cache_hit_rate: if lookups == 0 { 0.0 } else { cache_hits as f64 / lookups as f64 * 100.0 },
+            prompt_tokens: batches.iter().map(|b| b.prompt_tokens).sum(),
+            completion_tokens: batches.iter().map(|b| b.completion_tokens).sum(),
+            cost_usd: total_cost(&all),
+            retries,
+            issues,
+            latency: LatencySummary::from_latencies(&latencies),
+            batches,
+        }
+    }
+
+    fn summarise_phase(phase: &str, rows: &[&BatchTelemetry]) -> PhaseSummary {
+        PhaseSummary {
+            phase: phase.to_string(),
+            batches: rows.len(),
+            wall_time_ms: wall_time_ms(rows),
+            requests: rows.iter().map(|b| b.requests).sum(),
+            cache_hits: rows.iter().map(|b| b.cache_hits).sum(),
+            prompt_tokens: rows.iter().map(|b| b.prompt_tokens).sum(),
+            completion_tokens: rows.iter().map(|b| b.completion_tokens).sum(),
+            cost_usd: total_cost(rows),
+        }
+    }
+
+    /// Number of failed attempts of any kind
+    pub fn retry_count(&self) -> usize {
+        self.batches.iter().map(BatchTelemetry::retry_count).sum()
+    }
+}
+
+/// Wall time spanned by batches that may have run concurrently
+fn wall_time_ms(batches: &[&BatchTelemetry]) -> u64 {
+    let spans: Option<Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>> = batches
+        .iter()
+        .map(|b| {
+            let start = DateTime::parse_from_rfc3339(&b.started_at).ok()?;
+            Some((start, start + ChronoDuration::milliseconds(b.duration_ms as i64)))
+        })
+        .collect();
+
+    match spans {
+        Some(spans) if !spans.is_empty() => {
+            let start = spans.iter().map(|(s, _)| *s).min().unwrap_or_default();
+            let end = spans.iter().map(|(_, e)| *e).max().unwrap_or_default();
+            (end - start).num_milliseconds().max(0) as u64
+        }
+        // Without usable timestamps fall back to the sum of batch durations
+        _ => batches.iter().map(|b| b.duration_ms).sum(),
+    }
+}
+
+/// Total cost, `None` when no batch was priced
+fn total_cost(batches: &[&BatchTelemetry]) -> Option<f64> {
+    batches
+        .iter()
+        .filter_map(|b| b.cost_usd)
+        .fold(None, |total, cost| Some(total.unwrap_or(0.0) + cost))
+}
+
+/// Run history of a session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    /// The session
+    pub session: SessionInfo,
+    /// Entry counts
+    pub stats: SessionStats,
+    /// Runs, oldest first
+    pub runs: Vec<RunReport>,
+}
+
+impl SessionReport {
+    /// Build a report from the telemetry rows of a session, in the order they were recorded
+    pub fn new(session: SessionInfo, stats: SessionStats, records: Vec<BatchTelemetryRecord>) -> Self {
+        let mut grouped: Vec<(String, Vec<BatchTelemetry>)> = Vec::new();
+        for record in records {
+            match grouped.iter_mut().find(|(run_id, _)| *run_id == record.run_id) {
+                Some((_, batches)) => batches.push(record.batch),
+                None => grouped.push((record.run_id, vec![record.batch])),
+            }
+        }
+
+        Self {
+            session,
+            stats,
+            runs: grouped.into_iter().map(|(run_id, batches)| RunReport::new(run_id, batches)).collect(),
+        }
+    }
+
+    /// Render the report in the given format
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Text => Ok(self.to_text()),
+            ReportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ReportFormat::Html => Ok(self.to_html()),
+        }
+    }
+
+    /// Plain-text report
+    pub fn to_text(&self) -> String {
+        let s = &self.session;
+        let mut out = String::new();
+        let _ = writeln!(out, "Session {} | {} -> {} | {} / {}", &s.id[..8.min(s.id.len())], s.source_language, s.target_language, s.provider, s.model);
+        let _ = writeln!(out, "Source:   {}", s.source_file_path);
+        let _ = writeln!(
+            out,
+            "Entries:  {} total, {} translated, {} failed, {} validated",
+            self.stats.total_entries, self.stats.translated_entries, self.stats.failed_entries, self.stats.validated_entries
+        );
+
+        if self.runs.is_empty() {
+            let _ = writeln!(out, "\nNo run telemetry recorded for this session.");
+            return out;
+        }
+
+        for (i, run) in self.runs.iter().enumerate() {
+            let _ = writeln!(out, "\nRun {} ({}) started {}", i + 1, &run.run_id[..8.min(run.run_id.len())], run.started_at);
+            let _ = writeln!(out, "{:-<80}", "");
+            let _ = writeln!(out, "Wall time:   {}", format_ms(run.wall_time_ms));
+            for phase in &run.phases {
+                let _ = writeln!(
+                    out,
+                    "  {:<12} {:>10}  {} batch(es), {} request(s), {} token(s){}",
+                    phase.phase,
+                    format_ms(phase.wall_time_ms),
+                    phase.batches,
+                    phase.requests,
+                    phase.prompt_tokens + phase.completion_tokens,
+                    phase.cost_usd.map(|c| format!(", ${:.4}", c)).unwrap_or_default()
+                );
+            }
+            let _ = writeln!(
+                out,
+                "Requests:    {} ({} served from cache, {:.1}% hit rate)",
+                run.requests, run.cache_hits, run.cache_hit_rate
+            );
+            let _ = writeln!(
+                out,
+                "Tokens:      {} prompt + {} completion",
+                run.prompt_tokens, run.completion_tokens
+            );
+            if let Some(cost) = run.cost_usd {
+                let _ = writeln!(out, "Cost:        ${:.4}", cost);
+            }
+            if let Some(ref latency) = run.latency {
+                let _ = writeln!(
+                    out,
+                    "Latency:     p50 {} | p90 {} | p99 {} | max {} ({} requests)",
+                    format_ms(latency.p50_ms),
+                    format_ms(latency.p90_ms),
+                    format_ms(latency.p99_ms),
+                    format_ms(latency.max_ms),
+                    latency.samples
+                );
+            }
+            let _ = writeln!(out, "Retries:     {}", format_counts(&run.retries));
+            let _ = writeln!(out, "Issues:      {}", format_counts(&run.issues));
+
+            let translation: Vec<&BatchTelemetry> = run.batches.iter().filter(|b| b.phase == "translation").collect();
+            if !translation.is_empty() {
+                let _ = writeln!(out, "Translation batches:");
+                for batch in translation {
+                    let _ = writeln!(
+                        out,
+                        "  #{:<4} {:>4} entries {:>10}  {:>3} req  {:>3} cached  {:>7} tok{}{}",
+                        batch.batch_index,
+                        batch.entry_count,
+                        format_ms(batch.duration_ms),
+                        batch.requests,
+                        batch.cache_hits,
+                        batch.prompt_tokens + batch.completion_tokens,
+                        batch.cost_usd.map(|c| format!("  ${:.4}", c)).unwrap_or_default(),
+                        if batch.retries.is_empty() { String::new() } else { format!("  retries: {}", format_counts(&batch.retries)) }
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    /// Standalone HTML page
+    pub fn to_html(&self) -> String {
+        let s = &self.session;
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(out, "<html><head><meta charset=\"utf-8\"><title>Session {}</title>", escape_html(&s.id));
+        let _ = writeln!(
+            out,
+            "<style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin:1em 0}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:right}}th:first-child,td:first-child{{text-align:left}}</style>"
+        );
+        let _ = writeln!(out, "</head><body>");
+        let _ = writeln!(out, "<h1>Session {}</h1>", escape_html(&s.id));
+        let _ = writeln!(
+            out,
+            "<p>{} &rarr; {} | {} / {}<br>{}</p>",
+            escape_html(&s.source_language),
+            escape_html(&s.target_language),
+            escape_html(&s.provider),
+            escape_html(&s.model),
+            escape_html(&s.source_file_path)
+        );
+        let _ = writeln!(
+            out,
+            "<p>{} entries, {} translated, {} failed, {} validated</p>",
+            self.stats.total_entries, self.stats.translated_entries, self.stats.failed_entries, self.stats.validated_entries
+        );
+
+        if self.runs.is_empty() {
+            let _ = writeln!(out, "<p>No run telemetry recorded for this session.</p>");
+        }
+
+        for (i, run) in self.runs.iter().enumerate() {
+            let _ = writeln!(out, "<h2>Run {} <small>{} &middot; {}</small></h2>", i + 1, escape_html(&run.run_id), escape_html(&run.started_at));
+            let _ = writeln!(out, "<ul>");
+            let _ = writeln!(out, "<li>Wall time: {}</li>", format_ms(run.wall_time_ms));
+            let _ = writeln!(out, "<li>Requests: {} ({} from cache, {:.1}% hit rate)</li>", run.requests, run.cache_hits, run.cache_hit_rate);
+            let _ = writeln!(out, "<li>Tokens: {} prompt + {} completion</li>", run.prompt_tokens, run.completion_tokens);
+            if let Some(cost) = run.cost_usd {
+                let _ = writeln!(out, "<li>Cost: ${:.4}</li>", cost);
+            }
+            if let Some(ref latency) = run.latency {
+                let _ = writeln!(
+                    out,
+                    "<li>Latency: p50 {} &middot; p90 {} &middot; p99 {} &middot; max {}</li>",
+                    format_ms(latency.p50_ms),
+                    format_ms(latency.p90_ms),
+                    format_ms(latency.p99_ms),
+                    format_ms(latency.max_ms)
+                );
+            }
+            let _ = writeln!(out, "<li>Retries: {}</li>", escape_html(&format_counts(&run.retries)));
+            let _ = writeln!(out, "<li>Issues: {}</li>", escape_html(&format_counts(&run.issues)));
+            let _ = writeln!(out, "</ul>");
+
+            let _ = writeln!(out, "<table><tr><th>Phase</th><th>Wall time</th><th>Batches</th><th>Requests</th><th>Cache hits</th><th>Tokens</th><th>Cost</th></tr>");
+            for phase in &run.phases {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&phase.phase),
+                    format_ms(phase.wall_time_ms),
+                    phase.batches,
+                    phase.requests,
+                    phase.cache_hits,
+                    phase.prompt_tokens + phase.completion_tokens,
+                    phase.cost_usd.map(|c| format!("${:.4}", c)).unwrap_or_default()
+                );
+            }
+            let _ = writeln!(out, "</table>");
+
+            let _ = writeln!(out, "<table><tr><th>Batch</th><th>Phase</th><th>Entries</th><th>Duration</th><th>Requests</th><th>Cache hits</th><th>Tokens</th><th>Cost</th><th>Retries</th></tr>");
+            for batch in &run.batches {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    batch.batch_index,
+                    escape_html(&batch.phase),
+                    batch.entry_count,
+                    format_ms(batch.duration_ms),
+                    batch.requests,
+                    batch.cache_hits,
+                    batch.prompt_tokens + batch.completion_tokens,
+                    batch.cost_usd.map(|c| format!("${:.4}", c)).unwrap_or_default(),
+                    escape_html(&format_counts(&batch.retries))
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        let _ = writeln!(out, "</body></html>");
+        out
+    }
+}
+
+/// Milliseconds as a short human-readable duration
+fn format_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m{:02}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+/// Counts by kind as "kind: n, kind: n", or "none"
+fn format_counts(counts: &BTreeMap<String, usize>) -> String {
+    if counts.is_empty() {
+        return "none".to_string();
+    }
+    counts
+        .iter()
+        .map(|(kind, count)| format!("{}: {}", kind, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::SessionStatus;
+
+    fn batch(phase: &str, index: usize, started_at: &str, duration_ms: u64, latencies_ms: Vec<u64>) -> BatchTelemetry {
+        BatchTelemetry {
+            phase: phase.to_string(),
+            batch_index: index,
+            entry_count: 10,
+            started_at: started_at.to_string(),
+            duration_ms,
+            requests: latencies_ms.len(),
+            cache_hits: 1,
+            prompt_tokens: 100,
+            completion_tokens: 50,
+            cost_usd: Some(0.01),
+            latencies_ms,
+            retries: BTreeMap::new(),
+            issues: BTreeMap::new(),
+        }
+    }
+
+    fn session() -> SessionInfo {
+        SessionInfo {
+            id: "0123456789abcdef".to_string(),
+            source_file_path: "movie.srt".to_string(),
+            source_language: "en".to_string(),
+            target_language: "fr".to_string(),
+            provider: "ollama".to_string(),
+            model: "llama3".to_string(),
+            total_entries: 20,
+            completed_entries: 20,
+            pending_entries: 0,
+            status: SessionStatus::Completed,
+            created_at: String::new(),
+            updated_at: String::new(),
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_runReport_new_shouldMeasureConcurrentBatchesByWallTime() {
+        let mut second = batch("translation", 1, "2026-01-01T00:00:00.500+00:00", 1500, vec![300, 400]);
+        second.retries.insert("rate_limit".to_string(), 2);
+        let mut validation = batch("validation", 0, "2026-01-01T00:00:02+00:00", 500, vec![]);
+        validation.issues.insert("length_too_long".to_string(), 3);
+        let batches = vec![
+            batch("translation", 0, "2026-01-01T00:00:00+00:00", 1000, vec![100, 200]),
+            second,
+            validation,
+        ];
+
+        let run = RunReport::new("run".to_string(), batches);
+
+        assert_eq!(run.phases.len(), 2);
+        assert_eq!(run.phases[0].phase, "translation");
+        assert_eq!(run.phases[0].wall_time_ms, 2000);
+        assert_eq!(run.wall_time_ms, 2500);
+        assert_eq!((run.requests, run.cache_hits), (4, 3));
+        assert!((run.cache_hit_rate - 300.0 / 7.0).abs() < 1e-9);
+        assert!((run.cost_usd.unwrap() - 0.03).abs() < 1e-9);
+        assert_eq!(run.retries.get("rate_limit"), Some(&2));
+        assert_eq!(run.issues.get("length_too_long"), Some(&3));
+        let latency = run.latency.unwrap();
+        assert_eq!((latency.p50_ms, latency.p99_ms, latency.samples), (200, 400, 4));
+    }
+
+    #[test]
+    fn test_sessionReport_new_shouldGroupRecordsByRun() {
+        let records = vec![
+            BatchTelemetryRecord::new("s".to_string(), "first".to_string(), batch("translation", 0, "2026-01-01T00:00:00+00:00", 100, vec![50])),
+            BatchTelemetryRecord::new("s".to_string(), "second".to_string(), batch("translation", 0, "2026-01-02T00:00:00+00:00", 100, vec![50])),
+            BatchTelemetryRecord::new("s".to_string(), "first".to_string(), batch("validation", 0, "2026-01-01T00:00:01+00:00", 100, vec![])),
+        ];
+
+        let report = SessionReport::new(session(), SessionStats::new(), records);
+
+        assert_eq!(report.runs.len(), 2);
+        assert_eq!(report.runs[0].run_id, "first");
+        assert_eq!(report.runs[0].batches.len(), 2);
+        assert!(report.to_text().contains("Run 2 (second)"));
+        assert!(report.to_html().contains("<h2>Run 1"));
+        let json: serde_json::Value = serde_json::from_str(&report.render(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["runs"][1]["requests"], 1);
+    }
+
+    #[test]
+    fn test_reportFormat_fromPath_shouldUseExtension() {
+        assert_eq!(ReportFormat::from_path(Path::new("run.json")), ReportFormat::Json);
+        assert_eq!(ReportFormat::from_path(Path::new("run.HTML")), ReportFormat::Html);
+        assert_eq!(ReportFormat::from_path(Path::new("run.txt")), ReportFormat::Text);
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+}
@@ -0,0 +1,458 @@
+/*!
+ * Human review sheets for translation sessions.
+ *
+ * A review sheet lists every entry of a session with its timing, source
+ * text, machine translation and validation issues, plus an empty "final"
+ * column for an editor and a "terms" column where they can fix how a term is
+ * translated (`Ring = Anneau; Shire = Comté`). Sheets are written as CSV or
+ * as tab-separated values that spreadsheet applications open directly, and
+ * read back so the edits can be applied to the session.
+ */
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+use std::path::Path;
+
+use crate::subtitle_processor::SubtitleEntry;
+
+/// Column headers, in sheet order
+pub const REVIEW_COLUMNS: [&str; 8] = ["id", "start", "end", "source", "machine", "issues", "final", "terms"];
+
+/// Byte order mark written first so spreadsheet applications detect UTF-8
+const UTF8_BOM: char = '\u{feff}';
+
+/// Separator between validation issues in the issues column, and between terms in the terms column
+const ISSUE_SEPARATOR: &str = "; ";
+
+/// Separator between a source term and its translation in the terms column
+const TERM_SEPARATOR: &str = " = ";
+
+/// Capitalised words or word pairs, the name candidates of a source line
+static NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\p{Lu}\p{Ll}+(?:\s+\p{Lu}\p{Ll}+)?\b").unwrap());
+
+/// Review sheet file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewFormat {
+    /// Comma-separated values
+    Csv,
+    /// Tab-separated values, opened directly by Excel and LibreOffice
+    Tsv,
+}
+
+impl ReviewFormat {
+    /// Pick the format from a file extension (`.tsv`, `.tab` and `.txt` are TSV).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()) {
+            Some(ext) if matches!(ext.as_str(), "tsv" | "tab" | "txt") => ReviewFormat::Tsv,
+            _ => ReviewFormat::Csv,
+        }
+    }
+
+    /// Field delimiter
+    pub fn delimiter(&self) -> char {
+        match self {
+            ReviewFormat::Csv => ',',
+            ReviewFormat::Tsv => '\t',
+        }
+    }
+
+    /// Default file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReviewFormat::Csv => "csv",
+            ReviewFormat::Tsv => "tsv",
+        }
+    }
+}
+
+impl std::str::FromStr for ReviewFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ReviewFormat::Csv),
+            "tsv" => Ok(ReviewFormat::Tsv),
+            _ => Err(anyhow!("Invalid review format: {} (expected csv or tsv)", s)),
+        }
+    }
+}
+
+/// One entry of a review sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewRow {
+    /// Entry sequence number
+    pub entry_id: usize,
+    /// Start time in milliseconds
+    pub start_time_ms: u64,
+    /// End time in milliseconds
+    pub end_time_ms: u64,
+    /// Source text
+    pub source: String,
+    /// Machine translation (empty if the entry was never translated)
+    pub machine: String,
+    /// Validation issues found in the machine translation
+    pub issues: Vec<String>,
+    /// Editor's final text (empty = not reviewed)
+    pub final_text: String,
+    /// Term translations the editor chose, as (source, target) pairs
+    pub terms: Vec<(String, String)>,
+}
+
+impl ReviewRow {
+    /// Whether the editor filled in the final column.
+    pub fn is_reviewed(&self) -> bool {
+        !self.final_text.trim().is_empty()
+    }
+
+    /// Whether the final text differs from the machine translation.
+    pub fn is_changed(&self) -> bool {
+        self.is_reviewed() && self.final_text.trim() != self.machine.trim()
+    }
+
+    /// Source names the editor put back into the translation.
+    ///
+    /// A capitalised source word (or pair) that appears verbatim in the final
+    /// text but not in the machine translation was most likely a character
+    /// name the model translated or misspelled. A pair that does not qualify
+    /// as a whole ("Tell Gandalf") is checked word by word.
+    pub fn restored_names(&self) -> Vec<String> {
+        if !self.is_changed() {
+            return Vec::new();
+        }
+
+        let restored = |candidate: &str| {
+            candidate.chars().count() > 2
+                && contains_word(&self.final_text, candidate)
+                && !contains_word(&self.machine, candidate)
+        };
+
+        let mut names: Vec<String> = Vec::new();
+        for found in NAME_REGEX.find_iter(&self.source).map(|m| m.as_str()) {
+            let candidates: Vec<&str> = if restored(found) {
+                vec![found]
+            } else {
+                found.split_whitespace().filter(|word| *word != found && restored(word)).collect()
+            };
+            for candidate in candidates {
+                if !names.iter().any(|n| n == candidate) {
+                    names.push(candidate.to_string());
+                }
+            }
+        }
+        names
+    }
+}
+
+/// A review sheet: one row per session entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReviewSheet {
+    /// Rows in entry order
+    pub rows: Vec<ReviewRow>,
+}
+
+impl ReviewSheet {
+    /// Create a sheet from rows
+    pub fn new(rows: Vec<ReviewRow>) -> Self {
+        Self { rows }
+    }
+
+    /// Rows the editor filled in.
+    pub fn reviewed(&self) -> impl Iterator<Item = &ReviewRow> {
+        self.rows.iter().filter(|r| r.is_reviewed())
+    }
+
+    /// Render the sheet in the given format, header first.
+    pub fn render(&self, format: ReviewFormat) -> String {
+        let delimiter = format.delimiter();
+        let mut out = String::new();
+        out.push(UTF8_BOM);
+        out.push_str(&REVIEW_COLUMNS.join(&delimiter.to_string()));
+        out.push_str("\r\n");
+
+        for row in &self.rows {
+            let fields = [
+                row.entry_id.to_string(),
+                SubtitleEntry::format_timestamp(row.start_time_ms),
+                SubtitleEntry::format_timestamp(row.end_time_ms),
+                row.source.clone(),
+                row.machine.clone(),
+                row.issues.join(ISSUE_SEPARATOR),
+                row.final_text.clone(),
+                row.terms
+                    .iter()
+                    .map(|(source, target)| format!("{}{}{}", source, TERM_SEPARATOR, target))
+                    .collect::<Vec<_>>()
+                    .join(ISSUE_SEPARATOR),
+            ];
+            let line: Vec<String> = fields.iter().map(|f| quote_field(f, delimiter)).collect();
+            out.push_str(&line.join(&delimiter.to_string()));
+            out.push_str("\r\n");
+        }
+
+        out
+    }
+
+    /// Parse a sheet.
+    ///
+    /// Columns are found by header name, so editors may reorder them or add
+    /// their own. Only `id` and `final` are required.
+    pub fn parse(content: &str, format: ReviewFormat) -> Result<Self> {
+        let content = content.trim_start_matches(UTF8_BOM);
+        let mut records = split_records(content, format.delimiter())?.into_iter();
+
+        let header = records.next().ok_or_else(|| anyhow!("Review sheet is empty"))?;
+        let column = |name: &str| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+        let id_col = column("id").ok_or_else(|| anyhow!("Review sheet has no 'id' column"))?;
+        let final_col = column("final").ok_or_else(|| anyhow!("Review sheet has no 'final' column"))?;
+        let [start_col, end_col, source_col, machine_col, issues_col, terms_col] =
+            ["start", "end", "source", "machine", "issues", "terms"].map(column);
+
+        let mut rows = Vec::new();
+        for (line, record) in records.enumerate() {
+            if record.iter().all(|f| f.trim().is_empty()) {
+                continue;
+            }
+            let field = |col: Option<usize>| {
+                col.and_then(|c| record.get(c)).map(|f| f.replace("\r\n", "\n")).unwrap_or_default()
+            };
+            let time = |col: Option<usize>| -> Result<u64> {
+                let value = field(col);
+                if value.trim().is_empty() {
+                    Ok(0)
+                } else {
+                    SubtitleEntry::parse_timestamp(value.trim())
+                }
+            };
+
+            let id = field(Some(id_col));
+            let entry_id = id
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid entry id '{}' on row {}", id, line + 2))?;
+            let issues = field(issues_col);
+            let terms = parse_terms(&field(terms_col)).with_context(|| format!("Invalid terms on row {}", line + 2))?;
+
+            rows.push(ReviewRow {
+                entry_id,
+                start_time_ms: time(start_col).with_context(|| format!("Invalid start time on row {}", line + 2))?,
+                end_time_ms: time(end_col).with_context(|| format!("Invalid end time on row {}", line + 2))?,
+                source: field(source_col),
+                machine: field(machine_col),
+                issues: issues
+                    .split(ISSUE_SEPARATOR)
+                    .map(str::trim)
+                    .filter(|i| !i.is_empty())
+                    .map(String::from)
+                    .collect(),
+                final_text: field(Some(final_col)),
+                terms,
+            });
+        }
+
+        Ok(Self { rows })
+    }
+
+    /// Write the sheet to a file.
+    pub fn write_to_file(&self, path: &Path, format: ReviewFormat) -> Result<()> {
+        std::fs::write(path, self.render(format))
+            .with_context(|| format!("Failed to write review sheet: {}", path.display()))
+    }
+
+    /// Read a sheet from a file, picking the format from its extension.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read review sheet: {}", path.display()))?;
+        Self::parse(&content, ReviewFormat::from_path(path))
+            .with_context(|| format!("Failed to parse review sheet: {}", path.display()))
+    }
+}
+
+/// Outcome of importing a review sheet into a session
+#[derive(Debug, Clone, Default)]
+pub struct ReviewImportSummary {
+    /// Entries marked human-approved
+    pub approved: usize,
+    /// Approved entries whose text the editor changed
+    pub changed: usize,
+    /// Reviewed rows whose entry ID is not in the session
+    pub unknown: Vec<usize>,
+    /// Reviewed rows whose machine text no longer matches the session
+    pub outdated: Vec<usize>,
+    /// Names added to the session glossary
+    pub names: Vec<String>,
+    /// Term translations added to the session glossary
+    pub terms: usize,
+}
+
+impl fmt::Display for ReviewImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Approved {} entries ({} edited), added {} name(s) and {} term(s) to the glossary",
+            self.approved,
+            self.changed,
+            self.names.len(),
+            self.terms
+        )?;
+        if !self.unknown.is_empty() {
+            write!(f, ", skipped {} unknown entries", self.unknown.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a terms field: `source = target` pairs separated by semicolons.
+fn parse_terms(field: &str) -> Result<Vec<(String, String)>> {
+    field
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (source, target) = pair
+                .split_once('=')
+                .map(|(s, t)| (s.trim(), t.trim()))
+                .filter(|(s, t)| !s.is_empty() && !t.is_empty())
+                .ok_or_else(|| anyhow!("expected 'source = target', got '{}'", pair))?;
+            Ok((source.to_string(), target.to_string()))
+        })
+        .collect()
+}
+
+/// Whether `text` contains `word` delimited by non-word characters.
+fn contains_word(text: &str, word: &str) -> bool {
+    Regex::new(&format!(r"\b{}\b", regex::escape(word)))
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+/// Quote a field when it contains the delimiter, a quote or a line break.
+fn quote_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split delimited text into records, honouring quoted fields (RFC 4180).
+fn split_records(content: &str, delimiter: char) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(anyhow!("Unterminated quoted field"));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(entry_id: usize, source: &str, machine: &str, final_text: &str) -> ReviewRow {
+        ReviewRow {
+            entry_id,
+            start_time_ms: entry_id as u64 * 1000,
+            end_time_ms: entry_id as u64 * 1000 + 900,
+            source: source.to_string(),
+            machine: machine.to_string(),
+            issues: Vec::new(),
+            final_text: final_text.to_string(),
+            terms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_reviewSheet_render_shouldRoundTripQuotesAndLineBreaks() {
+        let mut first = row(1, "He said \"run\", then left.", "Il a dit « cours », puis est parti.", "");
+        first.issues = vec!["Length ratio 2.1".to_string(), "Missing italics".to_string()];
+        first.terms = vec![("run".to_string(), "cours".to_string()), ("Shire".to_string(), "Comté".to_string())];
+        let sheet = ReviewSheet::new(vec![first, row(2, "Line one\nLine two", "Ligne un\nLigne deux", "Ligne 1\nLigne 2")]);
+
+        for format in [ReviewFormat::Csv, ReviewFormat::Tsv] {
+            let rendered = sheet.render(format);
+            assert!(rendered.starts_with('\u{feff}'));
+
+            let parsed = ReviewSheet::parse(&rendered, format).unwrap();
+            assert_eq!(parsed, sheet);
+        }
+    }
+
+    #[test]
+    fn test_reviewSheet_parse_shouldFindColumnsByHeaderName() {
+        let content = "Final\tnotes\tID\nBonjour\tok\t3\n\t\t4\n";
+
+        let sheet = ReviewSheet::parse(content, ReviewFormat::Tsv).unwrap();
+
+        assert_eq!(sheet.rows.len(), 2);
+        assert_eq!(sheet.rows[0].entry_id, 3);
+        assert_eq!(sheet.rows[0].final_text, "Bonjour");
+        assert_eq!(sheet.reviewed().map(|r| r.entry_id).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn test_reviewSheet_parse_withMalformedTerm_shouldFail() {
+        let result = ReviewSheet::parse("id,final,terms\n1,Bonjour,Ring Anneau\n", ReviewFormat::Csv);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reviewSheet_parse_withoutIdColumn_shouldFail() {
+        let result = ReviewSheet::parse("source,final\nHello,Bonjour\n", ReviewFormat::Csv);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reviewRow_restoredNames_shouldFindNamesPutBackByEditor() {
+        let edited = row(1, "Tell Gandalf the bridge is out.", "Dis à Gandalph que le pont est coupé.", "Dis à Gandalf que le pont est coupé.");
+        let unchanged = row(2, "Tell Gandalf.", "Dis-le à Gandalf.", "Dis-le à Gandalf.");
+
+        assert_eq!(edited.restored_names(), vec!["Gandalf".to_string()]);
+        assert!(unchanged.restored_names().is_empty());
+    }
+
+    #[test]
+    fn test_reviewFormat_fromPath_shouldDetectTsv() {
+        assert_eq!(ReviewFormat::from_path(Path::new("review.tsv")), ReviewFormat::Tsv);
+        assert_eq!(ReviewFormat::from_path(Path::new("review.CSV")), ReviewFormat::Csv);
+        assert_eq!("tsv".parse::<ReviewFormat>().unwrap(), ReviewFormat::Tsv);
+    }
+}
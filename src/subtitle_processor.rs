@@ -10,6 +10,7 @@ use log::{error, warn, debug};
 use serde_json::{Value, from_str};
 use crate::app_config::SubtitleInfo;
 use crate::language_utils;
+use crate::video_timing::{FrameRate, TimingTransform};
 use tokio::process::Command;
 
 // @module: Subtitle processing and manipulation
@@ -243,6 +244,17 @@ impl SubtitleCollection {
         Ok(())
     }
     
+    /// Apply timing transforms in order, returning how many cues were clamped at zero
+    pub fn apply_timing_transforms(&mut self, transforms: &[TimingTransform]) -> Result<usize> {
+        let mut clamped = 0;
+        for transform in transforms {
+            clamped += transform
+                .apply(&mut self.entries)
+                .with_context(|| format!("Failed to apply {}", transform.description()))?;
+        }
+        Ok(clamped)
+    }
+    
     /// Split subtitles into chunks for translation
     /// 
     /// This method divides the subtitle entries into chunks that don't exceed the specified 
@@ -434,6 +446,46 @@ impl SubtitleCollection {
         Ok(tracks)
     }
     
+    /// Probe the frame rate of the first video stream in a file
+    pub async fn probe_frame_rate<P: AsRef<Path>>(video_path: P) -> Result<FrameRate> {
+        let video_path = video_path.as_ref();
+        
+        if !video_path.exists() {
+            return Err(anyhow!("Video file not found: {:?}", video_path));
+        }
+        
+        let ffprobe_future = Command::new("ffprobe")
+            .args([
+                "-v", "quiet",
+                "-print_format", "json",
+                "-show_entries", "stream=r_frame_rate,avg_frame_rate",
+                "-select_streams", "v:0",
+                video_path.to_str().unwrap_or("")
+            ])
+            .output();
+        
+        let timeout_duration = std::time::Duration::from_secs(60); // 1 minute timeout
+        let output = tokio::select! {
+            result = ffprobe_future => {
+                result.map_err(|e| anyhow!("Failed to execute ffprobe command: {}", e))?
+            },
+            _ = tokio::time::sleep(timeout_duration) => {
+                return Err(anyhow!("ffprobe command timed out after 60 seconds"));
+            }
+        };
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("ffprobe command failed: {}", stderr));
+        }
+        
+        let json: Value = from_str(&String::from_utf8_lossy(&output.stdout))
+            .context("Failed to parse ffprobe JSON output")?;
+        
+        FrameRate::from_ffprobe_json(&json)
+            .ok_or_else(|| anyhow!("No video frame rate found in {:?}", video_path))
+    }
+    
     /// Select a subtitle track based on preferred language
     pub fn select_subtitle_track(tracks: &[SubtitleInfo], preferred_language: &str) -> Option<usize> {
         if tracks.is_empty() {
@@ -26,12 +26,16 @@ use tokio::sync::Mutex;
 use tokio::sync::Semaphore;
 use futures::stream::{self, StreamExt};
 use futures::future::join_all;
+use chrono::Utc;
 
 use crate::subtitle_processor::SubtitleEntry;
 use crate::validation::MarkerValidator;
 
 use super::core::{LogEntry, TokenUsageStats, TranslationService};
 use super::formatting::FormatPreserver;
+use super::pipeline::PipelinePhase;
+use super::quality::errors::TranslationErrorKind;
+use super::telemetry::{BatchTelemetry, MetricsRecorder};
 
 /// Configuration for parallel translation
 #[derive(Clone, Debug)]
@@ -190,7 +194,9 @@ impl BatchTranslator {
         // Process batches concurrently
         let results = stream::iter(batches.iter().enumerate())
             .map(|(batch_index, batch)| {
-                let service = self.service.clone();
+                let recorder = MetricsRecorder::new();
+                let service = self.service.clone().with_metrics(recorder.clone());
+                let pricing = self.service.config.get_pricing();
                 let semaphore = semaphore.clone();
                 let log_capture = log_capture.clone();
                 let processed_batches = processed_batches.clone();
@@ -211,7 +217,7 @@ impl BatchTranslator {
                                 level: "ERROR".to_string(),
                                 message: format!("Failed to acquire semaphore permit: {}", e),
                             });
-                            return (batch_index, Err(anyhow!("Failed to acquire semaphore permit: {}", e)));
+                            return (batch_index, Err(anyhow!("Failed to acquire semaphore permit: {}", e)), None);
                         }
                     };
                     
@@ -225,6 +231,7 @@ impl BatchTranslator {
                     }
                     
                     // Process the batch
+                    let started_at = Utc::now();
                     let start_time = Instant::now();
                     let result = service.translate_batch_with_recovery(
                         batch,
@@ -267,7 +274,18 @@ impl BatchTranslator {
                         }
                     }
 
-                    (batch_index, result)
+                    let telemetry = BatchTelemetry::new(
+                        PipelinePhase::Translation,
+                        batch_index,
+                        batch.len(),
+                        started_at,
+                        start_time.elapsed(),
+                        recorder.take(),
+                        pricing,
+                    );
+                    service.report_batch(&telemetry);
+
+                    (batch_index, result, Some(telemetry))
                 }
             })
             .buffer_unordered(self.max_concurrent_requests)
@@ -280,9 +298,10 @@ impl BatchTranslator {
         
         // Sort results by batch index to maintain original order
         let mut sorted_results = results;
-        sorted_results.sort_by_key(|(idx, _)| *idx);
+        sorted_results.sort_by_key(|(idx, _, _)| *idx);
         
-        for (batch_idx, result) in sorted_results {
+        for (batch_idx, result, telemetry) in sorted_results {
+            token_stats.batches.extend(telemetry);
             match result {
                 Ok((entries, token_usage)) => {
                     all_entries.extend(entries);
@@ -411,9 +430,11 @@ impl BatchTranslator {
         let batch_callback = batch_complete_callback.map(Arc::new);
         
         // Process work items in parallel
-        let results = stream::iter(work_items.into_iter())
+        let results = stream::iter(work_items)
             .map(|(work_idx, entries, context_entries)| {
-                let service = self.service.clone();
+                let recorder = MetricsRecorder::new();
+                let service = self.service.clone().with_metrics(recorder.clone());
+                let pricing = self.service.config.get_pricing();
                 let semaphore = semaphore.clone();
                 let log_capture = log_capture.clone();
                 let token_stats = token_stats.clone();
@@ -432,6 +453,7 @@ impl BatchTranslator {
                         }
                     };
                     
+                    let started_at = Utc::now();
                     let start_time = Instant::now();
                     
                     // Translate entries individually or as a small batch
@@ -456,6 +478,23 @@ impl BatchTranslator {
                     };
                     
                     let duration = start_time.elapsed();
+
+                    // Record the batch telemetry, failed batches included
+                    {
+                        let telemetry = BatchTelemetry::new(
+                            PipelinePhase::Translation,
+                            work_idx,
+                            entries.len(),
+                            started_at,
+                            duration,
+                            recorder.take(),
+                            pricing,
+                        );
+                        service.report_batch(&telemetry);
+                        let mut stats = token_stats.lock().await;
+                        stats.add_token_usage(Some(telemetry.prompt_tokens), Some(telemetry.completion_tokens));
+                        stats.batches.push(telemetry);
+                    }
                     
                     // Update token stats if we have usage info
                     if let Ok(ref translated) = result {
@@ -525,7 +564,8 @@ impl BatchTranslator {
         }
         
         // Get final token stats
-        let final_stats = token_stats.lock().await.clone();
+        let mut final_stats = token_stats.lock().await.clone();
+        final_stats.batches.sort_by_key(|b| b.batch_index);
         
         if !errors.is_empty() && all_translated.is_empty() {
             return Err(anyhow!("All translation requests failed: {}", errors.join("; ")));
@@ -536,6 +576,31 @@ impl BatchTranslator {
 }
 
 impl TranslationService {
+    /// Translate entries with neighbouring entries shown as context only
+    ///
+    /// Used to re-translate a few cues in place, where the context may come
+    /// from both sides of the entries being translated.
+    pub async fn translate_entries_with_context(
+        &self,
+        entries: &[SubtitleEntry],
+        context_entries: &[SubtitleEntry],
+        source_language: &str,
+        target_language: &str,
+        log_capture: Arc<Mutex<Vec<LogEntry>>>,
+    ) -> Result<Vec<SubtitleEntry>> {
+        match entries {
+            [] => Ok(Vec::new()),
+            [entry] => self
+                .translate_single_entry_with_context(entry, context_entries, source_language, target_language, log_capture)
+                .await
+                .map(|entry| vec![entry]),
+            _ => {
+                self.translate_small_batch_with_context(entries, context_entries, source_language, target_language, log_capture)
+                    .await
+            }
+        }
+    }
+
     /// Translate a single entry in parallel mode (no markers needed)
     async fn translate_single_entry_parallel(
         &self,
@@ -550,10 +615,12 @@ impl TranslationService {
         }
         
         // Translate the entry text directly
-        let (translated_text, _) = self.translate_text_with_usage(
+        let context = self.cache_context(&[entry.text.as_str()], &[], &[]);
+        let (translated_text, _) = self.translate_text_in_context(
             &entry.text,
             source_language,
             target_language,
+            &context,
             Some(log_capture),
         ).await?;
         
@@ -590,10 +657,13 @@ impl TranslationService {
         }
         
         // Translate combined text
-        let (translated_text, _) = self.translate_text_with_usage(
+        let lines: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        let context = self.cache_context(&lines, &[], &[]);
+        let (translated_text, _) = self.translate_text_in_context(
             &combined_text,
             source_language,
             target_language,
+            &context,
             Some(log_capture.clone()),
         ).await?;
         
@@ -648,6 +718,7 @@ impl TranslationService {
         
         // Retry failed entries concurrently (not sequentially)
         if !entries_needing_retry.is_empty() {
+            self.record_retries(TranslationErrorKind::ParseError, entries_needing_retry.len());
             {
                 let mut logs = log_capture.lock().await;
                 logs.push(LogEntry {
@@ -747,10 +818,13 @@ impl TranslationService {
         prompt.push_str(&entry.text);
         
         // Translate with context
-        let (translated_text, _) = self.translate_text_with_usage(
+        let previous: Vec<&str> = context_entries.iter().map(|e| e.text.as_str()).collect();
+        let context = self.cache_context(&[entry.text.as_str()], &previous, &[]);
+        let (translated_text, _) = self.translate_text_in_context(
             &prompt,
             source_language,
             target_language,
+            &context,
             Some(log_capture),
         ).await?;
         
@@ -809,10 +883,14 @@ impl TranslationService {
         }
         
         // Translate combined text
-        let (translated_text, _) = self.translate_text_with_usage(
+        let lines: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        let previous: Vec<&str> = context_entries.iter().map(|e| e.text.as_str()).collect();
+        let context = self.cache_context(&lines, &previous, &[]);
+        let (translated_text, _) = self.translate_text_in_context(
             &combined_text,
             source_language,
             target_language,
+            &context,
             Some(log_capture.clone()),
         ).await?;
         
@@ -861,6 +939,7 @@ impl TranslationService {
         
         // Retry failed entries concurrently with context
         if !entries_needing_retry.is_empty() {
+            self.record_retries(TranslationErrorKind::ParseError, entries_needing_retry.len());
             {
                 let mut logs = log_capture.lock().await;
                 logs.push(LogEntry {
@@ -1067,11 +1146,14 @@ impl TranslationService {
         combined_text.push_str("<<END>>");
 
         // Translate the combined text
+        let lines: Vec<&str> = batch.iter().map(|e| e.text.as_str()).collect();
+        let context = self.cache_context(&lines, &[], &[]);
         let (translated_text, token_usage) = self
-            .translate_text_with_usage(
+            .translate_text_in_context(
                 &combined_text,
                 source_language,
                 target_language,
+                &context,
                 Some(log_capture.clone()),
             )
             .await?;
@@ -1210,6 +1292,7 @@ impl TranslationService {
 
         // Retry failed entries concurrently
         if !entries_needing_retry.is_empty() {
+            self.record_retries(TranslationErrorKind::ParseError, entries_needing_retry.len());
             {
                 let mut logs = log_capture.lock().await;
                 logs.push(LogEntry {
@@ -1295,10 +1378,12 @@ impl TranslationService {
         }
         
         // Translate the entry text
-        let (translated_text, _) = self.translate_text_with_usage(
+        let context = self.cache_context(&[entry.text.as_str()], &[], &[]);
+        let (translated_text, _) = self.translate_text_in_context(
             &entry.text,
             source_language,
             target_language,
+            &context,
             Some(log_capture)
         ).await?;
         
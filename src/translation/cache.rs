@@ -3,10 +3,14 @@
  *
  * This module provides a two-tier caching system for translations:
  * - L1: In-memory cache for fast access during current session
- * - L2: Database-backed cache for cross-session persistence
+ * - L2: Storage-backed cache for cross-session persistence (SQLite by default)
  *
  * This avoids redundant API calls and improves performance significantly,
  * especially for repeated translations of common phrases.
+ *
+ * Keys can optionally include a `CacheContext` (speaker, formality and a hash
+ * of the neighbouring lines), so short lines like "Right." are not reused
+ * across contexts where they translate differently.
  */
 
 // Allow dead code - some cache methods are for future use
@@ -14,13 +18,19 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use log::debug;
 
 use crate::database::models::CacheRecord;
-use crate::database::repository::Repository;
+use crate::database::repository::{CacheEvictionPolicy, Repository};
+use crate::database::storage::Storage;
+
+/// Number of L2 stores between two evictions
+const EVICTION_INTERVAL: usize = 500;
 
-/// Cache key combining source text, source language, and target language
+/// Cache key combining source text, languages and translation context
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct CacheKey {
     /// Source text to translate
@@ -31,19 +41,95 @@ struct CacheKey {
 
     /// Target language code
     target_language: String,
+
+    /// Context key (empty = any context)
+    context: String,
 }
 
 impl CacheKey {
     /// Create a new cache key
-    pub fn new(source_text: &str, source_language: &str, target_language: &str) -> Self {
+    pub fn new(source_text: &str, source_language: &str, target_language: &str, context: &str) -> Self {
         Self {
             source_text: source_text.to_string(),
             source_language: source_language.to_string(),
             target_language: target_language.to_string(),
+            context: context.to_string(),
         }
     }
 }
 
+/// Which parts of the translation context are included in cache keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheKeyFields {
+    /// Key by speaker (e.g. so gendered agreement is not reused across speakers)
+    #[serde(default)]
+    pub speaker: bool,
+
+    /// Key by formality (e.g. tu/vous)
+    #[serde(default)]
+    pub formality: bool,
+
+    /// Key by a hash of the neighbouring lines
+    #[serde(default)]
+    pub neighbours: bool,
+}
+
+/// Translation context that distinguishes cached translations of the same text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheContext {
+    /// Speaker of the line
+    pub speaker: Option<String>,
+
+    /// Formality register (e.g. "formal", "informal")
+    pub formality: Option<String>,
+
+    /// Hash of the previous and next lines
+    pub neighbour_hash: Option<String>,
+}
+
+impl CacheContext {
+    /// Set the speaker.
+    pub fn with_speaker(mut self, speaker: &str) -> Self {
+        self.speaker = Some(speaker.trim().to_lowercase());
+        self
+    }
+
+    /// Set the formality register.
+    pub fn with_formality(mut self, formality: &str) -> Self {
+        self.formality = Some(formality.trim().to_lowercase());
+        self
+    }
+
+    /// Set the neighbour hash from the lines before and after the text.
+    pub fn with_neighbours(mut self, previous: &[&str], next: &[&str]) -> Self {
+        let joined = format!("{}\u{1f}{}", previous.join("\n"), next.join("\n"));
+        self.neighbour_hash = Some(Repository::hash_text(&joined)[..16].to_string());
+        self
+    }
+
+    /// Keep only the parts enabled in `fields`.
+    pub fn filtered(&self, fields: &CacheKeyFields) -> Self {
+        Self {
+            speaker: self.speaker.clone().filter(|_| fields.speaker),
+            formality: self.formality.clone().filter(|_| fields.formality),
+            neighbour_hash: self.neighbour_hash.clone().filter(|_| fields.neighbours),
+        }
+    }
+
+    /// Get the key stored with cache entries (empty when there is no context).
+    pub fn key(&self) -> String {
+        let parts: Vec<String> = [
+            ("speaker", &self.speaker),
+            ("formality", &self.formality),
+            ("neighbours", &self.neighbour_hash),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_ref().map(|v| format!("{}={}", name, v)))
+        .collect();
+        parts.join(";")
+    }
+}
+
 /// Cache statistics
 #[derive(Debug, Clone, Default)]
 pub struct CacheStats {
@@ -98,6 +184,10 @@ pub struct CacheConfig {
     pub provider: String,
     /// Model name for cache key differentiation
     pub model: String,
+    /// Context parts included in cache keys
+    pub key_fields: CacheKeyFields,
+    /// Eviction applied to the L2 cache while storing
+    pub eviction: CacheEvictionPolicy,
 }
 
 impl Default for CacheConfig {
@@ -108,6 +198,8 @@ impl Default for CacheConfig {
             l1_max_entries: 10000,
             provider: String::new(),
             model: String::new(),
+            key_fields: CacheKeyFields::default(),
+            eviction: CacheEvictionPolicy::default(),
         }
     }
 }
@@ -117,14 +209,17 @@ pub struct TranslationCache {
     /// L1: In-memory cache storage
     l1_cache: Arc<RwLock<HashMap<CacheKey, String>>>,
 
-    /// L2: Database repository (optional)
-    l2_repo: Option<Repository>,
+    /// L2: Storage backend (optional)
+    l2_repo: Option<Arc<dyn Storage>>,
 
     /// Cache statistics
     stats: Arc<RwLock<CacheStats>>,
 
     /// Cache configuration
     config: CacheConfig,
+
+    /// L2 stores since the last eviction
+    stores_since_eviction: Arc<AtomicUsize>,
 }
 
 impl TranslationCache {
@@ -134,6 +229,7 @@ impl TranslationCache {
             l1_cache: Arc::new(RwLock::new(HashMap::new())),
             l2_repo: None,
             stats: Arc::new(RwLock::new(CacheStats::default())),
+            stores_since_eviction: Arc::new(AtomicUsize::new(0)),
             config: CacheConfig {
                 l1_enabled: enabled,
                 l2_enabled: false,
@@ -144,20 +240,34 @@ impl TranslationCache {
 
     /// Create a cache with both L1 and L2 tiers
     pub fn new_with_db(config: CacheConfig, repo: Repository) -> Self {
+        Self::new_with_storage(config, Arc::new(repo))
+    }
+
+    /// Create a cache with both tiers, using any storage backend for L2
+    pub fn new_with_storage(config: CacheConfig, storage: Arc<dyn Storage>) -> Self {
         Self {
             l1_cache: Arc::new(RwLock::new(HashMap::new())),
-            l2_repo: Some(repo),
+            l2_repo: Some(storage),
             stats: Arc::new(RwLock::new(CacheStats::default())),
+            stores_since_eviction: Arc::new(AtomicUsize::new(0)),
             config,
         }
     }
 
+    /// A view of this cache that shares its in-memory tier and statistics but never touches L2
+    pub fn memory_only(&self) -> Self {
+        let mut cache = self.clone();
+        cache.config.l2_enabled = false;
+        cache
+    }
+
     /// Create a cache with custom configuration but no L2
     pub fn with_config(config: CacheConfig) -> Self {
         Self {
             l1_cache: Arc::new(RwLock::new(HashMap::new())),
             l2_repo: None,
             stats: Arc::new(RwLock::new(CacheStats::default())),
+            stores_since_eviction: Arc::new(AtomicUsize::new(0)),
             config,
         }
     }
@@ -171,9 +281,25 @@ impl TranslationCache {
         source_language: &str,
         target_language: &str,
     ) -> Option<String> {
+        self.get_in_context(source_text, source_language, target_language, &CacheContext::default())
+            .await
+    }
+
+    /// Get a translation made in the given context
+    ///
+    /// Only the context parts enabled in the configured key fields are used.
+    pub async fn get_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        context: &CacheContext,
+    ) -> Option<String> {
+        let context_key = context.filtered(&self.config.key_fields).key();
+
         // Check L1 first
         if self.config.l1_enabled {
-            let key = CacheKey::new(source_text, source_language, target_language);
+            let key = CacheKey::new(source_text, source_language, target_language, &context_key);
             let cache = self.l1_cache.read().await;
 
             if let Some(translation) = cache.get(&key) {
@@ -200,12 +326,13 @@ impl TranslationCache {
         if self.config.l2_enabled {
             if let Some(ref repo) = self.l2_repo {
                 match repo
-                    .get_cached_translation(
+                    .get_cached_translation_in_context(
                         source_text,
                         source_language,
                         target_language,
                         &self.config.provider,
                         &self.config.model,
+                        &context_key,
                     )
                     .await
                 {
@@ -223,7 +350,7 @@ impl TranslationCache {
 
                         // Promote to L1
                         if self.config.l1_enabled {
-                            let key = CacheKey::new(source_text, source_language, target_language);
+                            let key = CacheKey::new(source_text, source_language, target_language, &context_key);
                             let mut cache = self.l1_cache.write().await;
                             cache.insert(key, translation.clone());
                         }
@@ -262,9 +389,24 @@ impl TranslationCache {
         target_language: &str,
         translation: &str,
     ) {
+        self.store_in_context(source_text, source_language, target_language, &CacheContext::default(), translation)
+            .await
+    }
+
+    /// Store a translation made in the given context
+    pub async fn store_in_context(
+        &self,
+        source_text: &str,
+        source_language: &str,
+        target_language: &str,
+        context: &CacheContext,
+        translation: &str,
+    ) {
+        let context_key = context.filtered(&self.config.key_fields).key();
+
         // Store in L1
         if self.config.l1_enabled {
-            let key = CacheKey::new(source_text, source_language, target_language);
+            let key = CacheKey::new(source_text, source_language, target_language, &context_key);
             let mut cache = self.l1_cache.write().await;
 
             // Check size limit
@@ -290,7 +432,7 @@ impl TranslationCache {
         if self.config.l2_enabled {
             if let Some(ref repo) = self.l2_repo {
                 let hash = Repository::hash_text(source_text);
-                let record = CacheRecord::new(
+                let mut record = CacheRecord::new(
                     hash,
                     source_text.to_string(),
                     source_language.to_string(),
@@ -299,6 +441,7 @@ impl TranslationCache {
                     self.config.provider.clone(),
                     self.config.model.clone(),
                 );
+                record.context_key = context_key;
 
                 if let Err(e) = repo.cache_translation(&record).await {
                     debug!("L2 cache store error: {}", e);
@@ -309,11 +452,30 @@ impl TranslationCache {
                         source_language,
                         target_language
                     );
+                    self.evict_if_due(repo.as_ref()).await;
                 }
             }
         }
     }
 
+    /// Apply the eviction policy to L2 every `EVICTION_INTERVAL` stores
+    async fn evict_if_due(&self, repo: &dyn Storage) {
+        if self.config.eviction.is_disabled()
+            || self.stores_since_eviction.fetch_add(1, Ordering::Relaxed) + 1 < EVICTION_INTERVAL
+        {
+            return;
+        }
+        self.stores_since_eviction.store(0, Ordering::Relaxed);
+
+        match repo.prune_cache(&self.config.eviction).await {
+            Ok(result) if result.total() > 0 => {
+                debug!("Evicted {} expired and {} excess L2 cache entries", result.expired, result.evicted);
+            }
+            Ok(_) => {}
+            Err(e) => debug!("L2 cache eviction error: {}", e),
+        }
+    }
+
     /// Get cache statistics
     pub async fn stats(&self) -> CacheStats {
         let mut stats = self.stats.read().await.clone();
@@ -418,6 +580,7 @@ impl TranslationCache {
                 &entry.source_text,
                 &entry.source_language,
                 &entry.target_language,
+                &entry.context_key,
             );
             cache.insert(key, entry.translated_text);
         }
@@ -444,6 +607,7 @@ impl Clone for TranslationCache {
             l2_repo: self.l2_repo.clone(),
             stats: self.stats.clone(),
             config: self.config.clone(),
+            stores_since_eviction: self.stores_since_eviction.clone(),
         }
     }
 }
@@ -534,6 +698,7 @@ mod tests {
             l1_max_entries: 100,
             provider: "test".to_string(),
             model: "test-model".to_string(),
+            ..Default::default()
         };
 
         let cache = TranslationCache::new_with_db(config, repo);
@@ -551,6 +716,29 @@ mod tests {
         assert_eq!(result.unwrap(), "Bonjour");
     }
 
+    #[tokio::test]
+    async fn test_memoryOnly_store_shouldNotPersistToL2() {
+        let repo = Repository::new_in_memory().expect("Failed to create test repo");
+
+        let config = CacheConfig {
+            l1_enabled: true,
+            l2_enabled: true,
+            l1_max_entries: 100,
+            provider: "test".to_string(),
+            model: "test-model".to_string(),
+            ..Default::default()
+        };
+
+        let cache = TranslationCache::new_with_db(config, repo);
+
+        cache.memory_only().store("Prompt", "prompt", "json_response", "{}").await;
+        assert_eq!(cache.get("Prompt", "prompt", "json_response").await.as_deref(), Some("{}"));
+
+        // Only the shared L1 held it
+        cache.clear_l1().await;
+        assert!(cache.get("Prompt", "prompt", "json_response").await.is_none());
+    }
+
     #[tokio::test]
     async fn test_l2_hit_should_promote_to_l1() {
         let repo = Repository::new_in_memory().expect("Failed to create test repo");
@@ -561,6 +749,7 @@ mod tests {
             l1_max_entries: 100,
             provider: "test".to_string(),
             model: "test-model".to_string(),
+            ..Default::default()
         };
 
         let cache = TranslationCache::new_with_db(config, repo);
@@ -591,6 +780,7 @@ mod tests {
             l1_max_entries: 2,
             provider: String::new(),
             model: String::new(),
+            ..Default::default()
         };
 
         let cache = TranslationCache::with_config(config);
@@ -606,6 +796,57 @@ mod tests {
         assert!(stats.l1_entries <= 2);
     }
 
+    #[tokio::test]
+    async fn test_context_key_fields_should_separate_translations_by_speaker() {
+        let config = CacheConfig {
+            l2_enabled: false,
+            key_fields: CacheKeyFields {
+                speaker: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let cache = TranslationCache::with_config(config);
+        let anna = CacheContext::default().with_speaker("Anna").with_formality("informal");
+        let tom = CacheContext::default().with_speaker("Tom");
+
+        cache.store_in_context("I'm ready.", "en", "fr", &anna, "Je suis prête.").await;
+        cache.store_in_context("I'm ready.", "en", "fr", &tom, "Je suis prêt.").await;
+
+        assert_eq!(cache.get_in_context("I'm ready.", "en", "fr", &anna).await, Some("Je suis prête.".to_string()));
+        assert_eq!(cache.get_in_context("I'm ready.", "en", "fr", &tom).await, Some("Je suis prêt.".to_string()));
+        assert!(cache.get("I'm ready.", "en", "fr").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_key_fields_should_ignore_context() {
+        let cache = TranslationCache::new(true);
+        let context = CacheContext::default()
+            .with_speaker("Anna")
+            .with_neighbours(&["Are you ready?"], &["Let's go."]);
+
+        cache.store_in_context("Right.", "en", "fr", &context, "D'accord.").await;
+
+        assert_eq!(cache.get("Right.", "en", "fr").await, Some("D'accord.".to_string()));
+    }
+
+    #[test]
+    fn test_cache_context_key_should_list_enabled_parts() {
+        let context = CacheContext::default()
+            .with_speaker(" Anna ")
+            .with_formality("Formal")
+            .with_neighbours(&["Are you ready?"], &[]);
+        let fields = CacheKeyFields {
+            speaker: true,
+            formality: true,
+            neighbours: false,
+        };
+
+        assert_eq!(context.filtered(&fields).key(), "speaker=anna;formality=formal");
+        assert_eq!(context.neighbour_hash.as_ref().map(String::len), Some(16));
+        assert_eq!(CacheContext::default().key(), "");
+    }
+
     #[test]
     fn test_cache_stats_hit_rate_should_calculate_correctly() {
         let stats = CacheStats {
@@ -55,7 +55,7 @@ impl ProviderProfile {
                 recommended_batch_size: 4,
                 supports_streaming: true,
             },
-            TranslationProvider::VLLM => Self {
+            TranslationProvider::Vllm => Self {
                 // vLLM excels at high-throughput with continuous batching
                 max_concurrent_requests: 16,
                 target_rpm: None, // No rate limit for local vLLM
@@ -245,6 +245,7 @@ mod tests {
                     scene_id: None,
                     confidence: None,
                     formatting: Vec::new(),
+                    class: Default::default(),
                 }
             })
             .collect()
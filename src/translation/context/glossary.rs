@@ -284,6 +284,12 @@ pub enum ConsistencyIssue {
         expected: String,
         translated: String,
     },
+    /// An honorific or cultural term did not follow the term policy
+    TermPolicyViolation {
+        term: String,
+        expected: String,
+        translated: String,
+    },
 }
 
 impl ConsistencyIssue {
@@ -299,6 +305,9 @@ impl ConsistencyIssue {
                     source, expected
                 )
             }
+            ConsistencyIssue::TermPolicyViolation { term, expected, .. } => {
+                format!("Term '{}' should be {} in translation", term, expected)
+            }
         }
     }
 }
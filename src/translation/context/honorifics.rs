@@ -0,0 +1,559 @@
+/*!
+ * Honorific and untranslatable-term preservation policy.
+ *
+ * Japanese and Korean dialogue carries honorifics (-san, senpai, oppa) and
+ * cultural terms that a show may want kept as-is or rendered as an agreed
+ * equivalent. A `TermPolicy` lists those terms and what to do with them;
+ * `TermPolicyEnforcer` turns it into prompt instructions, checks translations
+ * against it, and repairs violations.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::language_utils;
+
+use super::glossary::ConsistencyIssue;
+
+/// Kind of term covered by a policy rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TermKind {
+    /// Form of address attached to or replacing a name
+    #[default]
+    Honorific,
+    /// Culture-specific word without a good equivalent
+    CulturalTerm,
+}
+
+/// Whether a term attaches to a name or stands on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TermPlacement {
+    /// Attached to a name with a hyphen (Tanaka-san)
+    Suffix,
+    /// Used as a word of its own (senpai)
+    #[default]
+    Standalone,
+}
+
+/// What the translation should do with a detected term.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TermAction {
+    /// Keep the romanized form
+    #[default]
+    Keep,
+    /// Render the term as an agreed equivalent
+    Replace { with: String },
+    /// Drop the term
+    Omit,
+}
+
+/// A single term and how to handle it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermRule {
+    /// Source-script spellings (e.g. "さん")
+    #[serde(default)]
+    pub forms: Vec<String>,
+
+    /// Romanized spelling used in translations (e.g. "san")
+    pub romanized: String,
+
+    /// Kind of term
+    #[serde(default)]
+    pub kind: TermKind,
+
+    /// Whether the term is a name suffix
+    #[serde(default)]
+    pub placement: TermPlacement,
+
+    /// What to do with the term
+    #[serde(default)]
+    pub action: TermAction,
+
+    /// Renderings a model tends to substitute (e.g. "Mr."), undone on repair
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalents: Vec<String>,
+
+    /// Source words that contain a form but are not the term (e.g. "お父さん")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exceptions: Vec<String>,
+}
+
+impl TermRule {
+    /// Create a rule that keeps a name suffix.
+    pub fn suffix(forms: &[&str], romanized: &str) -> Self {
+        Self {
+            forms: forms.iter().map(|s| s.to_string()).collect(),
+            romanized: romanized.to_string(),
+            kind: TermKind::Honorific,
+            placement: TermPlacement::Suffix,
+            action: TermAction::Keep,
+            equivalents: Vec::new(),
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// Create a rule that keeps a standalone term.
+    pub fn standalone(forms: &[&str], romanized: &str) -> Self {
+        Self {
+            placement: TermPlacement::Standalone,
+            ..Self::suffix(forms, romanized)
+        }
+    }
+
+    /// Set the kind of term.
+    pub fn with_kind(mut self, kind: TermKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the action to apply.
+    pub fn with_action(mut self, action: TermAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Set renderings that repair should turn back into the term.
+    pub fn with_equivalents(mut self, equivalents: &[&str]) -> Self {
+        self.equivalents = equivalents.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Set source words that should not count as the term.
+    pub fn with_exceptions(mut self, exceptions: &[&str]) -> Self {
+        self.exceptions = exceptions.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// The romanized form as it appears in a translation ("-san", "senpai").
+    pub fn rendering(&self) -> String {
+        match self.placement {
+            TermPlacement::Suffix => format!("-{}", self.romanized),
+            TermPlacement::Standalone => self.romanized.clone(),
+        }
+    }
+
+    /// Label used in instructions and issues.
+    pub fn label(&self) -> String {
+        self.forms.first().cloned().unwrap_or_else(|| self.rendering())
+    }
+
+    /// Check whether the term occurs in source text.
+    pub fn occurs_in(&self, text: &str) -> bool {
+        let mut text = text.to_string();
+        for exception in &self.exceptions {
+            text = text.replace(exception.as_str(), "");
+        }
+
+        self.forms.iter().any(|f| !f.is_empty() && text.contains(f.as_str()))
+            || self.romanized_regex().is_some_and(|re| re.is_match(&text))
+    }
+
+    /// Describe what the policy expects, e.g. "kept as '-san'".
+    pub fn expectation(&self) -> String {
+        match &self.action {
+            TermAction::Keep => format!("kept as '{}'", self.rendering()),
+            TermAction::Replace { with } => format!("rendered as '{}'", with),
+            TermAction::Omit => "omitted".to_string(),
+        }
+    }
+
+    /// Render the prompt instruction for this term.
+    pub fn instruction(&self) -> String {
+        match (&self.action, self.placement) {
+            (TermAction::Keep, TermPlacement::Suffix) => format!(
+                "Keep '{}' as the suffix '{}' on the name (e.g. \"Tanaka{}\")",
+                self.label(),
+                self.rendering(),
+                self.rendering()
+            ),
+            (TermAction::Keep, TermPlacement::Standalone) => format!(
+                "Keep '{}' untranslated as '{}'",
+                self.label(),
+                self.rendering()
+            ),
+            (TermAction::Replace { with }, _) => {
+                format!("Render '{}' as '{}'", self.label(), with)
+            }
+            (TermAction::Omit, _) => format!("Drop '{}' from the translation", self.label()),
+        }
+    }
+
+    /// Check a translation against this rule.
+    pub fn is_followed(&self, translated: &str) -> bool {
+        let has_term = self.romanized_regex().is_some_and(|re| re.is_match(translated))
+            || self.forms.iter().any(|f| !f.is_empty() && translated.contains(f.as_str()));
+
+        match &self.action {
+            TermAction::Keep => has_term,
+            TermAction::Replace { with } => {
+                translated.to_lowercase().contains(&with.to_lowercase())
+            }
+            TermAction::Omit => !has_term,
+        }
+    }
+
+    /// Rewrite a translation so it follows this rule where possible.
+    pub fn apply(&self, translated: &str) -> String {
+        match &self.action {
+            TermAction::Keep => self.restore(translated),
+            TermAction::Replace { with } => {
+                let replaced = match self.romanized_regex() {
+                    Some(re) => re.replace_all(translated, with.as_str()).into_owned(),
+                    None => translated.to_string(),
+                };
+                self.forms
+                    .iter()
+                    .filter(|f| !f.is_empty())
+                    .fold(replaced, |text, form| text.replace(form.as_str(), with))
+            }
+            TermAction::Omit => {
+                let removed = match self.romanized_regex() {
+                    Some(re) => re.replace_all(translated, "").into_owned(),
+                    None => translated.to_string(),
+                };
+                let removed = self
+                    .forms
+                    .iter()
+                    .filter(|f| !f.is_empty())
+                    .fold(removed, |text, form| text.replace(form.as_str(), ""));
+                tidy_spacing(&removed)
+            }
+        }
+    }
+
+    /// Turn known equivalents back into the romanized term.
+    fn restore(&self, translated: &str) -> String {
+        let mut result = translated.to_string();
+        if self.romanized.trim().is_empty() {
+            return result;
+        }
+
+        for equivalent in &self.equivalents {
+            let escaped = regex::escape(equivalent);
+            match self.placement {
+                // "Mr. Tanaka" -> "Tanaka-san"
+                TermPlacement::Suffix => {
+                    let pattern = format!(r"(?:^|\b){}\s+(\p{{Lu}}[\p{{L}}'-]*)", escaped);
+                    if let Ok(re) = Regex::new(&pattern) {
+                        let rendering = self.rendering();
+                        result = re
+                            .replace_all(&result, |caps: &regex::Captures| {
+                                format!("{}{}", &caps[1], rendering)
+                            })
+                            .into_owned();
+                    }
+                }
+                // "Teacher, wait!" -> "Sensei, wait!"
+                TermPlacement::Standalone => {
+                    let pattern = format!(r"\b{}\b", escaped);
+                    if let Ok(re) = RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                        result = re
+                            .replace_all(&result, |caps: &regex::Captures| {
+                                match_case(&caps[0], &self.romanized)
+                            })
+                            .into_owned();
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Case-insensitive matcher for the romanized form in text, if there is one.
+    ///
+    /// An empty form would match everywhere, so it gets no matcher.
+    fn romanized_regex(&self) -> Option<Regex> {
+        if self.romanized.trim().is_empty() {
+            return None;
+        }
+        let escaped = regex::escape(self.romanized.trim());
+        let pattern = match self.placement {
+            TermPlacement::Suffix => format!(r"-{}\b", escaped),
+            TermPlacement::Standalone => format!(r"\b{}\b", escaped),
+        };
+        let re = RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .expect("escaped term is a valid regex");
+        Some(re)
+    }
+}
+
+/// Capitalize `replacement` if `original` starts with an uppercase letter.
+fn match_case(original: &str, replacement: &str) -> String {
+    let starts_upper = original.chars().next().is_some_and(|c| c.is_uppercase());
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) if starts_upper => first.to_uppercase().chain(chars).collect(),
+        _ => replacement.to_string(),
+    }
+}
+
+/// Collapse the doubled spaces and stray spaces before punctuation left by removals.
+fn tidy_spacing(text: &str) -> String {
+    static SPACES: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r" {2,}").unwrap());
+    static BEFORE_PUNCT: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r" +([,.!?])").unwrap());
+
+    let collapsed = SPACES.replace_all(text, " ");
+    BEFORE_PUNCT.replace_all(&collapsed, "$1").trim().to_string()
+}
+
+/// Set of term rules applied to a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermPolicy {
+    /// Rules, checked in order
+    #[serde(default)]
+    pub rules: Vec<TermRule>,
+}
+
+impl TermPolicy {
+    /// Create an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in policy for a source language (empty for languages without one).
+    pub fn for_language(source_language: &str) -> Self {
+        if language_utils::language_codes_match(source_language, "ja") {
+            Self::japanese()
+        } else if language_utils::language_codes_match(source_language, "ko") {
+            Self::korean()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Load a policy from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read term policy: {}", path.display()))?;
+        let policy: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse term policy: {}", path.display()))?;
+
+        if let Some(rule) = policy.rules.iter().find(|r| r.romanized.trim().is_empty()) {
+            bail!(
+                "Term policy {} has a rule without a romanized form: {}",
+                path.display(),
+                rule.label()
+            );
+        }
+
+        Ok(policy)
+    }
+
+    /// Default Japanese honorifics, all kept as romanized suffixes or terms.
+    pub fn japanese() -> Self {
+        Self {
+            rules: vec![
+                TermRule::suffix(&["さん"], "san")
+                    .with_equivalents(&["Mr.", "Mrs.", "Ms.", "Miss"])
+                    .with_exceptions(&[
+                        "お父さん", "お母さん", "お兄さん", "お姉さん", "おじさん",
+                        "おばさん", "おじいさん", "おばあさん", "皆さん", "みなさん",
+                    ]),
+                TermRule::suffix(&["さま", "様"], "sama").with_equivalents(&["Lord", "Lady"]),
+                TermRule::suffix(&["ちゃん"], "chan"),
+                TermRule::suffix(&["くん"], "kun"),
+                TermRule::suffix(&["殿"], "dono"),
+                TermRule::standalone(&["先輩", "せんぱい"], "senpai")
+                    .with_equivalents(&["upperclassman"]),
+                TermRule::standalone(&["先生", "せんせい"], "sensei")
+                    .with_equivalents(&["teacher"]),
+            ],
+        }
+    }
+
+    /// Default Korean forms of address, kept as romanized terms.
+    pub fn korean() -> Self {
+        Self {
+            rules: vec![
+                TermRule::standalone(&["오빠"], "oppa"),
+                TermRule::standalone(&["언니"], "unnie"),
+                TermRule::standalone(&["누나"], "noona"),
+                TermRule::standalone(&["형님"], "hyung"),
+                TermRule::standalone(&["선배"], "sunbae").with_equivalents(&["senior"]),
+            ],
+        }
+    }
+
+    /// Add a rule, replacing any existing rule with the same romanized form.
+    pub fn with_rule(mut self, rule: TermRule) -> Self {
+        self.rules.retain(|r| r.romanized != rule.romanized);
+        self.rules.push(rule);
+        self
+    }
+
+    /// Check whether the policy has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rules whose term occurs in the given source text.
+    pub fn detect(&self, original: &str) -> Vec<&TermRule> {
+        self.rules.iter().filter(|r| r.occurs_in(original)).collect()
+    }
+}
+
+/// Term policy enforcer for prompt guidance, checking and repair.
+pub struct TermPolicyEnforcer<'a> {
+    policy: &'a TermPolicy,
+}
+
+impl<'a> TermPolicyEnforcer<'a> {
+    /// Create a new term policy enforcer.
+    pub fn new(policy: &'a TermPolicy) -> Self {
+        Self { policy }
+    }
+
+    /// Prompt instructions for the terms found in a batch of source texts.
+    pub fn instructions<'t>(&self, originals: impl IntoIterator<Item = &'t str>) -> Vec<String> {
+        let originals: Vec<&str> = originals.into_iter().collect();
+        self.policy
+            .rules
+            .iter()
+            .filter(|rule| originals.iter().any(|text| rule.occurs_in(text)))
+            .map(TermRule::instruction)
+            .collect()
+    }
+
+    /// Check if a translation follows the policy for the terms in its original.
+    pub fn check_consistency(&self, original: &str, translated: &str) -> Vec<ConsistencyIssue> {
+        self.policy
+            .detect(original)
+            .into_iter()
+            .filter(|rule| !rule.is_followed(translated))
+            .map(|rule| ConsistencyIssue::TermPolicyViolation {
+                term: rule.label(),
+                expected: rule.expectation(),
+                translated: translated.to_string(),
+            })
+            .collect()
+    }
+
+    /// Enforce the policy in a translation (auto-fix).
+    pub fn enforce(&self, original: &str, translated: &str) -> String {
+        self.policy
+            .detect(original)
+            .into_iter()
+            .filter(|rule| !rule.is_followed(translated))
+            .fold(translated.to_string(), |text, rule| rule.apply(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_termPolicy_forLanguage_shouldSelectBuiltIns() {
+        assert!(!TermPolicy::for_language("ja").is_empty());
+        assert!(!TermPolicy::for_language("jpn").is_empty());
+        assert!(!TermPolicy::for_language("ko").is_empty());
+        assert!(TermPolicy::for_language("en").is_empty());
+    }
+
+    #[test]
+    fn test_termPolicy_detect_shouldIgnoreExceptions() {
+        let policy = TermPolicy::japanese();
+
+        let found = policy.detect("田中さん、おはよう");
+        assert!(found.iter().any(|r| r.romanized == "san"));
+
+        let found = policy.detect("お父さん、おはよう");
+        assert!(found.iter().all(|r| r.romanized != "san"));
+    }
+
+    #[test]
+    fn test_termPolicyEnforcer_instructions_shouldOnlyCoverDetectedTerms() {
+        let policy = TermPolicy::japanese();
+        let enforcer = TermPolicyEnforcer::new(&policy);
+
+        let instructions = enforcer.instructions(["先輩、待って！", "こんにちは"]);
+
+        assert_eq!(instructions.len(), 1);
+        assert!(instructions[0].contains("senpai"));
+    }
+
+    #[test]
+    fn test_termPolicyEnforcer_checkConsistency_shouldReportDroppedHonorific() {
+        let policy = TermPolicy::japanese();
+        let enforcer = TermPolicyEnforcer::new(&policy);
+
+        let issues = enforcer.check_consistency("田中さん、おはよう", "Good morning, Mr. Tanaka");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].description().contains("-san"));
+
+        let issues = enforcer.check_consistency("田中さん、おはよう", "Good morning, Tanaka-san");
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_termPolicyEnforcer_enforce_shouldRestoreKeptTerms() {
+        let policy = TermPolicy::japanese();
+        let enforcer = TermPolicyEnforcer::new(&policy);
+
+        assert_eq!(
+            enforcer.enforce("田中さん、おはよう", "Good morning, Mr. Tanaka"),
+            "Good morning, Tanaka-san"
+        );
+        assert_eq!(enforcer.enforce("先生、待って！", "Teacher, wait!"), "Sensei, wait!");
+    }
+
+    #[test]
+    fn test_termPolicyEnforcer_enforce_shouldApplyReplaceAndOmit() {
+        let policy = TermPolicy::new()
+            .with_rule(
+                TermRule::standalone(&["오빠"], "oppa")
+                    .with_action(TermAction::Replace { with: "big bro".to_string() }),
+            )
+            .with_rule(TermRule::suffix(&["ちゃん"], "chan").with_action(TermAction::Omit));
+        let enforcer = TermPolicyEnforcer::new(&policy);
+
+        assert_eq!(enforcer.enforce("오빠, 가자!", "Oppa, let's go!"), "big bro, let's go!");
+        assert_eq!(enforcer.enforce("ユキちゃん！", "Yuki-chan!"), "Yuki!");
+        assert!(enforcer.check_consistency("ユキちゃん！", "Yuki!").is_empty());
+    }
+
+    #[test]
+    fn test_termRule_withEmptyRomanized_shouldOnlyMatchForms() {
+        let rule = TermRule::standalone(&["先生"], "");
+        let omit = rule.clone().with_action(TermAction::Omit);
+
+        assert!(!rule.occurs_in("Hello there."));
+        assert!(rule.occurs_in("先生、待って！"));
+        assert_eq!(omit.apply("Wait, teacher!"), "Wait, teacher!");
+    }
+
+    #[test]
+    fn test_termPolicy_fromFile_withEmptyRomanized_shouldFail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("terms.json");
+        fs::write(&path, r#"{"rules": [{"forms": ["様"], "romanized": " ", "placement": "suffix"}]}"#).unwrap();
+
+        let err = TermPolicy::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("without a romanized form"));
+    }
+
+    #[test]
+    fn test_termPolicy_deserialize_shouldUseDefaults() {
+        let json = r#"{"rules": [{"forms": ["様"], "romanized": "sama", "placement": "suffix", "action": {"action": "replace", "with": "Lord"}}]}"#;
+        let policy: TermPolicy = serde_json::from_str(json).unwrap();
+
+        assert_eq!(policy.rules[0].kind, TermKind::Honorific);
+        assert_eq!(
+            policy.rules[0].action,
+            TermAction::Replace { with: "Lord".to_string() }
+        );
+    }
+}
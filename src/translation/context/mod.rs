@@ -4,6 +4,7 @@
  * This module provides context-aware translation support:
  * - Sliding window context for maintaining narrative flow
  * - Glossary extraction for terminology consistency
+ * - Honorific and cultural-term preservation policy
  */
 
 // Allow dead code in experimental context modules
@@ -12,6 +13,7 @@
 pub mod dynamic;
 pub mod fuzzy;
 pub mod glossary;
+pub mod honorifics;
 pub mod scenes;
 pub mod speakers;
 pub mod summary;
@@ -19,7 +21,8 @@ pub mod window;
 
 // Re-export types used by pipeline and other modules
 pub use glossary::{ConsistencyIssue, ExtractionConfig, GlossaryEnforcer, GlossaryExtractor, GlossaryPreflightChecker, PreflightReport};
+pub use honorifics::{TermPolicy, TermPolicyEnforcer};
 pub use scenes::{SceneDetectionConfig, SceneDetector};
-pub use summary::{HistorySummarizer, SummarizationConfig};
+pub use summary::{HistorySummarizer, HistorySummary, SummarizationConfig};
 pub use window::{ContextWindow, ContextWindowConfig, ContextWindowExt};
 
@@ -168,7 +168,7 @@ impl SceneDetector {
             })
             .collect();
 
-        gaps.sort_by(|a, b| b.1.cmp(&a.1));
+        gaps.sort_by_key(|g| std::cmp::Reverse(g.1));
         gaps.truncate(count);
         gaps
     }
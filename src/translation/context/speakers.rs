@@ -248,7 +248,7 @@ impl Default for SpeakerTracker {
 }
 
 /// Extract speaker name from text if present.
-fn extract_speaker(text: &str) -> Option<String> {
+pub fn extract_speaker(text: &str) -> Option<String> {
     SPEAKER_PATTERN
         .captures(text)
         .and_then(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()))
@@ -296,6 +296,7 @@ mod tests {
             scene_id: None,
             confidence: None,
             formatting: Vec::new(),
+            class: Default::default(),
         }
     }
 
@@ -6,7 +6,12 @@
  * This module provides summarization strategies.
  */
 
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::translation::core::TranslationService;
 use crate::translation::document::{DocumentEntry, SubtitleDocument};
+use crate::translation::prompts::engine::Template;
 
 /// Configuration for history summarization.
 #[derive(Debug, Clone)]
@@ -36,7 +41,7 @@ impl Default for SummarizationConfig {
 }
 
 /// Summary of a portion of the document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HistorySummary {
     /// The summary text
     pub text: String,
@@ -66,12 +71,22 @@ impl HistorySummary {
 /// History summarizer for compressing earlier content.
 pub struct HistorySummarizer {
     config: SummarizationConfig,
+    prompt_template: Option<Template>,
 }
 
 impl HistorySummarizer {
     /// Create a new summarizer with the given configuration.
     pub fn new(config: SummarizationConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            prompt_template: None,
+        }
+    }
+
+    /// Use a user-supplied template for the summarization prompt.
+    pub fn with_prompt_template(mut self, template: Option<Template>) -> Self {
+        self.prompt_template = template;
+        self
     }
 
     /// Create a summarizer with default configuration.
@@ -129,6 +144,18 @@ impl HistorySummarizer {
             content.push('\n');
         }
 
+        if let Some(ref template) = self.prompt_template {
+            let entries: Vec<serde_json::Value> = entries
+                .iter()
+                .map(|e| serde_json::json!({"id": e.id, "text": e.original_text, "speaker": e.speaker}))
+                .collect();
+            return template.render(&serde_json::json!({
+                "entries": entries,
+                "dialogue": content,
+                "max_chars": self.config.max_summary_chars,
+            }));
+        }
+
         format!(
             r#"Summarize the following dialogue in 2-3 sentences, focusing on:
 - Main characters and their relationships
@@ -143,6 +170,75 @@ Summary:"#,
         )
     }
 
+    /// Generate an abstractive summary by asking the provider.
+    ///
+    /// Falls back to the extractive summary when the provider fails or
+    /// returns nothing usable, so translation never stalls on summarization.
+    pub async fn summarize_abstractive(
+        &self,
+        service: &TranslationService,
+        entries: &[DocumentEntry],
+    ) -> HistorySummary {
+        if entries.is_empty() {
+            return HistorySummary::new(String::new(), 0, 0, 0);
+        }
+
+        let prompt = self.build_summarization_prompt(entries);
+        let text = match service.translate_text_uncached(&prompt, "prompt", "summary").await {
+            Ok(response) => self.clean_summary_response(&response),
+            Err(e) => {
+                warn!("History summarization failed, using extractive summary: {}", e);
+                String::new()
+            }
+        };
+
+        if text.is_empty() {
+            return self.summarize_extractive(entries);
+        }
+
+        let start_id = entries.first().map(|e| e.id).unwrap_or(0);
+        let end_id = entries.last().map(|e| e.id).unwrap_or(0);
+
+        HistorySummary::new(text, start_id, end_id, entries.len())
+    }
+
+    /// Clean up a raw LLM summary response.
+    fn clean_summary_response(&self, response: &str) -> String {
+        let trimmed = response.trim();
+        let without_label = trimmed
+            .strip_prefix("Summary:")
+            .or_else(|| trimmed.strip_prefix("**Summary:**"))
+            .unwrap_or(trimmed);
+
+        let collapsed = without_label.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.truncate_to_limit(&collapsed)
+    }
+
+    /// Select the most recent summaries that fit within the summary budget.
+    ///
+    /// Older summaries are dropped first so the rolling history always
+    /// describes what happened just before the current window.
+    pub fn recent_summaries<'a>(&self, summaries: &'a [HistorySummary]) -> &'a [HistorySummary] {
+        let mut total_chars = 0;
+        let mut start = summaries.len();
+
+        while start > 0 {
+            let len = summaries[start - 1].text.len() + 1;
+            if total_chars + len > self.config.max_summary_chars && start < summaries.len() {
+                break;
+            }
+            total_chars += len;
+            start -= 1;
+        }
+
+        &summaries[start..]
+    }
+
+    /// Get the number of entries each summary should cover.
+    pub fn entries_per_summary(&self) -> usize {
+        self.config.entries_per_summary.max(1)
+    }
+
     /// Extract likely character names from entries.
     fn extract_likely_names(&self, entries: &[DocumentEntry]) -> Vec<String> {
         use regex::Regex;
@@ -173,7 +269,7 @@ Summary:"#,
             .into_iter()
             .filter(|(_, count)| *count >= 2)
             .collect();
-        names.sort_by(|a, b| b.1.cmp(&a.1));
+        names.sort_by_key(|n| std::cmp::Reverse(n.1));
         names.into_iter().take(5).map(|(name, _)| name).collect()
     }
 
@@ -217,7 +313,11 @@ Summary:"#,
             return text.to_string();
         }
 
-        let truncated = &text[..self.config.max_summary_chars - 3];
+        let mut cut = self.config.max_summary_chars.saturating_sub(3);
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let truncated = &text[..cut];
         // Find last complete sentence or word
         if let Some(last_period) = truncated.rfind(". ") {
             format!("{}.", &truncated[..last_period])
@@ -361,6 +461,54 @@ mod tests {
         assert!(prompt.contains("Summarize"));
     }
 
+    #[test]
+    fn test_historySummarizer_buildSummarizationPrompt_shouldUseTemplate() {
+        let template = Template::parse("{% for entry in entries %}{{ entry.id }}|{% endfor %}").unwrap();
+        let summarizer = HistorySummarizer::with_defaults().with_prompt_template(Some(template));
+
+        let prompt = summarizer.build_summarization_prompt(&create_entries(&["a", "b", "c"]));
+
+        assert_eq!(prompt, "1|2|3|");
+    }
+
+    #[test]
+    fn test_historySummarizer_cleanSummaryResponse_shouldStripLabelAndWhitespace() {
+        let summarizer = HistorySummarizer::with_defaults();
+        let cleaned = summarizer.clean_summary_response("  Summary:\n John meets Mary.\n\nThey plan a heist.  ");
+
+        assert_eq!(cleaned, "John meets Mary. They plan a heist.");
+    }
+
+    #[test]
+    fn test_historySummarizer_recentSummaries_shouldKeepNewestWithinBudget() {
+        let summarizer = HistorySummarizer::new(SummarizationConfig {
+            max_summary_chars: 50,
+            ..Default::default()
+        });
+        let summaries = vec![
+            HistorySummary::new("A".repeat(30), 1, 10, 10),
+            HistorySummary::new("B".repeat(20), 11, 20, 10),
+            HistorySummary::new("C".repeat(20), 21, 30, 10),
+        ];
+
+        let recent = summarizer.recent_summaries(&summaries);
+
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].start_entry_id, 11);
+        assert_eq!(recent[1].start_entry_id, 21);
+    }
+
+    #[test]
+    fn test_historySummarizer_recentSummaries_shouldAlwaysKeepLatest() {
+        let summarizer = HistorySummarizer::new(SummarizationConfig {
+            max_summary_chars: 10,
+            ..Default::default()
+        });
+        let summaries = vec![HistorySummary::new("X".repeat(40), 1, 10, 10)];
+
+        assert_eq!(summarizer.recent_summaries(&summaries).len(), 1);
+    }
+
     #[test]
     fn test_historySummarizer_combineSummaries_shouldMerge() {
         let summary1 = HistorySummary::new("First part of story.".to_string(), 1, 10, 10);
@@ -8,11 +8,15 @@
  * - Lookahead: upcoming entries for forward context
  */
 
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
-use crate::translation::document::{DocumentEntry, Glossary, SubtitleDocument};
+use crate::translation::document::{ClassPolicy, DocumentEntry, EntryClass, Glossary, SubtitleDocument};
 use crate::translation::prompts::TranslatedEntryContext;
 
+use super::honorifics::TermPolicy;
+
 /// Configuration for context window sizes.
 #[derive(Debug, Clone)]
 pub struct ContextWindowConfig {
@@ -28,7 +32,7 @@ pub struct ContextWindowConfig {
     /// Whether to enable history summarization for long documents
     pub enable_summarization: bool,
 
-    /// Minimum entries before summarization kicks in
+    /// Minimum unsummarized history entries before summarization kicks in
     pub summarization_threshold: usize,
 }
 
@@ -97,11 +101,23 @@ pub struct ContextWindow {
     #[serde(default)]
     pub glossary: Glossary,
 
+    /// Honorific and cultural-term policy for this window
+    #[serde(default, skip_serializing_if = "TermPolicy::is_empty")]
+    pub term_policy: TermPolicy,
+
+    /// Per-class translation policy for this window
+    #[serde(default, skip_serializing_if = "ClassPolicy::is_default")]
+    pub class_policy: ClassPolicy,
+
     /// Current position in the document (first entry ID in current_batch)
     pub position: usize,
 
     /// Total entries in the document
     pub total_entries: usize,
+
+    /// Document indices of history entries not yet covered by a rolling summary
+    #[serde(skip)]
+    pub unsummarized_history: Range<usize>,
 }
 
 /// A simplified entry for the context window.
@@ -119,6 +135,10 @@ pub struct WindowEntry {
     /// Whether this is a sound effect
     #[serde(default)]
     pub is_sound_effect: bool,
+
+    /// Content class of the entry
+    #[serde(default, skip_serializing_if = "EntryClass::is_dialogue")]
+    pub class: EntryClass,
 }
 
 impl WindowEntry {
@@ -129,6 +149,7 @@ impl WindowEntry {
             text: entry.original_text.clone(),
             timecode: entry.timecode.format_srt(),
             is_sound_effect: entry.is_sound_effect(),
+            class: entry.class,
         }
     }
 }
@@ -173,6 +194,16 @@ impl ContextWindow {
             .map(WindowEntry::from_document_entry)
             .collect();
 
+        // History that scrolled out of the recent entries and has no summary yet
+        let summarized_through = doc.history_summaries.last().map(|s| s.end_entry_id);
+        let unsummarized_start = match summarized_through {
+            Some(end_id) => doc.entries[..recent_start]
+                .iter()
+                .position(|e| e.id > end_id)
+                .unwrap_or(recent_start),
+            None => 0,
+        };
+
         Self {
             source_language: source_language.to_string(),
             target_language: target_language.to_string(),
@@ -181,8 +212,11 @@ impl ContextWindow {
             current_batch,
             lookahead_entries,
             glossary: doc.glossary.clone(),
+            term_policy: doc.term_policy.clone(),
+            class_policy: doc.class_policy,
             position,
             total_entries,
+            unsummarized_history: unsummarized_start..recent_start,
         }
     }
 
@@ -210,10 +244,13 @@ impl ContextWindow {
     }
 
     /// Check if this window needs history summarization.
+    ///
+    /// True once enough entries have scrolled out of the recent window
+    /// without being covered by a rolling summary.
     pub fn needs_summarization(&self, config: &ContextWindowConfig) -> bool {
         config.enable_summarization
-            && self.history_summary.is_none()
-            && self.position >= config.summarization_threshold
+            && !self.unsummarized_history.is_empty()
+            && self.unsummarized_history.len() >= config.summarization_threshold
     }
 
     /// Set the history summary.
@@ -427,6 +464,31 @@ mod tests {
         assert!(window_late.needs_summarization(&config));
     }
 
+    #[test]
+    fn test_contextWindow_needsSummarization_shouldSkipSummarizedHistory() {
+        let mut doc = create_test_document(100);
+        doc.history_summaries.push(crate::translation::context::HistorySummary::new(
+            "Earlier events.".to_string(),
+            1,
+            30,
+            30,
+        ));
+        let config = ContextWindowConfig {
+            enable_summarization: true,
+            summarization_threshold: 30,
+            recent_entries_count: 10,
+            ..Default::default()
+        };
+
+        let window_covered = ContextWindow::new(&doc, 50, &config, "en", "fr");
+        let window_stale = ContextWindow::new(&doc, 75, &config, "en", "fr");
+
+        assert_eq!(window_covered.unsummarized_history, 30..40);
+        assert!(!window_covered.needs_summarization(&config));
+        assert_eq!(window_stale.unsummarized_history, 30..65);
+        assert!(window_stale.needs_summarization(&config));
+    }
+
     #[test]
     fn test_windowEntry_fromDocumentEntry_shouldPreserveData() {
         let entry = SubtitleEntry::new(1, 0, 1000, "[Door slams]".to_string());
@@ -11,14 +11,18 @@ use url::Url;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::app_config::{TranslationConfig, TranslationProvider as ConfigTranslationProvider, ExperimentalFeatures};
+use crate::app_config::{CacheConfig as CacheSettings, TranslationConfig, TranslationProvider as ConfigTranslationProvider, ExperimentalFeatures};
 use crate::providers::ollama::{Ollama, GenerationRequest};
 use crate::providers::openai::{OpenAI, OpenAIRequest};
 use crate::providers::anthropic::{Anthropic, AnthropicRequest};
-use crate::providers::vllm::{VLLM, VLLMRequest};
+use crate::providers::vllm::{Vllm, VLLMRequest};
 use crate::providers::Provider;
-use super::cache::TranslationCache;
+use crate::database::storage::Storage;
+use super::cache::{CacheConfig, CacheContext, TranslationCache};
+use super::context::speakers::extract_speaker;
 use super::concurrency::ProviderProfile;
+use super::quality::errors::TranslationErrorKind;
+use super::telemetry::{BatchTelemetry, MetricsRecorder};
 
 
 /// Token usage statistics for tracking API consumption
@@ -44,6 +48,9 @@ pub struct TokenUsageStats {
     
     /// Model name
     pub model: String,
+
+    /// Telemetry of each batch, in order
+    pub batches: Vec<BatchTelemetry>,
 }
 
 impl Default for TokenUsageStats {
@@ -63,6 +70,7 @@ impl TokenUsageStats {
             api_duration: Duration::from_secs(0),
             provider: String::new(),
             model: String::new(),
+            batches: Vec::new(),
         }
     }
     
@@ -89,6 +97,7 @@ impl TokenUsageStats {
             api_duration: Duration::from_secs(0),
             provider,
             model,
+            batches: Vec::new(),
         }
     }
     
@@ -184,9 +193,9 @@ enum TranslationProviderImpl {
     },
 
     /// vLLM high-throughput inference server
-    VLLM {
+    Vllm {
         /// Client instance
-        client: VLLM,
+        client: Vllm,
     },
 }
 
@@ -219,6 +228,9 @@ pub struct LogEntry {
     pub message: String,
 }
 
+/// Callback receiving the telemetry of each batch as soon as the batch finishes
+pub type BatchTelemetryCallback = Arc<dyn Fn(&BatchTelemetry) + Send + Sync>;
+
 /// Main translation service for subtitle translation
 pub struct TranslationService {
     /// Provider implementation
@@ -232,6 +244,15 @@ pub struct TranslationService {
     
     /// Translation cache for storing and retrieving translations
     pub cache: TranslationCache,
+
+    /// Collector of request telemetry (None = not recorded)
+    metrics: Option<MetricsRecorder>,
+
+    /// Receiver of finished batches' telemetry (None = not reported)
+    batch_telemetry_callback: Option<BatchTelemetryCallback>,
+
+    /// Formality register keying cached translations (from the style guide)
+    formality: Option<String>,
 }
 
 impl TranslationService {
@@ -299,12 +320,12 @@ impl TranslationService {
                     ),
                 }
             },
-            ConfigTranslationProvider::VLLM => {
+            ConfigTranslationProvider::Vllm => {
                 let retry_count = config.common.retry_count;
                 let retry_backoff_ms = config.common.retry_backoff_ms;
 
-                TranslationProviderImpl::VLLM {
-                    client: VLLM::new_with_config(
+                TranslationProviderImpl::Vllm {
+                    client: Vllm::new_with_config(
                         config.get_endpoint(),
                         config.get_api_key(),
                         retry_count,
@@ -326,6 +347,9 @@ impl TranslationService {
             config,
             options,
             cache: TranslationCache::new(true), // Enable cache by default
+            metrics: None,
+            batch_telemetry_callback: None,
+            formality: None,
         })
     }
 
@@ -341,6 +365,87 @@ impl TranslationService {
         self
     }
 
+    /// Apply the cache settings: whether caching is enabled, which context parts key it
+    /// and how the persistent tier is evicted.
+    ///
+    /// Translations persist across runs in `storage` when cross-session caching is on.
+    pub fn with_cache_config(mut self, config: &CacheSettings, storage: Option<Arc<dyn Storage>>) -> Self {
+        let cache_config = CacheConfig {
+            l1_enabled: config.enabled,
+            l2_enabled: config.enabled && config.cross_session && storage.is_some(),
+            provider: self.config.provider.to_lowercase_string(),
+            model: self.config.get_model(),
+            key_fields: config.context_key,
+            eviction: config.eviction_policy(),
+            ..Default::default()
+        };
+        self.cache = match storage {
+            Some(storage) if cache_config.l2_enabled => TranslationCache::new_with_storage(cache_config, storage),
+            _ => TranslationCache::with_config(cache_config),
+        };
+        self
+    }
+
+    /// Key cached translations by a formality register (e.g. the style guide's)
+    pub fn with_formality(mut self, formality: &str) -> Self {
+        self.formality = Some(formality.to_string());
+        self
+    }
+
+    /// Build the cache context of lines: their common speaker label, the
+    /// formality register and the neighbouring lines
+    pub fn cache_context(&self, lines: &[&str], previous: &[&str], next: &[&str]) -> CacheContext {
+        let mut context = CacheContext::default();
+
+        let mut speakers = lines.iter().map(|line| extract_speaker(line));
+        if let Some(Some(speaker)) = speakers.next()
+            && speakers.all(|other| other.as_deref() == Some(speaker.as_str()))
+        {
+            context = context.with_speaker(&speaker);
+        }
+        if let Some(ref formality) = self.formality {
+            context = context.with_formality(formality);
+        }
+        if !previous.is_empty() || !next.is_empty() {
+            context = context.with_neighbours(previous, next);
+        }
+
+        context
+    }
+
+    /// Record the telemetry of every request into `recorder`
+    pub fn with_metrics(mut self, recorder: MetricsRecorder) -> Self {
+        self.metrics = Some(recorder);
+        self
+    }
+
+    /// The telemetry recorder, if any
+    pub fn metrics(&self) -> Option<&MetricsRecorder> {
+        self.metrics.as_ref()
+    }
+
+    /// Report the telemetry of each batch to `callback` as soon as the batch finishes
+    pub fn with_batch_telemetry_callback(mut self, callback: BatchTelemetryCallback) -> Self {
+        self.batch_telemetry_callback = Some(callback);
+        self
+    }
+
+    /// Hand a finished batch's telemetry to the callback, if any
+    pub(crate) fn report_batch(&self, batch: &BatchTelemetry) {
+        if let Some(callback) = &self.batch_telemetry_callback {
+            callback(batch);
+        }
+    }
+
+    /// Record attempts that failed without a provider error and will be retried
+    pub(crate) fn record_retries(&self, kind: TranslationErrorKind, count: usize) {
+        if let Some(metrics) = &self.metrics {
+            for _ in 0..count {
+                metrics.record_failure(kind, false);
+            }
+        }
+    }
+
     /// Test the connection to the translation provider
     pub async fn test_connection(
         &self, 
@@ -453,7 +558,7 @@ impl TranslationService {
                     }
                 }
             },
-            TranslationProviderImpl::VLLM { client } => {
+            TranslationProviderImpl::Vllm { client } => {
                 // For vLLM, use the health check endpoint first, then test translation
                 let health_result = client.health_check().await;
                 match health_result {
@@ -514,6 +619,90 @@ impl TranslationService {
         source_language: &str, 
         target_language: &str,
         log_capture: Option<Arc<Mutex<Vec<LogEntry>>>>
+    ) -> Result<(String, Option<(Option<u64>, Option<u64>, Option<Duration>)>)> {
+        self.translate_text_in_context(text, source_language, target_language, &CacheContext::default(), log_capture)
+            .await
+    }
+
+    /// Translate text with token usage tracking, caching the result under a translation context
+    pub async fn translate_text_in_context(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+        context: &CacheContext,
+        log_capture: Option<Arc<Mutex<Vec<LogEntry>>>>
+    ) -> Result<(String, Option<(Option<u64>, Option<u64>, Option<Duration>)>)> {
+        self.translate_recorded(text, source_language, target_language, Some((&self.cache, context)), log_capture)
+            .await
+    }
+
+    /// Send a prompt whose response is reused within this run only
+    ///
+    /// For prompts wrapping subtitle lines (the pipeline's JSON batches): the
+    /// response is cached in memory under the context but never persisted, as
+    /// it is not a translation of one text into another.
+    pub async fn translate_prompt_in_context(
+        &self,
+        prompt: &str,
+        context: &CacheContext,
+        log_capture: Option<Arc<Mutex<Vec<LogEntry>>>>
+    ) -> Result<(String, Option<(Option<u64>, Option<u64>, Option<Duration>)>)> {
+        let cache = self.cache.memory_only();
+        self.translate_recorded(prompt, "prompt", "json_response", Some((&cache, context)), log_capture)
+            .await
+    }
+
+    /// Send text straight to the provider, neither reading nor writing the cache
+    ///
+    /// For helper prompts (summaries, semantic checks, the judge, back-translations)
+    /// whose output must not be served or stored as a translation.
+    pub async fn translate_text_uncached(&self, text: &str, source_language: &str, target_language: &str) -> Result<String> {
+        let (translated, _) = self
+            .translate_recorded(text, source_language, target_language, None, None)
+            .await?;
+        Ok(translated)
+    }
+
+    /// Translate text, recording the outcome in the metrics
+    async fn translate_recorded(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+        cache: Option<(&TranslationCache, &CacheContext)>,
+        log_capture: Option<Arc<Mutex<Vec<LogEntry>>>>
+    ) -> Result<(String, Option<(Option<u64>, Option<u64>, Option<Duration>)>)> {
+        let start_time = Instant::now();
+        let result = self
+            .request_translation(text, source_language, target_language, cache, log_capture)
+            .await;
+
+        if let Some(metrics) = &self.metrics
+            && !text.trim().is_empty()
+        {
+            match &result {
+                Ok((_, None)) => metrics.record_cache_hit(),
+                Ok((_, Some((prompt_tokens, completion_tokens, duration)))) => metrics.record_response(
+                    *prompt_tokens,
+                    *completion_tokens,
+                    duration.unwrap_or_else(|| start_time.elapsed()),
+                ),
+                Err(e) => metrics.record_failure(TranslationErrorKind::classify(&e.to_string()), true),
+            }
+        }
+
+        result
+    }
+
+    /// Translate text through the cache, if given, or the provider
+    async fn request_translation(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+        cache: Option<(&TranslationCache, &CacheContext)>,
+        log_capture: Option<Arc<Mutex<Vec<LogEntry>>>>
     ) -> Result<(String, Option<(Option<u64>, Option<u64>, Option<Duration>)>)> {
         let start_time = Instant::now();
         
@@ -523,7 +712,9 @@ impl TranslationService {
         }
         
         // Check cache first
-        if let Some(cached_translation) = self.cache.get(text, source_language, target_language).await {
+        if let Some((cache, context)) = cache
+            && let Some(cached_translation) = cache.get_in_context(text, source_language, target_language, context).await
+        {
             if let Some(log) = &log_capture {
                 log.lock().await.push(LogEntry {
                     level: "INFO".to_string(),
@@ -567,7 +758,9 @@ impl TranslationService {
                         let translated_text = response.response;
                         
                         // Store in cache
-                        self.cache.store(text, source_language, target_language, &translated_text).await;
+                        if let Some((cache, context)) = cache {
+                            cache.store_in_context(text, source_language, target_language, context, &translated_text).await;
+                        }
                         
                         // Return the translated text and token usage (Ollama doesn't provide token counts)
                         Ok((translated_text, Some((None, None, Some(duration)))))
@@ -623,7 +816,9 @@ impl TranslationService {
                         };
                         
                         // Store in cache
-                        self.cache.store(text, source_language, target_language, &translated_text).await;
+                        if let Some((cache, context)) = cache {
+                            cache.store_in_context(text, source_language, target_language, context, &translated_text).await;
+                        }
                         
                         // Return the translated text and token usage
                         Ok((translated_text, Some((prompt_tokens, completion_tokens, Some(duration)))))
@@ -672,7 +867,9 @@ impl TranslationService {
                         let completion_tokens = Some(response.usage.output_tokens as u64);
 
                         // Store in cache
-                        self.cache.store(text, source_language, target_language, &translated_text).await;
+                        if let Some((cache, context)) = cache {
+                            cache.store_in_context(text, source_language, target_language, context, &translated_text).await;
+                        }
 
                         // Return the translated text and token usage
                         Ok((translated_text, Some((prompt_tokens, completion_tokens, Some(duration)))))
@@ -690,7 +887,7 @@ impl TranslationService {
                     }
                 }
             },
-            TranslationProviderImpl::VLLM { client } => {
+            TranslationProviderImpl::Vllm { client } => {
                 // Create vLLM request
                 let request = VLLMRequest::new(self.config.get_model())
                     .add_message("system", &system_prompt)
@@ -728,7 +925,9 @@ impl TranslationService {
                         };
 
                         // Store in cache
-                        self.cache.store(text, source_language, target_language, &translated_text).await;
+                        if let Some((cache, context)) = cache {
+                            cache.store_in_context(text, source_language, target_language, context, &translated_text).await;
+                        }
 
                         // Return the translated text and token usage
                         Ok((translated_text, Some((prompt_tokens, completion_tokens, Some(duration)))))
@@ -781,7 +980,12 @@ impl Clone for TranslationService {
     fn clone(&self) -> Self {
         // Create a new instance with the same config
         // This should not fail if the original instance was created successfully
-        TranslationService::new(self.config.clone())
-            .expect("Failed to clone TranslationService - this indicates a serious configuration issue")
+        let mut service = TranslationService::new(self.config.clone())
+            .expect("Failed to clone TranslationService - this indicates a serious configuration issue");
+        service.cache = self.cache.clone();
+        service.metrics = self.metrics.clone();
+        service.batch_telemetry_callback = self.batch_telemetry_callback.clone();
+        service.formality = self.formality.clone();
+        service
     }
 } 
\ No newline at end of file
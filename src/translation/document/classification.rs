@@ -0,0 +1,346 @@
+/*!
+ * Entry classification and per-class translation policy.
+ *
+ * Subtitle entries are not all dialogue: song lyrics, on-screen signs,
+ * sound effects and bare speaker labels call for different handling.
+ * `EntryClass` labels each entry during analysis, and `ClassPolicy` decides
+ * what the translation does with each class, both in the prompt and in
+ * post-processing.
+ */
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Formatting tags and ASS override blocks, stripped before classifying.
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>|\{[^}]*\}").unwrap());
+
+/// A line holding only an uppercase speaker name, e.g. "JOHN:".
+static SPEAKER_LABEL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^-?\s*\p{Lu}[\p{Lu}\d .'-]*:$").unwrap());
+
+/// Position override placing a line away from the bottom, e.g. "{\an8}".
+static POSITION_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^}]*\\(an[4-9]|pos\()").unwrap());
+
+/// Longest unpositioned all-caps line still taken for a sign, in words.
+const MAX_SIGN_WORDS: usize = 4;
+
+/// Kind of content carried by a subtitle entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryClass {
+    /// Spoken dialogue
+    #[default]
+    Dialogue,
+    /// Song lyrics (♪ ... ♪)
+    Lyric,
+    /// On-screen text such as signs and captions
+    Sign,
+    /// Sound effect or non-speech description
+    SoundEffect,
+    /// Bare speaker label ("JOHN:")
+    SpeakerLabel,
+}
+
+impl EntryClass {
+    /// Classify an entry from its original text, outside any document.
+    pub fn classify(text: &str) -> Self {
+        Self::classify_in_document(text, false)
+    }
+
+    /// Classify the entries of a document from their original texts.
+    pub fn classify_document(texts: &[&str]) -> Vec<Self> {
+        let caps_document = Self::is_caps_document(texts);
+        texts.iter().map(|text| Self::classify_in_document(text, caps_document)).collect()
+    }
+
+    /// Whether most lines of a document are in capitals, as in some SDH tracks.
+    ///
+    /// Capitals then say nothing about signs, and only positioned lines are
+    /// taken for signs.
+    pub fn is_caps_document(texts: &[&str]) -> bool {
+        let cased: Vec<bool> = texts
+            .iter()
+            .map(|text| TAG_REGEX.replace_all(text, ""))
+            .filter(|stripped| stripped.chars().filter(|c| c.is_alphabetic()).count() >= 2)
+            .map(|stripped| Self::is_all_caps(&stripped))
+            .collect();
+        cased.iter().filter(|caps| **caps).count() * 2 > cased.len()
+    }
+
+    /// Classify an entry, telling whether its document is mostly in capitals.
+    pub fn classify_in_document(text: &str, caps_document: bool) -> Self {
+        let stripped = TAG_REGEX.replace_all(text, "");
+        let stripped = stripped.trim();
+
+        if stripped.is_empty() {
+            return EntryClass::Dialogue;
+        }
+
+        if stripped.contains('♪')
+            || stripped.contains('♫')
+            || (stripped.len() > 1 && stripped.starts_with('#') && stripped.ends_with('#'))
+        {
+            return EntryClass::Lyric;
+        }
+
+        if !stripped.contains('\n') && SPEAKER_LABEL_REGEX.is_match(stripped) {
+            return EntryClass::SpeakerLabel;
+        }
+
+        if (stripped.starts_with('[') && stripped.ends_with(']'))
+            || (stripped.starts_with('(') && stripped.ends_with(')'))
+        {
+            return EntryClass::SoundEffect;
+        }
+
+        if Self::looks_like_sign(text, stripped, caps_document) {
+            return EntryClass::Sign;
+        }
+
+        EntryClass::Dialogue
+    }
+
+    /// All-caps text that is either positioned like on-screen text ("{\an8}EXIT")
+    /// or a short single line without dialogue punctuation ("POLICE STATION").
+    fn looks_like_sign(text: &str, stripped: &str, caps_document: bool) -> bool {
+        if !Self::is_all_caps(stripped) {
+            return false;
+        }
+        if POSITION_TAG_REGEX.is_match(text) {
+            return true;
+        }
+
+        !caps_document
+            && !stripped.contains('\n')
+            && !stripped.starts_with('-')
+            && !stripped.contains(',')
+            && !stripped.ends_with(['.', '!', '?', '…'])
+            && stripped.split_whitespace().count() <= MAX_SIGN_WORDS
+    }
+
+    /// Whether text has at least two letters, all of them upper-case.
+    fn is_all_caps(text: &str) -> bool {
+        let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+        letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+    }
+
+    /// Check whether this is the default dialogue class.
+    pub fn is_dialogue(&self) -> bool {
+        *self == EntryClass::Dialogue
+    }
+
+    /// Label used in prompts and reports.
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryClass::Dialogue => "dialogue",
+            EntryClass::Lyric => "lyric",
+            EntryClass::Sign => "sign",
+            EntryClass::SoundEffect => "sound_effect",
+            EntryClass::SpeakerLabel => "speaker_label",
+        }
+    }
+}
+
+/// How entries of one class are translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassAction {
+    /// Translate like dialogue
+    #[default]
+    Translate,
+    /// Leave the original text untouched
+    KeepOriginal,
+    /// Translate freely as verse, keeping rhythm over literal meaning
+    Poetic,
+    /// Keep the original and add the translation below it
+    Bilingual,
+}
+
+/// Per-class translation policy. Dialogue is always translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ClassPolicy {
+    /// Song lyrics
+    #[serde(default)]
+    pub lyric: ClassAction,
+
+    /// On-screen signs
+    #[serde(default)]
+    pub sign: ClassAction,
+
+    /// Sound effects
+    #[serde(default)]
+    pub sound_effect: ClassAction,
+
+    /// Speaker labels
+    #[serde(default)]
+    pub speaker_label: ClassAction,
+}
+
+impl ClassPolicy {
+    /// Set the action for a class (ignored for dialogue).
+    pub fn with_action(mut self, class: EntryClass, action: ClassAction) -> Self {
+        match class {
+            EntryClass::Dialogue => {}
+            EntryClass::Lyric => self.lyric = action,
+            EntryClass::Sign => self.sign = action,
+            EntryClass::SoundEffect => self.sound_effect = action,
+            EntryClass::SpeakerLabel => self.speaker_label = action,
+        }
+        self
+    }
+
+    /// Action for a class.
+    pub fn action_for(&self, class: EntryClass) -> ClassAction {
+        match class {
+            EntryClass::Dialogue => ClassAction::Translate,
+            EntryClass::Lyric => self.lyric,
+            EntryClass::Sign => self.sign,
+            EntryClass::SoundEffect => self.sound_effect,
+            EntryClass::SpeakerLabel => self.speaker_label,
+        }
+    }
+
+    /// Check whether every class is translated like dialogue.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Prompt instruction for a class, if it needs one.
+    pub fn instruction(&self, class: EntryClass) -> Option<String> {
+        let label = class.label();
+        match (self.action_for(class), class) {
+            (ClassAction::KeepOriginal, _) => Some(format!(
+                "Return entries with class \"{}\" unchanged, exactly as in the original",
+                label
+            )),
+            (ClassAction::Poetic, _) => Some(format!(
+                "Translate entries with class \"{}\" as verse: keep line breaks, rhythm and rhyme over literal meaning",
+                label
+            )),
+            (ClassAction::Bilingual, _) => Some(format!(
+                "Translate entries with class \"{}\" without repeating the original text",
+                label
+            )),
+            (ClassAction::Translate, EntryClass::Sign) => Some(
+                "Entries with class \"sign\" are on-screen text: translate tersely and keep their capitalization"
+                    .to_string(),
+            ),
+            (ClassAction::Translate, EntryClass::Lyric) => Some(
+                "Entries with class \"lyric\" are song lyrics: keep the ♪ markers".to_string(),
+            ),
+            (ClassAction::Translate, _) => None,
+        }
+    }
+
+    /// Prompt instructions for the classes present in a batch, in class order.
+    pub fn instructions(&self, classes: impl IntoIterator<Item = EntryClass>) -> Vec<String> {
+        let present: Vec<EntryClass> = classes.into_iter().collect();
+        [
+            EntryClass::Lyric,
+            EntryClass::Sign,
+            EntryClass::SoundEffect,
+            EntryClass::SpeakerLabel,
+        ]
+        .into_iter()
+        .filter(|class| present.contains(class))
+        .filter_map(|class| self.instruction(class))
+        .collect()
+    }
+
+    /// Post-process a translation according to the entry's class.
+    pub fn post_process(&self, class: EntryClass, original: &str, translated: &str) -> String {
+        match self.action_for(class) {
+            ClassAction::KeepOriginal => original.to_string(),
+            ClassAction::Bilingual => {
+                let translated = translated.trim();
+                if translated.is_empty() || translated == original.trim() {
+                    original.to_string()
+                } else if translated.starts_with(original.trim()) {
+                    translated.to_string()
+                } else {
+                    format!("{}\n{}", original.trim_end(), translated)
+                }
+            }
+            ClassAction::Translate | ClassAction::Poetic => translated.to_string(),
+        }
+    }
+
+    /// Whether length checks make sense for translations of this class.
+    pub fn checks_length(&self, class: EntryClass) -> bool {
+        matches!(
+            self.action_for(class),
+            ClassAction::Translate | ClassAction::Poetic
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entryClass_classify_shouldDetectEachClass() {
+        assert_eq!(EntryClass::classify("♪ Under the sea ♪"), EntryClass::Lyric);
+        assert_eq!(EntryClass::classify("<i>♪ la la la</i>"), EntryClass::Lyric);
+        assert_eq!(EntryClass::classify("JOHN:"), EntryClass::SpeakerLabel);
+        assert_eq!(EntryClass::classify("[door slams]"), EntryClass::SoundEffect);
+        assert_eq!(EntryClass::classify("{\\an8}POLICE STATION"), EntryClass::Sign);
+        assert_eq!(EntryClass::classify("Where are you going?"), EntryClass::Dialogue);
+        assert_eq!(EntryClass::classify("STOP RIGHT THERE!"), EntryClass::Dialogue);
+        assert_eq!(EntryClass::classify("I TOLD YOU, WE SHOULD HAVE LEFT"), EntryClass::Dialogue);
+        assert_eq!(EntryClass::classify("WE NEED TO GO."), EntryClass::Dialogue);
+    }
+
+    #[test]
+    fn test_entryClass_classifyDocument_withCapsDocument_shouldOnlyTakePositionedSigns() {
+        let texts = ["WHERE WERE YOU", "AT HOME", "{\\an8}POLICE STATION", "Fine."];
+
+        let classes = EntryClass::classify_document(&texts);
+
+        assert_eq!(
+            classes,
+            vec![EntryClass::Dialogue, EntryClass::Dialogue, EntryClass::Sign, EntryClass::Dialogue]
+        );
+        assert_eq!(EntryClass::classify("AT HOME"), EntryClass::Sign);
+    }
+
+    #[test]
+    fn test_classPolicy_instructions_shouldOnlyCoverPresentClasses() {
+        let policy = ClassPolicy::default().with_action(EntryClass::Lyric, ClassAction::Poetic);
+
+        let instructions =
+            policy.instructions([EntryClass::Dialogue, EntryClass::Lyric, EntryClass::SoundEffect]);
+
+        assert_eq!(instructions.len(), 1);
+        assert!(instructions[0].contains("verse"));
+    }
+
+    #[test]
+    fn test_classPolicy_postProcess_shouldApplyAction() {
+        let policy = ClassPolicy::default()
+            .with_action(EntryClass::Lyric, ClassAction::KeepOriginal)
+            .with_action(EntryClass::Sign, ClassAction::Bilingual);
+
+        assert_eq!(
+            policy.post_process(EntryClass::Lyric, "♪ Hello ♪", "♪ Bonjour ♪"),
+            "♪ Hello ♪"
+        );
+        assert_eq!(
+            policy.post_process(EntryClass::Sign, "EXIT", "SORTIE"),
+            "EXIT\nSORTIE"
+        );
+        assert_eq!(
+            policy.post_process(EntryClass::Dialogue, "Hello", "Bonjour"),
+            "Bonjour"
+        );
+    }
+
+    #[test]
+    fn test_classPolicy_deserialize_shouldDefaultToTranslate() {
+        let policy: ClassPolicy = serde_json::from_str(r#"{"lyric": "keep_original"}"#).unwrap();
+
+        assert_eq!(policy.lyric, ClassAction::KeepOriginal);
+        assert_eq!(policy.sign, ClassAction::Translate);
+    }
+}
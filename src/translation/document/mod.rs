@@ -6,14 +6,20 @@
  * - Immutable timecode preservation
  * - Context tracking (scenes, speakers, glossary)
  * - Translation state management
+ * - Entry classification (dialogue, lyrics, signs, sound effects)
+ * - Retiming (extend, merge and split cues) after translation
  */
 
 #![allow(dead_code)]
 
+pub mod classification;
 pub mod model;
+pub mod retiming;
 
 // Re-export types used by other modules
+pub use classification::{ClassAction, ClassPolicy, EntryClass};
+pub use retiming::{RetimingConfig, RetimingEngine, RetimingReport};
 pub use model::{
-    DocumentEntry, FormattingTag, Glossary, Scene, SubtitleDocument, Timecode,
+    DocumentEntry, EntryRevision, FormattingTag, Glossary, RevisionOrigin, Scene, SubtitleDocument, Timecode,
 };
 
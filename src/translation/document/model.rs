@@ -7,8 +7,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use crate::subtitle_processor::SubtitleEntry;
+use crate::translation::context::{HistorySummary, TermPolicy};
+use crate::video_timing::FrameRate;
+
+use super::classification::{ClassPolicy, EntryClass};
 
 /// Complete subtitle document with metadata and translation context.
 ///
@@ -37,6 +42,22 @@ pub struct SubtitleDocument {
     /// Translation context summary (generated during analysis)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_summary: Option<String>,
+
+    /// Rolling summaries of already translated history, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history_summaries: Vec<HistorySummary>,
+
+    /// Honorific and cultural-term policy for the source language
+    #[serde(default, skip_serializing_if = "TermPolicy::is_empty")]
+    pub term_policy: TermPolicy,
+
+    /// How lyrics, signs, sound effects and speaker labels are translated
+    #[serde(default, skip_serializing_if = "ClassPolicy::is_default")]
+    pub class_policy: ClassPolicy,
+
+    /// Every candidate text produced for an entry during this run, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<EntryRevision>,
 }
 
 impl SubtitleDocument {
@@ -59,6 +80,10 @@ impl SubtitleDocument {
             scenes: Vec::new(),
             entries: document_entries,
             context_summary: None,
+            history_summaries: Vec::new(),
+            term_policy: TermPolicy::for_language(source_language),
+            class_policy: ClassPolicy::default(),
+            revisions: Vec::new(),
         }
     }
 
@@ -113,6 +138,141 @@ impl SubtitleDocument {
         let translated = self.entries.iter().filter(|e| e.translated_text.is_some()).count();
         (translated as f32 / self.entries.len() as f32) * 100.0
     }
+
+    /// Classify every entry, in the context of the whole document.
+    pub fn classify_entries(&mut self) {
+        let texts: Vec<&str> = self.entries.iter().map(|e| e.original_text.as_str()).collect();
+        let caps_document = EntryClass::is_caps_document(&texts);
+        for entry in &mut self.entries {
+            entry.class = entry.classify(caps_document);
+        }
+    }
+
+    /// Record the current translation of an entry as a new revision.
+    ///
+    /// Does nothing if the entry does not exist or has no translation.
+    pub fn record_revision(&mut self, entry_id: usize, origin: RevisionOrigin, detail: Option<String>) {
+        let Some(entry) = self.entries.iter().find(|e| e.id == entry_id) else {
+            return;
+        };
+        let Some(text) = entry.translated_text.clone() else {
+            return;
+        };
+
+        let confidence = entry.confidence;
+        self.revisions.push(EntryRevision {
+            detail,
+            confidence,
+            ..EntryRevision::new(entry_id, text, origin)
+        });
+    }
+
+    /// Get the latest revision of an entry.
+    pub fn latest_revision_mut(&mut self, entry_id: usize) -> Option<&mut EntryRevision> {
+        self.revisions.iter_mut().rev().find(|r| r.entry_id == entry_id)
+    }
+
+    /// Record validation outcomes on the latest revision of each entry.
+    ///
+    /// `issues` maps entry IDs to issue descriptions; entries without issues passed.
+    /// Revisions that already have an outcome are left unchanged.
+    pub fn record_validation_outcomes(&mut self, issues: &HashMap<usize, Vec<String>>) {
+        let mut seen = HashSet::new();
+        for revision in self.revisions.iter_mut().rev() {
+            if seen.insert(revision.entry_id) && revision.validation_passed.is_none() {
+                revision.set_outcome(issues.get(&revision.entry_id).map(Vec::as_slice).unwrap_or_default());
+            }
+        }
+    }
+}
+
+/// Where a candidate translation came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionOrigin {
+    /// Produced by the translation model
+    Translation,
+    /// Produced by the model when retried with feedback
+    FeedbackRetry,
+    /// Produced by an automatic repair
+    Repair,
+    /// Written or approved by a human reviewer
+    Human,
+}
+
+impl fmt::Display for RevisionOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevisionOrigin::Translation => write!(f, "translation"),
+            RevisionOrigin::FeedbackRetry => write!(f, "feedback_retry"),
+            RevisionOrigin::Repair => write!(f, "repair"),
+            RevisionOrigin::Human => write!(f, "human"),
+        }
+    }
+}
+
+impl std::str::FromStr for RevisionOrigin {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "translation" => Ok(RevisionOrigin::Translation),
+            "feedback_retry" => Ok(RevisionOrigin::FeedbackRetry),
+            "repair" => Ok(RevisionOrigin::Repair),
+            "human" => Ok(RevisionOrigin::Human),
+            _ => Err(anyhow::anyhow!("Invalid revision origin: {}", s)),
+        }
+    }
+}
+
+/// A candidate translation of an entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryRevision {
+    /// Entry ID (sequence number)
+    pub entry_id: usize,
+
+    /// Candidate text
+    pub text: String,
+
+    /// Where the text came from
+    pub origin: RevisionOrigin,
+
+    /// Repair strategy or retry feedback that produced the text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Model confidence (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+
+    /// Whether the text passed validation (None = not validated)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_passed: Option<bool>,
+
+    /// Validation issues, or the feedback the text was rejected with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_message: Option<String>,
+}
+
+impl EntryRevision {
+    /// Create an unvalidated revision.
+    pub fn new(entry_id: usize, text: String, origin: RevisionOrigin) -> Self {
+        Self {
+            entry_id,
+            text,
+            origin,
+            detail: None,
+            confidence: None,
+            validation_passed: None,
+            validation_message: None,
+        }
+    }
+
+    /// Set the validation outcome from a list of issue descriptions.
+    pub fn set_outcome(&mut self, issues: &[String]) {
+        self.validation_passed = Some(issues.is_empty());
+        self.validation_message = (!issues.is_empty()).then(|| issues.join("; "));
+    }
 }
 
 /// Document metadata.
@@ -166,6 +326,10 @@ pub struct DocumentEntry {
     /// Translation confidence score (0.0-1.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f32>,
+
+    /// Content class (set during analysis)
+    #[serde(default, skip_serializing_if = "EntryClass::is_dialogue")]
+    pub class: EntryClass,
 }
 
 impl DocumentEntry {
@@ -182,6 +346,7 @@ impl DocumentEntry {
             scene_id: None,
             formatting,
             confidence: None,
+            class: EntryClass::Dialogue,
         }
     }
 
@@ -239,10 +404,13 @@ impl DocumentEntry {
 
     /// Check if this is a sound effect or non-dialogue text.
     pub fn is_sound_effect(&self) -> bool {
-        let text = self.original_text.trim();
-        // Sound effects are typically in brackets
-        (text.starts_with('[') && text.ends_with(']'))
-            || (text.starts_with('(') && text.ends_with(')'))
+        EntryClass::classify(&self.original_text) == EntryClass::SoundEffect
+    }
+
+    /// Classify the entry as dialogue, lyric, sign, sound effect or speaker label,
+    /// telling whether its document is mostly in capitals.
+    pub fn classify(&self, caps_document: bool) -> EntryClass {
+        EntryClass::classify_in_document(&self.original_text, caps_document)
     }
 }
 
@@ -270,6 +438,15 @@ impl Timecode {
         self.end_ms.saturating_sub(self.start_ms)
     }
 
+    /// Snap both ends to the nearest frame boundary, keeping at least one frame.
+    pub fn snap_to_frames(&self, frame_rate: FrameRate) -> Self {
+        let start_ms = frame_rate.snap_ms(self.start_ms);
+        let end_ms = frame_rate
+            .snap_ms(self.end_ms)
+            .max(frame_rate.frame_to_ms(frame_rate.nearest_frame(start_ms) + 1));
+        Self { start_ms, end_ms }
+    }
+
     /// Format as SRT timestamp string.
     pub fn format_srt(&self) -> String {
         format!("{} --> {}", Self::format_ms(self.start_ms), Self::format_ms(self.end_ms))
@@ -474,6 +651,17 @@ mod tests {
         assert_eq!(tc.duration_ms(), 4000);
     }
 
+    #[test]
+    fn test_timecode_snapToFrames_shouldAlignToFrameBoundaries() {
+        let rate = FrameRate::new(24000, 1001);
+
+        let tc = Timecode::from_milliseconds(1000, 2010).snap_to_frames(rate);
+        assert_eq!((tc.start_ms, tc.end_ms), (1001, 2002));
+
+        let tiny = Timecode::from_milliseconds(1000, 1010).snap_to_frames(rate);
+        assert_eq!((tiny.start_ms, tiny.end_ms), (1001, 1043));
+    }
+
     #[test]
     fn test_timecode_formatSrt_shouldFormatCorrectly() {
         let tc = Timecode::from_milliseconds(3661001, 3665500);
@@ -486,6 +674,32 @@ mod tests {
         assert_eq!(ms, Some(3723456));
     }
 
+    #[test]
+    fn test_subtitleDocument_recordValidationOutcomes_shouldOnlyFillLatestRevisions() {
+        let entries = vec![
+            SubtitleEntry::new(1, 0, 1000, "Hello".to_string()),
+            SubtitleEntry::new(2, 1000, 2000, "Goodbye".to_string()),
+        ];
+        let mut doc = SubtitleDocument::from_entries(entries, "en");
+
+        doc.entries[0].set_translation("Salut".to_string(), Some(0.4));
+        doc.record_revision(1, RevisionOrigin::Translation, None);
+        doc.record_validation_outcomes(&HashMap::from([(1, vec!["Low confidence".to_string()])]));
+
+        doc.entries[0].set_translation("Bonjour".to_string(), Some(0.9));
+        doc.record_revision(1, RevisionOrigin::FeedbackRetry, Some("Provide a more confident translation".to_string()));
+        doc.record_revision(2, RevisionOrigin::Translation, None);
+        doc.record_validation_outcomes(&HashMap::new());
+
+        assert_eq!(doc.revisions.len(), 2);
+        assert_eq!(doc.revisions[0].validation_passed, Some(false));
+        assert_eq!(doc.revisions[0].validation_message.as_deref(), Some("Low confidence"));
+        assert_eq!(doc.revisions[1].text, "Bonjour");
+        assert_eq!(doc.revisions[1].confidence, Some(0.9));
+        assert_eq!(doc.revisions[1].validation_passed, Some(true));
+        assert!(doc.revisions[1].validation_message.is_none());
+    }
+
     #[test]
     fn test_documentEntry_fromSubtitleEntry_shouldConvertCorrectly() {
         let entry = SubtitleEntry::new(1, 1000, 5000, "Hello, world!".to_string());
@@ -540,6 +754,20 @@ mod tests {
         assert!(doc_entry.is_sound_effect());
     }
 
+    #[test]
+    fn test_subtitleDocument_classifyEntries_withCapsDocument_shouldNotTakeCapsLinesForSigns() {
+        let entries = vec![
+            SubtitleEntry::new(1, 0, 1000, "WHERE ARE YOU".to_string()),
+            SubtitleEntry::new(2, 1000, 2000, "OVER HERE".to_string()),
+            SubtitleEntry::new(3, 2000, 3000, "{\\an8}EXIT".to_string()),
+        ];
+        let mut doc = SubtitleDocument::from_entries(entries, "en");
+        doc.classify_entries();
+
+        let classes: Vec<EntryClass> = doc.entries.iter().map(|e| e.class).collect();
+        assert_eq!(classes, vec![EntryClass::Dialogue, EntryClass::Dialogue, EntryClass::Sign]);
+    }
+
     #[test]
     fn test_subtitleDocument_fromEntries_shouldCreateDocument() {
         let entries = vec![
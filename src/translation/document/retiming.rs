@@ -0,0 +1,540 @@
+/*!
+ * Retiming engine for translated subtitle documents.
+ *
+ * Translations often run longer than the source, pushing cues past a
+ * comfortable reading speed. The engine fixes timing after translation:
+ * - Splits overlong cues at sentence boundaries with proportional timing
+ * - Merges adjacent short cues from the same speaker
+ * - Extends end times into free gaps, keeping the minimum gap to the next cue
+ *
+ * Every change is recorded in a `RetimingReport`.
+ */
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::model::{DocumentEntry, SubtitleDocument, Timecode};
+
+/// Sentence ends followed by whitespace, used as split points.
+static SENTENCE_END_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"[.!?…。！？]["'»)\]]*\s+"#).unwrap());
+
+/// Configuration for the retiming engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetimingConfig {
+    /// Target maximum characters per second
+    pub max_cps: f64,
+
+    /// Minimum cue duration in milliseconds
+    pub min_duration_ms: u64,
+
+    /// Maximum cue duration in milliseconds
+    pub max_duration_ms: u64,
+
+    /// Minimum gap kept before the next cue in milliseconds
+    pub min_gap_ms: u64,
+
+    /// Maximum characters in one cue (split above, merge up to)
+    pub max_cue_chars: usize,
+
+    /// Largest gap between two cues that may still be merged
+    pub max_merge_gap_ms: u64,
+
+    /// Whether to extend end times into free gaps
+    pub extend_into_gaps: bool,
+
+    /// Whether to merge adjacent short cues
+    pub merge_short_cues: bool,
+
+    /// Whether to split overlong cues
+    pub split_long_cues: bool,
+}
+
+impl Default for RetimingConfig {
+    fn default() -> Self {
+        Self {
+            max_cps: 20.0,
+            min_duration_ms: 1000,
+            max_duration_ms: 7000,
+            min_gap_ms: 83, // Two frames at 24 fps
+            max_cue_chars: 84,
+            max_merge_gap_ms: 500,
+            extend_into_gaps: true,
+            merge_short_cues: true,
+            split_long_cues: true,
+        }
+    }
+}
+
+/// A single change made by the retiming engine.
+///
+/// Entry IDs refer to the document before retiming.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum RetimingChange {
+    /// End time moved later into a free gap
+    Extended {
+        entry_id: usize,
+        old_end_ms: u64,
+        new_end_ms: u64,
+    },
+    /// Following cue merged into this one
+    Merged {
+        entry_id: usize,
+        merged_entry_id: usize,
+    },
+    /// Cue split in two at a sentence boundary
+    Split {
+        entry_id: usize,
+        split_at_ms: u64,
+    },
+}
+
+impl RetimingChange {
+    /// Get the entry ID the change applies to.
+    pub fn entry_id(&self) -> usize {
+        match self {
+            RetimingChange::Extended { entry_id, .. } => *entry_id,
+            RetimingChange::Merged { entry_id, .. } => *entry_id,
+            RetimingChange::Split { entry_id, .. } => *entry_id,
+        }
+    }
+
+    /// Get a description of the change.
+    pub fn description(&self) -> String {
+        match self {
+            RetimingChange::Extended { entry_id, old_end_ms, new_end_ms } => format!(
+                "Entry {}: end extended from {}ms to {}ms",
+                entry_id, old_end_ms, new_end_ms
+            ),
+            RetimingChange::Merged { entry_id, merged_entry_id } => {
+                format!("Entry {}: merged with entry {}", entry_id, merged_entry_id)
+            }
+            RetimingChange::Split { entry_id, split_at_ms } => {
+                format!("Entry {}: split at {}ms", entry_id, split_at_ms)
+            }
+        }
+    }
+}
+
+/// Report of all retiming changes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetimingReport {
+    /// Changes in the order they were made
+    pub changes: Vec<RetimingChange>,
+
+    /// Number of entries before retiming
+    pub entries_before: usize,
+
+    /// Number of entries after retiming
+    pub entries_after: usize,
+
+    /// Entries (new IDs) still above the CPS limit after retiming
+    pub still_too_fast: Vec<usize>,
+}
+
+impl RetimingReport {
+    /// Check if anything was changed.
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Count changes of each kind as (extended, merged, split).
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.changes.iter().fold((0, 0, 0), |(e, m, s), change| match change {
+            RetimingChange::Extended { .. } => (e + 1, m, s),
+            RetimingChange::Merged { .. } => (e, m + 1, s),
+            RetimingChange::Split { .. } => (e, m, s + 1),
+        })
+    }
+
+    /// Get a summary of the report.
+    pub fn summary(&self) -> String {
+        let (extended, merged, split) = self.counts();
+        format!(
+            "Retiming: {} extended, {} merged, {} split ({} -> {} entries), {} still too fast",
+            extended,
+            merged,
+            split,
+            self.entries_before,
+            self.entries_after,
+            self.still_too_fast.len()
+        )
+    }
+}
+
+/// Retiming engine working on a `SubtitleDocument`.
+pub struct RetimingEngine {
+    config: RetimingConfig,
+}
+
+impl RetimingEngine {
+    /// Create a new retiming engine.
+    pub fn new(config: RetimingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Create a retiming engine with default configuration.
+    pub fn with_defaults() -> Self {
+        Self::new(RetimingConfig::default())
+    }
+
+    /// Retime the document in place and report every change.
+    ///
+    /// Entries are renumbered afterwards and scene boundaries follow them.
+    pub fn retime(&self, doc: &mut SubtitleDocument) -> RetimingReport {
+        let mut report = RetimingReport {
+            entries_before: doc.entries.len(),
+            ..Default::default()
+        };
+
+        // Keep each entry's pre-retiming ID so scenes can be remapped
+        let mut entries: Vec<(usize, DocumentEntry)> =
+            doc.entries.drain(..).map(|e| (e.id, e)).collect();
+
+        if self.config.split_long_cues {
+            entries = self.split_pass(entries, &mut report);
+        }
+        if self.config.merge_short_cues {
+            entries = self.merge_pass(entries, &mut report);
+        }
+        if self.config.extend_into_gaps {
+            self.extend_pass(&mut entries, &mut report);
+        }
+
+        // Renumber and remap scene boundaries to the new IDs
+        let mut id_map: HashMap<usize, usize> = HashMap::new();
+        for (index, (old_id, entry)) in entries.iter_mut().enumerate() {
+            entry.id = index + 1;
+            id_map.entry(*old_id).or_insert(entry.id);
+        }
+        for scene in &mut doc.scenes {
+            scene.start_entry_id = id_map.get(&scene.start_entry_id).copied().unwrap_or(scene.start_entry_id);
+            scene.end_entry_id = Self::last_new_id(&entries, scene.end_entry_id).unwrap_or(scene.end_entry_id);
+        }
+
+        doc.entries = entries.into_iter().map(|(_, e)| e).collect();
+        doc.metadata.total_entries = doc.entries.len();
+
+        report.entries_after = doc.entries.len();
+        report.still_too_fast = doc
+            .entries
+            .iter()
+            .filter(|e| self.cps(e) > self.config.max_cps)
+            .map(|e| e.id)
+            .collect();
+
+        report
+    }
+
+    /// Last new ID that came from the given old ID.
+    fn last_new_id(entries: &[(usize, DocumentEntry)], old_id: usize) -> Option<usize> {
+        entries.iter().rev().find(|(id, _)| *id == old_id).map(|(_, e)| e.id)
+    }
+
+    /// Split cues that are too long in characters or duration.
+    fn split_pass(
+        &self,
+        entries: Vec<(usize, DocumentEntry)>,
+        report: &mut RetimingReport,
+    ) -> Vec<(usize, DocumentEntry)> {
+        let mut result = Vec::with_capacity(entries.len());
+
+        for (old_id, entry) in entries {
+            let overlong = Self::display_text(&entry).chars().count() > self.config.max_cue_chars
+                || entry.timecode.duration_ms() > self.config.max_duration_ms;
+
+            match self.split_entry(&entry).filter(|_| overlong) {
+                Some((first, second)) => {
+                    report.changes.push(RetimingChange::Split {
+                        entry_id: old_id,
+                        split_at_ms: second.timecode.start_ms,
+                    });
+                    result.push((old_id, first));
+                    result.push((old_id, second));
+                }
+                None => result.push((old_id, entry)),
+            }
+        }
+
+        result
+    }
+
+    /// Split one entry at its most balanced sentence boundary.
+    ///
+    /// Cues with formatting tags are left alone, since a split would break the tags.
+    fn split_entry(&self, entry: &DocumentEntry) -> Option<(DocumentEntry, DocumentEntry)> {
+        let text = Self::display_text(entry);
+        if text.contains('<') || text.contains('{') {
+            return None;
+        }
+
+        let (head, tail) = Self::split_text(text)?;
+        let total_chars = (head.chars().count() + tail.chars().count()).max(1) as u64;
+        let duration = entry.timecode.duration_ms();
+        let split_at = entry.timecode.start_ms + duration * head.chars().count() as u64 / total_chars;
+        let first_end = split_at.saturating_sub(self.config.min_gap_ms).max(entry.timecode.start_ms + 1);
+
+        let mut first = entry.clone();
+        let mut second = entry.clone();
+        first.timecode = Timecode::from_milliseconds(entry.timecode.start_ms, first_end);
+        second.timecode = Timecode::from_milliseconds(split_at, entry.timecode.end_ms);
+
+        if entry.translated_text.is_some() {
+            first.translated_text = Some(head);
+            second.translated_text = Some(tail);
+
+            // Split the source too if it has a boundary; otherwise both halves keep it
+            if let Some((source_head, source_tail)) = Self::split_text(&entry.original_text) {
+                first.original_text = source_head;
+                second.original_text = source_tail;
+            }
+        } else {
+            first.original_text = head;
+            second.original_text = tail;
+        }
+
+        Some((first, second))
+    }
+
+    /// Split text at the sentence boundary closest to its middle.
+    fn split_text(text: &str) -> Option<(String, String)> {
+        let total = text.chars().count();
+        let boundary = SENTENCE_END_REGEX
+            .find_iter(text)
+            .map(|m| m.end())
+            .filter(|&end| end < text.len())
+            .min_by_key(|&end| (text[..end].chars().count() as i64 - total as i64 / 2).abs())?;
+
+        let head = text[..boundary].trim().to_string();
+        let tail = text[boundary..].trim().to_string();
+        if head.is_empty() || tail.is_empty() {
+            None
+        } else {
+            Some((head, tail))
+        }
+    }
+
+    /// Merge adjacent short cues from the same speaker.
+    fn merge_pass(
+        &self,
+        entries: Vec<(usize, DocumentEntry)>,
+        report: &mut RetimingReport,
+    ) -> Vec<(usize, DocumentEntry)> {
+        let mut result: Vec<(usize, DocumentEntry)> = Vec::with_capacity(entries.len());
+
+        for (old_id, entry) in entries {
+            if let Some((prev_id, prev)) = result.last_mut()
+                && self.can_merge(prev, &entry)
+            {
+                report.changes.push(RetimingChange::Merged {
+                    entry_id: *prev_id,
+                    merged_entry_id: old_id,
+                });
+                Self::merge_into(prev, entry);
+                continue;
+            }
+            result.push((old_id, entry));
+        }
+
+        result
+    }
+
+    /// Check whether two adjacent cues can be merged.
+    fn can_merge(&self, prev: &DocumentEntry, next: &DocumentEntry) -> bool {
+        let short = prev.timecode.duration_ms() < self.config.min_duration_ms
+            || next.timecode.duration_ms() < self.config.min_duration_ms;
+        let gap = next.timecode.start_ms.saturating_sub(prev.timecode.end_ms);
+        let combined_chars =
+            Self::display_text(prev).chars().count() + Self::display_text(next).chars().count() + 1;
+        // A leading dash marks a change of speaker
+        let dialogue_dash = |e: &DocumentEntry| Self::display_text(e).trim_start().starts_with('-');
+
+        short
+            && prev.speaker == next.speaker
+            && prev.class == next.class
+            && next.timecode.start_ms >= prev.timecode.end_ms
+            && gap <= self.config.max_merge_gap_ms
+            && combined_chars <= self.config.max_cue_chars
+            && prev.timecode.duration_ms() + gap + next.timecode.duration_ms() <= self.config.max_duration_ms
+            && !dialogue_dash(prev)
+            && !dialogue_dash(next)
+    }
+
+    /// Merge `next` into `prev`.
+    fn merge_into(prev: &mut DocumentEntry, next: DocumentEntry) {
+        prev.timecode = Timecode::from_milliseconds(prev.timecode.start_ms, next.timecode.end_ms);
+        prev.original_text = join_cue_text(&prev.original_text, &next.original_text);
+        prev.translated_text = match (prev.translated_text.take(), next.translated_text) {
+            (Some(a), Some(b)) => Some(join_cue_text(&a, &b)),
+            (a, b) => a.or(b),
+        };
+        prev.confidence = match (prev.confidence, next.confidence) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        for tag in next.formatting {
+            if !prev.formatting.contains(&tag) {
+                prev.formatting.push(tag);
+            }
+        }
+    }
+
+    /// Extend end times of short or fast cues into free gaps.
+    fn extend_pass(&self, entries: &mut [(usize, DocumentEntry)], report: &mut RetimingReport) {
+        for i in 0..entries.len() {
+            let next_start = entries.get(i + 1).map(|(_, e)| e.timecode.start_ms);
+            let (old_id, entry) = &mut entries[i];
+
+            let chars = Self::display_text(entry).chars().count() as f64;
+            let needed_ms = ((chars / self.config.max_cps) * 1000.0).ceil() as u64;
+            let target_ms = needed_ms
+                .max(self.config.min_duration_ms)
+                .min(self.config.max_duration_ms);
+
+            let start = entry.timecode.start_ms;
+            let old_end = entry.timecode.end_ms;
+            let mut new_end = start + target_ms;
+            if let Some(next_start) = next_start {
+                new_end = new_end.min(next_start.saturating_sub(self.config.min_gap_ms));
+            }
+
+            if new_end > old_end {
+                entry.timecode = Timecode::from_milliseconds(start, new_end);
+                report.changes.push(RetimingChange::Extended {
+                    entry_id: *old_id,
+                    old_end_ms: old_end,
+                    new_end_ms: new_end,
+                });
+            }
+        }
+    }
+
+    /// Reading speed of an entry in characters per second.
+    fn cps(&self, entry: &DocumentEntry) -> f64 {
+        let duration_secs = entry.timecode.duration_ms() as f64 / 1000.0;
+        if duration_secs <= 0.0 {
+            return f64::INFINITY;
+        }
+        Self::display_text(entry).chars().count() as f64 / duration_secs
+    }
+
+    /// Text shown on screen: the translation if present, otherwise the original.
+    fn display_text(entry: &DocumentEntry) -> &str {
+        entry.translated_text.as_deref().unwrap_or(&entry.original_text)
+    }
+}
+
+impl Default for RetimingEngine {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Join two cue texts, stacking them as lines when that stays within two lines.
+fn join_cue_text(a: &str, b: &str) -> String {
+    if a.lines().count() + b.lines().count() <= 2 {
+        format!("{}\n{}", a.trim_end(), b.trim_start())
+    } else {
+        format!("{} {}", a.trim_end(), b.trim_start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle_processor::SubtitleEntry;
+
+    fn create_document(entries: &[(u64, u64, &str, &str)]) -> SubtitleDocument {
+        let subtitle_entries = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (start, end, text, _))| SubtitleEntry::new(i + 1, *start, *end, text.to_string()))
+            .collect();
+        let mut doc = SubtitleDocument::from_entries(subtitle_entries, "en");
+        for (entry, (_, _, _, translated)) in doc.entries.iter_mut().zip(entries) {
+            entry.set_translation(translated.to_string(), Some(0.9));
+        }
+        doc
+    }
+
+    fn engine_with(config: RetimingConfig) -> RetimingEngine {
+        RetimingEngine::new(config)
+    }
+
+    #[test]
+    fn test_retimingEngine_extend_shouldRespectMinimumGap() {
+        let mut doc = create_document(&[
+            (0, 1000, "Hi there.", "Bonjour à vous, comment allez-vous ce matin ?"),
+            (2000, 4000, "Fine.", "Très bien."),
+        ]);
+        let engine = engine_with(RetimingConfig {
+            merge_short_cues: false,
+            split_long_cues: false,
+            ..Default::default()
+        });
+
+        let report = engine.retime(&mut doc);
+
+        assert_eq!(doc.entries[0].timecode.end_ms, 2000 - 83);
+        assert_eq!(
+            report.changes[0],
+            RetimingChange::Extended { entry_id: 1, old_end_ms: 1000, new_end_ms: 1917 }
+        );
+    }
+
+    #[test]
+    fn test_retimingEngine_merge_shouldJoinShortCuesFromSameSpeaker() {
+        let mut doc = create_document(&[
+            (0, 600, "Wait.", "Attends."),
+            (700, 1300, "Listen.", "Écoute."),
+            (5000, 5400, "- No.", "- Non."),
+        ]);
+        let engine = engine_with(RetimingConfig {
+            extend_into_gaps: false,
+            ..Default::default()
+        });
+
+        let report = engine.retime(&mut doc);
+
+        assert_eq!(doc.entries.len(), 2);
+        assert_eq!(doc.entries[0].translated_text.as_deref(), Some("Attends.\nÉcoute."));
+        assert_eq!(doc.entries[0].timecode, Timecode::from_milliseconds(0, 1300));
+        assert_eq!(doc.entries[1].id, 2);
+        assert_eq!(
+            report.changes,
+            vec![RetimingChange::Merged { entry_id: 1, merged_entry_id: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_retimingEngine_split_shouldUseSentenceBoundaryAndProportionalTiming() {
+        let mut doc = create_document(&[(
+            0,
+            8000,
+            "I never said that. You know it.",
+            "Je n'ai jamais dit une chose pareille. Et tu le sais très bien.",
+        )]);
+        let engine = engine_with(RetimingConfig {
+            extend_into_gaps: false,
+            ..Default::default()
+        });
+
+        let report = engine.retime(&mut doc);
+
+        assert_eq!(doc.entries.len(), 2);
+        assert_eq!(
+            doc.entries[0].translated_text.as_deref(),
+            Some("Je n'ai jamais dit une chose pareille.")
+        );
+        assert_eq!(doc.entries[1].original_text, "You know it.");
+        assert!(doc.entries[1].timecode.start_ms > 4000);
+        assert_eq!(doc.entries[1].timecode.end_ms, 8000);
+        assert!(doc.entries[0].timecode.end_ms < doc.entries[1].timecode.start_ms);
+        assert!(matches!(report.changes[0], RetimingChange::Split { entry_id: 1, .. }));
+        assert_eq!(report.entries_after, 2);
+    }
+}
@@ -8,7 +8,7 @@
 // Re-export main types
 pub use self::batch::BatchTranslator;
 pub use self::core::TranslationService;
-pub use self::pipeline::{PipelineAdapter, PipelineMode};
+pub use self::pipeline::{PipelineAdapter, PipelineConfig, PipelineMode};
 
 // Public modules
 pub mod batch;
@@ -24,3 +24,5 @@ pub(crate) mod document;
 pub(crate) mod prompts;
 pub(crate) mod quality;
 pub(crate) mod speculative;
+pub(crate) mod telemetry;
+pub(crate) mod update;
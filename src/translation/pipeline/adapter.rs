@@ -8,29 +8,105 @@
 
 use std::convert::Infallible;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
+use chrono::Utc;
+use log::{debug, info, warn};
 
 use crate::subtitle_processor::SubtitleEntry;
+use crate::translation::context::HistorySummary;
 use crate::translation::core::{TokenUsageStats, TranslationService};
-use crate::translation::document::SubtitleDocument;
-use crate::translation::pipeline::{PipelineConfig, PipelineProgress, TranslationPipeline};
+use crate::translation::document::{EntryRevision, SubtitleDocument};
+use crate::translation::pipeline::analysis_pass::AnalysisStore;
+use crate::translation::pipeline::validation_pass::ValidationReport;
+use crate::translation::pipeline::{PipelineConfig, PipelinePhase, PipelineProgress, TranslationPipeline};
+use crate::translation::telemetry::MetricsRecorder;
+
+/// Callback receiving every candidate translation produced during a run.
+pub type RevisionCallback = Box<dyn Fn(Vec<EntryRevision>) + Send + Sync>;
 
 /// Adapter for using the new pipeline with legacy SubtitleEntry-based code.
 pub struct PipelineAdapter {
     pipeline: TranslationPipeline,
     service: TranslationService,
+    /// Service for the back-translation check (None = `service`)
+    back_translation_service: Option<TranslationService>,
+    /// Service for the judge (None = `service`)
+    judge_service: Option<TranslationService>,
+    /// Summaries carried over from a previous run (e.g. a resumed session)
+    history_summaries: Vec<HistorySummary>,
+    revision_callback: Option<RevisionCallback>,
 }
 
 impl PipelineAdapter {
     /// Create a new pipeline adapter.
     pub fn new(service: TranslationService, config: PipelineConfig) -> Self {
+        let service = match service.metrics() {
+            Some(_) => service,
+            None => service.with_metrics(MetricsRecorder::new()),
+        };
         Self {
             pipeline: TranslationPipeline::new(config),
             service,
+            back_translation_service: None,
+            judge_service: None,
+            history_summaries: Vec::new(),
+            revision_callback: None,
         }
     }
 
+    /// Seed the document with previously generated history summaries.
+    pub fn with_history_summaries(mut self, summaries: Vec<HistorySummary>) -> Self {
+        self.history_summaries = summaries;
+        self
+    }
+
+    /// Keep glossary, characters and summary in a store across runs.
+    pub fn with_analysis_store(mut self, store: Arc<dyn AnalysisStore>) -> Self {
+        self.pipeline = self.pipeline.with_analysis_store(store);
+        self
+    }
+
+    /// Use a separate service (e.g. another provider) for the back-translation check.
+    pub fn with_back_translation_service(mut self, service: TranslationService) -> Self {
+        self.back_translation_service = Some(self.share_metrics(service));
+        self
+    }
+
+    /// Use a separate service (e.g. another provider or model) for the judge.
+    pub fn with_judge_service(mut self, service: TranslationService) -> Self {
+        self.judge_service = Some(self.share_metrics(service));
+        self
+    }
+
+    /// Record the requests of a quality-check service with the main service's.
+    fn share_metrics(&self, service: TranslationService) -> TranslationService {
+        match self.service.metrics() {
+            Some(metrics) => service.with_metrics(metrics.clone()),
+            None => service,
+        }
+    }
+
+    /// Set a callback that receives each history summary as soon as it is produced.
+    pub fn with_summary_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&HistorySummary) + Send + Sync + 'static,
+    {
+        self.pipeline = self.pipeline.with_summary_callback(Box::new(callback));
+        self
+    }
+
+    /// Set a callback that receives the revisions of all entries once translation finishes.
+    pub fn with_revision_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Vec<EntryRevision>) + Send + Sync + 'static,
+    {
+        self.revision_callback = Some(Box::new(callback));
+        self
+    }
+
     /// Create an adapter with default configuration.
     pub fn with_defaults(service: TranslationService, source_lang: &str, target_lang: &str) -> Self {
         let config = PipelineConfig::new(source_lang, target_lang);
@@ -80,6 +156,7 @@ impl PipelineAdapter {
         // Convert to SubtitleDocument
         let mut doc = SubtitleDocument::from_entries(entries, source_language);
         doc = doc.with_target_language(target_language);
+        doc.history_summaries = self.history_summaries.clone();
 
         // Create progress adapter
         let progress_adapter: Option<Box<dyn Fn(PipelineProgress) + Send>> =
@@ -96,23 +173,113 @@ impl PipelineAdapter {
         let batch_callback_clone = batch_callback.clone();
 
         // Run the pipeline
-        let _result = self
+        let mut result = self
             .pipeline
             .translate(&self.service, &mut doc, progress_adapter)
             .await?;
 
+        // Quality checks run before conversion so retranslated entries are returned
+        let config = self.pipeline.config();
+        let quality_checks = config.validation_config.enable_semantic_validation
+            || config.back_translation.is_some()
+            || config.judge.is_some();
+        if result.success && quality_checks {
+            let checks_start = (Utc::now(), Instant::now());
+            let mut report = result
+                .validation
+                .take()
+                .unwrap_or_else(|| ValidationReport::new(doc.entries.len()));
+            let issues_before = report.issues.len();
+
+            match self.pipeline.semantic_validate(&self.service, &doc, &mut report).await {
+                Ok(Some(divergent)) => info!("Semantic check: {} divergent entries", divergent),
+                Ok(None) => {}
+                Err(e) => warn!("Semantic check failed: {}", e),
+            }
+
+            let back_service = self.back_translation_service.as_ref().unwrap_or(&self.service);
+
+            match self
+                .pipeline
+                .back_translate(&self.service, back_service, &mut doc, &mut report)
+                .await
+            {
+                Ok(Some(back_report)) => {
+                    info!("{}", back_report.summary());
+                    for divergent in back_report.divergent() {
+                        debug!(
+                            "Entry {} diverged (similarity {:.2}): back-translated as '{}'",
+                            divergent.entry_id, divergent.similarity, divergent.back_translation
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Back-translation check failed: {}", e),
+            }
+
+            let judge_service = self.judge_service.as_ref().unwrap_or(&self.service);
+            match self
+                .pipeline
+                .judge(&self.service, judge_service, &mut doc, &mut report)
+                .await
+            {
+                Ok(Some(judge_report)) => {
+                    info!("{}", judge_report.summary());
+                    for annotation in &judge_report.annotations {
+                        debug!("Entry {}: {}", annotation.entry_id, annotation.describe());
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Judge pass failed: {}", e),
+            }
+
+            TranslationPipeline::record_phase(
+                &self.service,
+                &mut result.telemetry,
+                PipelinePhase::Validation,
+                doc.entries.len(),
+                checks_start,
+                &report.issues[issues_before..],
+            );
+
+            // The score now includes the semantic, back-translation and judge results
+            info!("{}", report.summary());
+            result.validation = Some(report);
+        }
+
         // Convert back to SubtitleEntry list
-        let translated_entries = doc.to_subtitle_entries();
+        let mut translated_entries = doc.to_subtitle_entries();
 
         // Fire batch callback with all completed entries if provided
         if let Some(cb) = batch_callback_clone {
             cb(translated_entries.clone());
         }
 
-        // Create token usage stats
-        // Note: The new pipeline doesn't track tokens in the same way as the legacy system
-        // Token tracking would need to be added to the pipeline for accurate reporting
-        let token_usage = TokenUsageStats::default();
+        if let Some(cb) = &self.revision_callback
+            && !doc.revisions.is_empty()
+        {
+            cb(std::mem::take(&mut doc.revisions));
+        }
+
+        // Retime last: the callbacks above track entries by their original sequence numbers
+        if let Some(report) = self.pipeline.retime(&mut doc) {
+            info!("{}", report.summary());
+            for change in &report.changes {
+                info!("{}", change.description());
+            }
+            translated_entries = doc.to_subtitle_entries();
+        }
+
+        // Token usage is summed from the per-batch telemetry
+        let mut token_usage = TokenUsageStats::with_provider_info(
+            self.service.config.provider.to_lowercase_string(),
+            self.service.config.get_model(),
+        );
+        for batch in &result.telemetry {
+            token_usage.add_token_usage(Some(batch.prompt_tokens), Some(batch.completion_tokens));
+            token_usage.api_duration += Duration::from_millis(batch.latencies_ms.iter().sum());
+        }
+        token_usage.batches = std::mem::take(&mut result.telemetry);
 
         Ok((translated_entries, token_usage))
     }
@@ -6,13 +6,23 @@
  * - Terminology (for consistent translation)
  * - Scene boundaries (for context segmentation)
  * - Content summary (for long document context)
+ * - Entry classes (dialogue, lyrics, signs, sound effects, speaker labels)
+ *
+ * Glossary, characters and summary can be kept in an `AnalysisStore`, so
+ * resumed sessions and later episodes of a series reuse earlier results.
  */
 
+use anyhow::Result;
+use async_trait::async_trait;
+use log::{debug, warn};
+
 use crate::translation::context::{
     ExtractionConfig, GlossaryExtractor, GlossaryPreflightChecker, HistorySummarizer,
     PreflightReport, SceneDetectionConfig, SceneDetector, SummarizationConfig,
 };
-use crate::translation::document::{Glossary, Scene, SubtitleDocument};
+use std::collections::HashMap;
+
+use crate::translation::document::{EntryClass, Glossary, Scene, SubtitleDocument};
 
 /// Configuration for the analysis pass.
 #[derive(Debug, Clone)]
@@ -37,6 +47,9 @@ pub struct AnalysisConfig {
 
     /// Whether to run glossary preflight check
     pub run_preflight: bool,
+
+    /// Whether to classify entries (lyrics, signs, sound effects, speaker labels)
+    pub classify_entries: bool,
 }
 
 impl Default for AnalysisConfig {
@@ -49,6 +62,7 @@ impl Default for AnalysisConfig {
             detect_scenes: true,
             generate_summary: true,
             run_preflight: false, // Controlled by experimental flag
+            classify_entries: true,
         }
     }
 }
@@ -64,6 +78,7 @@ impl AnalysisConfig {
             detect_scenes: false,
             generate_summary: false,
             run_preflight: false,
+            classify_entries: true,
         }
     }
 
@@ -77,6 +92,7 @@ impl AnalysisConfig {
             detect_scenes: true,
             generate_summary: true,
             run_preflight: true,
+            classify_entries: true,
         }
     }
 
@@ -103,6 +119,12 @@ impl AnalysisConfig {
         self.run_preflight = enabled;
         self
     }
+
+    /// Enable or disable entry classification.
+    pub fn with_entry_classification(mut self, enabled: bool) -> Self {
+        self.classify_entries = enabled;
+        self
+    }
 }
 
 /// Result of the analysis pass.
@@ -128,6 +150,9 @@ pub struct AnalysisResult {
 
     /// Glossary preflight report (if run)
     pub preflight_report: Option<PreflightReport>,
+
+    /// Classes of non-dialogue entries, by entry ID
+    pub entry_classes: HashMap<usize, EntryClass>,
 }
 
 impl AnalysisResult {
@@ -141,6 +166,7 @@ impl AnalysisResult {
             term_count: 0,
             scene_count: 0,
             preflight_report: None,
+            entry_classes: HashMap::new(),
         }
     }
 
@@ -150,6 +176,7 @@ impl AnalysisResult {
             || !self.scenes.is_empty()
             || self.summary.is_some()
             || self.preflight_report.is_some()
+            || !self.entry_classes.is_empty()
     }
 
     /// Get a summary description of the analysis.
@@ -176,6 +203,10 @@ impl AnalysisResult {
             parts.push(format!("preflight: {}", report.summary()));
         }
 
+        if !self.entry_classes.is_empty() {
+            parts.push(format!("{} non-dialogue entries", self.entry_classes.len()));
+        }
+
         if parts.is_empty() {
             "no analysis data".to_string()
         } else {
@@ -184,6 +215,96 @@ impl AnalysisResult {
     }
 }
 
+/// A character seen by the analysis pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterProfile {
+    /// Character name
+    pub name: String,
+
+    /// Number of entries mentioning the character
+    pub mentions: usize,
+}
+
+/// Analysis results kept between runs (a resumed session, later episodes of a series).
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisMemory {
+    /// Glossary terms, technical terms and character names
+    pub glossary: Glossary,
+
+    /// Characters mentioned in the document
+    pub characters: Vec<CharacterProfile>,
+
+    /// Content summary of the document
+    pub summary: Option<String>,
+}
+
+impl AnalysisMemory {
+    /// Capture the analysis results held by a document.
+    ///
+    /// Only characters mentioned in the document are listed, so saving
+    /// does not reset the counts of characters from other episodes.
+    pub fn from_document(doc: &SubtitleDocument) -> Self {
+        let mut characters: Vec<CharacterProfile> = doc
+            .glossary
+            .character_names
+            .iter()
+            .map(|name| CharacterProfile {
+                name: name.clone(),
+                mentions: doc.entries.iter().filter(|e| e.original_text.contains(name.as_str())).count(),
+            })
+            .filter(|profile| profile.mentions > 0)
+            .collect();
+        characters.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            glossary: doc.glossary.clone(),
+            characters,
+            summary: doc.context_summary.clone(),
+        }
+    }
+
+    /// Check if nothing was remembered.
+    pub fn is_empty(&self) -> bool {
+        self.glossary.is_empty() && self.characters.is_empty() && self.summary.is_none()
+    }
+
+    /// Merge another memory into this one; its terms, counts and summary win.
+    pub fn merge(&mut self, other: &AnalysisMemory) {
+        self.glossary.merge(&other.glossary);
+        for profile in &other.characters {
+            match self.characters.iter_mut().find(|p| p.name == profile.name) {
+                Some(existing) => existing.mentions = profile.mentions,
+                None => self.characters.push(profile.clone()),
+            }
+        }
+        self.characters.sort_by(|a, b| a.name.cmp(&b.name));
+        if other.summary.is_some() {
+            self.summary = other.summary.clone();
+        }
+    }
+
+    /// Apply remembered results to a document, overriding freshly analyzed ones.
+    pub fn apply_to(&self, doc: &mut SubtitleDocument) {
+        doc.glossary.merge(&self.glossary);
+        for profile in &self.characters {
+            doc.glossary.add_character(&profile.name);
+        }
+        if self.summary.is_some() {
+            doc.context_summary = self.summary.clone();
+        }
+    }
+}
+
+/// Persistent storage of analysis results.
+#[async_trait]
+pub trait AnalysisStore: Send + Sync {
+    /// Load the results of earlier analyses.
+    async fn load(&self) -> Result<AnalysisMemory>;
+
+    /// Save the results of an analysis.
+    async fn save(&self, memory: &AnalysisMemory) -> Result<()>;
+}
+
 /// Analysis pass for preprocessing documents before translation.
 pub struct AnalysisPass {
     config: AnalysisConfig,
@@ -246,6 +367,18 @@ impl AnalysisPass {
             result.preflight_report = Some(checker.check_entries(&doc.entries));
         }
 
+        // Classify entries
+        if self.config.classify_entries {
+            let texts: Vec<&str> = doc.entries.iter().map(|e| e.original_text.as_str()).collect();
+            result.entry_classes = doc
+                .entries
+                .iter()
+                .zip(EntryClass::classify_document(&texts))
+                .map(|(e, class)| (e.id, class))
+                .filter(|(_, class)| !class.is_dialogue())
+                .collect();
+        }
+
         result
     }
 
@@ -279,9 +412,68 @@ impl AnalysisPass {
             doc.context_summary = result.summary.clone();
         }
 
+        // Apply entry classes
+        if self.config.classify_entries {
+            for entry in &mut doc.entries {
+                entry.class = result.entry_classes.get(&entry.id).copied().unwrap_or_default();
+            }
+        }
+
         result
     }
 
+    /// Analyze a document, keeping earlier results where they exist.
+    ///
+    /// Remembered glossary terms and summary override fresh ones, so a
+    /// resumed session or a later episode translates them the same way.
+    /// Returns the analysis and the memory to save for the next run.
+    pub fn analyze_with_memory(&self, doc: &mut SubtitleDocument, memory: &AnalysisMemory) -> (AnalysisResult, AnalysisMemory) {
+        let mut result = self.analyze_and_update(doc);
+        memory.apply_to(doc);
+
+        let mut characters: Vec<String> = doc.glossary.character_names.iter().cloned().collect();
+        characters.sort();
+        doc.characters = characters;
+
+        result.character_count = doc.glossary.character_names.len();
+        result.term_count = doc.glossary.terms.len() + doc.glossary.technical_terms.len();
+
+        (result, AnalysisMemory::from_document(doc))
+    }
+
+    /// Analyze a document with results loaded from and saved to a store.
+    ///
+    /// Store failures are logged and do not stop the analysis.
+    pub async fn analyze_with_store(&self, doc: &mut SubtitleDocument, store: &dyn AnalysisStore) -> AnalysisResult {
+        let memory = store.load().await.unwrap_or_else(|e| {
+            warn!("Failed to load earlier analysis results: {}", e);
+            AnalysisMemory::default()
+        });
+        if !memory.is_empty() {
+            debug!(
+                "Loaded earlier analysis: {} terms, {} characters",
+                memory.glossary.terms.len() + memory.glossary.technical_terms.len(),
+                memory.characters.len()
+            );
+        }
+
+        let (result, memory) = self.analyze_with_memory(doc, &memory);
+
+        if let Err(e) = store.save(&memory).await {
+            warn!("Failed to save analysis results: {}", e);
+        }
+
+        result
+    }
+
+    /// Apply stored analysis results to a document without analysing it.
+    pub async fn apply_from_store(&self, doc: &mut SubtitleDocument, store: &dyn AnalysisStore) {
+        match store.load().await {
+            Ok(memory) => memory.apply_to(doc),
+            Err(e) => warn!("Failed to load earlier analysis results: {}", e),
+        }
+    }
+
     /// Extract glossary only (without full analysis).
     pub fn extract_glossary(&self, doc: &SubtitleDocument) -> Glossary {
         self.glossary_extractor.extract(&doc.entries)
@@ -352,6 +544,25 @@ mod tests {
         assert!(result.glossary.character_names.contains("Mary"));
     }
 
+    #[test]
+    fn test_analysisPass_analyzeAndUpdate_shouldClassifyEntries() {
+        let mut doc = create_test_document(&[
+            "♪ Over the hills ♪",
+            "{\\an8}POLICE STATION",
+            "[siren wailing]",
+            "Get in the car.",
+        ]);
+
+        let pass = AnalysisPass::with_defaults();
+        let result = pass.analyze_and_update(&mut doc);
+
+        assert_eq!(result.entry_classes.len(), 3);
+        assert_eq!(doc.entries[0].class, EntryClass::Lyric);
+        assert_eq!(doc.entries[1].class, EntryClass::Sign);
+        assert_eq!(doc.entries[2].class, EntryClass::SoundEffect);
+        assert_eq!(doc.entries[3].class, EntryClass::Dialogue);
+    }
+
     #[test]
     fn test_analysisPass_analyze_shouldDetectScenes() {
         // Create entries with a timing gap (simulating scene change)
@@ -394,6 +605,69 @@ mod tests {
         assert_eq!(doc.glossary.character_names.len(), result.character_count);
     }
 
+    #[test]
+    fn test_analysisPass_analyzeWithMemory_shouldKeepRememberedTermsAndSummary() {
+        let mut doc = create_test_document(&[
+            "Alice found the Ring.",
+            "Bob wanted the Ring.",
+            "Alice ran away from Bob.",
+        ]);
+        let mut memory = AnalysisMemory::default();
+        memory.glossary.add_term("Ring", "Anneau", None);
+        memory.glossary.add_character("Gandalf");
+        memory.summary = Some("Earlier summary".to_string());
+
+        let pass = AnalysisPass::with_defaults();
+        let (result, updated) = pass.analyze_with_memory(&mut doc, &memory);
+
+        assert_eq!(doc.glossary.get_translation("Ring"), Some("Anneau"));
+        assert_eq!(doc.context_summary.as_deref(), Some("Earlier summary"));
+        assert!(doc.characters.contains(&"Alice".to_string()));
+        assert!(doc.characters.contains(&"Gandalf".to_string()));
+        assert_eq!(result.character_count, doc.characters.len());
+
+        // Only characters mentioned in this document are counted
+        let alice = updated.characters.iter().find(|p| p.name == "Alice").unwrap();
+        assert_eq!(alice.mentions, 2);
+        assert!(!updated.characters.iter().any(|p| p.name == "Gandalf"));
+        assert_eq!(updated.summary.as_deref(), Some("Earlier summary"));
+    }
+
+    #[tokio::test]
+    async fn test_analysisPass_analyzeWithStore_shouldReuseSavedResults() {
+        struct MemoryStore(std::sync::Mutex<AnalysisMemory>);
+
+        #[async_trait]
+        impl AnalysisStore for MemoryStore {
+            async fn load(&self) -> Result<AnalysisMemory> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+
+            async fn save(&self, memory: &AnalysisMemory) -> Result<()> {
+                *self.0.lock().unwrap() = memory.clone();
+                Ok(())
+            }
+        }
+
+        let store = MemoryStore(std::sync::Mutex::new(AnalysisMemory::default()));
+        let pass = AnalysisPass::with_defaults();
+
+        let mut first = create_test_document(&["John met Mary.", "Mary smiled at John."]);
+        pass.analyze_with_store(&mut first, &store).await;
+        store.0.lock().unwrap().summary = Some("Saved summary".to_string());
+
+        let mut second = create_test_document(&["The rain kept falling.", "Nobody came."]);
+        pass.analyze_with_store(&mut second, &store).await;
+
+        assert!(second.glossary.character_names.contains("John"));
+        assert!(second.glossary.character_names.contains("Mary"));
+        assert_eq!(second.context_summary.as_deref(), Some("Saved summary"));
+
+        let mut third = create_test_document(&["Nobody came."]);
+        pass.apply_from_store(&mut third, &store).await;
+        assert!(third.glossary.character_names.contains("Mary"));
+    }
+
     #[test]
     fn test_analysisPass_minimalConfig_shouldBeEfficient() {
         let doc = create_test_document(&["Simple test.", "Another line."]);
@@ -18,4 +18,4 @@ pub mod validation_pass;
 
 // Re-export types used externally
 pub use adapter::{PipelineAdapter, PipelineMode};
-pub use orchestrator::{PipelineConfig, PipelineProgress, TranslationPipeline};
+pub use orchestrator::{PipelineConfig, PipelinePhase, PipelineProgress, TranslationPipeline};
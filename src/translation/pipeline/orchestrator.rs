@@ -5,17 +5,31 @@
  * 1. Analysis Pass: Document preprocessing
  * 2. Translation Pass: Main translation with JSON I/O
  * 3. Validation Pass: Quality assurance and auto-repair
+ *
+ * Retiming of the translated cues, the semantic check, the back-translation
+ * check and the LLM judge are separate, optional steps (`retime`,
+ * `semantic_validate`, `back_translate`, `judge`).
  */
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::translation::context::TermPolicy;
 use crate::translation::core::TranslationService;
-use crate::translation::document::SubtitleDocument;
+use crate::translation::document::{ClassPolicy, RetimingConfig, RetimingEngine, RetimingReport, SubtitleDocument};
+use crate::translation::prompts::{PromptKind, PromptTemplates, StyleGuide};
+use crate::translation::quality::back_translation::{BackTranslationConfig, BackTranslationReport, BackTranslator};
+use crate::translation::quality::judge::{JudgeConfig, JudgeReport, LlmJudge};
+use crate::translation::quality::semantic::{SemanticValidationConfig, SemanticValidator};
+use crate::translation::telemetry::BatchTelemetry;
 
-use super::analysis_pass::{AnalysisConfig, AnalysisPass, AnalysisResult};
-use super::translation_pass::{TranslationPass, TranslationPassConfig, TranslationStats};
-use super::validation_pass::{ValidationConfig, ValidationPass, ValidationReport};
+use super::analysis_pass::{AnalysisConfig, AnalysisPass, AnalysisResult, AnalysisStore};
+use super::translation_pass::{SummaryCallback, TranslationPass, TranslationPassConfig, TranslationStats};
+use super::validation_pass::{ValidationConfig, ValidationIssue, ValidationPass, ValidationReport};
 
 /// Configuration for the translation pipeline.
 #[derive(Debug, Clone)]
@@ -40,6 +54,21 @@ pub struct PipelineConfig {
 
     /// Target language
     pub target_language: String,
+
+    /// Term policy overriding the built-in one for the source language
+    pub term_policy: Option<TermPolicy>,
+
+    /// How lyrics, signs, sound effects and speaker labels are translated
+    pub class_policy: ClassPolicy,
+
+    /// Retiming applied after translation (None = keep source timing)
+    pub retiming: Option<RetimingConfig>,
+
+    /// Back-translation check run after validation (None = skipped)
+    pub back_translation: Option<BackTranslationConfig>,
+
+    /// LLM judge run after the back-translation check (None = skipped)
+    pub judge: Option<JudgeConfig>,
 }
 
 impl Default for PipelineConfig {
@@ -52,6 +81,11 @@ impl Default for PipelineConfig {
             validation_config: ValidationConfig::default(),
             source_language: "en".to_string(),
             target_language: "fr".to_string(),
+            term_policy: None,
+            class_policy: ClassPolicy::default(),
+            retiming: None,
+            back_translation: None,
+            judge: None,
         }
     }
 }
@@ -76,6 +110,11 @@ impl PipelineConfig {
             validation_config: ValidationConfig::default(),
             source_language: source_language.to_string(),
             target_language: target_language.to_string(),
+            term_policy: None,
+            class_policy: ClassPolicy::default(),
+            retiming: None,
+            back_translation: None,
+            judge: None,
         }
     }
 
@@ -89,6 +128,11 @@ impl PipelineConfig {
             validation_config: ValidationConfig::strict(),
             source_language: source_language.to_string(),
             target_language: target_language.to_string(),
+            term_policy: None,
+            class_policy: ClassPolicy::default(),
+            retiming: None,
+            back_translation: None,
+            judge: None,
         }
     }
 
@@ -121,6 +165,49 @@ impl PipelineConfig {
         self.validation_config = config;
         self
     }
+
+    /// Set the per-show style guide used by the translation and validation passes.
+    pub fn with_style_guide(mut self, guide: StyleGuide) -> Self {
+        self.validation_config.style_guide = Some(guide.clone());
+        self.translation_config = self.translation_config.with_style_guide(guide);
+        self
+    }
+
+    /// Set the honorific and cultural-term policy, replacing the built-in one.
+    pub fn with_term_policy(mut self, policy: TermPolicy) -> Self {
+        self.term_policy = Some(policy);
+        self
+    }
+
+    /// Set the per-class policy for lyrics, signs, sound effects and speaker labels.
+    pub fn with_class_policy(mut self, policy: ClassPolicy) -> Self {
+        self.class_policy = policy;
+        self
+    }
+
+    /// Retime cues after translation (extend, merge and split for reading speed).
+    pub fn with_retiming(mut self, config: RetimingConfig) -> Self {
+        self.retiming = Some(config);
+        self
+    }
+
+    /// Check sampled and flagged entries by translating them back to the source language.
+    pub fn with_back_translation(mut self, config: BackTranslationConfig) -> Self {
+        self.back_translation = Some(config);
+        self
+    }
+
+    /// Score translations with MQM annotations from a judge model.
+    pub fn with_judge(mut self, config: JudgeConfig) -> Self {
+        self.judge = Some(config);
+        self
+    }
+
+    /// Set user-supplied prompt templates used by the translation pass.
+    pub fn with_prompt_templates(mut self, templates: PromptTemplates) -> Self {
+        self.translation_config = self.translation_config.with_prompt_templates(templates);
+        self
+    }
 }
 
 /// Progress information during pipeline execution.
@@ -190,6 +277,17 @@ pub enum PipelinePhase {
     Validation,
 }
 
+impl PipelinePhase {
+    /// Stable name used in reports and the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelinePhase::Analysis => "analysis",
+            PipelinePhase::Translation => "translation",
+            PipelinePhase::Validation => "validation",
+        }
+    }
+}
+
 /// Result of the complete pipeline execution.
 #[derive(Debug, Clone)]
 pub struct PipelineResult {
@@ -210,6 +308,9 @@ pub struct PipelineResult {
 
     /// Error message if pipeline failed
     pub error: Option<String>,
+
+    /// Telemetry of every phase and batch, when the service records metrics
+    pub telemetry: Vec<BatchTelemetry>,
 }
 
 impl PipelineResult {
@@ -227,6 +328,7 @@ impl PipelineResult {
             duration,
             success: true,
             error: None,
+            telemetry: Vec::new(),
         }
     }
 
@@ -239,6 +341,7 @@ impl PipelineResult {
             duration,
             success: false,
             error: Some(error.to_string()),
+            telemetry: Vec::new(),
         }
     }
 
@@ -285,6 +388,8 @@ pub struct TranslationPipeline {
     analysis_pass: AnalysisPass,
     translation_pass: TranslationPass,
     validation_pass: ValidationPass,
+    /// Where analysis results are kept between runs (None = analyze from scratch)
+    analysis_store: Option<Arc<dyn AnalysisStore>>,
 }
 
 impl TranslationPipeline {
@@ -299,14 +404,52 @@ impl TranslationPipeline {
             analysis_pass,
             translation_pass,
             validation_pass,
+            analysis_store: None,
         }
     }
 
+    /// Load and save analysis results in a store, so reruns reuse them.
+    pub fn with_analysis_store(mut self, store: Arc<dyn AnalysisStore>) -> Self {
+        self.analysis_store = Some(store);
+        self
+    }
+
+    /// Hand each rolling history summary to `callback` as soon as it is produced.
+    pub fn with_summary_callback(mut self, callback: SummaryCallback) -> Self {
+        self.translation_pass = self.translation_pass.with_summary_callback(callback);
+        self
+    }
+
     /// Create a pipeline with default configuration for the given languages.
     pub fn for_languages(source_language: &str, target_language: &str) -> Self {
         Self::new(PipelineConfig::new(source_language, target_language))
     }
 
+    /// Apply the configured term and class policies to the document.
+    fn apply_document_policies(&self, doc: &mut SubtitleDocument) {
+        if let Some(ref policy) = self.config.term_policy {
+            doc.term_policy = policy.clone();
+        }
+        doc.class_policy = self.config.class_policy;
+    }
+
+    /// Render the prompts for one batch exactly as a run would, without calling a provider.
+    ///
+    /// Runs the analysis pass (if enabled) so glossary and context match a real run.
+    pub fn preview_prompts(&self, doc: &mut SubtitleDocument, batch_idx: usize) -> Option<(String, String)> {
+        self.apply_document_policies(doc);
+        if self.config.enable_analysis {
+            self.analysis_pass.analyze_and_update(doc);
+        }
+
+        self.translation_pass.preview_prompts(
+            doc,
+            batch_idx,
+            &self.config.source_language,
+            &self.config.target_language,
+        )
+    }
+
     /// Translate a document through the full pipeline.
     pub async fn translate(
         &self,
@@ -316,17 +459,23 @@ impl TranslationPipeline {
     ) -> Result<PipelineResult> {
         let start_time = Instant::now();
         let total_entries = doc.entries.len();
+        self.apply_document_policies(doc);
 
         let mut progress = PipelineProgress::new(PipelinePhase::Analysis, total_entries);
+        let mut telemetry = Vec::new();
 
         // Phase 1: Analysis
+        let analysis_start = (Utc::now(), Instant::now());
         let analysis_result = if self.config.enable_analysis {
             progress.update(0.0, "Analyzing document...");
             if let Some(ref callback) = progress_callback {
                 callback(progress.clone());
             }
 
-            let result = self.analysis_pass.analyze_and_update(doc);
+            let result = match self.analysis_store {
+                Some(ref store) => self.analysis_pass.analyze_with_store(doc, store.as_ref()).await,
+                None => self.analysis_pass.analyze_and_update(doc),
+            };
 
             progress.update(1.0, &format!("Analysis complete: {}", result.description()));
             if let Some(ref callback) = progress_callback {
@@ -335,8 +484,15 @@ impl TranslationPipeline {
 
             Some(result)
         } else {
+            // Terms and names chosen in review still apply
+            if let Some(ref store) = self.analysis_store {
+                self.analysis_pass.apply_from_store(doc, store.as_ref()).await;
+            }
             None
         };
+        if analysis_result.is_some() {
+            Self::record_phase(service, &mut telemetry, PipelinePhase::Analysis, total_entries, analysis_start, &[]);
+        }
 
         // Phase 2: Translation
         progress.next_phase(PipelinePhase::Translation);
@@ -384,7 +540,10 @@ impl TranslationPipeline {
             callback(progress.clone());
         }
 
+        telemetry.extend(translation_stats.batches.iter().cloned());
+
         // Phase 3: Validation
+        let validation_start = (Utc::now(), Instant::now());
         let validation_result = if self.config.enable_validation {
             progress.next_phase(PipelinePhase::Validation);
             progress.update(0.0, "Validating translations...");
@@ -403,15 +562,233 @@ impl TranslationPipeline {
         } else {
             None
         };
+        if let Some(ref report) = validation_result {
+            Self::record_phase(
+                service,
+                &mut telemetry,
+                PipelinePhase::Validation,
+                total_entries,
+                validation_start,
+                &report.issues,
+            );
+        }
 
         let duration = start_time.elapsed();
 
-        Ok(PipelineResult::success(
+        let mut result = PipelineResult::success(
             analysis_result,
             translation_stats,
             validation_result,
             duration,
-        ))
+        );
+        result.telemetry = telemetry;
+        Ok(result)
+    }
+
+    /// Record a whole phase as one telemetry row if the service records metrics.
+    ///
+    /// `issues` are the validation issues the phase found.
+    pub fn record_phase(
+        service: &TranslationService,
+        telemetry: &mut Vec<BatchTelemetry>,
+        phase: PipelinePhase,
+        entry_count: usize,
+        (started_at, start_time): (DateTime<Utc>, Instant),
+        issues: &[ValidationIssue],
+    ) {
+        let Some(metrics) = service.metrics() else {
+            return;
+        };
+        let index = telemetry.iter().filter(|b| b.phase == phase.as_str()).count();
+        let mut row = BatchTelemetry::new(
+            phase,
+            index,
+            entry_count,
+            started_at,
+            start_time.elapsed(),
+            metrics.take(),
+            service.config.get_pricing(),
+        );
+        row.issues = ValidationIssue::histogram(issues);
+        service.report_batch(&row);
+        telemetry.push(row);
+    }
+
+    /// Retime the translated document if retiming is configured.
+    ///
+    /// Kept out of `translate` because it renumbers entries; callers that track
+    /// entries by sequence number should record them before retiming.
+    pub fn retime(&self, doc: &mut SubtitleDocument) -> Option<RetimingReport> {
+        self.config
+            .retiming
+            .as_ref()
+            .map(|config| RetimingEngine::new(config.clone()).retime(doc))
+    }
+
+    /// Run the LLM semantic check if semantic validation is enabled.
+    ///
+    /// Every translated entry is checked with the semantic validation prompt
+    /// (or its template override); divergent entries are added to `report`.
+    /// Returns the number of divergent entries.
+    pub async fn semantic_validate(
+        &self,
+        service: &TranslationService,
+        doc: &SubtitleDocument,
+        report: &mut ValidationReport,
+    ) -> Result<Option<usize>> {
+        if !self.config.validation_config.enable_semantic_validation {
+            return Ok(None);
+        }
+
+        let template = self
+            .config
+            .translation_config
+            .prompt_templates
+            .get(PromptKind::SemanticValidation)
+            .cloned();
+        let validator = SemanticValidator::new(SemanticValidationConfig::default()).with_prompt_template(template);
+
+        let mut divergent = 0;
+        for entry in &doc.entries {
+            let Some(translated) = entry.translated_text.as_deref() else {
+                continue;
+            };
+
+            match validator
+                .validate(
+                    service,
+                    &entry.original_text,
+                    translated,
+                    &self.config.source_language,
+                    &self.config.target_language,
+                )
+                .await
+            {
+                Ok(result) => {
+                    if !result.passed() {
+                        divergent += 1;
+                    }
+                    report.add_semantic_result(entry.id, result);
+                }
+                Err(e) => warn!("Semantic check of entry {} failed: {}", entry.id, e),
+            }
+        }
+        report.calculate_score();
+
+        Ok(Some(divergent))
+    }
+
+    /// Run the back-translation check if it is configured.
+    ///
+    /// Entries flagged in `report` are always checked, plus a sample of the rest.
+    /// Divergent entries are added to `report` and, if enabled, retranslated
+    /// with the divergence as feedback and checked again, so `report` keeps only
+    /// the divergences the retranslation did not fix. `back_service` may use
+    /// another provider.
+    pub async fn back_translate(
+        &self,
+        service: &TranslationService,
+        back_service: &TranslationService,
+        doc: &mut SubtitleDocument,
+        report: &mut ValidationReport,
+    ) -> Result<Option<BackTranslationReport>> {
+        let Some(ref config) = self.config.back_translation else {
+            return Ok(None);
+        };
+
+        let translator = BackTranslator::new(config.clone());
+        let flagged: HashSet<usize> = report.issues.iter().map(|i| i.entry_id()).collect();
+        let entry_ids = translator.select_entries(doc, &flagged);
+
+        let mut back_report = translator
+            .check(
+                back_service,
+                doc,
+                &entry_ids,
+                &self.config.source_language,
+                &self.config.target_language,
+            )
+            .await;
+        report.add_back_translation(&back_report);
+
+        let divergent: Vec<usize> = back_report.divergent().map(|r| r.entry_id).collect();
+        if config.retranslate && !divergent.is_empty() {
+            back_report.retranslated = self.retranslate_with_feedback(service, doc, report, &divergent).await?;
+
+            let recheck = translator
+                .check(
+                    back_service,
+                    doc,
+                    &divergent,
+                    &self.config.source_language,
+                    &self.config.target_language,
+                )
+                .await;
+            report.update_back_translation(&recheck);
+            back_report.resolved = recheck.results.iter().filter(|r| !r.is_divergent()).count();
+        }
+
+        Ok(Some(back_report))
+    }
+
+    /// Run the LLM judge if it is configured.
+    ///
+    /// Annotations of entries scoring below the threshold are added to `report`
+    /// and, if enabled, those entries are retranslated with the annotations as
+    /// feedback. `judge_service` may use another provider or model.
+    pub async fn judge(
+        &self,
+        service: &TranslationService,
+        judge_service: &TranslationService,
+        doc: &mut SubtitleDocument,
+        report: &mut ValidationReport,
+    ) -> Result<Option<JudgeReport>> {
+        let Some(ref config) = self.config.judge else {
+            return Ok(None);
+        };
+
+        let template = self.config.translation_config.prompt_templates.get(PromptKind::Judge).cloned();
+        let judge = LlmJudge::new(config.clone()).with_prompt_template(template);
+        let mut judge_report = judge
+            .judge_document(judge_service, doc, &self.config.source_language, &self.config.target_language)
+            .await;
+        report.add_judge_result(&judge_report, config.min_entry_score);
+
+        let below = judge_report.entries_below(config.min_entry_score);
+        if config.retranslate && !below.is_empty() {
+            judge_report.retranslated = self.retranslate_with_feedback(service, doc, report, &below).await?;
+        }
+
+        Ok(Some(judge_report))
+    }
+
+    /// Retranslate entries with the failure reasons recorded for them in `report`.
+    async fn retranslate_with_feedback(
+        &self,
+        service: &TranslationService,
+        doc: &mut SubtitleDocument,
+        report: &ValidationReport,
+        entry_ids: &[usize],
+    ) -> Result<usize> {
+        let validation = &self.config.validation_config;
+        let mut feedback = report.failure_reasons_by_entry(validation.max_length_ratio, validation.min_length_ratio);
+        feedback.retain(|id, _| entry_ids.contains(id));
+
+        let updated = self
+            .translation_pass
+            .retranslate_entries(
+                service,
+                doc,
+                &self.config.source_language,
+                &self.config.target_language,
+                &feedback,
+            )
+            .await?;
+
+        // Validate the retranslated texts so their revisions carry an outcome
+        self.validation_pass.validate(doc).record_outcomes(doc);
+
+        Ok(updated)
     }
 
     /// Run only the analysis pass.
@@ -536,6 +913,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
     fn test_translationPipeline_analyze_shouldUpdateDocument() {
         let mut doc = create_test_document(10);
 
@@ -560,4 +938,22 @@ mod tests {
 
         assert_eq!(report.entries_validated, 5);
     }
+
+    #[test]
+    fn test_translationPipeline_retime_shouldOnlyRunWhenConfigured() {
+        let mut doc = create_test_document(3);
+        doc.entries[2].set_translation("Une ligne de test nettement plus longue que l'original".to_string(), Some(0.9));
+
+        let pipeline = TranslationPipeline::for_languages("en", "fr");
+        assert!(pipeline.retime(&mut doc).is_none());
+
+        let config = PipelineConfig::new("en", "fr").with_retiming(RetimingConfig {
+            max_cps: 10.0,
+            ..Default::default()
+        });
+        let report = TranslationPipeline::new(config).retime(&mut doc).unwrap();
+
+        assert!(report.has_changes());
+        assert_eq!(report.entries_after, 3);
+    }
 }
@@ -6,15 +6,28 @@
  * It uses sliding window context to maintain narrative coherence.
  */
 
+use std::collections::HashMap;
+use std::time::Instant;
+
 use anyhow::{anyhow, Result};
+use chrono::Utc;
+use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::translation::context::{ContextWindow, ContextWindowConfig};
+use crate::translation::context::{
+    ContextWindow, ContextWindowConfig, ContextWindowExt, HistorySummarizer, HistorySummary,
+    SummarizationConfig, TermPolicyEnforcer,
+};
+use crate::translation::cache::CacheContext;
 use crate::translation::core::TranslationService;
-use crate::translation::document::{DocumentEntry, Glossary, SubtitleDocument};
+use crate::translation::quality::errors::TranslationErrorKind;
+use crate::translation::telemetry::BatchTelemetry;
+use crate::translation::document::{ClassAction, DocumentEntry, Glossary, RevisionOrigin, SubtitleDocument};
+use crate::translation::pipeline::orchestrator::PipelinePhase;
 use crate::translation::pipeline::validation_pass::FailureReason;
 use crate::translation::prompts::{
-    TranslatedEntry, TranslationPromptBuilder, TranslationResponse,
+    PromptKind, PromptTemplates, StyleGuide, TranslatedEntry, TranslationPromptBuilder,
+    TranslationResponse,
 };
 
 /// Configuration for the translation pass.
@@ -34,6 +47,15 @@ pub struct TranslationPassConfig {
 
     /// Custom translation instructions
     pub custom_instructions: Option<String>,
+
+    /// Configuration for rolling history summaries
+    pub summarization_config: SummarizationConfig,
+
+    /// Per-show style guide rendered into the prompt
+    pub style_guide: Option<StyleGuide>,
+
+    /// User-supplied prompt templates (empty = built-in prompts)
+    pub prompt_templates: PromptTemplates,
 }
 
 impl Default for TranslationPassConfig {
@@ -44,6 +66,9 @@ impl Default for TranslationPassConfig {
             accept_glossary_updates: true,
             use_extractive_fallback: true,
             custom_instructions: None,
+            summarization_config: SummarizationConfig::default(),
+            style_guide: None,
+            prompt_templates: PromptTemplates::default(),
         }
     }
 }
@@ -57,6 +82,9 @@ impl TranslationPassConfig {
             accept_glossary_updates: false,
             use_extractive_fallback: true,
             custom_instructions: None,
+            summarization_config: SummarizationConfig::default(),
+            style_guide: None,
+            prompt_templates: PromptTemplates::default(),
         }
     }
 
@@ -68,6 +96,9 @@ impl TranslationPassConfig {
             accept_glossary_updates: true,
             use_extractive_fallback: true,
             custom_instructions: None,
+            summarization_config: SummarizationConfig::default(),
+            style_guide: None,
+            prompt_templates: PromptTemplates::default(),
         }
     }
 
@@ -76,6 +107,18 @@ impl TranslationPassConfig {
         self.custom_instructions = Some(instructions.to_string());
         self
     }
+
+    /// Set the style guide for translation.
+    pub fn with_style_guide(mut self, guide: StyleGuide) -> Self {
+        self.style_guide = Some(guide);
+        self
+    }
+
+    /// Set user-supplied prompt templates.
+    pub fn with_prompt_templates(mut self, templates: PromptTemplates) -> Self {
+        self.prompt_templates = templates;
+        self
+    }
 }
 
 /// Result of translating a single batch.
@@ -129,15 +172,32 @@ impl BatchResult {
     }
 }
 
+/// Callback receiving each rolling history summary as soon as it is produced.
+pub type SummaryCallback = Box<dyn Fn(&HistorySummary) + Send + Sync>;
+
 /// Translation pass for translating document entries.
 pub struct TranslationPass {
     config: TranslationPassConfig,
+    summarizer: HistorySummarizer,
+    summary_callback: Option<SummaryCallback>,
 }
 
 impl TranslationPass {
     /// Create a new translation pass with the given configuration.
     pub fn new(config: TranslationPassConfig) -> Self {
-        Self { config }
+        let summarizer = HistorySummarizer::new(config.summarization_config.clone())
+            .with_prompt_template(config.prompt_templates.get(PromptKind::Summarization).cloned());
+        Self {
+            config,
+            summarizer,
+            summary_callback: None,
+        }
+    }
+
+    /// Hand each history summary to `callback` as soon as it is produced.
+    pub fn with_summary_callback(mut self, callback: SummaryCallback) -> Self {
+        self.summary_callback = Some(callback);
+        self
     }
 
     /// Create a translation pass with default configuration.
@@ -156,13 +216,14 @@ impl TranslationPass {
         // Build the prompt
         let builder = self.build_prompt_from_window(window);
         let (system_prompt, user_prompt) = builder.build();
+        let cache_context = Self::cache_context(service, window);
 
         // Try translation with retries
         let mut last_error = None;
         let mut retries = 0;
 
         while retries <= self.config.max_retries {
-            match self.attempt_translation(service, &system_prompt, &user_prompt).await {
+            match self.attempt_translation(service, &system_prompt, &user_prompt, &cache_context).await {
                 Ok(response) => {
                     let mut result = BatchResult::new(response.translations, entry_ids.clone());
                     result.retries_used = retries;
@@ -215,15 +276,21 @@ impl TranslationPass {
         let (system_prompt, user_prompt) = builder.build();
 
         // Append feedback instructions to the prompt
-        let feedback_section = self.build_feedback_section(failure_reasons);
-        let enhanced_user_prompt = if feedback_section.is_empty() {
-            user_prompt
-        } else {
-            format!("{}\n\n{}", user_prompt, feedback_section)
+        let enhanced_user_prompt = match self.render_feedback_template(&user_prompt, failure_reasons) {
+            Some(prompt) => prompt,
+            None => {
+                let feedback_section = self.build_feedback_section(failure_reasons);
+                if feedback_section.is_empty() {
+                    user_prompt
+                } else {
+                    format!("{}\n\n{}", user_prompt, feedback_section)
+                }
+            }
         };
 
         // Attempt translation with enhanced prompt
-        match self.attempt_translation(service, &system_prompt, &enhanced_user_prompt).await {
+        let cache_context = Self::cache_context(service, window);
+        match self.attempt_translation(service, &system_prompt, &enhanced_user_prompt, &cache_context).await {
             Ok(response) => {
                 let mut result = BatchResult::new(response.translations, entry_ids.clone());
 
@@ -252,6 +319,27 @@ impl TranslationPass {
         }
     }
 
+    /// Render the user's feedback-retry template, if one is configured.
+    fn render_feedback_template(&self, user_prompt: &str, failure_reasons: &[FailureReason]) -> Option<String> {
+        let feedback: Vec<serde_json::Value> = failure_reasons
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "entry_id": r.entry_id(),
+                    "instruction": r.to_feedback_instruction(),
+                })
+            })
+            .collect();
+
+        self.config.prompt_templates.render(
+            PromptKind::FeedbackRetry,
+            &serde_json::json!({
+                "user_prompt": user_prompt,
+                "feedback": feedback,
+            }),
+        )
+    }
+
     /// Build a feedback section from failure reasons
     fn build_feedback_section(&self, failure_reasons: &[FailureReason]) -> String {
         if failure_reasons.is_empty() {
@@ -297,6 +385,27 @@ impl TranslationPass {
             builder = builder.with_custom_instructions(instructions);
         }
 
+        if let Some(ref guide) = self.config.style_guide {
+            builder = builder.with_style_guide(guide);
+        }
+
+        let term_instructions = TermPolicyEnforcer::new(&window.term_policy)
+            .instructions(window.current_batch.iter().map(|e| e.text.as_str()));
+        if !term_instructions.is_empty() {
+            builder = builder.with_term_instructions(term_instructions);
+        }
+
+        let class_instructions = window
+            .class_policy
+            .instructions(window.current_batch.iter().map(|e| e.class));
+        if !class_instructions.is_empty() {
+            builder = builder.with_class_instructions(class_instructions);
+        }
+
+        if !self.config.prompt_templates.is_empty() {
+            builder = builder.with_templates(&self.config.prompt_templates);
+        }
+
         // Build entries to translate from window
         let entries_to_translate: Vec<DocumentEntry> = window
             .current_batch
@@ -310,6 +419,7 @@ impl TranslationPass {
                 scene_id: None,
                 formatting: Vec::new(),
                 confidence: None,
+                class: we.class,
             })
             .collect();
 
@@ -328,28 +438,40 @@ impl TranslationPass {
                 scene_id: None,
                 formatting: Vec::new(),
                 confidence: None,
+                class: we.class,
             })
             .collect();
 
         builder.with_lookahead(&lookahead_entries)
     }
 
+    /// Cache context of a window: speaker of the batch, formality and the surrounding lines.
+    fn cache_context(service: &TranslationService, window: &ContextWindow) -> CacheContext {
+        let lines: Vec<&str> = window.current_batch.iter().map(|e| e.text.as_str()).collect();
+        let previous: Vec<&str> = window.recent_entries.iter().map(|e| e.original.as_str()).collect();
+        let next: Vec<&str> = window.lookahead_entries.iter().map(|e| e.text.as_str()).collect();
+        service.cache_context(&lines, &previous, &next)
+    }
+
     /// Attempt a single translation request.
     async fn attempt_translation(
         &self,
         service: &TranslationService,
         system_prompt: &str,
         user_prompt: &str,
+        cache_context: &CacheContext,
     ) -> Result<TranslationResponse> {
         // Build combined prompt for translation
         let combined_prompt = format!("{}\n\n{}", system_prompt, user_prompt);
 
         // Use the translation service
-        let response =
-            service.translate_text(&combined_prompt, "prompt", "json_response").await?;
+        let (response, _) = service
+            .translate_prompt_in_context(&combined_prompt, cache_context, None)
+            .await?;
 
-        // Parse the JSON response
+        // Parse the JSON response; an unparsable response is retried
         self.parse_translation_response(&response)
+            .inspect_err(|_| service.record_retries(TranslationErrorKind::ParseError, 1))
     }
 
     /// Parse a JSON translation response.
@@ -415,21 +537,102 @@ impl TranslationPass {
     }
 
     /// Apply batch results to a document.
+    ///
+    /// Translations are post-processed according to the document's class policy,
+    /// so kept-original entries get their source text even if the model left them out.
     pub fn apply_batch_result(&self, doc: &mut SubtitleDocument, result: &BatchResult) {
+        self.apply_batch_result_as(doc, result, RevisionOrigin::Translation, None);
+    }
+
+    /// Apply batch results, recording each new text as a revision with the given origin.
+    fn apply_batch_result_as(
+        &self,
+        doc: &mut SubtitleDocument,
+        result: &BatchResult,
+        origin: RevisionOrigin,
+        detail: Option<String>,
+    ) {
+        let class_policy = doc.class_policy;
+        let mut applied = Vec::new();
+
         for translation in &result.translations {
             if let Some(entry) = doc.entries.iter_mut().find(|e| e.id == translation.id) {
                 if !translation.translated.is_empty() {
-                    entry.set_translation(translation.translated.clone(), translation.confidence);
+                    let text = class_policy.post_process(entry.class, &entry.original_text, &translation.translated);
+                    entry.set_translation(text, translation.confidence);
+                    applied.push(entry.id);
                 }
             }
         }
 
+        for id in &result.entry_ids {
+            if let Some(entry) = doc.entries.iter_mut().find(|e| e.id == *id)
+                && entry.translated_text.is_none()
+                && class_policy.action_for(entry.class) == ClassAction::KeepOriginal
+            {
+                entry.set_translation(entry.original_text.clone(), Some(1.0));
+                applied.push(entry.id);
+            }
+        }
+
+        for id in applied {
+            doc.record_revision(id, origin, detail.clone());
+        }
+
         // Apply glossary updates
         if !result.glossary_updates.is_empty() {
             doc.glossary.merge(&result.glossary_updates);
         }
     }
 
+    /// Retranslate individual entries, each with the feedback recorded for it.
+    ///
+    /// Each entry gets its own single-entry window so the surrounding
+    /// translations still serve as context. Returns the number of entries updated.
+    pub async fn retranslate_entries(
+        &self,
+        service: &TranslationService,
+        doc: &mut SubtitleDocument,
+        source_language: &str,
+        target_language: &str,
+        feedback: &HashMap<usize, Vec<FailureReason>>,
+    ) -> Result<usize> {
+        let window_config = ContextWindowConfig {
+            batch_size: 1,
+            ..self.config.window_config.clone()
+        };
+
+        let mut entry_ids: Vec<usize> = feedback.keys().copied().collect();
+        entry_ids.sort();
+
+        let mut updated = 0;
+        for entry_id in entry_ids {
+            let Some(position) = doc.entries.iter().position(|e| e.id == entry_id) else {
+                continue;
+            };
+            let previous = doc.entries[position].translated_text.clone();
+            let instructions: Vec<String> =
+                feedback[&entry_id].iter().map(|r| r.to_feedback_instruction()).collect();
+
+            // The text being replaced failed a check, whatever validation said before
+            if let Some(revision) = doc.latest_revision_mut(entry_id) {
+                revision.set_outcome(&instructions);
+            }
+
+            let window = doc.window_at(position, &window_config, source_language, target_language);
+            let result = self
+                .translate_with_feedback_retry(service, &window, &feedback[&entry_id])
+                .await?;
+            self.apply_batch_result_as(doc, &result, RevisionOrigin::FeedbackRetry, Some(instructions.join("; ")));
+
+            if doc.entries[position].translated_text != previous {
+                updated += 1;
+            }
+        }
+
+        Ok(updated)
+    }
+
     /// Translate a full document using sliding windows.
     pub async fn translate_document(
         &self,
@@ -446,26 +649,46 @@ impl TranslationPass {
             return Ok(stats);
         }
 
-        // Create context windows
-        let windows: Vec<ContextWindow> = {
-            use crate::translation::context::ContextWindowExt;
-            doc.context_windows(
-                self.config.window_config.clone(),
-                source_language,
-                target_language,
-            )
-            .collect()
-        };
+        let window_config = &self.config.window_config;
+        let batch_size = window_config.batch_size.max(1);
+        stats.total_batches = total_entries.div_ceil(batch_size);
+
+        // Windows are built lazily so each one sees the translations and
+        // summaries produced by the batches before it
+        for batch_idx in 0..stats.total_batches {
+            let started_at = Utc::now();
+            let start_time = Instant::now();
+            let position = batch_idx * batch_size;
+            let mut window = doc.window_at(position, window_config, source_language, target_language);
+
+            if window.needs_summarization(window_config) {
+                stats.summaries_generated += self.summarize_history(service, doc, &window).await;
+                window = doc.window_at(position, window_config, source_language, target_language);
+            }
 
-        stats.total_batches = windows.len();
+            if let Some(summary) = self.rolling_history_summary(&doc.history_summaries) {
+                window = window.with_history_summary(summary.text);
+            }
 
-        // Process each window
-        for (batch_idx, window) in windows.into_iter().enumerate() {
             let result = self.translate_batch(service, &window).await?;
 
             // Apply results
             self.apply_batch_result(doc, &result);
 
+            if let Some(metrics) = service.metrics() {
+                let telemetry = BatchTelemetry::new(
+                    PipelinePhase::Translation,
+                    batch_idx,
+                    result.entry_ids.len(),
+                    started_at,
+                    start_time.elapsed(),
+                    metrics.take(),
+                    service.config.get_pricing(),
+                );
+                service.report_batch(&telemetry);
+                stats.batches.push(telemetry);
+            }
+
             // Update stats
             stats.completed_batches += 1;
             stats.total_entries_translated += result.translations.len();
@@ -483,6 +706,70 @@ impl TranslationPass {
 
         Ok(stats)
     }
+
+    /// Build the exact system and user prompts for one batch without calling a provider.
+    ///
+    /// Uses the summaries already stored on the document; history that would be
+    /// summarized by the provider during a real run is not summarized here.
+    pub fn preview_prompts(
+        &self,
+        doc: &SubtitleDocument,
+        batch_idx: usize,
+        source_language: &str,
+        target_language: &str,
+    ) -> Option<(String, String)> {
+        let window_config = &self.config.window_config;
+        let position = batch_idx * window_config.batch_size.max(1);
+        if position >= doc.entries.len() {
+            return None;
+        }
+
+        let mut window = doc.window_at(position, window_config, source_language, target_language);
+        if let Some(summary) = self.rolling_history_summary(&doc.history_summaries) {
+            window = window.with_history_summary(summary.text);
+        }
+
+        Some(self.build_prompt_from_window(&window).build())
+    }
+
+    /// Summarize history that scrolled out of the window, appending the
+    /// summaries to the document. Returns the number of summaries created.
+    async fn summarize_history(
+        &self,
+        service: &TranslationService,
+        doc: &mut SubtitleDocument,
+        window: &ContextWindow,
+    ) -> usize {
+        let mut summaries = Vec::new();
+
+        for chunk in doc.entries[window.unsummarized_history.clone()].chunks(self.summarizer.entries_per_summary()) {
+            let summary = self.summarizer.summarize_abstractive(service, chunk).await;
+            if !summary.text.is_empty() {
+                debug!(
+                    "Summarized entries {}-{} for rolling history",
+                    summary.start_entry_id, summary.end_entry_id
+                );
+                if let Some(ref callback) = self.summary_callback {
+                    callback(&summary);
+                }
+                summaries.push(summary);
+            }
+        }
+
+        let count = summaries.len();
+        doc.history_summaries.extend(summaries);
+        count
+    }
+
+    /// Combine the most recent history summaries into one prompt summary.
+    fn rolling_history_summary(&self, summaries: &[HistorySummary]) -> Option<HistorySummary> {
+        if summaries.is_empty() {
+            return None;
+        }
+
+        let recent = self.summarizer.recent_summaries(summaries);
+        Some(self.summarizer.combine_summaries(recent))
+    }
 }
 
 impl Default for TranslationPass {
@@ -508,6 +795,12 @@ pub struct TranslationStats {
 
     /// Number of times fallback extraction was used
     pub fallback_used_count: usize,
+
+    /// Number of rolling history summaries generated
+    pub summaries_generated: usize,
+
+    /// Telemetry of each batch, when the service records metrics
+    pub batches: Vec<BatchTelemetry>,
 }
 
 impl TranslationStats {
@@ -560,6 +853,59 @@ mod tests {
         assert!(config.use_extractive_fallback);
     }
 
+    #[test]
+    fn test_translationPass_previewPrompts_shouldRenderRequestedBatch() {
+        use crate::subtitle_processor::SubtitleEntry;
+        use crate::translation::prompts::engine::Template;
+
+        let entries: Vec<SubtitleEntry> = (1..=25)
+            .map(|i| SubtitleEntry::new(i, i as u64 * 1000, i as u64 * 1000 + 900, format!("Line {}", i)))
+            .collect();
+        let doc = SubtitleDocument::from_entries(entries, "en");
+
+        let templates = PromptTemplates::new().with_template(
+            PromptKind::User,
+            Template::parse("{% for entry in entries %}{{ entry.id }} {% endfor %}").unwrap(),
+        );
+        let mut config = TranslationPassConfig::default().with_prompt_templates(templates);
+        config.window_config.batch_size = 10;
+        let pass = TranslationPass::new(config);
+
+        let (system, user) = pass.preview_prompts(&doc, 1, "en", "fr").unwrap();
+
+        assert!(system.contains("en to fr"));
+        assert_eq!(user.trim(), "11 12 13 14 15 16 17 18 19 20");
+        assert!(pass.preview_prompts(&doc, 3, "en", "fr").is_none());
+    }
+
+    #[test]
+    fn test_translationPass_applyBatchResult_shouldApplyClassPolicy() {
+        use crate::subtitle_processor::SubtitleEntry;
+        use crate::translation::document::{ClassPolicy, EntryClass};
+
+        let entries = vec![
+            SubtitleEntry::new(1, 0, 1000, "♪ Hello ♪".to_string()),
+            SubtitleEntry::new(2, 1000, 2000, "EXIT".to_string()),
+            SubtitleEntry::new(3, 2000, 3000, "Let's go.".to_string()),
+        ];
+        let mut doc = SubtitleDocument::from_entries(entries, "en");
+        doc.classify_entries();
+        doc.class_policy = ClassPolicy::default()
+            .with_action(EntryClass::Lyric, ClassAction::KeepOriginal)
+            .with_action(EntryClass::Sign, ClassAction::Bilingual);
+
+        let translations = vec![
+            TranslatedEntry { id: 2, translated: "SORTIE".to_string(), confidence: None },
+            TranslatedEntry { id: 3, translated: "On y va.".to_string(), confidence: None },
+        ];
+        let pass = TranslationPass::with_defaults();
+        pass.apply_batch_result(&mut doc, &BatchResult::new(translations, vec![1, 2, 3]));
+
+        assert_eq!(doc.entries[0].translated_text.as_deref(), Some("♪ Hello ♪"));
+        assert_eq!(doc.entries[1].translated_text.as_deref(), Some("EXIT\nSORTIE"));
+        assert_eq!(doc.entries[2].translated_text.as_deref(), Some("On y va."));
+    }
+
     #[test]
     fn test_batchResult_isComplete_shouldDetectMissingTranslations() {
         let translations = vec![
@@ -676,6 +1022,29 @@ Hope this helps!"#;
         assert!(feedback.contains("John"));
     }
 
+    #[test]
+    fn test_translationPass_rollingHistorySummary_shouldCombineRecentSummaries() {
+        let pass = TranslationPass::with_defaults();
+        let summaries = vec![
+            HistorySummary::new("Anna arrives in Paris.".to_string(), 1, 50, 50),
+            HistorySummary::new("She meets Luc at the station.".to_string(), 51, 100, 50),
+        ];
+
+        let combined = pass.rolling_history_summary(&summaries).unwrap();
+
+        assert!(combined.text.contains("Anna arrives"));
+        assert!(combined.text.contains("meets Luc"));
+        assert_eq!(combined.start_entry_id, 1);
+        assert_eq!(combined.end_entry_id, 100);
+    }
+
+    #[test]
+    fn test_translationPass_rollingHistorySummary_withNoSummaries_shouldReturnNone() {
+        let pass = TranslationPass::with_defaults();
+
+        assert!(pass.rolling_history_summary(&[]).is_none());
+    }
+
     #[test]
     fn test_translationPass_buildFeedbackSection_empty_shouldReturnEmpty() {
         let pass = TranslationPass::with_defaults();
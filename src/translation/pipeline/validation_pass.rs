@@ -6,13 +6,24 @@
  * - Length ratio: Check translated text length is reasonable
  * - Formatting: Ensure formatting tags are preserved
  * - Glossary consistency: Verify terminology is consistent
+ * - Term policy: Verify honorifics and cultural terms follow the policy
+ * - Style guide: Verify translations follow the show's style rules
  * - Timecode integrity: Verify timecodes are unchanged
  */
 
-use crate::translation::context::{ConsistencyIssue, GlossaryEnforcer};
-use crate::translation::document::{DocumentEntry, FormattingTag, SubtitleDocument};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::translation::context::{ConsistencyIssue, GlossaryEnforcer, TermPolicyEnforcer};
+use crate::translation::document::{DocumentEntry, FormattingTag, RevisionOrigin, SubtitleDocument};
+use crate::translation::prompts::StyleGuide;
+use crate::translation::quality::back_translation::BackTranslationReport;
+use crate::translation::quality::judge::{JudgeReport, MqmAnnotation, MqmSeverity};
+use crate::translation::quality::repair::{RepairConfig, RepairEngine};
 use crate::translation::quality::semantic::{SemanticIssue, SemanticValidationResult};
 
+/// Share of the judge's MQM score in the quality score when the judge ran
+const MQM_SCORE_WEIGHT: f32 = 0.3;
+
 /// Configuration for the validation pass.
 #[derive(Debug, Clone)]
 pub struct ValidationConfig {
@@ -36,6 +47,12 @@ pub struct ValidationConfig {
 
     /// Whether to run semantic validation (requires SemanticValidator)
     pub enable_semantic_validation: bool,
+
+    /// Per-show style guide whose rules translations are checked against
+    pub style_guide: Option<StyleGuide>,
+
+    /// Repair engine run on every translated entry during auto-repair (None = off)
+    pub repair_config: Option<RepairConfig>,
 }
 
 impl Default for ValidationConfig {
@@ -48,6 +65,8 @@ impl Default for ValidationConfig {
             enable_auto_repair: true,
             min_confidence_threshold: 0.5,
             enable_semantic_validation: false,
+            style_guide: None,
+            repair_config: None,
         }
     }
 }
@@ -63,6 +82,8 @@ impl ValidationConfig {
             enable_auto_repair: true,
             min_confidence_threshold: 0.7,
             enable_semantic_validation: true,
+            style_guide: None,
+            repair_config: None,
         }
     }
 
@@ -76,6 +97,8 @@ impl ValidationConfig {
             enable_auto_repair: true,
             min_confidence_threshold: 0.3,
             enable_semantic_validation: false,
+            style_guide: None,
+            repair_config: None,
         }
     }
 }
@@ -133,6 +156,19 @@ pub enum ValidationIssue {
         confidence: f32,
         issues: Vec<SemanticIssue>,
     },
+
+    /// Error annotated by the LLM judge
+    MqmError {
+        entry_id: usize,
+        annotation: MqmAnnotation,
+    },
+
+    /// Translation breaks a style guide rule
+    StyleGuideViolation {
+        entry_id: usize,
+        rule: String,
+        detail: String,
+    },
 }
 
 /// Structured failure reason for feedback-informed retry
@@ -178,6 +214,22 @@ pub enum FailureReason {
         entry_id: usize,
         issue_description: String,
     },
+    /// Honorific or cultural term did not follow the term policy
+    TermPolicyViolation {
+        entry_id: usize,
+        term: String,
+        expected: String,
+    },
+    /// The judge annotated an MQM error
+    MqmError {
+        entry_id: usize,
+        issue_description: String,
+    },
+    /// A style guide rule was broken
+    StyleGuideViolation {
+        entry_id: usize,
+        detail: String,
+    },
 }
 
 impl FailureReason {
@@ -191,6 +243,9 @@ impl FailureReason {
             FailureReason::DroppedFormatting { entry_id, .. } => *entry_id,
             FailureReason::LowConfidence { entry_id, .. } => *entry_id,
             FailureReason::SemanticDivergence { entry_id, .. } => *entry_id,
+            FailureReason::TermPolicyViolation { entry_id, .. } => *entry_id,
+            FailureReason::MqmError { entry_id, .. } => *entry_id,
+            FailureReason::StyleGuideViolation { entry_id, .. } => *entry_id,
         }
     }
 
@@ -214,11 +269,45 @@ impl FailureReason {
             FailureReason::SemanticDivergence { issue_description, .. } => {
                 format!("Fix semantic issue: {}", issue_description)
             }
+            FailureReason::TermPolicyViolation { term, expected, .. } => {
+                format!("'{}' must be {}", term, expected)
+            }
+            FailureReason::MqmError { issue_description, .. } => {
+                format!("Fix the {}", issue_description)
+            }
+            FailureReason::StyleGuideViolation { detail, .. } => {
+                format!("Follow the style guide (found {})", detail)
+            }
         }
     }
 }
 
 impl ValidationIssue {
+    /// Stable name of the issue type, used in reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValidationIssue::MissingTranslation { .. } => "missing_translation",
+            ValidationIssue::LengthTooLong { .. } => "length_too_long",
+            ValidationIssue::LengthTooShort { .. } => "length_too_short",
+            ValidationIssue::MissingFormatting { .. } => "missing_formatting",
+            ValidationIssue::GlossaryInconsistency { .. } => "glossary_inconsistency",
+            ValidationIssue::LowConfidence { .. } => "low_confidence",
+            ValidationIssue::EmptyTranslation { .. } => "empty_translation",
+            ValidationIssue::SemanticDivergence { .. } => "semantic_divergence",
+            ValidationIssue::MqmError { .. } => "mqm_error",
+            ValidationIssue::StyleGuideViolation { .. } => "style_guide_violation",
+        }
+    }
+
+    /// Count issues by kind.
+    pub fn histogram(issues: &[ValidationIssue]) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        for issue in issues {
+            *histogram.entry(issue.kind().to_string()).or_default() += 1;
+        }
+        histogram
+    }
+
     /// Get the entry ID associated with this issue.
     pub fn entry_id(&self) -> usize {
         match self {
@@ -230,6 +319,8 @@ impl ValidationIssue {
             ValidationIssue::LowConfidence { entry_id, .. } => *entry_id,
             ValidationIssue::EmptyTranslation { entry_id } => *entry_id,
             ValidationIssue::SemanticDivergence { entry_id, .. } => *entry_id,
+            ValidationIssue::MqmError { entry_id, .. } => *entry_id,
+            ValidationIssue::StyleGuideViolation { entry_id, .. } => *entry_id,
         }
     }
 
@@ -264,6 +355,12 @@ impl ValidationIssue {
                     entry_id, confidence, issue_count
                 )
             }
+            ValidationIssue::MqmError { entry_id, annotation } => {
+                format!("Entry {} {}", entry_id, annotation.describe())
+            }
+            ValidationIssue::StyleGuideViolation { entry_id, rule, detail } => {
+                format!("Entry {} breaks style rule {}: {}", entry_id, rule, detail)
+            }
         }
     }
 
@@ -286,6 +383,13 @@ impl ValidationIssue {
                 // Lower confidence = higher severity (closer to 1.0)
                 0.8 + (1.0 - confidence) * 0.2
             }
+            ValidationIssue::MqmError { annotation, .. } => match annotation.severity {
+                MqmSeverity::Neutral => 0.0,
+                MqmSeverity::Minor => 0.3,
+                MqmSeverity::Major => 0.7,
+                MqmSeverity::Critical => 1.0,
+            },
+            ValidationIssue::StyleGuideViolation { .. } => 0.4,
         }
     }
 
@@ -339,6 +443,13 @@ impl ValidationIssue {
                             expected_translation: expected.clone(),
                         })
                     }
+                    ConsistencyIssue::TermPolicyViolation { term, expected, .. } => {
+                        Some(FailureReason::TermPolicyViolation {
+                            entry_id: *entry_id,
+                            term: term.clone(),
+                            expected: expected.clone(),
+                        })
+                    }
                 }
             }
             ValidationIssue::MissingFormatting { entry_id, tag } => {
@@ -364,6 +475,16 @@ impl ValidationIssue {
                     issue_description: description,
                 })
             }
+            ValidationIssue::MqmError { entry_id, annotation } => Some(FailureReason::MqmError {
+                entry_id: *entry_id,
+                issue_description: annotation.describe(),
+            }),
+            ValidationIssue::StyleGuideViolation { entry_id, detail, .. } => {
+                Some(FailureReason::StyleGuideViolation {
+                    entry_id: *entry_id,
+                    detail: detail.clone(),
+                })
+            }
             // Non-retryable issues
             ValidationIssue::MissingTranslation { .. } | ValidationIssue::EmptyTranslation { .. } => None,
         }
@@ -386,6 +507,14 @@ pub enum RepairAction {
         after: String,
     },
 
+    /// Applied repair engine strategies (e.g. line reflow)
+    AppliedRepair {
+        entry_id: usize,
+        description: String,
+        before: String,
+        after: String,
+    },
+
     /// No repair possible
     NoRepairPossible {
         entry_id: usize,
@@ -399,10 +528,21 @@ impl RepairAction {
         match self {
             RepairAction::AddedFormatting { entry_id, .. } => *entry_id,
             RepairAction::AppliedGlossaryCorrection { entry_id, .. } => *entry_id,
+            RepairAction::AppliedRepair { entry_id, .. } => *entry_id,
             RepairAction::NoRepairPossible { entry_id, .. } => *entry_id,
         }
     }
 
+    /// Get the repair strategy that changed the text (None if nothing changed).
+    pub fn strategy(&self) -> Option<String> {
+        match self {
+            RepairAction::AddedFormatting { tag, .. } => Some(format!("added {:?} formatting", tag)),
+            RepairAction::AppliedGlossaryCorrection { .. } => Some("glossary correction".to_string()),
+            RepairAction::AppliedRepair { description, .. } => Some(description.clone()),
+            RepairAction::NoRepairPossible { .. } => None,
+        }
+    }
+
     /// Get a description of the action.
     pub fn description(&self) -> String {
         match self {
@@ -412,6 +552,9 @@ impl RepairAction {
             RepairAction::AppliedGlossaryCorrection { entry_id, before, after } => {
                 format!("Entry {}: '{}' -> '{}'", entry_id, before, after)
             }
+            RepairAction::AppliedRepair { entry_id, before, after, .. } => {
+                format!("Entry {}: '{}' -> '{}'", entry_id, before, after)
+            }
             RepairAction::NoRepairPossible { entry_id, reason } => {
                 format!("Entry {}: {}", entry_id, reason)
             }
@@ -452,6 +595,17 @@ impl RepairResult {
         self.success = false;
         self.unresolved_issues.push(issue);
     }
+
+    /// Get the strategies that changed each entry, in entry order.
+    pub fn strategies_by_entry(&self) -> BTreeMap<usize, Vec<String>> {
+        let mut by_entry = BTreeMap::new();
+        for action in &self.actions {
+            if let Some(strategy) = action.strategy() {
+                by_entry.entry(action.entry_id()).or_insert_with(Vec::new).push(strategy);
+            }
+        }
+        by_entry
+    }
 }
 
 impl Default for RepairResult {
@@ -475,6 +629,9 @@ pub struct ValidationReport {
     /// Overall quality score (0.0 - 1.0)
     pub quality_score: f32,
 
+    /// MQM score (0.0 - 1.0) of the judged translations (None if the judge did not run)
+    pub mqm_score: Option<f32>,
+
     /// Repair result (if auto-repair was attempted)
     pub repair_result: Option<RepairResult>,
 }
@@ -487,6 +644,7 @@ impl ValidationReport {
             entries_validated,
             entries_with_issues: 0,
             quality_score: 1.0,
+            mqm_score: None,
             repair_result: None,
         }
     }
@@ -496,17 +654,21 @@ impl ValidationReport {
         self.issues.push(issue);
     }
 
-    /// Calculate the quality score based on issues.
+    /// Calculate the quality score based on issues, blended with the judge's MQM score if any.
     pub fn calculate_score(&mut self) {
         if self.entries_validated == 0 {
-            self.quality_score = 1.0;
+            self.quality_score = self.mqm_score.unwrap_or(1.0);
             return;
         }
 
         let total_severity: f32 = self.issues.iter().map(|i| i.severity()).sum();
         let max_severity = self.entries_validated as f32;
 
-        self.quality_score = (1.0 - total_severity / max_severity).max(0.0);
+        let issue_score = (1.0 - total_severity / max_severity).max(0.0);
+        self.quality_score = match self.mqm_score {
+            Some(mqm) => issue_score * (1.0 - MQM_SCORE_WEIGHT) + mqm * MQM_SCORE_WEIGHT,
+            None => issue_score,
+        };
 
         // Count unique entries with issues
         let mut entry_ids: Vec<usize> = self.issues.iter().map(|i| i.entry_id()).collect();
@@ -527,12 +689,17 @@ impl ValidationReport {
 
     /// Get a summary of the report.
     pub fn summary(&self) -> String {
+        let mqm = self
+            .mqm_score
+            .map(|score| format!(" (MQM {:.2}%)", score * 100.0))
+            .unwrap_or_default();
         format!(
-            "Validated {} entries: {} issues found, {} entries affected, quality score: {:.2}%",
+            "Validated {} entries: {} issues found, {} entries affected, quality score: {:.2}%{}",
             self.entries_validated,
             self.issues.len(),
             self.entries_with_issues,
-            self.quality_score * 100.0
+            self.quality_score * 100.0,
+            mqm
         )
     }
 
@@ -559,6 +726,23 @@ impl ValidationReport {
         by_entry
     }
 
+    /// Get issue descriptions grouped by entry ID
+    pub fn issues_by_entry(&self) -> std::collections::HashMap<usize, Vec<String>> {
+        let mut by_entry = std::collections::HashMap::new();
+        for issue in &self.issues {
+            by_entry
+                .entry(issue.entry_id())
+                .or_insert_with(Vec::new)
+                .push(issue.description());
+        }
+        by_entry
+    }
+
+    /// Record this report's outcome on the latest revision of each entry.
+    pub fn record_outcomes(&self, doc: &mut SubtitleDocument) {
+        doc.record_validation_outcomes(&self.issues_by_entry());
+    }
+
     /// Add semantic validation result to this report.
     ///
     /// This method allows incorporating pre-computed semantic validation results
@@ -573,6 +757,66 @@ impl ValidationReport {
         }
     }
 
+    /// Add the divergent entries of a back-translation run to this report.
+    pub fn add_back_translation(&mut self, report: &BackTranslationReport) {
+        for result in report.divergent() {
+            self.add_semantic_result(result.entry_id, result.result.clone());
+        }
+        self.calculate_score();
+    }
+
+    /// Replace the back-translation findings of re-checked entries.
+    ///
+    /// Meaning-change divergences recorded for the checked entries are dropped
+    /// and the ones the re-check still finds are added back.
+    pub fn update_back_translation(&mut self, report: &BackTranslationReport) {
+        let checked: HashSet<usize> = report.results.iter().map(|r| r.entry_id).collect();
+        self.issues.retain(|issue| match issue {
+            ValidationIssue::SemanticDivergence { entry_id, issues, .. } => {
+                !checked.contains(entry_id)
+                    || !issues.iter().any(|i| matches!(i, SemanticIssue::MeaningChanged { .. }))
+            }
+            _ => true,
+        });
+        self.add_back_translation(report);
+    }
+
+    /// Add the annotations of entries the judge scored below `min_entry_score`.
+    ///
+    /// Annotations on entries above the threshold stay in the judge report
+    /// only, but every annotation counts towards the MQM part of the score.
+    pub fn add_judge_result(&mut self, report: &JudgeReport, min_entry_score: f32) {
+        if !report.entry_scores.is_empty() {
+            self.mqm_score = Some(report.score());
+        }
+        let below = report.entries_below(min_entry_score);
+        for annotation in report.annotations.iter().filter(|a| below.contains(&a.entry_id)) {
+            self.add_issue(ValidationIssue::MqmError {
+                entry_id: annotation.entry_id,
+                annotation: annotation.clone(),
+            });
+        }
+        self.calculate_score();
+    }
+
+    /// Entry IDs queued for retranslation by the back-translation check or the judge.
+    pub fn retranslation_queue(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self
+            .issues
+            .iter()
+            .filter(|issue| {
+                matches!(
+                    issue,
+                    ValidationIssue::SemanticDivergence { .. } | ValidationIssue::MqmError { .. }
+                )
+            })
+            .map(|issue| issue.entry_id())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
     /// Check if semantic validation is recommended for this report.
     ///
     /// Returns true if there are entries with low confidence that would
@@ -629,8 +873,16 @@ impl ValidationPass {
     pub fn validate_and_repair(&self, doc: &mut SubtitleDocument) -> ValidationReport {
         let mut report = self.validate(doc);
 
-        if self.config.enable_auto_repair && !report.issues.is_empty() {
-            let repair_result = self.auto_repair(doc, &report.issues);
+        let run_engine = self.config.repair_config.is_some();
+        if self.config.enable_auto_repair && (!report.issues.is_empty() || run_engine) {
+            // Texts about to be repaired keep the issues they were flagged with
+            report.record_outcomes(doc);
+
+            let mut repair_result = self.auto_repair(doc, &report.issues);
+            self.run_repair_engine(doc, &mut repair_result);
+            for (entry_id, strategies) in repair_result.strategies_by_entry() {
+                doc.record_revision(entry_id, RevisionOrigin::Repair, Some(strategies.join("; ")));
+            }
             report.repair_result = Some(repair_result);
 
             // Re-validate after repair
@@ -639,6 +891,7 @@ impl ValidationPass {
             report.calculate_score();
         }
 
+        report.record_outcomes(doc);
         report
     }
 
@@ -664,8 +917,8 @@ impl ValidationPass {
             return;
         }
 
-        // Check length ratio
-        if !original.is_empty() {
+        // Check length ratio (kept-original and bilingual entries are exempt)
+        if !original.is_empty() && doc.class_policy.checks_length(entry.class) {
             let ratio = translated.len() as f32 / original.len() as f32;
 
             if ratio > self.config.max_length_ratio {
@@ -695,6 +948,17 @@ impl ValidationPass {
             self.check_glossary_consistency(entry, doc, report);
         }
 
+        // Check style guide rules
+        if let Some(ref guide) = self.config.style_guide {
+            for (rule, detail) in guide.check(translated) {
+                report.add_issue(ValidationIssue::StyleGuideViolation {
+                    entry_id: entry.id,
+                    rule: rule.name().to_string(),
+                    detail,
+                });
+            }
+        }
+
         // Check confidence threshold
         if let Some(confidence) = entry.confidence {
             if confidence < self.config.min_confidence_threshold {
@@ -749,7 +1013,10 @@ impl ValidationPass {
         };
 
         let enforcer = GlossaryEnforcer::new(&doc.glossary);
-        let issues = enforcer.check_consistency(&entry.original_text, translated);
+        let mut issues = enforcer.check_consistency(&entry.original_text, translated);
+        issues.extend(
+            TermPolicyEnforcer::new(&doc.term_policy).check_consistency(&entry.original_text, translated),
+        );
 
         for issue in issues {
             report.add_issue(ValidationIssue::GlossaryInconsistency {
@@ -784,12 +1051,18 @@ impl ValidationPass {
                         }
                     }
                 }
-                ValidationIssue::GlossaryInconsistency { entry_id, .. } => {
+                ValidationIssue::GlossaryInconsistency { entry_id, issue } => {
                     if let Some(entry) = doc.entries.iter_mut().find(|e| e.id == *entry_id) {
                         if let Some(ref mut translated) = entry.translated_text {
-                            let enforcer = GlossaryEnforcer::new(&doc.glossary);
                             let before = translated.clone();
-                            let repaired = enforcer.enforce(&entry.original_text, translated);
+                            let repaired = match issue {
+                                ConsistencyIssue::TermPolicyViolation { .. } => {
+                                    TermPolicyEnforcer::new(&doc.term_policy)
+                                        .enforce(&entry.original_text, translated)
+                                }
+                                _ => GlossaryEnforcer::new(&doc.glossary)
+                                    .enforce(&entry.original_text, translated),
+                            };
                             if repaired != before {
                                 *translated = repaired.clone();
                                 result.add_action(RepairAction::AppliedGlossaryCorrection {
@@ -811,6 +1084,38 @@ impl ValidationPass {
         result
     }
 
+    /// Run the configured repair engine over every translated entry.
+    fn run_repair_engine(&self, doc: &mut SubtitleDocument, result: &mut RepairResult) {
+        let Some(ref config) = self.config.repair_config else {
+            return;
+        };
+        let engine = RepairEngine::with_config(config.clone());
+
+        for entry in &mut doc.entries {
+            let Some(before) = entry.translated_text.clone() else {
+                continue;
+            };
+            let repairs = engine.repair_entry(entry, &doc.glossary);
+            let Some(after) = engine.get_final_text(&repairs).filter(|after| *after != before) else {
+                continue;
+            };
+
+            let description = repairs
+                .iter()
+                .filter(|r| r.success)
+                .map(|r| r.description.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("; ");
+            entry.translated_text = Some(after.clone());
+            result.add_action(RepairAction::AppliedRepair {
+                entry_id: entry.id,
+                description,
+                before,
+                after,
+            });
+        }
+    }
+
     /// Attempt to repair missing formatting by adding tags.
     fn repair_formatting(&self, translated: &str, tag: FormattingTag, original: &str) -> String {
         match tag {
@@ -866,6 +1171,8 @@ impl Default for ValidationPass {
 mod tests {
     use super::*;
     use crate::subtitle_processor::SubtitleEntry;
+    use crate::translation::quality::back_translation::{BackTranslationConfig, BackTranslator};
+    use crate::translation::quality::judge::MqmCategory;
 
     fn create_test_document(entries: Vec<(&str, Option<&str>)>) -> SubtitleDocument {
         let subtitle_entries: Vec<SubtitleEntry> = entries
@@ -977,6 +1284,119 @@ mod tests {
         assert!(report.repair_result.is_some());
     }
 
+    #[test]
+    fn test_validationPass_validateAndRepair_shouldRecordRepairRevision() {
+        let subtitle_entries = vec![SubtitleEntry::new(1, 0, 1000, "<i>Whispered text</i>".to_string())];
+
+        let mut doc = SubtitleDocument::from_entries(subtitle_entries, "en");
+        doc.entries[0].set_translation("Texte chuchoté".to_string(), Some(0.9));
+        doc.record_revision(1, RevisionOrigin::Translation, None);
+
+        ValidationPass::with_defaults().validate_and_repair(&mut doc);
+
+        assert_eq!(doc.revisions.len(), 2);
+        assert_eq!(doc.revisions[0].validation_passed, Some(false));
+        assert!(doc.revisions[0].validation_message.is_some());
+        assert_eq!(doc.revisions[1].origin, RevisionOrigin::Repair);
+        assert_eq!(doc.revisions[1].text, "<i>Texte chuchoté</i>");
+        assert_eq!(doc.revisions[1].detail.as_deref(), Some("added Italic formatting"));
+        assert_eq!(doc.revisions[1].validation_passed, Some(true));
+    }
+
+    #[test]
+    fn test_validationPass_validateAndRepair_shouldEnforceTermPolicy() {
+        let subtitle_entries = vec![SubtitleEntry::new(1, 0, 1000, "田中さん、おはよう".to_string())];
+
+        let mut doc = SubtitleDocument::from_entries(subtitle_entries, "ja");
+        doc.entries[0].set_translation("Good morning, Mr. Tanaka".to_string(), Some(0.9));
+
+        let pass = ValidationPass::with_defaults();
+        let report = pass.validate(&doc);
+        let issue = report
+            .issues
+            .iter()
+            .find(|i| matches!(
+                i,
+                ValidationIssue::GlossaryInconsistency {
+                    issue: ConsistencyIssue::TermPolicyViolation { .. },
+                    ..
+                }
+            ))
+            .expect("term policy violation should be reported");
+        assert!(matches!(
+            issue.to_failure_reason(1.5, 0.3),
+            Some(FailureReason::TermPolicyViolation { entry_id: 1, .. })
+        ));
+
+        let report = pass.validate_and_repair(&mut doc);
+
+        assert_eq!(
+            doc.entries[0].translated_text.as_deref(),
+            Some("Good morning, Tanaka-san")
+        );
+        assert!(report.repair_result.unwrap().actions.iter().any(|a| matches!(
+            a,
+            RepairAction::AppliedGlossaryCorrection { entry_id: 1, .. }
+        )));
+    }
+
+    #[test]
+    fn test_validationPass_validate_withStyleGuide_shouldReportViolations() {
+        use crate::translation::prompts::style_guide::StyleRule;
+
+        let doc = create_test_document(vec![
+            ("Hello!", Some("Bonjour !!")),
+            ("Goodbye.", Some("Au revoir.")),
+        ]);
+        let pass = ValidationPass::new(ValidationConfig {
+            style_guide: Some(StyleGuide {
+                rules: vec![StyleRule::NoDoubledExclamation],
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let report = pass.validate(&doc);
+        let violations: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| matches!(i, ValidationIssue::StyleGuideViolation { .. }))
+            .collect();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].entry_id(), 1);
+        assert!(matches!(
+            violations[0].to_failure_reason(1.5, 0.3),
+            Some(FailureReason::StyleGuideViolation { entry_id: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_validationPass_validateAndRepair_withRepairConfig_shouldReflowLines() {
+        let doc_entries = vec![(
+            "I told you we should have left before the storm.",
+            Some("Je t'avais dit qu'on aurait dû partir avant la tempête de ce soir"),
+        )];
+        let mut doc = create_test_document(doc_entries.clone());
+        let pass = ValidationPass::new(ValidationConfig {
+            repair_config: Some(RepairConfig::reflow_only("fr")),
+            ..Default::default()
+        });
+
+        let report = pass.validate_and_repair(&mut doc);
+
+        let translated = doc.entries[0].translated_text.as_deref().unwrap();
+        assert_eq!(translated.lines().count(), 2);
+        assert!(matches!(
+            report.repair_result.unwrap().actions.as_slice(),
+            [RepairAction::AppliedRepair { entry_id: 1, .. }]
+        ));
+
+        let mut doc = create_test_document(doc_entries);
+        ValidationPass::with_defaults().validate_and_repair(&mut doc);
+        assert_eq!(doc.entries[0].translated_text.as_deref().map(|t| t.lines().count()), Some(1));
+    }
+
     #[test]
     fn test_validationReport_qualityScore_shouldCalculateCorrectly() {
         let mut report = ValidationReport::new(10);
@@ -1174,6 +1594,91 @@ mod tests {
         assert_eq!(report.issues.len(), 0); // Passed results not added
     }
 
+    #[test]
+    fn test_validationReport_addBackTranslation_shouldQueueDivergentEntries() {
+        let translator = BackTranslator::new(BackTranslationConfig::default());
+        let back_report = BackTranslationReport {
+            results: vec![
+                translator.evaluate(1, "We have to go now.", "Il faut partir.", "We must go now."),
+                translator.evaluate(2, "I love you", "Pose l'argent", "Put the money down"),
+            ],
+            ..Default::default()
+        };
+        let mut report = ValidationReport::new(3);
+
+        report.add_back_translation(&back_report);
+
+        assert_eq!(report.retranslation_queue(), vec![2]);
+        assert!(!report.passed());
+        let reasons = report.failure_reasons_by_entry(1.5, 0.3);
+        assert!(reasons[&2][0].to_feedback_instruction().contains("Meaning changed"));
+    }
+
+    #[test]
+    fn test_validationReport_updateBackTranslation_shouldClearResolvedEntries() {
+        let translator = BackTranslator::new(BackTranslationConfig::default());
+        let mut report = ValidationReport::new(3);
+        report.add_back_translation(&BackTranslationReport {
+            results: vec![
+                translator.evaluate(2, "I love you", "Pose l'argent", "Put the money down"),
+                translator.evaluate(3, "Open the door", "Ferme la fenêtre", "Close the window"),
+            ],
+            ..Default::default()
+        });
+
+        report.update_back_translation(&BackTranslationReport {
+            results: vec![
+                translator.evaluate(2, "I love you", "Je t'aime", "I love you"),
+                translator.evaluate(3, "Open the door", "Apporte un café", "Bring a coffee"),
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(report.retranslation_queue(), vec![3]);
+        assert_eq!(report.issues.len(), 1);
+        match &report.issues[0] {
+            ValidationIssue::SemanticDivergence { issues, .. } => match &issues[0] {
+                SemanticIssue::MeaningChanged { translated_meaning, .. } => {
+                    assert_eq!(translated_meaning, "Bring a coffee");
+                }
+                other => panic!("Expected MeaningChanged, got {:?}", other),
+            },
+            other => panic!("Expected SemanticDivergence, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validationReport_addJudgeResult_shouldQueueEntriesBelowThreshold() {
+        let annotation = |entry_id, severity| MqmAnnotation {
+            entry_id,
+            category: MqmCategory::Accuracy,
+            severity,
+            span: Some("déteste".to_string()),
+            description: "love became hate".to_string(),
+        };
+        let judge_report = JudgeReport {
+            annotations: vec![annotation(1, MqmSeverity::Minor), annotation(2, MqmSeverity::Critical)],
+            entry_scores: [(1, 0.9), (2, 0.0)].into_iter().collect(),
+            words: 20,
+            ..Default::default()
+        };
+        let mut report = ValidationReport::new(2);
+
+        report.add_judge_result(&judge_report, 0.7);
+
+        assert_eq!(report.issues.len(), 1);
+        // 11 penalty points over 20 words, blended with the issue score of 0.5
+        assert!((report.mqm_score.unwrap() - 0.45).abs() < 0.001);
+        assert!((report.quality_score - (0.5 * (1.0 - MQM_SCORE_WEIGHT) + 0.45 * MQM_SCORE_WEIGHT)).abs() < 0.001);
+        assert_eq!(report.retranslation_queue(), vec![2]);
+        assert!(!report.passed());
+        let reasons = report.failure_reasons_by_entry(1.5, 0.3);
+        assert_eq!(
+            reasons[&2][0].to_feedback_instruction(),
+            "Fix the critical accuracy error in 'déteste': love became hate"
+        );
+    }
+
     #[test]
     fn test_validationReport_needsSemanticValidation_shouldDetectLowConfidence() {
         let mut report = ValidationReport::new(3);
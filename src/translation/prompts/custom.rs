@@ -0,0 +1,170 @@
+/*!
+ * User-overridable prompt templates.
+ *
+ * A template directory may contain any of the following files; prompts
+ * without a file keep the built-in wording:
+ * - `system.tmpl` - system prompt for the translation pass
+ * - `user.tmpl` - user prompt for a translation batch
+ * - `feedback_retry.tmpl` - user prompt when retrying with validation feedback
+ * - `semantic_validation.tmpl` - semantic equivalence check
+ * - `summarization.tmpl` - rolling history summarization
+ * - `judge.tmpl` - MQM quality judge
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::engine::Template;
+
+/// The prompts a user can override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromptKind {
+    /// System prompt for the translation pass
+    System,
+    /// User prompt for a translation batch
+    User,
+    /// User prompt for a retry with validation feedback
+    FeedbackRetry,
+    /// Semantic validation prompt
+    SemanticValidation,
+    /// History summarization prompt
+    Summarization,
+    /// MQM quality judge prompt
+    Judge,
+}
+
+impl PromptKind {
+    /// All prompt kinds.
+    pub const ALL: [PromptKind; 6] = [
+        PromptKind::System,
+        PromptKind::User,
+        PromptKind::FeedbackRetry,
+        PromptKind::SemanticValidation,
+        PromptKind::Summarization,
+        PromptKind::Judge,
+    ];
+
+    /// File name of the template inside a template directory.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            PromptKind::System => "system.tmpl",
+            PromptKind::User => "user.tmpl",
+            PromptKind::FeedbackRetry => "feedback_retry.tmpl",
+            PromptKind::SemanticValidation => "semantic_validation.tmpl",
+            PromptKind::Summarization => "summarization.tmpl",
+            PromptKind::Judge => "judge.tmpl",
+        }
+    }
+}
+
+impl fmt::Display for PromptKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.file_name().trim_end_matches(".tmpl");
+        write!(f, "{}", name)
+    }
+}
+
+/// A set of user-supplied prompt templates.
+#[derive(Debug, Clone, Default)]
+pub struct PromptTemplates {
+    templates: HashMap<PromptKind, Template>,
+}
+
+impl PromptTemplates {
+    /// Create an empty set (all prompts use the built-in wording).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every template file present in a directory.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Prompt template directory not found: {}",
+                dir.display()
+            ));
+        }
+
+        let mut templates = Self::new();
+        for kind in PromptKind::ALL {
+            let path = dir.join(kind.file_name());
+            if !path.exists() {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read prompt template: {}", path.display()))?;
+            let template = Template::parse(&source)
+                .with_context(|| format!("Invalid prompt template: {}", path.display()))?;
+            templates.templates.insert(kind, template);
+        }
+
+        Ok(templates)
+    }
+
+    /// Set the template for a prompt kind.
+    pub fn with_template(mut self, kind: PromptKind, template: Template) -> Self {
+        self.templates.insert(kind, template);
+        self
+    }
+
+    /// Get the template for a prompt kind, if overridden.
+    pub fn get(&self, kind: PromptKind) -> Option<&Template> {
+        self.templates.get(&kind)
+    }
+
+    /// Render the override for a prompt kind, if one exists.
+    pub fn render(&self, kind: PromptKind, context: &Value) -> Option<String> {
+        self.get(kind).map(|t| t.render(context))
+    }
+
+    /// Prompt kinds that are overridden.
+    pub fn overridden(&self) -> Vec<PromptKind> {
+        PromptKind::ALL
+            .into_iter()
+            .filter(|k| self.templates.contains_key(k))
+            .collect()
+    }
+
+    /// Check whether no prompt is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_promptTemplates_loadDir_shouldLoadPresentFiles() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("system.tmpl"), "Translate {{ source_language }}").unwrap();
+        fs::write(dir.path().join("summarization.tmpl"), "Summarize {{ dialogue }}").unwrap();
+
+        let templates = PromptTemplates::load_dir(dir.path()).unwrap();
+
+        assert_eq!(
+            templates.overridden(),
+            vec![PromptKind::System, PromptKind::Summarization]
+        );
+        assert!(templates.get(PromptKind::User).is_none());
+    }
+
+    #[test]
+    fn test_promptTemplates_loadDir_shouldReportInvalidTemplate() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("user.tmpl"), "{% if entries %}unclosed").unwrap();
+
+        let err = PromptTemplates::load_dir(dir.path()).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("user.tmpl"));
+    }
+}
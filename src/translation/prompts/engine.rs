@@ -0,0 +1,391 @@
+/*!
+ * A small template language for user-supplied prompts.
+ *
+ * Supported syntax:
+ * - `{{ name }}` / `{{ entry.text }}` - variable substitution with dotted paths
+ * - `{{ value | json }}` - filters: `json`, `upper`, `lower`, `trim`, `length`
+ * - `{% if name %}...{% else %}...{% endif %}` - conditionals (`if not name` negates)
+ * - `{% for item in list %}...{% endfor %}` - loops over lists, or over maps as
+ *   `{key, value}` pairs; `loop.index`, `loop.first` and `loop.last` are available
+ * - `{# comment #}` - ignored
+ *
+ * Tags that sit alone on a line are removed together with that line, so
+ * templates can be laid out readably. Missing variables render as empty text.
+ */
+
+use anyhow::{anyhow, Result};
+use serde_json::{Map, Value};
+
+/// A parsed template node.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Expr {
+        path: String,
+        filters: Vec<String>,
+    },
+    If {
+        path: String,
+        negate: bool,
+        then_branch: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+    For {
+        var: String,
+        path: String,
+        body: Vec<Node>,
+    },
+}
+
+/// A lexed template token.
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Expr(String, usize),
+    Tag(String, usize),
+}
+
+/// Token stream consumed by the parser.
+type TokenIter = std::vec::IntoIter<Token>;
+
+/// A closing tag (`else`, `endif`, ...) and its line.
+type EndTag = Option<(String, usize)>;
+
+const FILTERS: &[&str] = &["json", "upper", "lower", "trim", "length"];
+
+/// A compiled prompt template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parse a template from source.
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = lex(source)?;
+        let mut iter = tokens.into_iter();
+        let (nodes, _) = parse_nodes(&mut iter, &[])?;
+        Ok(Self { nodes })
+    }
+
+    /// Render the template against a JSON context object.
+    pub fn render(&self, context: &Value) -> String {
+        let mut out = String::new();
+        let mut scopes: Vec<(String, Value)> = Vec::new();
+        render_nodes(&self.nodes, context, &mut scopes, &mut out);
+        out
+    }
+}
+
+/// Split the source into text, expression and tag tokens.
+fn lex(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < source.len() {
+        let next = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| source[pos..].find(open).map(|i| (pos + i, *open)))
+            .min_by_key(|(i, _)| *i);
+
+        let Some((start, open)) = next else {
+            tokens.push(Token::Text(source[pos..].to_string()));
+            break;
+        };
+
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let line = source[..start].matches('\n').count() + 1;
+        let inner_start = start + open.len();
+        let inner_end = source[inner_start..]
+            .find(close)
+            .map(|i| inner_start + i)
+            .ok_or_else(|| anyhow!("Template error at line {}: unclosed '{}'", line, open))?;
+        let mut end = inner_end + close.len();
+
+        // Standalone block tags swallow their whole line
+        let mut text_end = start;
+        if open != "{{" {
+            let line_start = source[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let line_end = source[end..].find('\n').map(|i| end + i + 1).unwrap_or(source.len());
+            if line_start >= pos
+                && source[line_start..start].trim().is_empty()
+                && source[end..line_end].trim().is_empty()
+            {
+                text_end = line_start;
+                end = line_end;
+            }
+        }
+
+        if text_end > pos {
+            tokens.push(Token::Text(source[pos..text_end].to_string()));
+        }
+
+        let inner = source[inner_start..inner_end].trim().to_string();
+        match open {
+            "{{" => tokens.push(Token::Expr(inner, line)),
+            "{%" => tokens.push(Token::Tag(inner, line)),
+            _ => {}
+        }
+
+        pos = end;
+    }
+
+    Ok(tokens)
+}
+
+/// Parse nodes until one of the `terminators` tags is reached.
+///
+/// Returns the nodes and the terminating tag (if any) with its line.
+fn parse_nodes(iter: &mut TokenIter, terminators: &[&str]) -> Result<(Vec<Node>, EndTag)> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = iter.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Expr(expr, line) => nodes.push(parse_expr(&expr, line)?),
+            Token::Tag(tag, line) => {
+                let words: Vec<&str> = tag.split_whitespace().collect();
+                match words.as_slice() {
+                    ["if", "not", path] | ["if", path] => {
+                        let negate = words.len() == 3;
+                        let (then_branch, end) = parse_nodes(iter, &["else", "endif"])?;
+                        let else_branch = match end {
+                            Some((ref t, _)) if t == "else" => {
+                                let (branch, end) = parse_nodes(iter, &["endif"])?;
+                                expect_end(end, "endif", line)?;
+                                branch
+                            }
+                            other => {
+                                expect_end(other, "endif", line)?;
+                                Vec::new()
+                            }
+                        };
+                        nodes.push(Node::If {
+                            path: path.to_string(),
+                            negate,
+                            then_branch,
+                            else_branch,
+                        });
+                    }
+                    ["for", var, "in", path] => {
+                        let (body, end) = parse_nodes(iter, &["endfor"])?;
+                        expect_end(end, "endfor", line)?;
+                        nodes.push(Node::For {
+                            var: var.to_string(),
+                            path: path.to_string(),
+                            body,
+                        });
+                    }
+                    [keyword] if terminators.contains(keyword) => {
+                        return Ok((nodes, Some((keyword.to_string(), line))));
+                    }
+                    _ => {
+                        return Err(anyhow!("Template error at line {}: unknown tag '{}'", line, tag));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn expect_end(end: EndTag, expected: &str, line: usize) -> Result<()> {
+    match end {
+        Some((tag, _)) if tag == expected => Ok(()),
+        _ => Err(anyhow!(
+            "Template error at line {}: missing '{{% {} %}}'",
+            line,
+            expected
+        )),
+    }
+}
+
+fn parse_expr(expr: &str, line: usize) -> Result<Node> {
+    let mut parts = expr.split('|').map(str::trim);
+    let path = parts.next().unwrap_or_default();
+    if path.is_empty() {
+        return Err(anyhow!("Template error at line {}: empty expression", line));
+    }
+
+    let filters: Vec<String> = parts.map(str::to_string).collect();
+    if let Some(unknown) = filters.iter().find(|f| !FILTERS.contains(&f.as_str())) {
+        return Err(anyhow!("Template error at line {}: unknown filter '{}'", line, unknown));
+    }
+
+    Ok(Node::Expr {
+        path: path.to_string(),
+        filters,
+    })
+}
+
+fn render_nodes(nodes: &[Node], context: &Value, scopes: &mut Vec<(String, Value)>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Expr { path, filters } => {
+                let mut value = lookup(path, context, scopes);
+                for filter in filters {
+                    value = apply_filter(filter, value);
+                }
+                out.push_str(&value_to_string(&value));
+            }
+            Node::If {
+                path,
+                negate,
+                then_branch,
+                else_branch,
+            } => {
+                let truthy = is_truthy(&lookup(path, context, scopes));
+                let branch = if truthy != *negate { then_branch } else { else_branch };
+                render_nodes(branch, context, scopes, out);
+            }
+            Node::For { var, path, body } => {
+                let items: Vec<Value> = match lookup(path, context, scopes) {
+                    Value::Array(items) => items,
+                    Value::Object(map) => map
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let mut pair = Map::new();
+                            pair.insert("key".to_string(), Value::String(key));
+                            pair.insert("value".to_string(), value);
+                            Value::Object(pair)
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                let count = items.len();
+                for (i, item) in items.into_iter().enumerate() {
+                    let mut loop_info = Map::new();
+                    loop_info.insert("index".to_string(), Value::from(i + 1));
+                    loop_info.insert("first".to_string(), Value::Bool(i == 0));
+                    loop_info.insert("last".to_string(), Value::Bool(i + 1 == count));
+
+                    scopes.push(("loop".to_string(), Value::Object(loop_info)));
+                    scopes.push((var.clone(), item));
+                    render_nodes(body, context, scopes, out);
+                    scopes.pop();
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Resolve a dotted path against loop scopes first, then the root context.
+fn lookup(path: &str, context: &Value, scopes: &[(String, Value)]) -> Value {
+    let mut segments = path.split('.');
+    let head = segments.next().unwrap_or_default();
+
+    let mut current = scopes
+        .iter()
+        .rev()
+        .find(|(name, _)| name == head)
+        .map(|(_, value)| value)
+        .or_else(|| context.get(head));
+
+    for segment in segments {
+        current = current.and_then(|value| match value {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => value.get(segment),
+        });
+    }
+
+    current.cloned().unwrap_or(Value::Null)
+}
+
+fn apply_filter(filter: &str, value: Value) -> Value {
+    match filter {
+        "json" => Value::String(serde_json::to_string_pretty(&value).unwrap_or_default()),
+        "upper" => Value::String(value_to_string(&value).to_uppercase()),
+        "lower" => Value::String(value_to_string(&value).to_lowercase()),
+        "trim" => Value::String(value_to_string(&value).trim().to_string()),
+        "length" => Value::from(match &value {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            Value::String(s) => s.chars().count(),
+            _ => 0,
+        }),
+        _ => value,
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string_pretty(value).unwrap_or_default(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().map(|f| f != 0.0).unwrap_or(false),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_template_render_shouldSubstituteVariables() {
+        let template = Template::parse("From {{ source }} to {{ target | upper }}").unwrap();
+        let rendered = template.render(&json!({"source": "en", "target": "fr"}));
+
+        assert_eq!(rendered, "From en to FR");
+    }
+
+    #[test]
+    fn test_template_render_shouldHandleConditionals() {
+        let template =
+            Template::parse("{% if summary %}Story: {{ summary }}{% else %}No story{% endif %}")
+                .unwrap();
+
+        assert_eq!(template.render(&json!({"summary": "A heist"})), "Story: A heist");
+        assert_eq!(template.render(&json!({"summary": ""})), "No story");
+        assert_eq!(template.render(&json!({})), "No story");
+    }
+
+    #[test]
+    fn test_template_render_shouldLoopAndDropStandaloneTagLines() {
+        let source = "Entries:\n{% for entry in entries %}\n{{ loop.index }}. [{{ entry.id }}] {{ entry.text }}\n{% endfor %}\nDone";
+        let template = Template::parse(source).unwrap();
+        let rendered = template.render(&json!({
+            "entries": [{"id": 7, "text": "Hello"}, {"id": 8, "text": "Bye"}]
+        }));
+
+        assert_eq!(rendered, "Entries:\n1. [7] Hello\n2. [8] Bye\nDone");
+    }
+
+    #[test]
+    fn test_template_render_shouldIterateMapsAsPairs() {
+        let template =
+            Template::parse("{% for term in terms %}{{ term.key }}={{ term.value }};{% endfor %}")
+                .unwrap();
+        let rendered = template.render(&json!({"terms": {"ring": "anneau"}}));
+
+        assert_eq!(rendered, "ring=anneau;");
+    }
+
+    #[test]
+    fn test_template_parse_shouldRejectUnbalancedTags() {
+        assert!(Template::parse("{% if a %}open").is_err());
+        assert!(Template::parse("{% endfor %}").is_err());
+        assert!(Template::parse("{{ a | shout }}").is_err());
+        assert!(Template::parse("{{ unclosed").is_err());
+    }
+}
@@ -5,13 +5,20 @@
  * - System prompt templates for different translation scenarios
  * - Dynamic prompt construction based on context
  * - Provider-specific prompt adaptations
+ * - Per-show style guides
+ * - User-overridable prompt templates
  */
 
 #![allow(dead_code)]
 
+pub mod custom;
+pub mod engine;
+pub mod style_guide;
 pub mod templates;
 
 // Re-export types used by pipeline
+pub use custom::{PromptKind, PromptTemplates};
+pub use style_guide::StyleGuide;
 pub use templates::{
     TranslatedEntry, TranslatedEntryContext,
     TranslationPromptBuilder, TranslationResponse,
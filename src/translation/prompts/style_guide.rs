@@ -0,0 +1,266 @@
+/*!
+ * Per-show style guides for translation prompts.
+ *
+ * A style guide captures the conventions translators agree on for a show:
+ * - Default formality and how to render honorifics
+ * - Profanity handling
+ * - Number and unit conventions
+ * - Free-form notes
+ * - Checkable rules (e.g. no doubled exclamation marks, metric units only)
+ *
+ * Guides are loaded from JSON files, rendered into the prompt instructions,
+ * and their checkable rules are enforced by the consistency checker.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Matches quantities expressed in imperial units.
+///
+/// Bare "pounds" is left out as it is as often money as weight; "lb" is not.
+static IMPERIAL_UNIT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b\d+(?:[.,]\d+)?\s*(?:miles?|mph|feet|foot|ft|inch(?:es)?|yards?|lbs?|ounces?|oz|gallons?|°\s?F|fahrenheit)\b",
+    )
+    .unwrap()
+});
+
+/// How profanity in the source should be handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfanityPolicy {
+    /// Translate profanity with equivalent strength
+    #[default]
+    Preserve,
+    /// Use milder equivalents
+    Soften,
+    /// Replace profanity with neutral wording
+    Remove,
+}
+
+impl ProfanityPolicy {
+    /// Prompt instruction for this policy.
+    pub fn instruction(&self) -> &'static str {
+        match self {
+            ProfanityPolicy::Preserve => "Translate profanity with equivalent strength",
+            ProfanityPolicy::Soften => "Soften profanity to milder equivalents",
+            ProfanityPolicy::Remove => "Replace profanity with neutral wording",
+        }
+    }
+}
+
+/// A style rule that can be checked against translated text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum StyleRule {
+    /// Never write "!!" (a single exclamation mark is enough)
+    NoDoubledExclamation,
+
+    /// Quantities must use metric units
+    MetricUnits,
+
+    /// A term that must never appear in translations
+    ForbiddenTerm {
+        term: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+    },
+}
+
+impl StyleRule {
+    /// Short identifier for reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StyleRule::NoDoubledExclamation => "no_doubled_exclamation",
+            StyleRule::MetricUnits => "metric_units",
+            StyleRule::ForbiddenTerm { .. } => "forbidden_term",
+        }
+    }
+
+    /// Prompt instruction for this rule.
+    pub fn instruction(&self) -> String {
+        match self {
+            StyleRule::NoDoubledExclamation => {
+                "Never double exclamation marks; use a single '!'".to_string()
+            }
+            StyleRule::MetricUnits => {
+                "Always express measurements in metric units (km, m, kg, °C)".to_string()
+            }
+            StyleRule::ForbiddenTerm { term, replacement } => match replacement {
+                Some(replacement) => format!("Never use '{}'; use '{}' instead", term, replacement),
+                None => format!("Never use '{}'", term),
+            },
+        }
+    }
+
+    /// Check a translation against this rule, returning a description of the violation.
+    pub fn check(&self, text: &str) -> Option<String> {
+        match self {
+            StyleRule::NoDoubledExclamation => {
+                if text.contains("!!") {
+                    Some("doubled exclamation mark".to_string())
+                } else {
+                    None
+                }
+            }
+            StyleRule::MetricUnits => IMPERIAL_UNIT_REGEX
+                .find(text)
+                .map(|m| format!("imperial unit in '{}'", m.as_str())),
+            StyleRule::ForbiddenTerm { term, .. } => {
+                let pattern = format!(r"(?i)\b{}\b", regex::escape(term));
+                let found = Regex::new(&pattern)
+                    .map(|re| re.is_match(text))
+                    .unwrap_or_else(|_| text.to_lowercase().contains(&term.to_lowercase()));
+                if found {
+                    Some(format!("forbidden term '{}'", term))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Style guide for a show, loaded from a JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleGuide {
+    /// Name of the show or guide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Default formality (e.g. "informal between friends, formal with strangers")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formality: Option<String>,
+
+    /// Profanity handling
+    pub profanity: ProfanityPolicy,
+
+    /// How to render honorifics (e.g. "keep -san and -sama as-is")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub honorifics: Option<String>,
+
+    /// Number formatting conventions (e.g. "spell out numbers under ten")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numbers: Option<String>,
+
+    /// Additional free-form guidance
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+
+    /// Checkable rules
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<StyleRule>,
+}
+
+impl StyleGuide {
+    /// Load a style guide from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read style guide: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse style guide: {}", path.display()))
+    }
+
+    /// Render the guide as a list of prompt instructions.
+    pub fn render_instructions(&self) -> Vec<String> {
+        let mut instructions = Vec::new();
+
+        if let Some(ref formality) = self.formality {
+            instructions.push(format!("Formality: {}", formality));
+        }
+        instructions.push(self.profanity.instruction().to_string());
+        if let Some(ref honorifics) = self.honorifics {
+            instructions.push(format!("Honorifics: {}", honorifics));
+        }
+        if let Some(ref numbers) = self.numbers {
+            instructions.push(format!("Numbers: {}", numbers));
+        }
+        instructions.extend(self.notes.iter().cloned());
+        instructions.extend(self.rules.iter().map(|r| r.instruction()));
+
+        instructions
+    }
+
+    /// Check a translation against all checkable rules.
+    pub fn check(&self, text: &str) -> Vec<(&StyleRule, String)> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.check(text).map(|detail| (rule, detail)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_styleGuide_fromFile_shouldParseJson() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{
+                "name": "Test Show",
+                "formality": "informal between friends",
+                "profanity": "soften",
+                "rules": [
+                    {{"rule": "no_doubled_exclamation"}},
+                    {{"rule": "forbidden_term", "term": "okay", "replacement": "d'accord"}}
+                ]
+            }}"#
+        )
+        .unwrap();
+
+        let guide = StyleGuide::from_file(file.path()).unwrap();
+
+        assert_eq!(guide.name.as_deref(), Some("Test Show"));
+        assert_eq!(guide.profanity, ProfanityPolicy::Soften);
+        assert_eq!(guide.rules.len(), 2);
+    }
+
+    #[test]
+    fn test_styleGuide_renderInstructions_shouldIncludeRules() {
+        let guide = StyleGuide {
+            honorifics: Some("keep -san".to_string()),
+            rules: vec![StyleRule::MetricUnits],
+            ..Default::default()
+        };
+
+        let instructions = guide.render_instructions();
+
+        assert!(instructions.iter().any(|i| i == "Honorifics: keep -san"));
+        assert!(instructions.iter().any(|i| i.contains("metric units")));
+    }
+
+    #[test]
+    fn test_styleRule_check_shouldDetectViolations() {
+        assert!(StyleRule::NoDoubledExclamation.check("Non !!").is_some());
+        assert!(StyleRule::NoDoubledExclamation.check("Non !").is_none());
+
+        assert!(StyleRule::MetricUnits.check("It's 5 miles away").is_some());
+        assert!(StyleRule::MetricUnits.check("C'est à 8 km").is_none());
+
+        let rule = StyleRule::ForbiddenTerm {
+            term: "okay".to_string(),
+            replacement: None,
+        };
+        assert!(rule.check("Okay, on y va").is_some());
+        assert!(rule.check("D'accord, on y va").is_none());
+    }
+
+    #[test]
+    fn test_styleRule_metricUnits_withCurrencyPounds_shouldNotFlag() {
+        assert!(StyleRule::MetricUnits.check("It cost me 20 pounds.").is_none());
+        assert!(StyleRule::MetricUnits.check("Ça coûte 5 pounds").is_none());
+        assert!(StyleRule::MetricUnits.check("He weighs 180 lbs").is_some());
+    }
+}
@@ -6,8 +6,11 @@
  */
 
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-use crate::translation::document::{DocumentEntry, Glossary};
+use crate::translation::document::{DocumentEntry, EntryClass, Glossary};
+use crate::translation::prompts::custom::{PromptKind, PromptTemplates};
+use crate::translation::prompts::style_guide::StyleGuide;
 
 /// System prompt template for subtitle translation.
 #[derive(Debug, Clone)]
@@ -81,6 +84,10 @@ pub struct TranslationPromptBuilder {
     lookahead_entries: Vec<LookaheadEntry>,
     glossary: Option<Glossary>,
     custom_instructions: Option<String>,
+    style_guide: Option<StyleGuide>,
+    term_instructions: Vec<String>,
+    class_instructions: Vec<String>,
+    templates: PromptTemplates,
 }
 
 impl TranslationPromptBuilder {
@@ -95,6 +102,10 @@ impl TranslationPromptBuilder {
             lookahead_entries: Vec::new(),
             glossary: None,
             custom_instructions: None,
+            style_guide: None,
+            term_instructions: Vec::new(),
+            class_instructions: Vec::new(),
+            templates: PromptTemplates::default(),
         }
     }
 
@@ -118,6 +129,7 @@ impl TranslationPromptBuilder {
                 id: e.id,
                 text: e.original_text.clone(),
                 timecode: e.timecode.format_srt(),
+                class: e.class,
             })
             .collect();
         self
@@ -147,14 +159,84 @@ impl TranslationPromptBuilder {
         self
     }
 
+    /// Set the style guide for the show.
+    pub fn with_style_guide(mut self, guide: &StyleGuide) -> Self {
+        self.style_guide = Some(guide.clone());
+        self
+    }
+
+    /// Set instructions for honorifics and cultural terms in this batch.
+    pub fn with_term_instructions(mut self, instructions: Vec<String>) -> Self {
+        self.term_instructions = instructions;
+        self
+    }
+
+    /// Set instructions for lyrics, signs and other non-dialogue entries in this batch.
+    pub fn with_class_instructions(mut self, instructions: Vec<String>) -> Self {
+        self.class_instructions = instructions;
+        self
+    }
+
+    /// Use user-supplied templates for the system and user prompts.
+    pub fn with_templates(mut self, templates: &PromptTemplates) -> Self {
+        self.templates = templates.clone();
+        self
+    }
+
     /// Build the system prompt.
     pub fn build_system_prompt(&self) -> String {
-        PromptTemplate::subtitle_translator().render(&self.source_language, &self.target_language)
+        let request = self.build_request();
+        self.templates
+            .render(PromptKind::System, &self.template_context(&request))
+            .unwrap_or_else(|| {
+                PromptTemplate::subtitle_translator()
+                    .render(&self.source_language, &self.target_language)
+            })
     }
 
     /// Build the user prompt as a JSON request.
     pub fn build_user_prompt(&self) -> String {
-        let request = TranslationRequest {
+        let request = self.build_request();
+        if let Some(prompt) = self
+            .templates
+            .render(PromptKind::User, &self.template_context(&request))
+        {
+            return prompt;
+        }
+
+        serde_json::to_string_pretty(&request).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Variables available to user templates.
+    ///
+    /// Besides the individual fields, `request` holds the full JSON request
+    /// the built-in user prompt would send.
+    pub fn template_context(&self, request: &TranslationRequest) -> serde_json::Value {
+        let glossary = request.context.glossary.clone().unwrap_or(GlossaryContext {
+            character_names: Vec::new(),
+            terms: std::collections::HashMap::new(),
+        });
+
+        json!({
+            "source_language": self.source_language,
+            "target_language": self.target_language,
+            "history_summary": self.history_summary,
+            "recent_translations": self.recent_translations,
+            "entries": self.entries_to_translate,
+            "lookahead": self.lookahead_entries,
+            "glossary": glossary,
+            "instructions": request.instructions,
+            "style_guide": request.instructions.style_guide,
+            "term_policy": request.instructions.term_policy,
+            "entry_classes": request.instructions.entry_classes,
+            "custom_instructions": self.custom_instructions,
+            "request": request,
+        })
+    }
+
+    /// Build the structured translation request.
+    fn build_request(&self) -> TranslationRequest {
+        TranslationRequest {
             task: "translate_subtitles".to_string(),
             source_language: self.source_language.clone(),
             target_language: self.target_language.clone(),
@@ -191,10 +273,15 @@ impl TranslationPromptBuilder {
                 preserve_sound_effects: true,
                 max_length_ratio: 1.2,
                 custom: self.custom_instructions.clone(),
+                style_guide: self
+                    .style_guide
+                    .as_ref()
+                    .map(|g| g.render_instructions())
+                    .unwrap_or_default(),
+                term_policy: self.term_instructions.clone(),
+                entry_classes: self.class_instructions.clone(),
             },
-        };
-
-        serde_json::to_string_pretty(&request).unwrap_or_else(|_| "{}".to_string())
+        }
     }
 
     /// Build both system and user prompts.
@@ -269,6 +356,10 @@ pub struct EntryToTranslate {
 
     /// Timecode (for reference, not to be modified)
     pub timecode: String,
+
+    /// Content class (omitted for dialogue)
+    #[serde(default, skip_serializing_if = "EntryClass::is_dialogue")]
+    pub class: EntryClass,
 }
 
 /// A lookahead entry for forward context.
@@ -308,6 +399,18 @@ pub struct TranslationInstructions {
     /// Custom instructions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom: Option<String>,
+
+    /// Show style guide rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub style_guide: Vec<String>,
+
+    /// Honorific and cultural-term handling for this batch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub term_policy: Vec<String>,
+
+    /// Handling of lyrics, signs and other non-dialogue entries in this batch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_classes: Vec<String>,
 }
 
 /// Expected response structure from LLM.
@@ -387,6 +490,49 @@ mod tests {
         assert_eq!(request.target_language, "French");
     }
 
+    #[test]
+    fn test_translationPromptBuilder_withStyleGuide_shouldRenderInstructions() {
+        let guide = StyleGuide {
+            formality: Some("formal".to_string()),
+            ..Default::default()
+        };
+        let builder = TranslationPromptBuilder::new("en", "fr").with_style_guide(&guide);
+
+        let request: TranslationRequest =
+            serde_json::from_str(&builder.build_user_prompt()).unwrap();
+
+        assert!(request
+            .instructions
+            .style_guide
+            .contains(&"Formality: formal".to_string()));
+    }
+
+    #[test]
+    fn test_translationPromptBuilder_withTemplates_shouldRenderOverrides() {
+        use crate::translation::prompts::engine::Template;
+
+        let templates = PromptTemplates::new()
+            .with_template(
+                PromptKind::System,
+                Template::parse("Translate {{ source_language }} into {{ target_language }}.").unwrap(),
+            )
+            .with_template(
+                PromptKind::User,
+                Template::parse("{% for entry in entries %}{{ entry.id }}: {{ entry.text }}\n{% endfor %}").unwrap(),
+            );
+        let entries = vec![DocumentEntry::from_subtitle_entry(
+            crate::subtitle_processor::SubtitleEntry::new(4, 0, 1000, "Hello".to_string()),
+        )];
+
+        let (system, user) = TranslationPromptBuilder::new("en", "fr")
+            .with_templates(&templates)
+            .with_entries_to_translate(&entries)
+            .build();
+
+        assert_eq!(system, "Translate en into fr.");
+        assert_eq!(user, "4: Hello\n");
+    }
+
     #[test]
     fn test_translationResponse_deserialize_shouldParseValidJson() {
         let json = r#"{
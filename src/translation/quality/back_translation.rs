@@ -0,0 +1,385 @@
+/*!
+ * Back-translation quality check.
+ *
+ * Asking the translating model whether its own output is equivalent tends
+ * to be lenient. This check translates sampled or flagged entries back into
+ * the source language, optionally with a different provider, and compares
+ * the result with the original text. Entries that drift too far are
+ * reported as `SemanticIssue::MeaningChanged`.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::translation::core::TranslationService;
+use crate::translation::document::SubtitleDocument;
+use crate::translation::quality::semantic::{SemanticIssue, SemanticValidationResult};
+
+/// Formatting tags, which carry no meaning for the comparison
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>|\{[^}]*\}").unwrap());
+
+/// Character n-gram size used by the similarity metric
+const NGRAM_SIZE: usize = 3;
+
+/// Configuration for the back-translation check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackTranslationConfig {
+    /// Whether to run the check after the validation pass
+    pub enabled: bool,
+
+    /// Fraction of translated entries sampled for the check (0.0 disables sampling)
+    pub sample_rate: f32,
+
+    /// Whether entries flagged by the validation pass are always checked
+    pub include_flagged: bool,
+
+    /// Similarity (0.0-1.0) below which an entry counts as divergent
+    pub min_similarity: f32,
+
+    /// Provider used for the back-translation (None = the translating provider)
+    pub provider: Option<String>,
+
+    /// Whether divergent entries are retranslated with feedback
+    pub retranslate: bool,
+}
+
+impl Default for BackTranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: 0.1,
+            include_flagged: true,
+            min_similarity: 0.35,
+            provider: None,
+            retranslate: true,
+        }
+    }
+}
+
+/// Back-translation of a single entry
+#[derive(Debug, Clone)]
+pub struct BackTranslationResult {
+    /// Entry ID
+    pub entry_id: usize,
+
+    /// The entry's translated text
+    pub translated: String,
+
+    /// The translation rendered back into the source language
+    pub back_translation: String,
+
+    /// Similarity between the original and the back-translation (0.0-1.0)
+    pub similarity: f32,
+
+    /// Semantic result derived from the similarity
+    pub result: SemanticValidationResult,
+}
+
+impl BackTranslationResult {
+    /// Whether the back-translation diverged from the original.
+    pub fn is_divergent(&self) -> bool {
+        !self.result.is_equivalent
+    }
+}
+
+/// Outcome of a back-translation run over a document
+#[derive(Debug, Clone, Default)]
+pub struct BackTranslationReport {
+    /// Per-entry results, in document order
+    pub results: Vec<BackTranslationResult>,
+
+    /// Entries whose back-translation request failed
+    pub failed: Vec<usize>,
+
+    /// Number of divergent entries that were retranslated
+    pub retranslated: usize,
+
+    /// Number of retranslated entries that passed the re-check
+    pub resolved: usize,
+}
+
+impl BackTranslationReport {
+    /// Number of entries checked.
+    pub fn checked(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Results for entries that diverged.
+    pub fn divergent(&self) -> impl Iterator<Item = &BackTranslationResult> {
+        self.results.iter().filter(|r| r.is_divergent())
+    }
+
+    /// Average similarity over all checked entries.
+    pub fn average_similarity(&self) -> f32 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+        self.results.iter().map(|r| r.similarity).sum::<f32>() / self.results.len() as f32
+    }
+
+    /// Get a summary of the report.
+    pub fn summary(&self) -> String {
+        format!(
+            "Back-translation: {} entries checked, {} divergent, {} retranslated ({} resolved), average similarity {:.2}",
+            self.checked(),
+            self.divergent().count(),
+            self.retranslated,
+            self.resolved,
+            self.average_similarity()
+        )
+    }
+}
+
+/// Back-translation checker
+pub struct BackTranslator {
+    config: BackTranslationConfig,
+}
+
+impl BackTranslator {
+    /// Create a new back-translator with the given configuration
+    pub fn new(config: BackTranslationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &BackTranslationConfig {
+        &self.config
+    }
+
+    /// Pick the entries to check: every flagged entry plus an evenly spaced sample.
+    ///
+    /// Sampling is deterministic so repeated runs check the same entries.
+    pub fn select_entries(&self, doc: &SubtitleDocument, flagged: &HashSet<usize>) -> Vec<usize> {
+        let stride = if self.config.sample_rate > 0.0 {
+            Some((1.0 / self.config.sample_rate.min(1.0)).round().max(1.0) as usize)
+        } else {
+            None
+        };
+
+        doc.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry
+                    .translated_text
+                    .as_ref()
+                    .is_some_and(|t| !t.trim().is_empty())
+            })
+            .filter(|(idx, entry)| {
+                (self.config.include_flagged && flagged.contains(&entry.id))
+                    || stride.is_some_and(|s| idx % s == 0)
+            })
+            .map(|(_, entry)| entry.id)
+            .collect()
+    }
+
+    /// Back-translate the selected entries and compare them with the originals.
+    ///
+    /// A failed request is logged and recorded, not treated as divergence.
+    pub async fn check(
+        &self,
+        service: &TranslationService,
+        doc: &SubtitleDocument,
+        entry_ids: &[usize],
+        source_lang: &str,
+        target_lang: &str,
+    ) -> BackTranslationReport {
+        let mut report = BackTranslationReport::default();
+
+        for entry in doc.entries.iter().filter(|e| entry_ids.contains(&e.id)) {
+            let Some(translated) = entry.translated_text.as_deref() else {
+                continue;
+            };
+
+            match service.translate_text_uncached(translated, target_lang, source_lang).await {
+                Ok(back) => {
+                    let result = self.evaluate(entry.id, &entry.original_text, translated, back.trim());
+                    debug!(
+                        "Entry {} back-translation similarity {:.2}",
+                        entry.id, result.similarity
+                    );
+                    report.results.push(result);
+                }
+                Err(e) => {
+                    warn!("Back-translation of entry {} failed: {}", entry.id, e);
+                    report.failed.push(entry.id);
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Compare an original with its back-translation.
+    pub fn evaluate(
+        &self,
+        entry_id: usize,
+        original: &str,
+        translated: &str,
+        back_translation: &str,
+    ) -> BackTranslationResult {
+        let similarity = Self::similarity(original, back_translation);
+
+        let result = if similarity >= self.config.min_similarity {
+            SemanticValidationResult::equivalent(1.0)
+        } else {
+            SemanticValidationResult::divergent(
+                similarity,
+                vec![SemanticIssue::MeaningChanged {
+                    original_meaning: original.to_string(),
+                    translated_meaning: back_translation.to_string(),
+                }],
+            )
+        };
+
+        BackTranslationResult {
+            entry_id,
+            translated: translated.to_string(),
+            back_translation: back_translation.to_string(),
+            similarity,
+            result,
+        }
+    }
+
+    /// Character trigram Dice similarity (0.0-1.0) of two texts.
+    ///
+    /// Tags, case and punctuation are ignored. Character n-grams are robust
+    /// to inflection and word order changes that a back-translation usually
+    /// introduces, while still dropping when content words differ.
+    pub fn similarity(a: &str, b: &str) -> f32 {
+        let a = Self::ngrams(&Self::normalize(a));
+        let b = Self::ngrams(&Self::normalize(b));
+
+        let total_a: usize = a.values().sum();
+        let total_b: usize = b.values().sum();
+        if total_a == 0 && total_b == 0 {
+            return 1.0;
+        }
+        if total_a == 0 || total_b == 0 {
+            return 0.0;
+        }
+
+        let shared: usize = a
+            .iter()
+            .map(|(gram, count)| b.get(gram).map_or(0, |other| (*count).min(*other)))
+            .sum();
+
+        (2 * shared) as f32 / (total_a + total_b) as f32
+    }
+
+    /// Lowercase, strip tags and punctuation, collapse whitespace.
+    fn normalize(text: &str) -> String {
+        let stripped = TAG_REGEX.replace_all(text, " ");
+        let cleaned: String = stripped
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect::<String>()
+            .to_lowercase();
+        cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Count the character n-grams of a space-padded text.
+    fn ngrams(text: &str) -> HashMap<String, usize> {
+        let mut grams = HashMap::new();
+        if text.is_empty() {
+            return grams;
+        }
+
+        let chars: Vec<char> = format!(" {} ", text).chars().collect();
+        for window in chars.windows(NGRAM_SIZE.min(chars.len())) {
+            *grams.entry(window.iter().collect::<String>()).or_insert(0) += 1;
+        }
+        grams
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle_processor::SubtitleEntry;
+
+    fn create_test_document(count: usize) -> SubtitleDocument {
+        let entries: Vec<SubtitleEntry> = (1..=count)
+            .map(|i| SubtitleEntry::new(i, (i as u64) * 1000, (i as u64) * 1000 + 900, format!("Line {}", i)))
+            .collect();
+        let mut doc = SubtitleDocument::from_entries(entries, "en");
+        for entry in doc.entries.iter_mut() {
+            entry.set_translation(format!("Ligne {}", entry.id), Some(0.9));
+        }
+        doc
+    }
+
+    #[test]
+    fn test_backTranslator_similarity_shouldIgnoreCaseTagsAndPunctuation() {
+        let similarity = BackTranslator::similarity("<i>Where are you going?</i>", "where are you going");
+
+        assert!((similarity - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_backTranslator_similarity_shouldRankParaphraseAboveUnrelated() {
+        let original = "I left my keys in the car";
+        let paraphrase = BackTranslator::similarity(original, "I left the keys in my car");
+        let unrelated = BackTranslator::similarity(original, "She is afraid of the dark");
+
+        assert!(paraphrase > 0.6, "{}", paraphrase);
+        assert!(unrelated < 0.35, "{}", unrelated);
+    }
+
+    #[test]
+    fn test_backTranslator_evaluate_shouldReportMeaningChanged() {
+        let translator = BackTranslator::new(BackTranslationConfig::default());
+
+        let result = translator.evaluate(4, "I love you", "Pose l'argent sur la table", "Put the money on the table");
+
+        assert!(result.is_divergent());
+        assert!(!result.result.passed());
+        match &result.result.issues[0] {
+            SemanticIssue::MeaningChanged { original_meaning, translated_meaning } => {
+                assert_eq!(original_meaning, "I love you");
+                assert_eq!(translated_meaning, "Put the money on the table");
+            }
+            other => panic!("Expected MeaningChanged, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_backTranslator_evaluate_shouldPassCloseBackTranslation() {
+        let translator = BackTranslator::new(BackTranslationConfig::default());
+
+        let result = translator.evaluate(1, "We have to go now.", "Il faut partir maintenant.", "We must go now.");
+
+        assert!(!result.is_divergent());
+        assert!(result.result.passed());
+    }
+
+    #[test]
+    fn test_backTranslator_selectEntries_shouldSampleAndIncludeFlagged() {
+        let translator = BackTranslator::new(BackTranslationConfig {
+            sample_rate: 0.25,
+            ..Default::default()
+        });
+        let doc = create_test_document(10);
+        let flagged: HashSet<usize> = [6].into_iter().collect();
+
+        assert_eq!(translator.select_entries(&doc, &flagged), vec![1, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_backTranslator_selectEntries_shouldSkipUntranslatedAndHonourFlaggedOnly() {
+        let translator = BackTranslator::new(BackTranslationConfig {
+            sample_rate: 0.0,
+            ..Default::default()
+        });
+        let mut doc = create_test_document(4);
+        doc.entries[2].translated_text = None;
+        let flagged: HashSet<usize> = [2, 3].into_iter().collect();
+
+        assert_eq!(translator.select_entries(&doc, &flagged), vec![2]);
+    }
+}
@@ -6,11 +6,13 @@
  * - Style: Consistent formality, tone, punctuation
  * - Character names: Names preserved correctly
  * - Technical terms: Domain-specific vocabulary
+ * - Style guide: Checkable rules from the show's style guide
  */
 
 use std::collections::{HashMap, HashSet};
 
 use crate::translation::document::{DocumentEntry, Glossary, SubtitleDocument};
+use crate::translation::prompts::StyleGuide;
 
 /// Configuration for consistency checking.
 #[derive(Debug, Clone)]
@@ -112,6 +114,13 @@ pub enum StyleIssue {
     InconsistentNumberFormat {
         entry_ids: Vec<usize>,
     },
+
+    /// Translation breaks a style guide rule
+    StyleGuideViolation {
+        rule: String,
+        entry_id: usize,
+        detail: String,
+    },
 }
 
 impl StyleIssue {
@@ -127,6 +136,7 @@ impl StyleIssue {
             StyleIssue::InconsistentFormality { .. } => 0.5,
             StyleIssue::MixedQuoteStyles { .. } => 0.2,
             StyleIssue::InconsistentNumberFormat { .. } => 0.2,
+            StyleIssue::StyleGuideViolation { .. } => 0.4,
         }
     }
 
@@ -168,6 +178,9 @@ impl StyleIssue {
             StyleIssue::InconsistentNumberFormat { entry_ids } => {
                 format!("Inconsistent number formats in {} entries", entry_ids.len())
             }
+            StyleIssue::StyleGuideViolation { rule, entry_id, detail } => {
+                format!("Entry {}: style rule '{}' violated ({})", entry_id, rule, detail)
+            }
         }
     }
 }
@@ -265,6 +278,7 @@ impl Default for ConsistencyReport {
 /// Consistency checker for translations.
 pub struct ConsistencyChecker {
     config: ConsistencyConfig,
+    style_guide: Option<StyleGuide>,
 }
 
 impl ConsistencyChecker {
@@ -272,12 +286,22 @@ impl ConsistencyChecker {
     pub fn new() -> Self {
         Self {
             config: ConsistencyConfig::default(),
+            style_guide: None,
         }
     }
 
     /// Create with custom config.
     pub fn with_config(config: ConsistencyConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            style_guide: None,
+        }
+    }
+
+    /// Check translations against a show style guide.
+    pub fn with_style_guide(mut self, guide: StyleGuide) -> Self {
+        self.style_guide = Some(guide);
+        self
     }
 
     /// Check consistency of a document.
@@ -295,6 +319,7 @@ impl ConsistencyChecker {
 
         if self.config.check_style {
             self.check_style(doc, &mut report);
+            self.check_style_guide(doc, &mut report);
         }
 
         if self.config.check_punctuation {
@@ -451,6 +476,25 @@ impl ConsistencyChecker {
         }
     }
 
+    /// Check translations against the style guide's checkable rules.
+    fn check_style_guide(&self, doc: &SubtitleDocument, report: &mut ConsistencyReport) {
+        let Some(ref guide) = self.style_guide else {
+            return;
+        };
+
+        for entry in &doc.entries {
+            if let Some(ref translated) = entry.translated_text {
+                for (rule, detail) in guide.check(translated) {
+                    report.add_issue(StyleIssue::StyleGuideViolation {
+                        rule: rule.name().to_string(),
+                        entry_id: entry.id,
+                        detail,
+                    });
+                }
+            }
+        }
+    }
+
     /// Detect formality level of text.
     fn detect_formality(&self, text: &str) -> FormalityLevel {
         let lower = text.to_lowercase();
@@ -597,6 +641,39 @@ mod tests {
         assert!(name_issues.is_empty());
     }
 
+    #[test]
+    fn test_consistencyChecker_withStyleGuide_shouldReportViolations() {
+        use crate::translation::prompts::style_guide::StyleRule;
+
+        let mut doc = create_test_document();
+        doc.entries[0].set_translation("John a dit bonjour !!".to_string(), None);
+        doc.entries[1].set_translation("John est à 3 miles.".to_string(), None);
+        doc.entries[2].set_translation("La fin.".to_string(), None);
+
+        let guide = StyleGuide {
+            rules: vec![StyleRule::NoDoubledExclamation, StyleRule::MetricUnits],
+            ..Default::default()
+        };
+        let checker = ConsistencyChecker::new().with_style_guide(guide);
+        let report = checker.check(&doc);
+
+        let violations: Vec<_> = report
+            .issues
+            .iter()
+            .filter_map(|i| match i {
+                StyleIssue::StyleGuideViolation { rule, entry_id, .. } => {
+                    Some((rule.as_str(), *entry_id))
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            violations,
+            vec![("no_doubled_exclamation", 1), ("metric_units", 2)]
+        );
+    }
+
     #[test]
     fn test_consistencyReport_calculateScore_shouldReflectIssues() {
         let mut report = ConsistencyReport::new();
@@ -68,6 +68,46 @@ impl TranslationErrorKind {
         }
     }
 
+    /// Classify an error from its message, e.g. a provider error.
+    pub fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let contains_any = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+
+        if contains_any(&["rate limit", "429", "too many requests"]) {
+            TranslationErrorKind::RateLimit
+        } else if contains_any(&["timeout", "timed out"]) {
+            TranslationErrorKind::Timeout
+        } else if contains_any(&["connection", "network", "dns"]) {
+            TranslationErrorKind::Network
+        } else if contains_any(&["authentication", "api key", "401", "403", "configuration"]) {
+            TranslationErrorKind::ConfigError
+        } else if contains_any(&["parse", "json", "marker"]) {
+            TranslationErrorKind::ParseError
+        } else if contains_any(&["invalid response", "empty response"]) {
+            TranslationErrorKind::InvalidResponse
+        } else if contains_any(&["api request failed", "api responded"]) {
+            TranslationErrorKind::ProviderError
+        } else {
+            TranslationErrorKind::Unknown
+        }
+    }
+
+    /// Stable name used in reports and the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranslationErrorKind::Network => "network",
+            TranslationErrorKind::RateLimit => "rate_limit",
+            TranslationErrorKind::Timeout => "timeout",
+            TranslationErrorKind::InvalidResponse => "invalid_response",
+            TranslationErrorKind::ParseError => "parse_error",
+            TranslationErrorKind::ValidationFailed => "validation_failed",
+            TranslationErrorKind::ProviderError => "provider_error",
+            TranslationErrorKind::ConfigError => "config_error",
+            TranslationErrorKind::ResourceExhausted => "resource_exhausted",
+            TranslationErrorKind::Unknown => "unknown",
+        }
+    }
+
     /// Get maximum recommended retries for this error type.
     pub fn max_retries(&self) -> usize {
         match self {
@@ -569,6 +609,19 @@ mod tests {
         assert!(!TranslationErrorKind::ResourceExhausted.is_retryable());
     }
 
+    #[test]
+    fn test_translationErrorKind_classify_shouldMatchProviderErrors() {
+        assert_eq!(TranslationErrorKind::classify("Rate limit exceeded: slow down"), TranslationErrorKind::RateLimit);
+        assert_eq!(TranslationErrorKind::classify("Connection error: refused"), TranslationErrorKind::Network);
+        assert_eq!(TranslationErrorKind::classify("request timed out"), TranslationErrorKind::Timeout);
+        assert_eq!(
+            TranslationErrorKind::classify("Failed to parse translation response: EOF"),
+            TranslationErrorKind::ParseError
+        );
+        assert_eq!(TranslationErrorKind::classify("something odd"), TranslationErrorKind::Unknown);
+        assert_eq!(TranslationErrorKind::RateLimit.as_str(), "rate_limit");
+    }
+
     #[test]
     fn test_translationError_shouldRetry_shouldRespectLimits() {
         let error = TranslationError::new(TranslationErrorKind::Network, "test");
@@ -0,0 +1,531 @@
+/*!
+ * LLM-as-judge quality scoring.
+ *
+ * Sends batches of (source, translation, context) to a judge model, which
+ * annotates errors using MQM-style categories and severities. Penalties
+ * are weighted by severity and normalized by word count into a per-entry
+ * score, which feeds the `mqm` dimension of `QualityScore`. Entries that
+ * score below the threshold are queued for retranslation with feedback.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::translation::core::TranslationService;
+use crate::translation::document::SubtitleDocument;
+use crate::translation::prompts::engine::Template;
+
+/// MQM error category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MqmCategory {
+    /// Mistranslation, addition, omission
+    Accuracy,
+    /// Grammar, spelling, punctuation, unnatural phrasing
+    Fluency,
+    /// Wrong or inconsistent term or name
+    Terminology,
+    /// Wrong register, tone or awkward style
+    Style,
+    /// Dates, numbers, units, quotes and other locale conventions
+    LocaleConvention,
+}
+
+impl MqmCategory {
+    /// Parse a category name as returned by the judge.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+            "accuracy" | "mistranslation" | "addition" | "omission" => Some(Self::Accuracy),
+            "fluency" | "grammar" | "spelling" | "punctuation" => Some(Self::Fluency),
+            "terminology" => Some(Self::Terminology),
+            "style" | "register" => Some(Self::Style),
+            "locale_convention" | "locale" => Some(Self::LocaleConvention),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MqmCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MqmCategory::Accuracy => "accuracy",
+            MqmCategory::Fluency => "fluency",
+            MqmCategory::Terminology => "terminology",
+            MqmCategory::Style => "style",
+            MqmCategory::LocaleConvention => "locale convention",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// MQM error severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MqmSeverity {
+    /// Noted but not penalized
+    Neutral,
+    /// Noticeable but does not hinder understanding
+    Minor,
+    /// Changes or obscures the meaning
+    Major,
+    /// Misleading or offensive; the subtitle is unusable
+    Critical,
+}
+
+impl MqmSeverity {
+    /// Parse a severity name as returned by the judge.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "neutral" | "none" => Some(Self::Neutral),
+            "minor" => Some(Self::Minor),
+            "major" => Some(Self::Major),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
+    /// MQM penalty points for one error of this severity.
+    pub fn penalty(&self) -> f32 {
+        match self {
+            MqmSeverity::Neutral => 0.0,
+            MqmSeverity::Minor => 1.0,
+            MqmSeverity::Major => 5.0,
+            MqmSeverity::Critical => 10.0,
+        }
+    }
+}
+
+impl fmt::Display for MqmSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MqmSeverity::Neutral => "neutral",
+            MqmSeverity::Minor => "minor",
+            MqmSeverity::Major => "major",
+            MqmSeverity::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One error annotated by the judge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqmAnnotation {
+    /// Entry ID
+    pub entry_id: usize,
+
+    /// Error category
+    pub category: MqmCategory,
+
+    /// Error severity
+    pub severity: MqmSeverity,
+
+    /// Offending part of the translation, if given
+    pub span: Option<String>,
+
+    /// Explanation from the judge
+    pub description: String,
+}
+
+impl MqmAnnotation {
+    /// Get a human-readable description of the annotation
+    pub fn describe(&self) -> String {
+        match self.span {
+            Some(ref span) if !span.is_empty() => {
+                format!("{} {} error in '{}': {}", self.severity, self.category, span, self.description)
+            }
+            _ => format!("{} {} error: {}", self.severity, self.category, self.description),
+        }
+    }
+}
+
+/// Configuration for the judge pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JudgeConfig {
+    /// Whether to run the judge after the validation pass
+    pub enabled: bool,
+
+    /// Provider of the judge model (None = the translating provider)
+    pub provider: Option<String>,
+
+    /// Judge model name (None = the provider's configured model)
+    pub model: Option<String>,
+
+    /// Entries sent to the judge per request
+    pub batch_size: usize,
+
+    /// Preceding entries included as context for each batch
+    pub context_entries: usize,
+
+    /// Entry score (0.0-1.0) below which an entry is retranslated
+    pub min_entry_score: f32,
+
+    /// Whether low-scoring entries are retranslated with the judge's feedback
+    pub retranslate: bool,
+}
+
+impl Default for JudgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: None,
+            model: None,
+            batch_size: 10,
+            context_entries: 3,
+            min_entry_score: 0.7,
+            retranslate: true,
+        }
+    }
+}
+
+/// Outcome of a judge run over a document
+#[derive(Debug, Clone, Default)]
+pub struct JudgeReport {
+    /// All annotations, in document order
+    pub annotations: Vec<MqmAnnotation>,
+
+    /// Score (0.0-1.0) of each judged entry
+    pub entry_scores: HashMap<usize, f32>,
+
+    /// Words in the judged translations
+    pub words: usize,
+
+    /// Entries whose batch could not be judged
+    pub failed: Vec<usize>,
+
+    /// Number of low-scoring entries that were retranslated
+    pub retranslated: usize,
+}
+
+impl JudgeReport {
+    /// Total MQM penalty points.
+    pub fn penalty(&self) -> f32 {
+        self.annotations.iter().map(|a| a.severity.penalty()).sum()
+    }
+
+    /// Document score: 1 - penalty per word, clamped to 0.0-1.0.
+    pub fn score(&self) -> f32 {
+        mqm_score(self.penalty(), self.words)
+    }
+
+    /// Annotations for one entry.
+    pub fn annotations_for(&self, entry_id: usize) -> Vec<&MqmAnnotation> {
+        self.annotations.iter().filter(|a| a.entry_id == entry_id).collect()
+    }
+
+    /// Entries scoring below the threshold, in ascending ID order.
+    pub fn entries_below(&self, threshold: f32) -> Vec<usize> {
+        let mut ids: Vec<usize> = self
+            .entry_scores
+            .iter()
+            .filter(|(_, score)| **score < threshold)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Error counts per category.
+    pub fn counts_by_category(&self) -> HashMap<MqmCategory, usize> {
+        let mut counts = HashMap::new();
+        for annotation in &self.annotations {
+            *counts.entry(annotation.category).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Get a summary of the report.
+    pub fn summary(&self) -> String {
+        format!(
+            "Judge: MQM score {:.1}% over {} entries, {} errors ({:.0} penalty points), {} retranslated",
+            self.score() * 100.0,
+            self.entry_scores.len(),
+            self.annotations.len(),
+            self.penalty(),
+            self.retranslated
+        )
+    }
+}
+
+/// MQM score for a penalty over a number of words.
+pub fn mqm_score(penalty: f32, words: usize) -> f32 {
+    (1.0 - penalty / words.max(1) as f32).clamp(0.0, 1.0)
+}
+
+/// An entry as presented to the judge
+#[derive(Debug, Clone, Serialize)]
+struct JudgeItem {
+    id: usize,
+    source: String,
+    translation: String,
+}
+
+/// Judge that scores translations with MQM annotations
+pub struct LlmJudge {
+    config: JudgeConfig,
+    prompt_template: Option<Template>,
+}
+
+impl LlmJudge {
+    /// Create a new judge with the given configuration
+    pub fn new(config: JudgeConfig) -> Self {
+        Self {
+            config,
+            prompt_template: None,
+        }
+    }
+
+    /// Use a user-supplied template for the judge prompt.
+    pub fn with_prompt_template(mut self, template: Option<Template>) -> Self {
+        self.prompt_template = template;
+        self
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &JudgeConfig {
+        &self.config
+    }
+
+    /// Judge every translated entry of a document in batches.
+    ///
+    /// A batch whose request or response fails is logged and its entries
+    /// recorded as failed; they get no score.
+    pub async fn judge_document(
+        &self,
+        service: &TranslationService,
+        doc: &SubtitleDocument,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> JudgeReport {
+        let mut report = JudgeReport::default();
+        let translated: Vec<usize> = doc
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.translated_text.as_ref().is_some_and(|t| !t.trim().is_empty()))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        for batch in translated.chunks(self.config.batch_size.max(1)) {
+            let items: Vec<JudgeItem> = batch
+                .iter()
+                .map(|&idx| {
+                    let entry = &doc.entries[idx];
+                    JudgeItem {
+                        id: entry.id,
+                        source: entry.original_text.clone(),
+                        translation: entry.translated_text.clone().unwrap_or_default(),
+                    }
+                })
+                .collect();
+            let context_start = batch[0].saturating_sub(self.config.context_entries);
+            let context: Vec<JudgeItem> = doc.entries[context_start..batch[0]]
+                .iter()
+                .map(|e| JudgeItem {
+                    id: e.id,
+                    source: e.original_text.clone(),
+                    translation: e.translated_text.clone().unwrap_or_default(),
+                })
+                .collect();
+
+            let prompt = self.build_prompt(&items, &context, source_lang, target_lang);
+            let annotations = match service.translate_text_uncached(&prompt, "validation", "mqm_judge").await {
+                Ok(response) => self.parse_response(&response, &items),
+                Err(e) => Err(e),
+            };
+
+            match annotations {
+                Ok(annotations) => {
+                    for item in &items {
+                        let words = item.translation.split_whitespace().count();
+                        let penalty: f32 = annotations
+                            .iter()
+                            .filter(|a| a.entry_id == item.id)
+                            .map(|a| a.severity.penalty())
+                            .sum();
+                        report.entry_scores.insert(item.id, mqm_score(penalty, words));
+                        report.words += words;
+                    }
+                    report.annotations.extend(annotations);
+                }
+                Err(e) => {
+                    warn!("Judge request for entries {}-{} failed: {}", items[0].id, items[items.len() - 1].id, e);
+                    report.failed.extend(items.iter().map(|i| i.id));
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Build the prompt for a batch
+    fn build_prompt(&self, items: &[JudgeItem], context: &[JudgeItem], source_lang: &str, target_lang: &str) -> String {
+        if let Some(ref template) = self.prompt_template {
+            return template.render(&serde_json::json!({
+                "source_language": source_lang,
+                "target_language": target_lang,
+                "entries": items,
+                "context": context,
+            }));
+        }
+
+        let entries = serde_json::to_string_pretty(items).unwrap_or_else(|_| "[]".to_string());
+        let context = if context.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\nPreceding subtitles for context (do not annotate):\n{}\n",
+                serde_json::to_string_pretty(context).unwrap_or_else(|_| "[]".to_string())
+            )
+        };
+
+        format!(
+            r#"You are a professional subtitle quality reviewer. Annotate errors in these {source_lang} to {target_lang} subtitle translations using MQM.
+{context}
+Subtitles to review:
+{entries}
+
+Categories: accuracy, fluency, terminology, style, locale_convention
+Severities: minor (noticeable, meaning intact), major (meaning changed or obscured), critical (misleading or unusable)
+Subtitles are condensed by design; do not penalize shortening that keeps the meaning.
+
+Respond with JSON only:
+{{
+    "annotations": [
+        {{"id": 1, "category": "accuracy", "severity": "major", "span": "...", "explanation": "..."}}
+    ]
+}}
+Return an empty list when there are no errors."#,
+        )
+    }
+
+    /// Parse the judge response into annotations for the batch entries
+    fn parse_response(&self, response: &str, items: &[JudgeItem]) -> Result<Vec<MqmAnnotation>> {
+        #[derive(Deserialize)]
+        struct ParsedResponse {
+            #[serde(default)]
+            annotations: Vec<ParsedAnnotation>,
+        }
+
+        #[derive(Deserialize)]
+        struct ParsedAnnotation {
+            id: usize,
+            category: String,
+            severity: String,
+            span: Option<String>,
+            #[serde(default, alias = "description")]
+            explanation: String,
+        }
+
+        let parsed: ParsedResponse = serde_json::from_str(&Self::extract_json(response)?)?;
+
+        Ok(parsed
+            .annotations
+            .into_iter()
+            .filter(|a| items.iter().any(|i| i.id == a.id))
+            .filter_map(|a| {
+                Some(MqmAnnotation {
+                    entry_id: a.id,
+                    category: MqmCategory::parse(&a.category)?,
+                    severity: MqmSeverity::parse(&a.severity)?,
+                    span: a.span.filter(|s| !s.is_empty()),
+                    description: a.explanation,
+                })
+            })
+            .collect())
+    }
+
+    /// Extract JSON from response text
+    fn extract_json(response: &str) -> Result<String> {
+        let trimmed = response.trim();
+
+        if let (Some(start), Some(end)) = (trimmed.find('{'), trimmed.rfind('}'))
+            && end > start
+        {
+            return Ok(trimmed[start..=end].to_string());
+        }
+
+        anyhow::bail!("Could not extract JSON from judge response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<JudgeItem> {
+        vec![
+            JudgeItem { id: 1, source: "I love you".to_string(), translation: "Je te déteste".to_string() },
+            JudgeItem { id: 2, source: "See you tomorrow".to_string(), translation: "À demain".to_string() },
+        ]
+    }
+
+    #[test]
+    fn test_llmJudge_parseResponse_shouldReadAnnotationsFromCodeBlock() {
+        let judge = LlmJudge::new(JudgeConfig::default());
+        let response = r#"Here you go:
+```json
+{"annotations": [
+  {"id": 1, "category": "Accuracy", "severity": "critical", "span": "déteste", "explanation": "love became hate"},
+  {"id": 2, "category": "locale convention", "severity": "minor", "explanation": "spacing"},
+  {"id": 9, "category": "fluency", "severity": "minor", "explanation": "not in batch"},
+  {"id": 2, "category": "vibes", "severity": "minor", "explanation": "unknown category"}
+]}
+```"#;
+
+        let annotations = judge.parse_response(response, &items()).unwrap();
+
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(annotations[0].category, MqmCategory::Accuracy);
+        assert_eq!(annotations[0].severity, MqmSeverity::Critical);
+        assert_eq!(annotations[0].span.as_deref(), Some("déteste"));
+        assert_eq!(annotations[1].category, MqmCategory::LocaleConvention);
+        assert!(annotations[0].describe().contains("critical accuracy error in 'déteste'"));
+    }
+
+    #[test]
+    fn test_llmJudge_parseResponse_shouldFailWithoutJson() {
+        let judge = LlmJudge::new(JudgeConfig::default());
+
+        assert!(judge.parse_response("Looks good to me!", &items()).is_err());
+    }
+
+    #[test]
+    fn test_llmJudge_buildPrompt_shouldIncludeEntriesAndContext() {
+        let judge = LlmJudge::new(JudgeConfig::default());
+        let all = items();
+
+        let prompt = judge.build_prompt(&all[1..], &all[..1], "English", "French");
+
+        assert!(prompt.contains("English to French"));
+        assert!(prompt.contains("Preceding subtitles for context"));
+        assert!(prompt.contains("À demain"));
+    }
+
+    #[test]
+    fn test_judgeReport_score_shouldWeightSeverityByWords() {
+        let annotation = |entry_id, severity| MqmAnnotation {
+            entry_id,
+            category: MqmCategory::Fluency,
+            severity,
+            span: None,
+            description: String::new(),
+        };
+        let report = JudgeReport {
+            annotations: vec![annotation(1, MqmSeverity::Minor), annotation(2, MqmSeverity::Major)],
+            entry_scores: [(1, 0.9), (2, 0.5), (3, 1.0)].into_iter().collect(),
+            words: 30,
+            ..Default::default()
+        };
+
+        assert!((report.penalty() - 6.0).abs() < f32::EPSILON);
+        assert!((report.score() - 0.8).abs() < 1e-6);
+        assert_eq!(report.entries_below(0.7), vec![2]);
+        assert_eq!(report.counts_by_category()[&MqmCategory::Fluency], 2);
+        assert_eq!(mqm_score(10.0, 4), 0.0);
+    }
+}
@@ -0,0 +1,293 @@
+/*!
+ * Line breaking for translated subtitle text.
+ *
+ * Reflows text following common broadcast rules:
+ * - A configurable maximum number of characters per line
+ * - At most two lines
+ * - Balanced "pyramid" lines (the top line no longer than the bottom one)
+ * - No break after an article or preposition (language-specific lists)
+ * - No break inside a character name
+ */
+
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::language_utils;
+
+/// Formatting tags, which take no space on screen.
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>|\{[^}]*\}").unwrap());
+
+/// Default maximum characters per line.
+const DEFAULT_MAX_CHARS_PER_LINE: usize = 42;
+
+/// Configuration for line breaking.
+#[derive(Debug, Clone)]
+pub struct LineBreakConfig {
+    /// Maximum visible characters per line
+    pub max_chars_per_line: usize,
+
+    /// Words a line must not end with (lowercase)
+    pub no_break_after: HashSet<String>,
+}
+
+impl Default for LineBreakConfig {
+    fn default() -> Self {
+        Self::for_language("en")
+    }
+}
+
+impl LineBreakConfig {
+    /// Create a config with the article and preposition list for a language.
+    ///
+    /// Languages without a list get no word restrictions.
+    pub fn for_language(language: &str) -> Self {
+        let code = language_utils::normalize_to_part1_or_part2t(language).unwrap_or_default();
+        let words: &[&str] = match code.as_str() {
+            "en" => &[
+                "a", "an", "the", "of", "to", "in", "on", "at", "by", "for", "with", "from",
+                "into", "about",
+            ],
+            "fr" => &[
+                "le", "la", "les", "l'", "un", "une", "des", "du", "de", "d'", "à", "au", "aux",
+                "en", "dans", "sur", "pour", "par", "avec", "sans", "chez",
+            ],
+            "de" => &[
+                "der", "die", "das", "den", "dem", "des", "ein", "eine", "einen", "einem",
+                "einer", "eines", "zu", "mit", "von", "bei", "nach", "aus", "für", "auf", "an",
+                "in", "um", "über", "unter",
+            ],
+            "es" => &[
+                "el", "la", "los", "las", "un", "una", "unos", "unas", "de", "del", "a", "al",
+                "en", "con", "por", "para", "sin", "sobre",
+            ],
+            "it" => &[
+                "il", "lo", "la", "i", "gli", "le", "un", "uno", "una", "l'", "di", "a", "da",
+                "in", "con", "su", "per", "tra", "fra", "del", "della",
+            ],
+            "pt" => &[
+                "o", "a", "os", "as", "um", "uma", "de", "do", "da", "em", "no", "na", "com",
+                "por", "para",
+            ],
+            _ => &[],
+        };
+
+        Self {
+            max_chars_per_line: DEFAULT_MAX_CHARS_PER_LINE,
+            no_break_after: words.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// Set the maximum characters per line.
+    pub fn with_max_chars_per_line(mut self, max_chars: usize) -> Self {
+        self.max_chars_per_line = max_chars.max(1);
+        self
+    }
+}
+
+/// Line breaker applying the broadcast rules to a single subtitle.
+pub struct LineBreaker {
+    config: LineBreakConfig,
+}
+
+impl LineBreaker {
+    /// Create a new line breaker.
+    pub fn new(config: LineBreakConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &LineBreakConfig {
+        &self.config
+    }
+
+    /// Visible length of a line, ignoring formatting tags.
+    pub fn visible_len(text: &str) -> usize {
+        TAG_REGEX.replace_all(text, "").chars().count()
+    }
+
+    /// Check whether text already follows the rules.
+    pub fn is_well_formed(&self, text: &str, names: &HashSet<String>) -> bool {
+        let lines: Vec<&str> = text.lines().collect();
+        match lines.as_slice() {
+            [line] => Self::visible_len(line) <= self.config.max_chars_per_line,
+            [top, bottom] => {
+                Self::visible_len(top) <= self.config.max_chars_per_line
+                    && Self::visible_len(bottom) <= self.config.max_chars_per_line
+                    && (Self::is_dialogue(&lines) || self.break_allowed(top, bottom, names))
+            }
+            _ => false,
+        }
+    }
+
+    /// Reflow text into at most two balanced lines.
+    ///
+    /// Two-speaker dialogue ("- Hi.\n- Hello.") keeps one speaker per line. Text that
+    /// cannot fit in two lines is still balanced, but will exceed the line limit.
+    pub fn break_lines(&self, text: &str, names: &HashSet<String>) -> String {
+        let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        if lines.len() == 2 && Self::is_dialogue(&lines) {
+            return lines.join("\n");
+        }
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let single = words.join(" ");
+        if Self::visible_len(&single) <= self.config.max_chars_per_line || words.len() < 2 {
+            return single;
+        }
+
+        // Try the strict rules first, then drop the word rules if nothing fits
+        let best = self
+            .best_break(&words, names, true)
+            .or_else(|| self.best_break(&words, names, false));
+
+        match best {
+            Some(index) => format!("{}\n{}", words[..index].join(" "), words[index..].join(" ")),
+            None => single,
+        }
+    }
+
+    /// Index of the first word on the bottom line for the best break, if any fits.
+    ///
+    /// With `strict`, breaks after articles/prepositions and inside names are
+    /// rejected and both lines must fit; otherwise the most balanced break wins.
+    fn best_break(&self, words: &[&str], names: &HashSet<String>, strict: bool) -> Option<usize> {
+        (1..words.len())
+            .filter_map(|index| {
+                let top = words[..index].join(" ");
+                let bottom = words[index..].join(" ");
+                let (top_len, bottom_len) = (Self::visible_len(&top), Self::visible_len(&bottom));
+                let fits = top_len <= self.config.max_chars_per_line
+                    && bottom_len <= self.config.max_chars_per_line;
+
+                if strict && (!fits || !self.break_allowed(&top, &bottom, names)) {
+                    return None;
+                }
+
+                Some((index, Self::break_cost(&top, top_len, bottom_len, fits)))
+            })
+            .min_by_key(|&(_, cost)| cost)
+            .map(|(index, _)| index)
+    }
+
+    /// Cost of a break: imbalance, a top-heavy shape and overflow cost more;
+    /// breaking after punctuation costs less.
+    fn break_cost(top: &str, top_len: usize, bottom_len: usize, fits: bool) -> i64 {
+        let mut cost = (top_len as i64 - bottom_len as i64).abs();
+        if top_len > bottom_len {
+            cost += 3;
+        }
+        if TAG_REGEX
+            .replace_all(top, "")
+            .trim_end()
+            .ends_with([',', '.', '!', '?', ';', ':', '…'])
+        {
+            cost -= 8;
+        }
+        if !fits {
+            cost += 1000;
+        }
+        cost
+    }
+
+    /// Check the word rules for a break between `top` and `bottom`.
+    fn break_allowed(&self, top: &str, bottom: &str, names: &HashSet<String>) -> bool {
+        let last_word = TAG_REGEX
+            .replace_all(top.split_whitespace().last().unwrap_or(""), "")
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+            .to_lowercase();
+        if self.config.no_break_after.contains(&last_word) {
+            return false;
+        }
+
+        // Reject a break that falls inside a multi-word name
+        let joined = format!("{} {}", top.trim_end(), bottom.trim_start());
+        let break_at = top.trim_end().len();
+        !names.iter().filter(|n| n.contains(' ')).any(|name| {
+            joined
+                .match_indices(name.as_str())
+                .any(|(start, _)| start < break_at && break_at < start + name.len())
+        })
+    }
+
+    /// Two lines that each start with a dialogue dash.
+    fn is_dialogue(lines: &[&str]) -> bool {
+        lines.iter().all(|l| TAG_REGEX.replace_all(l, "").trim_start().starts_with('-'))
+    }
+}
+
+impl Default for LineBreaker {
+    fn default() -> Self {
+        Self::new(LineBreakConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_names() -> HashSet<String> {
+        HashSet::new()
+    }
+
+    #[test]
+    fn test_lineBreaker_breakLines_shouldKeepShortTextOnOneLine() {
+        let breaker = LineBreaker::default();
+
+        assert_eq!(breaker.break_lines("Where are\nyou going?", &no_names()), "Where are you going?");
+    }
+
+    #[test]
+    fn test_lineBreaker_breakLines_shouldBalanceIntoPyramid() {
+        let breaker = LineBreaker::default();
+
+        let result = breaker.break_lines(
+            "I told you we should have left before the storm came in tonight",
+            &no_names(),
+        );
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.chars().count() <= 42));
+        assert!(lines[0].chars().count() <= lines[1].chars().count());
+    }
+
+    #[test]
+    fn test_lineBreaker_breakLines_shouldNotBreakAfterArticle() {
+        let breaker = LineBreaker::new(LineBreakConfig::for_language("fr").with_max_chars_per_line(30));
+
+        let result = breaker.break_lines("Je pense que nous devrions prendre la voiture ce soir", &no_names());
+
+        let top = result.lines().next().unwrap();
+        let last_word = top.split_whitespace().last().unwrap();
+        assert!(!["la", "le", "les", "de"].contains(&last_word), "{}", result);
+    }
+
+    #[test]
+    fn test_lineBreaker_breakLines_shouldNotSplitNames() {
+        let breaker = LineBreaker::new(LineBreakConfig::for_language("en").with_max_chars_per_line(25));
+        let names: HashSet<String> = ["Mary Jane Watson".to_string()].into_iter().collect();
+
+        let result = breaker.break_lines("Have you seen Mary Jane Watson today?", &names);
+
+        assert!(result.contains("Mary Jane Watson"), "{}", result);
+    }
+
+    #[test]
+    fn test_lineBreaker_breakLines_shouldKeepDialogueLines() {
+        let breaker = LineBreaker::default();
+        let text = "- Are you coming?\n- No.";
+
+        assert_eq!(breaker.break_lines(text, &no_names()), text);
+        assert!(breaker.is_well_formed(text, &no_names()));
+    }
+
+    #[test]
+    fn test_lineBreaker_isWellFormed_shouldRejectThreeLines() {
+        let breaker = LineBreaker::default();
+
+        assert!(!breaker.is_well_formed("One\nTwo\nThree", &no_names()));
+        assert!(!breaker.is_well_formed("We went to the\nbeach", &no_names()));
+    }
+}
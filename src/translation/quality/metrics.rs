@@ -498,6 +498,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_qualityScore_grade_shouldReturnCorrectGrade() {
         let mut score = QualityScore::default();
         score.overall = 0.95;
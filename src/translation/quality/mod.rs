@@ -7,9 +7,13 @@
 // Allow dead code in experimental quality modules
 #![allow(dead_code)]
 
+pub mod back_translation;
 pub mod consistency;
 pub mod errors;
+pub mod judge;
 pub mod language_pairs;
+pub mod line_breaking;
 pub mod metrics;
+pub mod reference;
 pub mod repair;
 pub mod semantic;
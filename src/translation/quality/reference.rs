@@ -0,0 +1,621 @@
+/*!
+ * Reference-based quality metrics.
+ *
+ * Scores a machine translation against an existing human subtitle track
+ * in the same language. Cues are aligned by time, since the two tracks
+ * rarely split dialogue the same way, and chrF, BLEU and TER are reported
+ * for the whole file, for each scene and for each entry.
+ *
+ * - chrF: character n-gram F-score (n = 1..6, beta = 2), whitespace ignored
+ * - BLEU: word n-gram precision (n = 1..4) with brevity penalty
+ * - TER: word edit distance over reference length; block shifts are not
+ *   modelled, so it is an upper bound of full TER (lower is better)
+ */
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::subtitle_processor::SubtitleEntry;
+use crate::translation::context::{SceneDetectionConfig, SceneDetector};
+use crate::translation::document::SubtitleDocument;
+
+/// Formatting tags, which are not part of the compared text
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>|\{[^}]*\}").unwrap());
+
+/// Word tokens: runs of word characters or single punctuation marks
+static TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\w+|[^\w\s]").unwrap());
+
+/// Highest character n-gram order for chrF
+const CHRF_ORDER: usize = 6;
+
+/// Recall weight for chrF
+const CHRF_BETA: f64 = 2.0;
+
+/// Highest word n-gram order for BLEU
+const BLEU_ORDER: usize = 4;
+
+/// chrF, BLEU and TER scores on a 0-100 scale
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ReferenceScores {
+    /// Character n-gram F-score (higher is better)
+    pub chrf: f64,
+    /// BLEU (higher is better)
+    pub bleu: f64,
+    /// Translation edit rate (lower is better, may exceed 100)
+    pub ter: f64,
+}
+
+impl ReferenceScores {
+    /// Format the scores on one line.
+    pub fn format(&self) -> String {
+        format!("chrF {:.1}  BLEU {:.1}  TER {:.1}", self.chrf, self.bleu, self.ter)
+    }
+}
+
+/// Sufficient statistics for the three metrics
+///
+/// Corpus-level scores are computed from summed statistics rather than by
+/// averaging sentence scores, as the metrics are defined.
+#[derive(Debug, Clone, Default)]
+pub struct MetricStats {
+    chrf_matches: [usize; CHRF_ORDER],
+    chrf_hypothesis: [usize; CHRF_ORDER],
+    chrf_reference: [usize; CHRF_ORDER],
+    bleu_matches: [usize; BLEU_ORDER],
+    bleu_totals: [usize; BLEU_ORDER],
+    hypothesis_words: usize,
+    reference_words: usize,
+    edits: usize,
+}
+
+impl MetricStats {
+    /// Collect statistics for a hypothesis and its reference.
+    pub fn from_pair(hypothesis: &str, reference: &str) -> Self {
+        let mut stats = Self::default();
+
+        let hyp_chars: Vec<char> = normalize(hypothesis).chars().filter(|c| !c.is_whitespace()).collect();
+        let ref_chars: Vec<char> = normalize(reference).chars().filter(|c| !c.is_whitespace()).collect();
+        for n in 1..=CHRF_ORDER {
+            let hyp = ngram_counts(&hyp_chars, n);
+            let reference = ngram_counts(&ref_chars, n);
+            stats.chrf_matches[n - 1] = overlap(&hyp, &reference);
+            stats.chrf_hypothesis[n - 1] = hyp.values().sum();
+            stats.chrf_reference[n - 1] = reference.values().sum();
+        }
+
+        let hyp_words = tokenize(hypothesis);
+        let ref_words = tokenize(reference);
+        for n in 1..=BLEU_ORDER {
+            let hyp = ngram_counts(&hyp_words, n);
+            let reference = ngram_counts(&ref_words, n);
+            stats.bleu_matches[n - 1] = overlap(&hyp, &reference);
+            stats.bleu_totals[n - 1] = hyp.values().sum();
+        }
+
+        stats.hypothesis_words = hyp_words.len();
+        stats.reference_words = ref_words.len();
+        stats.edits = edit_distance(&hyp_words, &ref_words);
+        stats
+    }
+
+    /// Add another set of statistics to this one.
+    pub fn add(&mut self, other: &MetricStats) {
+        for n in 0..CHRF_ORDER {
+            self.chrf_matches[n] += other.chrf_matches[n];
+            self.chrf_hypothesis[n] += other.chrf_hypothesis[n];
+            self.chrf_reference[n] += other.chrf_reference[n];
+        }
+        for n in 0..BLEU_ORDER {
+            self.bleu_matches[n] += other.bleu_matches[n];
+            self.bleu_totals[n] += other.bleu_totals[n];
+        }
+        self.hypothesis_words += other.hypothesis_words;
+        self.reference_words += other.reference_words;
+        self.edits += other.edits;
+    }
+
+    /// Compute the scores.
+    ///
+    /// `smooth` applies exponential BLEU smoothing, which keeps short
+    /// segments without a matching 4-gram from scoring zero.
+    pub fn scores(&self, smooth: bool) -> ReferenceScores {
+        ReferenceScores {
+            chrf: self.chrf(),
+            bleu: self.bleu(smooth),
+            ter: self.ter(),
+        }
+    }
+
+    fn chrf(&self) -> f64 {
+        let mut precision = 0.0;
+        let mut recall = 0.0;
+        let mut orders = 0;
+        for n in 0..CHRF_ORDER {
+            if self.chrf_hypothesis[n] == 0 || self.chrf_reference[n] == 0 {
+                continue;
+            }
+            precision += self.chrf_matches[n] as f64 / self.chrf_hypothesis[n] as f64;
+            recall += self.chrf_matches[n] as f64 / self.chrf_reference[n] as f64;
+            orders += 1;
+        }
+
+        if orders == 0 {
+            return if self.chrf_hypothesis[0] == 0 && self.chrf_reference[0] == 0 { 100.0 } else { 0.0 };
+        }
+        let precision = precision / orders as f64;
+        let recall = recall / orders as f64;
+        if precision + recall == 0.0 {
+            return 0.0;
+        }
+
+        let beta2 = CHRF_BETA * CHRF_BETA;
+        100.0 * (1.0 + beta2) * precision * recall / (beta2 * precision + recall)
+    }
+
+    fn bleu(&self, smooth: bool) -> f64 {
+        if self.hypothesis_words == 0 {
+            return if self.reference_words == 0 { 100.0 } else { 0.0 };
+        }
+
+        let mut log_precision = 0.0;
+        let mut smoothing = 1.0;
+        for n in 0..BLEU_ORDER {
+            let total = self.bleu_totals[n];
+            let matches = self.bleu_matches[n];
+            let precision = if total == 0 {
+                // Hypothesis shorter than n words: nothing to count at this order
+                if smooth { 1.0 } else { return 0.0 }
+            } else if matches == 0 {
+                if !smooth {
+                    return 0.0;
+                }
+                smoothing *= 2.0;
+                1.0 / (smoothing * total as f64)
+            } else {
+                matches as f64 / total as f64
+            };
+            log_precision += precision.ln() / BLEU_ORDER as f64;
+        }
+
+        let brevity_penalty = if self.hypothesis_words < self.reference_words {
+            (1.0 - self.reference_words as f64 / self.hypothesis_words as f64).exp()
+        } else {
+            1.0
+        };
+
+        100.0 * brevity_penalty * log_precision.exp()
+    }
+
+    fn ter(&self) -> f64 {
+        if self.reference_words == 0 {
+            return if self.edits == 0 { 0.0 } else { 100.0 };
+        }
+        100.0 * self.edits as f64 / self.reference_words as f64
+    }
+}
+
+/// Strip tags and join lines.
+fn normalize(text: &str) -> String {
+    TAG_REGEX
+        .replace_all(text, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split text into word and punctuation tokens.
+fn tokenize(text: &str) -> Vec<String> {
+    TOKEN_REGEX
+        .find_iter(&normalize(text))
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Count the n-grams of a sequence.
+fn ngram_counts<T: Clone + Eq + std::hash::Hash>(items: &[T], n: usize) -> HashMap<Vec<T>, usize> {
+    let mut counts = HashMap::new();
+    if items.len() >= n {
+        for window in items.windows(n) {
+            *counts.entry(window.to_vec()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Clipped n-gram matches between two count maps.
+fn overlap<T: Eq + std::hash::Hash>(hypothesis: &HashMap<T, usize>, reference: &HashMap<T, usize>) -> usize {
+    hypothesis
+        .iter()
+        .map(|(gram, count)| reference.get(gram).map_or(0, |r| (*count).min(*r)))
+        .sum()
+}
+
+/// Word-level Levenshtein distance.
+fn edit_distance(hypothesis: &[String], reference: &[String]) -> usize {
+    let mut previous: Vec<usize> = (0..=reference.len()).collect();
+    for (i, hyp) in hypothesis.iter().enumerate() {
+        let mut current = vec![i + 1; reference.len() + 1];
+        for (j, reference_word) in reference.iter().enumerate() {
+            let substitution = previous[j] + usize::from(hyp != reference_word);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[reference.len()]
+}
+
+/// A translated cue with the reference text aligned to it
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryEvaluation {
+    /// Sequence number of the translated cue
+    pub seq_num: usize,
+    /// Start time in milliseconds
+    pub start_time_ms: u64,
+    /// End time in milliseconds
+    pub end_time_ms: u64,
+    /// Machine translation
+    pub hypothesis: String,
+    /// Human reference text of the overlapping cues
+    pub reference: String,
+    /// Sequence numbers of the reference cues aligned to this one
+    pub reference_seq_nums: Vec<usize>,
+    /// Sentence-level scores
+    pub scores: ReferenceScores,
+    #[serde(skip)]
+    stats: MetricStats,
+}
+
+/// Scores for one scene
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneEvaluation {
+    /// Scene number
+    pub scene_id: usize,
+    /// First translated cue in the scene
+    pub first_seq_num: usize,
+    /// Last translated cue in the scene
+    pub last_seq_num: usize,
+    /// Start time in milliseconds
+    pub start_time_ms: u64,
+    /// End time in milliseconds
+    pub end_time_ms: u64,
+    /// Scores over the scene's cues
+    pub scores: ReferenceScores,
+}
+
+/// Reference evaluation of one file
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceReport {
+    /// File that was evaluated
+    pub file: String,
+    /// Scores over the whole file
+    pub overall: ReferenceScores,
+    /// Translated cues with no overlapping reference cue
+    pub unaligned_hypothesis: usize,
+    /// Reference cues with no overlapping translated cue
+    pub unaligned_reference: usize,
+    /// Per-scene scores
+    pub scenes: Vec<SceneEvaluation>,
+    /// Per-entry scores
+    pub entries: Vec<EntryEvaluation>,
+}
+
+impl ReferenceReport {
+    /// One-line summary of the file scores.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} ({} entries, {} scenes)",
+            self.file,
+            self.overall.format(),
+            self.entries.len(),
+            self.scenes.len()
+        )
+    }
+
+    /// Entries with the lowest chrF, worst first.
+    pub fn worst_entries(&self, count: usize) -> Vec<&EntryEvaluation> {
+        let mut entries: Vec<&EntryEvaluation> = self.entries.iter().collect();
+        entries.sort_by(|a, b| a.scores.chrf.total_cmp(&b.scores.chrf));
+        entries.truncate(count);
+        entries
+    }
+
+    /// Format the report for display, listing the `worst` lowest-scoring entries.
+    pub fn format_report(&self, worst: usize) -> String {
+        let mut out = format!("{}\n", self.summary());
+        if self.unaligned_hypothesis > 0 || self.unaligned_reference > 0 {
+            out.push_str(&format!(
+                "  Unaligned: {} translated cues, {} reference cues\n",
+                self.unaligned_hypothesis, self.unaligned_reference
+            ));
+        }
+
+        out.push_str("  Scenes:\n");
+        for scene in &self.scenes {
+            out.push_str(&format!(
+                "    #{:<3} entries {}-{}: {}\n",
+                scene.scene_id,
+                scene.first_seq_num,
+                scene.last_seq_num,
+                scene.scores.format()
+            ));
+        }
+
+        if worst > 0 && !self.entries.is_empty() {
+            out.push_str("  Lowest-scoring entries:\n");
+            for entry in self.worst_entries(worst) {
+                out.push_str(&format!(
+                    "    {}: {}\n      MT:  {}\n      REF: {}\n",
+                    entry.seq_num,
+                    entry.scores.format(),
+                    normalize(&entry.hypothesis),
+                    normalize(&entry.reference)
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+/// Scores translations against a human reference track
+pub struct ReferenceEvaluator {
+    scene_config: SceneDetectionConfig,
+}
+
+impl ReferenceEvaluator {
+    /// Create an evaluator with default scene detection.
+    pub fn new() -> Self {
+        Self {
+            scene_config: SceneDetectionConfig::default(),
+        }
+    }
+
+    /// Use a custom scene detection configuration.
+    pub fn with_scene_config(mut self, config: SceneDetectionConfig) -> Self {
+        self.scene_config = config;
+        self
+    }
+
+    /// Align the reference cues to the translated ones by time.
+    ///
+    /// Each reference cue goes to the translated cue it overlaps most, so
+    /// one translated cue can collect several reference cues. Returns the
+    /// reference cues per translated cue and the unaligned reference cues.
+    pub fn align(hypothesis: &[SubtitleEntry], reference: &[SubtitleEntry]) -> (Vec<Vec<usize>>, Vec<usize>) {
+        let mut aligned = vec![Vec::new(); hypothesis.len()];
+        let mut unaligned = Vec::new();
+
+        for (ref_idx, ref_entry) in reference.iter().enumerate() {
+            let best = hypothesis
+                .iter()
+                .enumerate()
+                .map(|(idx, hyp)| {
+                    let start = hyp.start_time_ms.max(ref_entry.start_time_ms);
+                    let end = hyp.end_time_ms.min(ref_entry.end_time_ms);
+                    (idx, end.saturating_sub(start))
+                })
+                .filter(|(_, overlap)| *overlap > 0)
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+
+            match best {
+                Some((idx, _)) => aligned[idx].push(ref_idx),
+                None => unaligned.push(ref_idx),
+            }
+        }
+
+        (aligned, unaligned)
+    }
+
+    /// Evaluate a translation against the reference track.
+    ///
+    /// Reference cues with no translated counterpart count as missed lines:
+    /// they are scored against an empty hypothesis in the file and scene totals.
+    pub fn evaluate(&self, file: &str, hypothesis: &[SubtitleEntry], reference: &[SubtitleEntry]) -> ReferenceReport {
+        let (aligned, unaligned_reference) = Self::align(hypothesis, reference);
+        let missed: Vec<(u64, MetricStats)> = unaligned_reference
+            .iter()
+            .map(|&i| (reference[i].start_time_ms, MetricStats::from_pair("", &reference[i].text)))
+            .collect();
+
+        let entries: Vec<EntryEvaluation> = hypothesis
+            .iter()
+            .zip(&aligned)
+            .map(|(hyp, ref_indices)| {
+                let reference_text = ref_indices
+                    .iter()
+                    .map(|&i| reference[i].text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let stats = MetricStats::from_pair(&hyp.text, &reference_text);
+                EntryEvaluation {
+                    seq_num: hyp.seq_num,
+                    start_time_ms: hyp.start_time_ms,
+                    end_time_ms: hyp.end_time_ms,
+                    hypothesis: hyp.text.clone(),
+                    reference: reference_text,
+                    reference_seq_nums: ref_indices.iter().map(|&i| reference[i].seq_num).collect(),
+                    scores: stats.scores(true),
+                    stats,
+                }
+            })
+            .collect();
+
+        let mut total = MetricStats::default();
+        for entry in &entries {
+            total.add(&entry.stats);
+        }
+        for (_, stats) in &missed {
+            total.add(stats);
+        }
+
+        ReferenceReport {
+            file: file.to_string(),
+            overall: total.scores(false),
+            unaligned_hypothesis: aligned.iter().filter(|a| a.is_empty()).count(),
+            unaligned_reference: unaligned_reference.len(),
+            scenes: self.evaluate_scenes(hypothesis, &entries, &missed),
+            entries,
+        }
+    }
+
+    /// Group the entry statistics by detected scene.
+    ///
+    /// Missed reference cues go to the last scene starting at or before them,
+    /// or to the first scene when they precede every translated cue.
+    fn evaluate_scenes(
+        &self,
+        hypothesis: &[SubtitleEntry],
+        entries: &[EntryEvaluation],
+        missed: &[(u64, MetricStats)],
+    ) -> Vec<SceneEvaluation> {
+        let doc = SubtitleDocument::from_entries(hypothesis.to_vec(), "und");
+        let scenes = SceneDetector::new(self.scene_config.clone()).detect_scenes(&doc.entries);
+
+        let mut evaluations: Vec<(SceneEvaluation, MetricStats)> = scenes
+            .iter()
+            .filter_map(|scene| {
+                let first = entries.iter().position(|e| e.seq_num == scene.start_entry_id)?;
+                let last = entries.iter().position(|e| e.seq_num == scene.end_entry_id)?;
+                let scene_entries = &entries[first..=last];
+
+                let mut stats = MetricStats::default();
+                for entry in scene_entries {
+                    stats.add(&entry.stats);
+                }
+
+                let evaluation = SceneEvaluation {
+                    scene_id: scene.id,
+                    first_seq_num: entries[first].seq_num,
+                    last_seq_num: entries[last].seq_num,
+                    start_time_ms: entries[first].start_time_ms,
+                    end_time_ms: entries[last].end_time_ms,
+                    scores: ReferenceScores::default(),
+                };
+                Some((evaluation, stats))
+            })
+            .collect();
+
+        for (start_time_ms, missed_stats) in missed {
+            let scene = evaluations
+                .iter()
+                .rposition(|(scene, _)| scene.start_time_ms <= *start_time_ms)
+                .unwrap_or(0);
+            if let Some((_, stats)) = evaluations.get_mut(scene) {
+                stats.add(missed_stats);
+            }
+        }
+
+        evaluations
+            .into_iter()
+            .map(|(mut scene, stats)| {
+                scene.scores = stats.scores(true);
+                scene
+            })
+            .collect()
+    }
+}
+
+impl Default for ReferenceEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seq: usize, start: u64, end: u64, text: &str) -> SubtitleEntry {
+        SubtitleEntry::new(seq, start, end, text.to_string())
+    }
+
+    #[test]
+    fn test_metricStats_scores_shouldBePerfectForIdenticalText() {
+        let scores = MetricStats::from_pair("<i>The cat sat on the mat.</i>", "The cat sat\non the mat.").scores(false);
+
+        assert!((scores.chrf - 100.0).abs() < 1e-9);
+        assert!((scores.bleu - 100.0).abs() < 1e-9);
+        assert_eq!(scores.ter, 0.0);
+    }
+
+    #[test]
+    fn test_metricStats_scores_shouldMatchKnownValues() {
+        let stats = MetricStats::from_pair("the cat sat on a mat", "the cat sat on the mat");
+        let scores = stats.scores(false);
+
+        // 1 substitution over 6 reference words
+        assert!((scores.ter - 100.0 / 6.0).abs() < 1e-9);
+        // Precisions 5/6, 3/5, 2/4, 1/3 with no brevity penalty
+        let expected_bleu = 100.0 * ((5.0f64 / 6.0) * (3.0 / 5.0) * (2.0 / 4.0) * (1.0 / 3.0)).powf(0.25);
+        assert!((scores.bleu - expected_bleu).abs() < 1e-9);
+        assert!(scores.chrf > 60.0 && scores.chrf < 100.0);
+    }
+
+    #[test]
+    fn test_metricStats_scores_shouldSmoothShortSegments() {
+        let stats = MetricStats::from_pair("Go now", "Go away now");
+
+        assert_eq!(stats.scores(false).bleu, 0.0);
+        assert!(stats.scores(true).bleu > 0.0);
+    }
+
+    #[test]
+    fn test_referenceEvaluator_align_shouldMergeSplitReferenceCues() {
+        let hypothesis = vec![entry(1, 1000, 4000, "Hello there, how are you?"), entry(2, 6000, 7000, "Fine.")];
+        let reference = vec![
+            entry(1, 1000, 2400, "Hello there,"),
+            entry(2, 2500, 4000, "how are you?"),
+            entry(3, 9000, 9500, "Bye."),
+        ];
+
+        let (aligned, unaligned) = ReferenceEvaluator::align(&hypothesis, &reference);
+
+        assert_eq!(aligned, vec![vec![0, 1], vec![]]);
+        assert_eq!(unaligned, vec![2]);
+    }
+
+    #[test]
+    fn test_referenceEvaluator_evaluate_shouldReportFileScenesAndEntries() {
+        let hypothesis = vec![
+            entry(1, 1000, 2000, "Where is the car?"),
+            entry(2, 2100, 3000, "In the garage."),
+            entry(3, 20000, 21000, "I am hungry."),
+        ];
+        let reference = vec![
+            entry(1, 1000, 2000, "Where is the car?"),
+            entry(2, 2100, 3000, "In the garage."),
+            entry(3, 20000, 21000, "Let's go home."),
+        ];
+
+        let report = ReferenceEvaluator::new().evaluate("ep01.mkv", &hypothesis, &reference);
+
+        assert_eq!(report.entries.len(), 3);
+        assert_eq!(report.scenes.len(), 2);
+        assert!((report.scenes[0].scores.chrf - 100.0).abs() < 1e-9);
+        assert!(report.scenes[1].scores.chrf < 50.0);
+        assert!(report.overall.chrf < 100.0 && report.overall.chrf > report.scenes[1].scores.chrf);
+        assert_eq!(report.worst_entries(1)[0].seq_num, 3);
+        assert!(report.format_report(1).contains("Lowest-scoring entries"));
+    }
+
+    #[test]
+    fn test_referenceEvaluator_evaluate_withUnalignedReference_shouldCountItAsMissed() {
+        let hypothesis = vec![entry(1, 1000, 2000, "Where is the car?")];
+        let reference = vec![
+            entry(1, 1000, 2000, "Where is the car?"),
+            entry(2, 2500, 3000, "In the garage."),
+        ];
+
+        let report = ReferenceEvaluator::new().evaluate("ep01.mkv", &hypothesis, &reference);
+
+        assert_eq!(report.unaligned_reference, 1);
+        assert!((report.entries[0].scores.chrf - 100.0).abs() < 1e-9);
+        assert!(report.overall.chrf < 100.0);
+        assert!(report.overall.ter > 0.0);
+        assert_eq!(report.scenes.len(), 1);
+        assert_eq!(report.scenes[0].scores, report.overall);
+    }
+}
@@ -6,10 +6,13 @@
  * - Terminology correction
  * - Length adjustment
  * - Punctuation normalization
+ * - Line breaking (reflow to broadcast line rules)
  */
 
 use crate::translation::document::{DocumentEntry, FormattingTag, Glossary};
 
+use super::line_breaking::{LineBreakConfig, LineBreaker};
+
 /// Types of repairs that can be applied.
 #[derive(Debug, Clone, PartialEq)]
 pub enum RepairStrategy {
@@ -28,6 +31,9 @@ pub enum RepairStrategy {
     /// Truncate overly long translation
     Truncate { max_ratio: f32 },
 
+    /// Reflow line breaks to the line rules
+    ReflowLines,
+
     /// Request retranslation
     Retranslate,
 
@@ -49,6 +55,7 @@ impl RepairStrategy {
             RepairStrategy::ApplyGlossary => 80,
             RepairStrategy::NormalizePunctuation => 70,
             RepairStrategy::Truncate { .. } => 60,
+            RepairStrategy::ReflowLines => 50,
             RepairStrategy::Retranslate => 10,
             RepairStrategy::NoRepair => 0,
         }
@@ -132,6 +139,12 @@ pub struct RepairConfig {
 
     /// Target quote style (if normalizing)
     pub target_quote_style: QuoteStyle,
+
+    /// Enable line reflow
+    pub reflow_lines: bool,
+
+    /// Line breaking rules (if reflowing)
+    pub line_break: LineBreakConfig,
 }
 
 impl Default for RepairConfig {
@@ -144,11 +157,38 @@ impl Default for RepairConfig {
             truncate_long: false, // Risky, disabled by default
             max_length_ratio: 1.5,
             target_quote_style: QuoteStyle::Double,
+            reflow_lines: false, // Needs the target language's line rules
+            line_break: LineBreakConfig::default(),
         }
     }
 }
 
 impl RepairConfig {
+    /// Create a config that only reflows lines, with the target language's line rules.
+    pub fn reflow_only(target_language: &str) -> Self {
+        Self {
+            restore_formatting: false,
+            apply_glossary: false,
+            normalize_punctuation: false,
+            preserve_names: false,
+            reflow_lines: true,
+            ..Self::default()
+        }
+        .with_target_language(target_language)
+    }
+
+    /// Use the line-breaking rules of the target language.
+    pub fn with_target_language(mut self, language: &str) -> Self {
+        self.line_break = LineBreakConfig::for_language(language);
+        self
+    }
+
+    /// Override the line length limit used when reflowing.
+    pub fn with_max_chars_per_line(mut self, max_chars: usize) -> Self {
+        self.line_break = self.line_break.with_max_chars_per_line(max_chars);
+        self
+    }
+
     /// Create an aggressive config that repairs everything.
     pub fn aggressive() -> Self {
         Self {
@@ -159,6 +199,8 @@ impl RepairConfig {
             truncate_long: true,
             max_length_ratio: 1.5,
             target_quote_style: QuoteStyle::Double,
+            reflow_lines: true,
+            line_break: LineBreakConfig::default(),
         }
     }
 
@@ -172,6 +214,8 @@ impl RepairConfig {
             truncate_long: false,
             max_length_ratio: 2.0,
             target_quote_style: QuoteStyle::Double,
+            reflow_lines: false,
+            line_break: LineBreakConfig::default(),
         }
     }
 }
@@ -261,6 +305,16 @@ impl RepairEngine {
 
         if self.config.truncate_long {
             if let Some(repair) = self.repair_length(entry, &current) {
+                if repair.success {
+                    current = repair.repaired.clone().unwrap_or(current);
+                }
+                repairs.push(repair);
+            }
+        }
+
+        // Reflow last so it sees the final wording
+        if self.config.reflow_lines {
+            if let Some(repair) = self.repair_line_breaks(&current, glossary) {
                 repairs.push(repair);
             }
         }
@@ -268,6 +322,26 @@ impl RepairEngine {
         repairs
     }
 
+    /// Reflow line breaks that break the line rules.
+    fn repair_line_breaks(&self, translated: &str, glossary: &Glossary) -> Option<SmartRepair> {
+        let breaker = LineBreaker::new(self.config.line_break.clone());
+        if breaker.is_well_formed(translated, &glossary.character_names) {
+            return None;
+        }
+
+        let reflowed = breaker.break_lines(translated, &glossary.character_names);
+        if reflowed == translated {
+            return None;
+        }
+
+        Some(SmartRepair::success(
+            RepairStrategy::ReflowLines,
+            translated,
+            &reflowed,
+            &format!("Reflowed into {} line(s)", reflowed.lines().count()),
+        ))
+    }
+
     /// Repair missing character names.
     fn repair_names(
         &self,
@@ -628,6 +702,40 @@ mod tests {
         assert!(repair.repaired.as_ref().unwrap().contains("point d'extraction"));
     }
 
+    #[test]
+    fn test_repairEngine_repairEntry_shouldReflowLongSingleLine() {
+        let entry = create_entry(
+            1,
+            "I told you we should have left before the storm.",
+            Some("Je t'avais dit qu'on aurait dû partir avant la tempête de ce soir"),
+        );
+        let engine = RepairEngine::with_config(RepairConfig::reflow_only("fr"));
+
+        let repairs = engine.repair_entry(&entry, &Glossary::new());
+        let reflow = repairs
+            .iter()
+            .find(|r| r.strategy == RepairStrategy::ReflowLines)
+            .expect("long line should be reflowed");
+
+        let text = engine.get_final_text(&repairs).unwrap();
+        assert_eq!(reflow.repaired.as_deref(), Some(text.as_str()));
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().all(|l| l.chars().count() <= 42));
+    }
+
+    #[test]
+    fn test_repairConfig_withMaxCharsPerLine_shouldReflowToTheLimit() {
+        let entry = create_entry(1, "I told you we'd leave tonight.", Some("Je t'avais dit qu'on partirait ce soir."));
+
+        let default_engine = RepairEngine::with_config(RepairConfig::reflow_only("fr"));
+        assert!(default_engine.get_final_text(&default_engine.repair_entry(&entry, &Glossary::new())).is_none());
+
+        let engine = RepairEngine::with_config(RepairConfig::reflow_only("fr").with_max_chars_per_line(30));
+        let text = engine.get_final_text(&engine.repair_entry(&entry, &Glossary::new())).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().all(|l| l.chars().count() <= 30));
+    }
+
     #[test]
     fn test_repairStrategy_priority_shouldOrderCorrectly() {
         assert!(RepairStrategy::PreserveName.priority() > RepairStrategy::RestoreFormatting.priority());
@@ -10,6 +10,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::translation::core::TranslationService;
+use crate::translation::prompts::engine::Template;
 
 /// Result of semantic validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,12 +185,22 @@ impl SemanticValidationConfig {
 /// Semantic validator using LLM
 pub struct SemanticValidator {
     config: SemanticValidationConfig,
+    prompt_template: Option<Template>,
 }
 
 impl SemanticValidator {
     /// Create a new semantic validator with the given configuration
     pub fn new(config: SemanticValidationConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            prompt_template: None,
+        }
+    }
+
+    /// Use a user-supplied template for the validation prompt.
+    pub fn with_prompt_template(mut self, template: Option<Template>) -> Self {
+        self.prompt_template = template;
+        self
     }
 
     /// Create a validator with default configuration
@@ -210,7 +221,7 @@ impl SemanticValidator {
         let prompt = self.build_validation_prompt(original, translated, source_lang, target_lang);
 
         // Call the LLM for semantic analysis
-        let response = service.translate_text(&prompt, "validation", "semantic_check").await?;
+        let response = service.translate_text_uncached(&prompt, "validation", "semantic_check").await?;
 
         // Parse the response
         self.parse_validation_response(&response, original, translated)
@@ -235,6 +246,16 @@ impl SemanticValidator {
             checks.push("omitted information");
         }
 
+        if let Some(ref template) = self.prompt_template {
+            return template.render(&serde_json::json!({
+                "original": original,
+                "translated": translated,
+                "source_language": source_lang,
+                "target_language": target_lang,
+                "checks": checks,
+            }));
+        }
+
         format!(
             r#"Analyze the semantic equivalence between these texts.
 
@@ -502,6 +523,20 @@ mod tests {
         assert!(prompt.contains("meaning preservation"));
     }
 
+    #[test]
+    fn test_semanticValidator_buildPrompt_withTemplate_shouldRenderTemplate() {
+        let template = Template::parse(
+            "{{ original }} => {{ translated }} ({% for check in checks %}{{ check }}{% if not loop.last %}, {% endif %}{% endfor %})",
+        )
+        .unwrap();
+        let validator = SemanticValidator::new(SemanticValidationConfig::lenient())
+            .with_prompt_template(Some(template));
+
+        let prompt = validator.build_validation_prompt("Hello", "Bonjour", "en", "fr");
+
+        assert_eq!(prompt, "Hello => Bonjour (meaning preservation, omitted information)");
+    }
+
     #[test]
     fn test_semanticValidator_extractJson_shouldHandleWrappedJson() {
         let validator = SemanticValidator::with_defaults();
@@ -0,0 +1,221 @@
+/*!
+ * Per-batch telemetry of a translation run.
+ *
+ * A `MetricsRecorder` attached to a `TranslationService` counts every
+ * request the service makes: cache hits, tokens, provider latency and
+ * failed attempts by error kind. Translators drain it after each batch into
+ * a `BatchTelemetry`, which sessions store for `sessions report`.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::app_config::TokenPricing;
+use crate::translation::pipeline::PipelinePhase;
+use crate::translation::quality::errors::TranslationErrorKind;
+
+/// Requests made by a service since the recorder was last drained
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestMetrics {
+    /// Requests sent to the provider
+    pub requests: usize,
+    /// Translations served from the cache
+    pub cache_hits: usize,
+    /// Prompt tokens reported by the provider
+    pub prompt_tokens: u64,
+    /// Completion tokens reported by the provider
+    pub completion_tokens: u64,
+    /// Latency of each successful request
+    pub latencies_ms: Vec<u64>,
+    /// Failed attempts by error kind
+    pub retries: BTreeMap<String, usize>,
+}
+
+/// Shared collector of request metrics
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRecorder {
+    metrics: Arc<Mutex<RequestMetrics>>,
+}
+
+impl MetricsRecorder {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a translation served from the cache
+    pub fn record_cache_hit(&self) {
+        self.update(|m| m.cache_hits += 1);
+    }
+
+    /// Record a successful provider request
+    pub fn record_response(&self, prompt_tokens: Option<u64>, completion_tokens: Option<u64>, latency: Duration) {
+        self.update(|m| {
+            m.requests += 1;
+            m.prompt_tokens += prompt_tokens.unwrap_or(0);
+            m.completion_tokens += completion_tokens.unwrap_or(0);
+            m.latencies_ms.push(latency.as_millis() as u64);
+        });
+    }
+
+    /// Record a failed attempt, counted as a request when it reached the provider
+    pub fn record_failure(&self, kind: TranslationErrorKind, sent: bool) {
+        self.update(|m| {
+            if sent {
+                m.requests += 1;
+            }
+            *m.retries.entry(kind.as_str().to_string()).or_default() += 1;
+        });
+    }
+
+    /// Take the metrics recorded so far, leaving the recorder empty
+    pub fn take(&self) -> RequestMetrics {
+        let mut taken = RequestMetrics::default();
+        self.update(|m| taken = std::mem::take(m));
+        taken
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RequestMetrics)) {
+        let mut metrics = self.metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut metrics);
+    }
+}
+
+/// Telemetry of one batch of a run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchTelemetry {
+    /// Pipeline phase the batch belongs to
+    pub phase: String,
+    /// Position of the batch within its phase
+    pub batch_index: usize,
+    /// Entries in the batch
+    pub entry_count: usize,
+    /// Start time (RFC 3339)
+    pub started_at: String,
+    /// Wall time of the batch
+    pub duration_ms: u64,
+    /// Requests sent to the provider
+    pub requests: usize,
+    /// Translations served from the cache
+    pub cache_hits: usize,
+    /// Prompt tokens
+    pub prompt_tokens: u64,
+    /// Completion tokens
+    pub completion_tokens: u64,
+    /// Cost in US dollars, when the provider has a price
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    /// Latency of each successful request
+    #[serde(default)]
+    pub latencies_ms: Vec<u64>,
+    /// Failed attempts by error kind
+    #[serde(default)]
+    pub retries: BTreeMap<String, usize>,
+    /// Validation issues found, by kind
+    #[serde(default)]
+    pub issues: BTreeMap<String, usize>,
+}
+
+impl BatchTelemetry {
+    /// Build the telemetry of a batch from the metrics recorded while it ran
+    pub fn new(
+        phase: PipelinePhase,
+        batch_index: usize,
+        entry_count: usize,
+        started_at: DateTime<Utc>,
+        duration: Duration,
+        metrics: RequestMetrics,
+        pricing: Option<TokenPricing>,
+    ) -> Self {
+        Self {
+            phase: phase.as_str().to_string(),
+            batch_index,
+            entry_count,
+            started_at: started_at.to_rfc3339(),
+            duration_ms: duration.as_millis() as u64,
+            requests: metrics.requests,
+            cache_hits: metrics.cache_hits,
+            prompt_tokens: metrics.prompt_tokens,
+            completion_tokens: metrics.completion_tokens,
+            cost_usd: pricing.map(|p| p.cost(metrics.prompt_tokens, metrics.completion_tokens)),
+            latencies_ms: metrics.latencies_ms,
+            retries: metrics.retries,
+            issues: BTreeMap::new(),
+        }
+    }
+
+    /// Number of failed attempts of any kind
+    pub fn retry_count(&self) -> usize {
+        self.retries.values().sum()
+    }
+}
+
+/// Nearest-rank percentile of a sorted list
+pub fn percentile(sorted: &[u64], percent: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((percent / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metricsRecorder_take_shouldDrainRecordedRequests() {
+        let recorder = MetricsRecorder::new();
+        let shared = recorder.clone();
+
+        shared.record_response(Some(100), Some(40), Duration::from_millis(250));
+        shared.record_cache_hit();
+        shared.record_failure(TranslationErrorKind::RateLimit, true);
+        shared.record_failure(TranslationErrorKind::ParseError, false);
+
+        let metrics = recorder.take();
+        assert_eq!((metrics.requests, metrics.cache_hits), (2, 1));
+        assert_eq!((metrics.prompt_tokens, metrics.completion_tokens), (100, 40));
+        assert_eq!(metrics.latencies_ms, vec![250]);
+        assert_eq!(metrics.retries.get("rate_limit"), Some(&1));
+        assert_eq!(recorder.take(), RequestMetrics::default());
+    }
+
+    #[test]
+    fn test_batchTelemetry_new_shouldPriceTokens() {
+        let metrics = RequestMetrics {
+            requests: 1,
+            prompt_tokens: 2_000_000,
+            completion_tokens: 1_000_000,
+            ..Default::default()
+        };
+        let pricing = TokenPricing { input_per_million: 0.5, output_per_million: 2.0 };
+
+        let batch = BatchTelemetry::new(
+            PipelinePhase::Translation,
+            0,
+            3,
+            Utc::now(),
+            Duration::from_secs(2),
+            metrics,
+            Some(pricing),
+        );
+
+        assert_eq!(batch.phase, "translation");
+        assert_eq!(batch.duration_ms, 2000);
+        assert_eq!(batch.cost_usd, Some(3.0));
+    }
+
+    #[test]
+    fn test_percentile_shouldUseNearestRank() {
+        let latencies = [100, 200, 300, 400, 1000];
+
+        assert_eq!(percentile(&latencies, 50.0), Some(300));
+        assert_eq!(percentile(&latencies, 90.0), Some(1000));
+        assert_eq!(percentile(&latencies, 0.0), Some(100));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+}
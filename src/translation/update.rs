@@ -0,0 +1,258 @@
+/*!
+ * Incremental update of a translated subtitle file after source edits.
+ *
+ * The source a translation was made from and the edited source are aligned
+ * on their text with `EntryDiff`. Aligned cues keep their translation and
+ * take the timing of the edited source. The remaining cues are paired by
+ * time overlap, to tell an edited line from an inserted one, and are
+ * translated again with their neighbours as context.
+ */
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+use crate::session::diff::EntryDiff;
+use crate::subtitle_processor::SubtitleEntry;
+
+/// Cues to reuse and to re-translate when updating a translation
+#[derive(Debug, Clone)]
+pub struct UpdatePlan {
+    /// Edited source entries, numbered from 1
+    source: Vec<SubtitleEntry>,
+    /// Translation of each source entry, when known
+    translations: Vec<Option<String>>,
+    /// Entries whose translation was reused
+    pub reused: usize,
+    /// Reused entries whose timing changed
+    pub retimed: usize,
+    /// Entries with a previous counterpart but a new text
+    pub edited: usize,
+    /// Entries without a previous counterpart
+    pub added: usize,
+    /// Previous entries without a counterpart in the edited source
+    pub removed: usize,
+}
+
+impl UpdatePlan {
+    /// Align the previous source and its translation with the edited source
+    pub fn new(
+        previous_source: &[SubtitleEntry],
+        previous_translation: &[SubtitleEntry],
+        source: &[SubtitleEntry],
+    ) -> Self {
+        let previous_texts = pair_translations(previous_source, previous_translation);
+        let old_texts: Vec<&str> = previous_source.iter().map(|e| e.text.as_str()).collect();
+        let new_texts: Vec<&str> = source.iter().map(|e| e.text.as_str()).collect();
+        let diff = EntryDiff::compute(&old_texts, &new_texts);
+
+        let mut plan = Self {
+            source: source
+                .iter()
+                .enumerate()
+                .map(|(i, e)| SubtitleEntry { seq_num: i + 1, ..e.clone() })
+                .collect(),
+            translations: vec![None; source.len()],
+            reused: 0,
+            retimed: 0,
+            edited: 0,
+            added: 0,
+            removed: 0,
+        };
+
+        let mut counterpart: Vec<Option<usize>> = vec![None; source.len()];
+        let mut old_used = vec![false; previous_source.len()];
+        for &(o, n) in &diff.unchanged {
+            old_used[o] = true;
+            counterpart[n] = Some(o);
+            if let Some(text) = &previous_texts[o] {
+                plan.translations[n] = Some(text.clone());
+                plan.reused += 1;
+                let old = &previous_source[o];
+                if (old.start_time_ms, old.end_time_ms) != (source[n].start_time_ms, source[n].end_time_ms) {
+                    plan.retimed += 1;
+                }
+            }
+        }
+
+        // Cues left over on both sides at the same time are edits of each other
+        for n in 0..source.len() {
+            if plan.translations[n].is_some() {
+                continue;
+            }
+            if counterpart[n].is_none() {
+                counterpart[n] = previous_source
+                    .iter()
+                    .enumerate()
+                    .filter(|(o, old)| !old_used[*o] && overlap(old, &source[n]) > 0)
+                    .max_by_key(|(_, old)| overlap(old, &source[n]))
+                    .map(|(o, _)| o);
+            }
+            match counterpart[n] {
+                Some(o) => {
+                    old_used[o] = true;
+                    plan.edited += 1;
+                }
+                None => plan.added += 1,
+            }
+        }
+        plan.removed = old_used.iter().filter(|used| !**used).count();
+
+        plan
+    }
+
+    /// Number of entries that still need a translation
+    pub fn pending_count(&self) -> usize {
+        self.translations.iter().filter(|t| t.is_none()).count()
+    }
+
+    /// Group the pending entries into requests with surrounding source entries as context
+    ///
+    /// Each item holds at most `batch_size` consecutive entries to translate,
+    /// and up to `context_count` entries on each side of them.
+    pub fn work_items(&self, batch_size: usize, context_count: usize) -> Vec<(Vec<SubtitleEntry>, Vec<SubtitleEntry>)> {
+        let batch_size = batch_size.max(1);
+        let mut items = Vec::new();
+        let mut i = 0;
+        while i < self.source.len() {
+            if self.translations[i].is_some() {
+                i += 1;
+                continue;
+            }
+            let run_end = (i..self.source.len())
+                .find(|&j| self.translations[j].is_some())
+                .unwrap_or(self.source.len());
+            for start in (i..run_end).step_by(batch_size) {
+                let end = (start + batch_size).min(run_end);
+                let before = start.saturating_sub(context_count)..start;
+                let after = end..(end + context_count).min(self.source.len());
+                let context = self.source[before].iter().chain(&self.source[after]).cloned().collect();
+                items.push((self.source[start..end].to_vec(), context));
+            }
+            i = run_end;
+        }
+        items
+    }
+
+    /// Fill in translated entries, matched on their sequence number
+    pub fn apply(&mut self, translated: &[SubtitleEntry]) {
+        for entry in translated {
+            if let Some(slot) = entry.seq_num.checked_sub(1).and_then(|i| self.translations.get_mut(i)) {
+                *slot = Some(entry.text.clone());
+            }
+        }
+    }
+
+    /// Build the updated translation, with the timing of the edited source
+    pub fn into_entries(self) -> Result<Vec<SubtitleEntry>> {
+        let pending = self.pending_count();
+        if pending > 0 {
+            return Err(anyhow!("{} entries still need a translation", pending));
+        }
+        Ok(self
+            .source
+            .into_iter()
+            .zip(self.translations)
+            .map(|(entry, text)| SubtitleEntry { text: text.unwrap_or_default(), ..entry })
+            .collect())
+    }
+}
+
+impl fmt::Display for UpdatePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} reused ({} retimed), {} edited, {} added, {} removed",
+            self.reused, self.retimed, self.edited, self.added, self.removed
+        )
+    }
+}
+
+/// Find the translation of each previous source entry
+///
+/// A translation made by yastwai has one cue per source cue; for any other
+/// file the translated cue overlapping the source cue the most is used.
+fn pair_translations(source: &[SubtitleEntry], translation: &[SubtitleEntry]) -> Vec<Option<String>> {
+    if source.len() == translation.len() {
+        return translation.iter().map(|e| Some(e.text.clone())).collect();
+    }
+    source
+        .iter()
+        .map(|s| {
+            translation
+                .iter()
+                .filter(|t| overlap(s, t) > 0)
+                .max_by_key(|t| overlap(s, t))
+                .map(|t| t.text.clone())
+        })
+        .collect()
+}
+
+/// Overlap of two cues in milliseconds
+fn overlap(a: &SubtitleEntry, b: &SubtitleEntry) -> u64 {
+    a.end_time_ms.min(b.end_time_ms).saturating_sub(a.start_time_ms.max(b.start_time_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues(items: &[(u64, &str)]) -> Vec<SubtitleEntry> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, (start, text))| SubtitleEntry::new(i + 1, *start, *start + 1000, text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_updatePlan_new_shouldReuseUnchangedCuesWithNewTiming() {
+        let previous = cues(&[(0, "Hello."), (2000, "Where is he?"), (4000, "Goodbye.")]);
+        let translation = cues(&[(0, "Bonjour."), (2000, "Où est-il ?"), (4000, "Au revoir.")]);
+        let source = cues(&[(500, "Hello."), (2000, "Where did he go?"), (4000, "Goodbye.")]);
+
+        let mut plan = UpdatePlan::new(&previous, &translation, &source);
+
+        assert_eq!((plan.reused, plan.retimed, plan.edited, plan.added, plan.removed), (2, 1, 1, 0, 0));
+        let items = plan.work_items(3, 2);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].0[0].text, "Where did he go?");
+        let context: Vec<&str> = items[0].1.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(context, vec!["Hello.", "Goodbye."]);
+
+        let mut translated = items[0].0[0].clone();
+        translated.text = "Où est-il allé ?".to_string();
+        plan.apply(&[translated]);
+        let entries = plan.into_entries().unwrap();
+        assert_eq!(entries[0].start_time_ms, 500);
+        assert_eq!(entries[0].text, "Bonjour.");
+        assert_eq!(entries[1].text, "Où est-il allé ?");
+    }
+
+    #[test]
+    fn test_updatePlan_new_shouldTellEditedFromAddedByTime() {
+        let previous = cues(&[(0, "One."), (2000, "Tow."), (6000, "Three.")]);
+        let translation = cues(&[(0, "Un."), (2000, "Deu."), (6000, "Trois.")]);
+        let source = cues(&[(0, "One."), (2000, "Two."), (4000, "Two and a half."), (6000, "Three.")]);
+
+        let plan = UpdatePlan::new(&previous, &translation, &source);
+
+        assert_eq!((plan.reused, plan.edited, plan.added, plan.removed), (2, 1, 1, 0));
+        assert_eq!(plan.pending_count(), 2);
+        assert_eq!(plan.to_string(), "2 reused (0 retimed), 1 edited, 1 added, 0 removed");
+    }
+
+    #[test]
+    fn test_updatePlan_workItems_shouldSplitRunsIntoBatches() {
+        let previous = cues(&[(0, "A"), (9000, "Z")]);
+        let source = cues(&[(0, "A"), (2000, "B"), (3000, "C"), (4000, "D"), (9000, "Z")]);
+
+        let plan = UpdatePlan::new(&previous, &previous, &source);
+        let items = plan.work_items(2, 1);
+
+        assert!(plan.clone().into_entries().is_err());
+        assert_eq!(items.len(), 2);
+        let texts = |entries: &[SubtitleEntry]| entries.iter().map(|e| e.text.clone()).collect::<Vec<_>>();
+        assert_eq!((texts(&items[0].0), texts(&items[0].1)), (vec!["B".to_string(), "C".to_string()], vec!["A".to_string(), "D".to_string()]));
+        assert_eq!((texts(&items[1].0), texts(&items[1].1)), (vec!["D".to_string()], vec!["C".to_string(), "Z".to_string()]));
+    }
+}
@@ -0,0 +1,526 @@
+/*!
+ * Audio-based subtitle sync check.
+ *
+ * Builds a speech map of the audio track from ffmpeg's `silencedetect`
+ * filter and correlates it with cue times to suggest a global offset and
+ * a linear drift. Only ffmpeg is required; nothing leaves the machine.
+ */
+
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use log::debug;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::subtitle_processor::SubtitleEntry;
+use crate::video_timing::{SyncPoint, TimingTransform};
+
+/// Regex for silencedetect start lines
+static SILENCE_START_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"silence_start:\s*(-?[\d.]+)").expect("Invalid silence start regex")
+});
+
+/// Regex for silencedetect end lines
+static SILENCE_END_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"silence_end:\s*([\d.]+)").expect("Invalid silence end regex")
+});
+
+/// Regex for the input duration ffmpeg prints
+static DURATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Duration:\s*(\d+):(\d{2}):(\d{2})\.(\d+)").expect("Invalid duration regex")
+});
+
+/// Coarse search step for the offset in ms
+const COARSE_STEP_MS: i64 = 100;
+
+/// Fine search step around the coarse peak in ms
+const FINE_STEP_MS: i64 = 10;
+
+/// Minimum cues in a segment for its offset to count toward drift
+const MIN_CUES_PER_SEGMENT: usize = 5;
+
+/// Configuration for the audio sync check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSyncConfig {
+    /// Whether to run the check on subtitles extracted from a video
+    pub enabled: bool,
+    /// Whether to apply the suggested correction
+    pub apply: bool,
+    /// Audio level below which the track counts as silent
+    pub noise_db: f64,
+    /// Shortest silence that splits speech, in ms
+    pub min_silence_ms: u64,
+    /// Largest offset searched in either direction, in ms
+    pub max_offset_ms: i64,
+    /// Number of segments used to estimate drift
+    pub drift_segments: usize,
+    /// Smallest offset or drift (over the file) worth correcting, in ms
+    pub min_correction_ms: i64,
+    /// Smallest gain in speech overlap needed to trust a correction
+    pub min_improvement: f64,
+}
+
+impl Default for AudioSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            apply: false,
+            noise_db: -30.0,
+            min_silence_ms: 300,
+            max_offset_ms: 5000,
+            drift_segments: 4,
+            min_correction_ms: 150,
+            min_improvement: 0.03,
+        }
+    }
+}
+
+/// Speech intervals of an audio track
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeechMap {
+    /// Sorted, non-overlapping (start_ms, end_ms) intervals with audible sound
+    intervals: Vec<(u64, u64)>,
+}
+
+impl SpeechMap {
+    /// Create from speech intervals in any order
+    pub fn new(mut intervals: Vec<(u64, u64)>) -> Self {
+        intervals.retain(|(start, end)| end > start);
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    /// Build from ffmpeg `silencedetect` output by inverting the silences.
+    ///
+    /// The track length comes from the `Duration:` line, falling back to the
+    /// end of the last silence.
+    pub fn from_silencedetect(output: &str) -> Self {
+        let to_ms = |seconds: &str| seconds.parse::<f64>().ok().map(|s| (s.max(0.0) * 1000.0).round() as u64);
+
+        let mut silences = Vec::new();
+        let mut open_start: Option<u64> = None;
+        for line in output.lines() {
+            if let Some(start) = SILENCE_START_REGEX.captures(line).and_then(|c| to_ms(&c[1])) {
+                open_start = Some(start);
+            } else if let Some(end) = SILENCE_END_REGEX.captures(line).and_then(|c| to_ms(&c[1])) {
+                silences.push((open_start.take().unwrap_or(0), end));
+            }
+        }
+
+        let duration_ms = DURATION_REGEX
+            .captures(output)
+            .and_then(|c| {
+                let hours: u64 = c[1].parse().ok()?;
+                let minutes: u64 = c[2].parse().ok()?;
+                let seconds: u64 = c[3].parse().ok()?;
+                let fraction: f64 = format!("0.{}", &c[4]).parse().ok()?;
+                Some(hours * 3_600_000 + minutes * 60_000 + seconds * 1000 + (fraction * 1000.0).round() as u64)
+            })
+            .or_else(|| silences.last().map(|s| s.1))
+            .unwrap_or(0);
+
+        // A silence still open at the end runs to the end of the track
+        if let Some(start) = open_start {
+            silences.push((start, duration_ms));
+        }
+
+        let mut speech = Vec::new();
+        let mut cursor = 0;
+        for (start, end) in silences {
+            if start > cursor {
+                speech.push((cursor, start));
+            }
+            cursor = cursor.max(end);
+        }
+        if duration_ms > cursor {
+            speech.push((cursor, duration_ms));
+        }
+
+        Self::new(speech)
+    }
+
+    /// Run ffmpeg silence detection on the first audio track of a video
+    pub async fn detect(video_path: &Path, config: &AudioSyncConfig) -> Result<Self> {
+        if !video_path.exists() {
+            return Err(anyhow!("Video file not found: {:?}", video_path));
+        }
+
+        let filter = format!(
+            "silencedetect=noise={}dB:d={:.3}",
+            config.noise_db,
+            config.min_silence_ms as f64 / 1000.0
+        );
+        let ffmpeg_future = Command::new("ffmpeg")
+            .args([
+                "-nostats",
+                "-i", video_path.to_str().unwrap_or_default(),
+                "-map", "0:a:0",
+                "-af", &filter,
+                "-f", "null",
+                "-",
+            ])
+            .output();
+
+        let timeout_duration = std::time::Duration::from_secs(600); // Decoding a full track takes a while
+        let output = tokio::select! {
+            result = ffmpeg_future => {
+                result.map_err(|e| anyhow!("Failed to execute ffmpeg command for silence detection: {}", e))?
+            },
+            _ = tokio::time::sleep(timeout_duration) => {
+                return Err(anyhow!("ffmpeg silence detection timed out after 10 minutes"));
+            }
+        };
+
+        // silencedetect reports on stderr
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(anyhow!("ffmpeg silence detection failed: {}", stderr));
+        }
+
+        let map = Self::from_silencedetect(&stderr);
+        debug!("Speech map: {} intervals, {}ms of speech", map.intervals.len(), map.speech_ms());
+        Ok(map)
+    }
+
+    /// Speech intervals
+    pub fn intervals(&self) -> &[(u64, u64)] {
+        &self.intervals
+    }
+
+    /// Total speech time in ms
+    pub fn speech_ms(&self) -> u64 {
+        self.intervals.iter().map(|(start, end)| end - start).sum()
+    }
+
+    /// Check if no speech was found
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Milliseconds of speech between two times
+    pub fn overlap_ms(&self, start_ms: i64, end_ms: i64) -> u64 {
+        if end_ms <= 0 || end_ms <= start_ms {
+            return 0;
+        }
+        let (start, end) = (start_ms.max(0) as u64, end_ms as u64);
+
+        let first = self.intervals.partition_point(|&(_, e)| e <= start);
+        self.intervals[first..]
+            .iter()
+            .take_while(|&&(s, _)| s < end)
+            .map(|&(s, e)| e.min(end) - s.max(start))
+            .sum()
+    }
+}
+
+/// Best offset found for one stretch of cues
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SegmentOffset {
+    /// Start of the first cue in the segment
+    pub start_ms: u64,
+    /// End of the last cue in the segment
+    pub end_ms: u64,
+    /// Offset to add to the cues, in ms
+    pub offset_ms: i64,
+    /// Fraction of cue time overlapping speech at that offset
+    pub score: f64,
+}
+
+/// Result of the audio sync check
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioSyncReport {
+    /// Offset to add to every cue, in ms
+    pub offset_ms: i64,
+    /// Additional shift per hour of runtime, in ms
+    pub drift_ms_per_hour: f64,
+    /// Speech overlap of the cues as they are
+    pub score_before: f64,
+    /// Speech overlap after the suggested correction
+    pub score_after: f64,
+    /// Per-segment offsets used for the drift estimate
+    pub segments: Vec<SegmentOffset>,
+    /// First and last cue (seq_num, start_ms) the correction is anchored to
+    anchors: Option<((usize, u64), (usize, u64))>,
+}
+
+impl AudioSyncReport {
+    /// Correction in ms suggested at a time
+    pub fn correction_at(&self, time_ms: u64) -> i64 {
+        self.offset_ms + (self.drift_ms_per_hour * time_ms as f64 / 3_600_000.0).round() as i64
+    }
+
+    /// Whether the correction is large and reliable enough to apply
+    pub fn is_significant(&self, config: &AudioSyncConfig) -> bool {
+        let Some(((_, first), (_, last))) = self.anchors else {
+            return false;
+        };
+        let largest = self.correction_at(first).abs().max(self.correction_at(last).abs());
+        largest >= config.min_correction_ms && self.score_after - self.score_before >= config.min_improvement
+    }
+
+    /// Timing transform that applies the correction
+    pub fn suggested_transform(&self) -> Option<TimingTransform> {
+        let ((first_cue, first), (last_cue, last)) = self.anchors?;
+        let shifted = |time_ms: u64| (time_ms as i64 + self.correction_at(time_ms)).max(0) as u64;
+
+        if self.drift_ms_per_hour.abs() < f64::EPSILON || first_cue == last_cue {
+            return Some(TimingTransform::Offset { offset_ms: self.offset_ms });
+        }
+
+        Some(TimingTransform::Sync {
+            first: SyncPoint { cue: first_cue, time_ms: shifted(first) },
+            second: SyncPoint { cue: last_cue, time_ms: shifted(last) },
+        })
+    }
+
+    /// One-line description for logs
+    pub fn summary(&self) -> String {
+        format!(
+            "offset {:+}ms, drift {:+.0}ms/h, speech overlap {:.0}% -> {:.0}%",
+            self.offset_ms,
+            self.drift_ms_per_hour,
+            self.score_before * 100.0,
+            self.score_after * 100.0
+        )
+    }
+}
+
+/// Correlates cue times with a speech map
+pub struct AudioSyncChecker {
+    config: AudioSyncConfig,
+}
+
+impl AudioSyncChecker {
+    /// Create a checker with the given configuration
+    pub fn new(config: AudioSyncConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fraction of cue time overlapping speech when shifted by an offset
+    fn score(entries: &[SubtitleEntry], speech: &SpeechMap, offset_ms: i64) -> f64 {
+        let total: u64 = entries.iter().map(|e| e.end_time_ms.saturating_sub(e.start_time_ms)).sum();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let overlap: u64 = entries
+            .iter()
+            .map(|e| speech.overlap_ms(e.start_time_ms as i64 + offset_ms, e.end_time_ms as i64 + offset_ms))
+            .sum();
+        overlap as f64 / total as f64
+    }
+
+    /// Offset with the best score, preferring the smallest shift on ties
+    fn best_offset(&self, entries: &[SubtitleEntry], speech: &SpeechMap) -> (i64, f64) {
+        let max = self.config.max_offset_ms.max(0);
+        let search = |from: i64, to: i64, step: usize, best: (i64, f64)| {
+            (from..=to).step_by(step).fold(best, |best, offset| {
+                let score = Self::score(entries, speech, offset);
+                let better = score > best.1 + 1e-9
+                    || ((score - best.1).abs() <= 1e-9 && offset.abs() < best.0.abs());
+                if better { (offset, score) } else { best }
+            })
+        };
+
+        let coarse = search(-max, max, COARSE_STEP_MS as usize, (0, Self::score(entries, speech, 0)));
+        search(
+            (coarse.0 - COARSE_STEP_MS).max(-max),
+            (coarse.0 + COARSE_STEP_MS).min(max),
+            FINE_STEP_MS as usize,
+            coarse,
+        )
+    }
+
+    /// Estimate offset and drift of the cues against the speech map
+    pub fn check(&self, entries: &[SubtitleEntry], speech: &SpeechMap) -> AudioSyncReport {
+        let score_before = Self::score(entries, speech, 0);
+        let (global_offset, global_score) = self.best_offset(entries, speech);
+
+        let segment_len = entries.len().div_ceil(self.config.drift_segments.max(1)).max(1);
+        let segments: Vec<SegmentOffset> = entries
+            .chunks(segment_len)
+            .filter(|chunk| chunk.len() >= MIN_CUES_PER_SEGMENT)
+            .map(|chunk| {
+                let (offset_ms, score) = self.best_offset(chunk, speech);
+                SegmentOffset {
+                    start_ms: chunk[0].start_time_ms,
+                    end_ms: chunk[chunk.len() - 1].end_time_ms,
+                    offset_ms,
+                    score,
+                }
+            })
+            .collect();
+
+        let anchors = entries
+            .first()
+            .zip(entries.last())
+            .map(|(first, last)| ((first.seq_num, first.start_time_ms), (last.seq_num, last.start_time_ms)));
+
+        let mut report = AudioSyncReport {
+            offset_ms: global_offset,
+            drift_ms_per_hour: 0.0,
+            score_before,
+            score_after: global_score,
+            segments,
+            anchors,
+        };
+
+        // Fit offset = intercept + slope * time through the segment offsets
+        if let Some((intercept, slope)) = Self::fit_line(&report.segments) {
+            let drifting = AudioSyncReport {
+                offset_ms: intercept.round() as i64,
+                drift_ms_per_hour: slope * 3_600_000.0,
+                ..report.clone()
+            };
+            let corrected = Self::corrected_score(entries, speech, &drifting);
+            if corrected > report.score_after + 1e-9 {
+                report = AudioSyncReport { score_after: corrected, ..drifting };
+            }
+        }
+
+        report
+    }
+
+    /// Least-squares line through segment midpoints and offsets
+    fn fit_line(segments: &[SegmentOffset]) -> Option<(f64, f64)> {
+        if segments.len() < 2 {
+            return None;
+        }
+
+        let points: Vec<(f64, f64)> = segments
+            .iter()
+            .map(|s| ((s.start_ms + s.end_ms) as f64 / 2.0, s.offset_ms as f64))
+            .collect();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let var_x: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        if var_x == 0.0 {
+            return None;
+        }
+
+        let slope = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>() / var_x;
+        Some((mean_y - slope * mean_x, slope))
+    }
+
+    /// Speech overlap after applying a report's per-cue correction
+    fn corrected_score(entries: &[SubtitleEntry], speech: &SpeechMap, report: &AudioSyncReport) -> f64 {
+        let shifted: Vec<SubtitleEntry> = entries
+            .iter()
+            .map(|e| {
+                let correction = report.correction_at(e.start_time_ms);
+                let mut shifted = e.clone();
+                shifted.start_time_ms = (e.start_time_ms as i64 + correction).max(0) as u64;
+                shifted.end_time_ms = (e.end_time_ms as i64 + correction).max(0) as u64;
+                shifted
+            })
+            .collect();
+        Self::score(&shifted, speech, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seq: usize, start: u64, end: u64) -> SubtitleEntry {
+        SubtitleEntry::new(seq, start, end, "Line".to_string())
+    }
+
+    /// Speech every 4s lasting 2s, with cues matching it after `shift` and `scale`
+    fn fixture(count: usize, shift: i64, scale: f64) -> (SpeechMap, Vec<SubtitleEntry>) {
+        // Irregular gaps so that only one offset lines up
+        let starts: Vec<u64> = (0..count as u64).map(|i| 10_000 + i * 4000 + (i * i * 37) % 900).collect();
+        let speech = SpeechMap::new(starts.iter().map(|&s| (s, s + 2000)).collect());
+        let entries = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| {
+                let start = ((s as f64 / scale) as i64 - shift).max(0) as u64;
+                entry(i + 1, start, start + 2000)
+            })
+            .collect();
+        (speech, entries)
+    }
+
+    #[test]
+    fn test_speechMap_fromSilencedetect_shouldInvertSilences() {
+        let output = "  Duration: 00:00:20.00, start: 0.000000, bitrate: 128 kb/s\n\
+            [silencedetect @ 0x1] silence_start: 0\n\
+            [silencedetect @ 0x1] silence_end: 2.5 | silence_duration: 2.5\n\
+            [silencedetect @ 0x1] silence_start: 6.25\n\
+            [silencedetect @ 0x1] silence_end: 8 | silence_duration: 1.75\n\
+            [silencedetect @ 0x1] silence_start: 15\n";
+
+        let map = SpeechMap::from_silencedetect(output);
+
+        assert_eq!(map.intervals(), &[(2500, 6250), (8000, 15_000)]);
+        assert_eq!(map.speech_ms(), 10_750);
+    }
+
+    #[test]
+    fn test_speechMap_overlapMs_shouldSumPartialIntervals() {
+        let map = SpeechMap::new(vec![(1000, 2000), (3000, 4000), (1500, 2500)]);
+
+        assert_eq!(map.intervals(), &[(1000, 2500), (3000, 4000)]);
+        assert_eq!(map.overlap_ms(2000, 3500), 1000);
+        assert_eq!(map.overlap_ms(-500, 1200), 200);
+        assert_eq!(map.overlap_ms(5000, 6000), 0);
+    }
+
+    #[test]
+    fn test_audioSyncChecker_check_shouldFindConstantOffset() {
+        let (speech, entries) = fixture(40, 700, 1.0);
+        let checker = AudioSyncChecker::new(AudioSyncConfig::default());
+
+        let report = checker.check(&entries, &speech);
+
+        assert_eq!(report.offset_ms, 700);
+        assert!(report.drift_ms_per_hour.abs() < 1.0);
+        assert!(report.score_after > 0.99);
+        assert!(report.is_significant(&AudioSyncConfig::default()));
+        assert_eq!(report.suggested_transform(), Some(TimingTransform::Offset { offset_ms: 700 }));
+    }
+
+    #[test]
+    fn test_audioSyncChecker_check_shouldEstimateDriftAndApplyIt() {
+        // Cues run 0.1% slow: the last one is ~160ms early
+        let (speech, mut entries) = fixture(40, 0, 1.001);
+        let checker = AudioSyncChecker::new(AudioSyncConfig::default());
+
+        let report = checker.check(&entries, &speech);
+
+        assert!(report.drift_ms_per_hour > 2000.0, "{}", report.summary());
+        assert!(report.score_after > report.score_before);
+        let transform = report.suggested_transform().unwrap();
+        assert!(matches!(transform, TimingTransform::Sync { .. }));
+
+        transform.apply(&mut entries).unwrap();
+        let after = AudioSyncChecker::score(&entries, &speech, 0);
+        assert!(after > report.score_before);
+    }
+
+    #[test]
+    fn test_audioSyncChecker_check_withSyncedCues_shouldNotSuggestCorrection() {
+        let (speech, entries) = fixture(40, 0, 1.0);
+        let checker = AudioSyncChecker::new(AudioSyncConfig::default());
+
+        let report = checker.check(&entries, &speech);
+
+        assert_eq!(report.offset_ms, 0);
+        assert!(!report.is_significant(&AudioSyncConfig::default()));
+    }
+}
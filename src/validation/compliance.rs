@@ -0,0 +1,451 @@
+/*!
+ * Broadcaster compliance profiles and QC reports.
+ *
+ * A compliance profile bundles the timed-text rules of a distributor spec
+ * (reading speed, line length, durations, frame gap, italics and forbidden
+ * characters, shot changes) and configures the timecode, length and format
+ * validators together. Checking a finished subtitle file against a profile yields a
+ * pass/fail QC report.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::subtitle_processor::SubtitleEntry;
+use crate::video_timing::{SHOT_CHANGE_OUT_FRAMES, ShotChanges};
+
+use super::formatting::{FormatValidator, FormatValidatorConfig};
+use super::length::{LengthValidator, LengthValidatorConfig};
+use super::timecodes::{TimecodeValidator, TimecodeValidatorConfig};
+
+/// Names of the built-in profiles
+const BUILTIN_PROFILES: &[&str] = &["netflix", "teletext", "generic"];
+
+/// Timed-text rules of a distributor or broadcaster spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComplianceProfile {
+    /// Profile name shown in reports
+    pub name: String,
+    /// Maximum reading speed in characters per second
+    pub max_cps: f64,
+    /// Maximum visible characters per line
+    pub max_chars_per_line: usize,
+    /// Maximum lines per subtitle
+    pub max_lines: usize,
+    /// Minimum subtitle duration in ms
+    pub min_duration_ms: u64,
+    /// Maximum subtitle duration in ms
+    pub max_duration_ms: u64,
+    /// Minimum gap between consecutive subtitles in frames
+    pub min_frame_gap: u32,
+    /// Frame rate used to convert frame counts to milliseconds
+    pub frame_rate: f64,
+    /// Cue edges within this many frames of a shot change must sit on it (0 = unchecked)
+    pub shot_change_window_frames: u32,
+    /// Whether italic tags are allowed
+    pub allow_italics: bool,
+    /// Characters that must not appear in the subtitles
+    pub forbidden_chars: Vec<char>,
+}
+
+impl Default for ComplianceProfile {
+    fn default() -> Self {
+        Self {
+            name: "generic".to_string(),
+            max_cps: 25.0,
+            max_chars_per_line: 42,
+            max_lines: 2,
+            min_duration_ms: 700,
+            max_duration_ms: 7000,
+            min_frame_gap: 0,
+            frame_rate: 25.0,
+            shot_change_window_frames: 0,
+            allow_italics: true,
+            forbidden_chars: Vec::new(),
+        }
+    }
+}
+
+impl ComplianceProfile {
+    /// Netflix-style timed-text rules for adult programs.
+    pub fn netflix() -> Self {
+        Self {
+            name: "netflix".to_string(),
+            max_cps: 20.0,
+            max_chars_per_line: 42,
+            max_lines: 2,
+            min_duration_ms: 833,
+            max_duration_ms: 7000,
+            min_frame_gap: 2,
+            frame_rate: 23.976,
+            shot_change_window_frames: 12,
+            allow_italics: true,
+            forbidden_chars: Vec::new(),
+        }
+    }
+
+    /// Teletext broadcast rules: short lines, no italics, basic character set.
+    pub fn teletext() -> Self {
+        Self {
+            name: "teletext".to_string(),
+            max_cps: 17.0,
+            max_chars_per_line: 37,
+            max_lines: 2,
+            min_duration_ms: 1000,
+            max_duration_ms: 8000,
+            min_frame_gap: 2,
+            frame_rate: 25.0,
+            shot_change_window_frames: 0,
+            allow_italics: false,
+            forbidden_chars: vec!['♪', '♫', '…', '“', '”', '‘', '’'],
+        }
+    }
+
+    /// Look up a built-in profile by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "netflix" => Some(Self::netflix()),
+            "teletext" => Some(Self::teletext()),
+            "generic" => Some(Self::default()),
+            _ => None,
+        }
+    }
+
+    /// Names of all built-in profiles.
+    pub fn builtin_names() -> &'static [&'static str] {
+        BUILTIN_PROFILES
+    }
+
+    /// Load a user-defined profile from a JSON file.
+    ///
+    /// Missing fields fall back to the generic profile; a missing name
+    /// falls back to the file stem.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read compliance profile: {}", path.display()))?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse compliance profile: {}", path.display()))?;
+
+        if let Some(object) = value.as_object_mut()
+            && !object.contains_key("name")
+            && let Some(stem) = path.file_stem()
+        {
+            object.insert("name".to_string(), stem.to_string_lossy().into());
+        }
+
+        serde_json::from_value(value)
+            .with_context(|| format!("Invalid compliance profile: {}", path.display()))
+    }
+
+    /// Resolve a built-in profile name or a path to a profile file.
+    pub fn resolve(name_or_path: &str) -> Result<Self> {
+        if let Some(profile) = Self::builtin(name_or_path) {
+            return Ok(profile);
+        }
+
+        let path = Path::new(name_or_path);
+        if path.exists() {
+            return Self::from_file(path);
+        }
+
+        Err(anyhow!(
+            "Unknown compliance profile '{}' (built-in: {})",
+            name_or_path,
+            BUILTIN_PROFILES.join(", ")
+        ))
+    }
+
+    /// Length of a number of frames in milliseconds, rounded down or up.
+    fn frames_ms(&self, frames: u64, round_up: bool) -> u64 {
+        if self.frame_rate <= 0.0 {
+            return 0;
+        }
+        let ms = frames as f64 * 1000.0 / self.frame_rate;
+        if round_up { ms.ceil() as u64 } else { ms.floor() as u64 }
+    }
+
+    /// Minimum gap between subtitles in milliseconds.
+    pub fn min_gap_ms(&self) -> u64 {
+        self.frames_ms(self.min_frame_gap as u64, false)
+    }
+
+    /// Timecode validator configuration for this profile.
+    ///
+    /// Shot-change checks only run when the video's shot changes are known.
+    pub fn timecode_config(&self, shot_changes: Option<&ShotChanges>) -> TimecodeValidatorConfig {
+        TimecodeValidatorConfig {
+            max_cps: self.max_cps,
+            min_duration_ms: self.min_duration_ms,
+            max_duration_ms: self.max_duration_ms,
+            check_overlaps: true,
+            max_gap_warning_ms: 0,
+            min_gap_ms: self.min_gap_ms(),
+            shot_changes: shot_changes.cloned(),
+            shot_change_window_ms: self.frames_ms(self.shot_change_window_frames as u64, false),
+            // Out points sit a couple of frames before the shot change
+            shot_change_tolerance_ms: self.frames_ms(SHOT_CHANGE_OUT_FRAMES, true),
+        }
+    }
+
+    /// Length validator configuration for this profile.
+    pub fn length_config(&self) -> LengthValidatorConfig {
+        LengthValidatorConfig {
+            max_chars_per_line: Some(self.max_chars_per_line),
+            max_lines: Some(self.max_lines),
+            ..Default::default()
+        }
+    }
+
+    /// Format validator configuration for this profile.
+    ///
+    /// Checks that compare against the source are disabled: QC runs on the
+    /// delivered file alone.
+    pub fn format_config(&self) -> FormatValidatorConfig {
+        FormatValidatorConfig {
+            check_position_tags: false,
+            check_style_tags: true,
+            check_line_count: false,
+            check_language_indicators: false,
+            allow_italics: self.allow_italics,
+            forbidden_chars: self.forbidden_chars.clone(),
+        }
+    }
+}
+
+/// A single rule violation in a QC report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QcViolation {
+    /// Sequence number of the offending subtitle
+    pub seq_num: usize,
+    /// Check that failed ("timing", "length" or "format")
+    pub check: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Pass/fail QC report for one subtitle file
+#[derive(Debug, Clone, Serialize)]
+pub struct QcReport {
+    /// File that was checked
+    pub file: String,
+    /// Profile the file was checked against
+    pub profile: String,
+    /// Number of subtitles checked
+    pub entries_checked: usize,
+    /// Whether the file passed every rule
+    pub passed: bool,
+    /// All violations, ordered by subtitle
+    pub violations: Vec<QcViolation>,
+}
+
+impl QcReport {
+    /// Number of distinct subtitles with at least one violation.
+    pub fn failed_entries(&self) -> usize {
+        let mut seq_nums: Vec<usize> = self.violations.iter().map(|v| v.seq_num).collect();
+        seq_nums.dedup();
+        seq_nums.len()
+    }
+
+    /// One-line verdict for logs.
+    pub fn summary(&self) -> String {
+        if self.passed {
+            format!(
+                "QC PASS {} ({}): {} subtitles",
+                self.file, self.profile, self.entries_checked
+            )
+        } else {
+            format!(
+                "QC FAIL {} ({}): {} violations in {}/{} subtitles",
+                self.file,
+                self.profile,
+                self.violations.len(),
+                self.failed_entries(),
+                self.entries_checked
+            )
+        }
+    }
+
+    /// Verdict followed by one line per violation.
+    pub fn format_report(&self) -> String {
+        let mut report = self.summary();
+        for violation in &self.violations {
+            report.push_str(&format!(
+                "\n  #{} [{}] {}",
+                violation.seq_num, violation.check, violation.message
+            ));
+        }
+        report
+    }
+}
+
+/// Checks subtitle files against a compliance profile
+pub struct ComplianceChecker {
+    profile: ComplianceProfile,
+    timecode_validator: TimecodeValidator,
+    length_validator: LengthValidator,
+    format_validator: FormatValidator,
+}
+
+impl ComplianceChecker {
+    /// Create a checker with validators configured from the profile
+    pub fn new(profile: ComplianceProfile) -> Self {
+        Self {
+            timecode_validator: TimecodeValidator::with_config(profile.timecode_config(None)),
+            length_validator: LengthValidator::with_config(profile.length_config()),
+            format_validator: FormatValidator::with_config(profile.format_config()),
+            profile,
+        }
+    }
+
+    /// Also check cue edges against the video's shot changes
+    pub fn with_shot_changes(mut self, shot_changes: &ShotChanges) -> Self {
+        self.timecode_validator =
+            TimecodeValidator::with_config(self.profile.timecode_config(Some(shot_changes)));
+        self
+    }
+
+    /// Get the profile
+    pub fn profile(&self) -> &ComplianceProfile {
+        &self.profile
+    }
+
+    /// Check the subtitles of one file and build its QC report
+    pub fn check(&self, file: &str, entries: &[SubtitleEntry]) -> QcReport {
+        let mut violations = Vec::new();
+
+        let timecodes = self.timecode_validator.validate_collection(entries);
+        for (entry, timing) in entries.iter().zip(&timecodes.entry_results) {
+            // Text checks compare the delivered text against itself, so ratio checks never fire
+            let length = self
+                .length_validator
+                .validate_entry(entry.seq_num, &entry.text, &entry.text);
+            let format = self
+                .format_validator
+                .validate_entry(entry.seq_num, &entry.text, &entry.text);
+
+            let messages = timing
+                .issues
+                .iter()
+                .map(|issue| ("timing", issue.to_string()))
+                .chain(length.issues.iter().map(|issue| ("length", issue.to_string())))
+                .chain(format.issues.iter().map(|issue| ("format", issue.to_string())));
+
+            violations.extend(messages.map(|(check, message)| QcViolation {
+                seq_num: entry.seq_num,
+                check: check.to_string(),
+                message,
+            }));
+        }
+
+        QcReport {
+            file: file.to_string(),
+            profile: self.profile.name.clone(),
+            entries_checked: entries.len(),
+            passed: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seq: usize, start: u64, end: u64, text: &str) -> SubtitleEntry {
+        SubtitleEntry::new(seq, start, end, text.to_string())
+    }
+
+    #[test]
+    fn test_complianceProfile_builtin_shouldResolveKnownNames() {
+        for name in ComplianceProfile::builtin_names() {
+            let profile = ComplianceProfile::resolve(name).unwrap();
+            assert_eq!(&profile.name, name);
+        }
+        assert_eq!(ComplianceProfile::builtin("NETFLIX"), Some(ComplianceProfile::netflix()));
+        assert!(ComplianceProfile::resolve("no-such-profile").is_err());
+    }
+
+    #[test]
+    fn test_complianceProfile_minGapMs_shouldConvertFrames() {
+        assert_eq!(ComplianceProfile::netflix().min_gap_ms(), 83);
+        assert_eq!(ComplianceProfile::teletext().min_gap_ms(), 80);
+        assert_eq!(ComplianceProfile::default().min_gap_ms(), 0);
+    }
+
+    #[test]
+    fn test_complianceProfile_fromFile_shouldFillDefaultsAndName() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("studio.json");
+        fs::write(&path, r#"{"max_cps": 15.0, "allow_italics": false, "forbidden_chars": ["~"]}"#)
+            .unwrap();
+
+        let profile = ComplianceProfile::resolve(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(profile.name, "studio");
+        assert_eq!(profile.max_cps, 15.0);
+        assert!(!profile.allow_italics);
+        assert_eq!(profile.forbidden_chars, vec!['~']);
+        assert_eq!(profile.max_chars_per_line, ComplianceProfile::default().max_chars_per_line);
+    }
+
+    #[test]
+    fn test_complianceChecker_check_withCompliantFile_shouldPass() {
+        let checker = ComplianceChecker::new(ComplianceProfile::netflix());
+        let entries = vec![
+            entry(1, 0, 2000, "Where were you?"),
+            entry(2, 2100, 4500, "<i>At the station,</i>\nwaiting for you."),
+        ];
+
+        let report = checker.check("ep01.srt", &entries);
+
+        assert!(report.passed, "{}", report.format_report());
+        assert_eq!(report.entries_checked, 2);
+        assert!(report.summary().starts_with("QC PASS ep01.srt (netflix)"));
+    }
+
+    #[test]
+    fn test_complianceChecker_check_withViolations_shouldFailPerRule() {
+        let checker = ComplianceChecker::new(ComplianceProfile::teletext());
+        let entries = vec![
+            entry(1, 0, 1500, "<i>Hello…</i>"),
+            entry(2, 1520, 3000, "This line is definitely longer than thirty-seven characters"),
+        ];
+
+        let report = checker.check("ep02.srt", &entries);
+
+        assert!(!report.passed);
+        assert_eq!(report.failed_entries(), 2);
+        let checks: Vec<(usize, &str)> = report
+            .violations
+            .iter()
+            .map(|v| (v.seq_num, v.check.as_str()))
+            .collect();
+        assert!(checks.contains(&(1, "format")));
+        assert!(checks.contains(&(2, "timing")));
+        assert!(checks.contains(&(2, "length")));
+        assert!(report.format_report().contains("#2 [timing] Gap of 20ms after entry 1"));
+    }
+
+    #[test]
+    fn test_complianceChecker_withShotChanges_shouldFlagCuesJustOffShots() {
+        let shots = ShotChanges::new(vec![2000, 6000]);
+        let checker = ComplianceChecker::new(ComplianceProfile::netflix()).with_shot_changes(&shots);
+        let entries = vec![
+            entry(1, 2000, 4000, "On the shot change."),
+            entry(2, 4100, 5917, "Ends two frames early."),
+            entry(3, 6250, 8500, "Starts just after it."),
+        ];
+
+        let report = checker.check("ep03.srt", &entries);
+
+        assert!(!report.passed);
+        assert_eq!(report.violations.len(), 1, "{}", report.format_report());
+        assert_eq!(report.violations[0].seq_num, 3);
+        assert!(report.violations[0].message.starts_with("Cue starts 250ms from shot change"));
+    }
+}
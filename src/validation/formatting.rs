@@ -6,6 +6,7 @@
  * - Style tags (<i>, <b>, <u>)
  * - Line break patterns
  * - Language indicators
+ * - Italics and forbidden characters required by delivery specs
  */
 
 use log::debug;
@@ -84,6 +85,12 @@ pub enum FormatIssue {
     LanguageIndicatorChanged {
         source_indicator: String,
     },
+    /// Italics used where the delivery spec does not allow them
+    ItalicsNotAllowed,
+    /// Character that the delivery spec forbids
+    ForbiddenCharacter {
+        character: char,
+    },
 }
 
 impl std::fmt::Display for FormatIssue {
@@ -126,6 +133,12 @@ impl std::fmt::Display for FormatIssue {
             FormatIssue::LanguageIndicatorChanged { source_indicator } => {
                 write!(f, "Language indicator changed: {}", source_indicator)
             }
+            FormatIssue::ItalicsNotAllowed => {
+                write!(f, "Italics are not allowed")
+            }
+            FormatIssue::ForbiddenCharacter { character } => {
+                write!(f, "Forbidden character: '{}' (U+{:04X})", character, *character as u32)
+            }
         }
     }
 }
@@ -159,6 +172,10 @@ pub struct FormatValidatorConfig {
     pub check_line_count: bool,
     /// Check language indicators
     pub check_language_indicators: bool,
+    /// Whether italic tags may appear in the translation
+    pub allow_italics: bool,
+    /// Characters that must not appear in the translation
+    pub forbidden_chars: Vec<char>,
 }
 
 impl Default for FormatValidatorConfig {
@@ -168,6 +185,8 @@ impl Default for FormatValidatorConfig {
             check_style_tags: true,
             check_line_count: false, // Off by default as AI may reasonably change line breaks
             check_language_indicators: true,
+            allow_italics: true,
+            forbidden_chars: Vec::new(),
         }
     }
 }
@@ -219,6 +238,12 @@ impl FormatValidator {
             issues.extend(self.check_language_indicators(source_text, translated_text));
         }
 
+        // Check delivery restrictions
+        if !self.config.allow_italics && translated_text.contains("<i>") {
+            issues.push(FormatIssue::ItalicsNotAllowed);
+        }
+        issues.extend(self.check_forbidden_chars(translated_text));
+
         if issues.is_empty() {
             FormatEntryResult::passed(seq_num)
         } else {
@@ -313,6 +338,20 @@ impl FormatValidator {
         issues
     }
 
+    /// Check for forbidden characters, reporting each distinct character once
+    fn check_forbidden_chars(&self, text: &str) -> Vec<FormatIssue> {
+        let mut issues: Vec<FormatIssue> = Vec::new();
+
+        for character in text.chars().filter(|c| self.config.forbidden_chars.contains(c)) {
+            let issue = FormatIssue::ForbiddenCharacter { character };
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+
     /// Validate a collection of translation pairs
     pub fn validate_collection(
         &self,
@@ -500,4 +539,30 @@ mod tests {
         assert_eq!(tags[0], r"{\an8}");
         assert_eq!(tags[1], r"{\an2}");
     }
+
+    #[test]
+    fn test_validateEntry_withDeliveryRestrictions_shouldReportItalicsAndForbiddenChars() {
+        let config = FormatValidatorConfig {
+            allow_italics: false,
+            forbidden_chars: vec!['♪', '…'],
+            ..Default::default()
+        };
+        let validator = FormatValidator::with_config(config);
+
+        let result = validator.validate_entry(
+            1,
+            "<i>La la la...</i>",
+            "<i>♪ La la la… ♪</i>",
+        );
+
+        assert!(!result.passed);
+        assert_eq!(
+            result.issues,
+            vec![
+                FormatIssue::ItalicsNotAllowed,
+                FormatIssue::ForbiddenCharacter { character: '♪' },
+                FormatIssue::ForbiddenCharacter { character: '…' },
+            ]
+        );
+    }
 }
@@ -4,10 +4,13 @@
  * This module validates that translation lengths are reasonable:
  * - Length ratio between source and translated text
  * - Absolute length limits
+ * - Line length and line count limits
  * - Empty translation detection
  */
 
 use log::debug;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
 /// Default minimum length ratio (translation / source)
 const DEFAULT_MIN_LENGTH_RATIO: f64 = 0.3;
@@ -15,6 +18,11 @@ const DEFAULT_MIN_LENGTH_RATIO: f64 = 0.3;
 /// Default maximum length ratio (translation / source)
 const DEFAULT_MAX_LENGTH_RATIO: f64 = 3.0;
 
+/// Regex for markup that takes no space on screen (HTML style tags and ASS overrides)
+static MARKUP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<[^>]+>|\{\\[^}]*\}").expect("Invalid markup regex")
+});
+
 /// Result of length validation for a single entry
 #[derive(Debug, Clone)]
 pub struct LengthEntryResult {
@@ -73,6 +81,17 @@ pub enum LengthIssue {
     UnexpectedTranslation {
         translated_len: usize,
     },
+    /// A line exceeds the maximum visible characters
+    LineTooLong {
+        line: usize,
+        length: usize,
+        max_length: usize,
+    },
+    /// The entry has more lines than allowed
+    TooManyLines {
+        line_count: usize,
+        max_lines: usize,
+    },
 }
 
 impl std::fmt::Display for LengthIssue {
@@ -112,6 +131,16 @@ impl std::fmt::Display for LengthIssue {
                     translated_len
                 )
             }
+            LengthIssue::LineTooLong { line, length, max_length } => {
+                write!(
+                    f,
+                    "Line {} too long: {} chars (max: {})",
+                    line, length, max_length
+                )
+            }
+            LengthIssue::TooManyLines { line_count, max_lines } => {
+                write!(f, "Too many lines: {} (max: {})", line_count, max_lines)
+            }
         }
     }
 }
@@ -147,6 +176,10 @@ pub struct LengthValidatorConfig {
     pub fail_on_empty: bool,
     /// Minimum source length to apply ratio checks (shorter texts get lenient treatment)
     pub min_source_length_for_ratio: usize,
+    /// Maximum visible characters per line (None = unchecked)
+    pub max_chars_per_line: Option<usize>,
+    /// Maximum number of lines per entry (None = unchecked)
+    pub max_lines: Option<usize>,
 }
 
 impl Default for LengthValidatorConfig {
@@ -156,6 +189,8 @@ impl Default for LengthValidatorConfig {
             max_ratio: DEFAULT_MAX_LENGTH_RATIO,
             fail_on_empty: true,
             min_source_length_for_ratio: 10, // Don't ratio-check very short texts
+            max_chars_per_line: None,
+            max_lines: None,
         }
     }
 }
@@ -229,6 +264,8 @@ impl LengthValidator {
             return LengthEntryResult::passed(seq_num, 1.0);
         }
 
+        issues.extend(self.check_lines(translated_trimmed));
+
         // Only check ratio if source is long enough
         if source_len >= self.config.min_source_length_for_ratio {
             if ratio < self.config.min_ratio {
@@ -257,6 +294,41 @@ impl LengthValidator {
         }
     }
 
+    /// Check line count and per-line visible length limits
+    fn check_lines(&self, text: &str) -> Vec<LengthIssue> {
+        let mut issues = Vec::new();
+        let lines: Vec<&str> = text.lines().collect();
+
+        if let Some(max_lines) = self.config.max_lines
+            && lines.len() > max_lines
+        {
+            issues.push(LengthIssue::TooManyLines {
+                line_count: lines.len(),
+                max_lines,
+            });
+        }
+
+        if let Some(max_length) = self.config.max_chars_per_line {
+            for (idx, line) in lines.iter().enumerate() {
+                let length = Self::visible_len(line);
+                if length > max_length {
+                    issues.push(LengthIssue::LineTooLong {
+                        line: idx + 1,
+                        length,
+                        max_length,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Count the characters of a line as displayed, ignoring markup
+    pub fn visible_len(line: &str) -> usize {
+        MARKUP_REGEX.replace_all(line, "").trim().chars().count()
+    }
+
     /// Validate a collection of translation pairs
     pub fn validate_collection(
         &self,
@@ -441,6 +513,7 @@ mod tests {
             max_ratio: 10.0,
             fail_on_empty: false,
             min_source_length_for_ratio: 5,
+            ..Default::default()
         };
         let validator = LengthValidator::with_config(config);
 
@@ -453,4 +526,32 @@ mod tests {
 
         assert!(result.passed);
     }
+
+    #[test]
+    fn test_validateEntry_withLineLimits_shouldReportLongAndExtraLines() {
+        let config = LengthValidatorConfig {
+            max_chars_per_line: Some(20),
+            max_lines: Some(2),
+            ..Default::default()
+        };
+        let validator = LengthValidator::with_config(config);
+
+        let result = validator.validate_entry(
+            1,
+            "Short line\nAnother\nThird",
+            "<i>Short line</i>\nThis second line is far too long\nThird",
+        );
+
+        assert!(!result.passed);
+        assert!(result.issues.contains(&LengthIssue::TooManyLines {
+            line_count: 3,
+            max_lines: 2,
+        }));
+        assert!(result.issues.contains(&LengthIssue::LineTooLong {
+            line: 2,
+            length: 32,
+            max_length: 20,
+        }));
+        assert_eq!(result.issues.len(), 2);
+    }
 }
@@ -6,12 +6,16 @@
  * - Timecode validation (timing integrity)
  * - Format preservation validation (tags, styles)
  * - Length validation (reasonable translation length ratios)
+ * - Compliance profiles (distributor QC specs across all validators)
+ * - Audio sync (cue times against the audio track's speech map)
  */
 
 // Allow dead code and unused imports - validation types are for library consumers
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+pub mod audio_sync;
+pub mod compliance;
 pub mod markers;
 pub mod timecodes;
 pub mod formatting;
@@ -19,5 +23,7 @@ pub mod length;
 pub mod service;
 
 // Re-export main types
+pub use audio_sync::{AudioSyncChecker, AudioSyncConfig, AudioSyncReport, SpeechMap};
+pub use compliance::{ComplianceChecker, ComplianceProfile, QcReport};
 pub use markers::MarkerValidator;
 pub use service::{ValidationConfig, ValidationService};
@@ -571,6 +571,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::overly_complex_bool_expr)]
     fn test_validateEntry_withMissingPositionTag_shouldWarn() {
         let service = ValidationService::new();
 
@@ -6,11 +6,13 @@
  * - Logically consistent (start < end)
  * - Non-overlapping between entries
  * - Within reasonable reading speed limits
+ * - Not starting or ending just off a shot change
  */
 
 use log::debug;
 
 use crate::subtitle_processor::SubtitleEntry;
+use crate::video_timing::ShotChanges;
 
 /// Maximum characters per second for readable subtitles
 /// Research suggests 15-25 CPS is readable
@@ -86,6 +88,18 @@ pub enum TimecodeIssue {
         prev_seq_num: usize,
         gap_ms: u64,
     },
+    /// Gap to the previous entry is shorter than the required minimum
+    GapTooSmall {
+        prev_seq_num: usize,
+        gap_ms: u64,
+        min_gap_ms: u64,
+    },
+    /// Cue starts or ends close to, but not on, a shot change
+    NearShotChange {
+        shot_change_ms: u64,
+        distance_ms: u64,
+        at_start: bool,
+    },
 }
 
 impl std::fmt::Display for TimecodeIssue {
@@ -125,6 +139,22 @@ impl std::fmt::Display for TimecodeIssue {
                     gap_ms, prev_seq_num
                 )
             }
+            TimecodeIssue::GapTooSmall { prev_seq_num, gap_ms, min_gap_ms } => {
+                write!(
+                    f,
+                    "Gap of {}ms after entry {} is below minimum {}ms",
+                    gap_ms, prev_seq_num, min_gap_ms
+                )
+            }
+            TimecodeIssue::NearShotChange { shot_change_ms, distance_ms, at_start } => {
+                write!(
+                    f,
+                    "Cue {} {}ms from shot change at {}",
+                    if *at_start { "starts" } else { "ends" },
+                    distance_ms,
+                    SubtitleEntry::format_timestamp(*shot_change_ms)
+                )
+            }
         }
     }
 }
@@ -162,6 +192,14 @@ pub struct TimecodeValidatorConfig {
     pub check_overlaps: bool,
     /// Maximum gap in ms before warning (0 = disable)
     pub max_gap_warning_ms: u64,
+    /// Minimum gap in ms between consecutive entries (0 = disable)
+    pub min_gap_ms: u64,
+    /// Shot changes of the video, if known
+    pub shot_changes: Option<ShotChanges>,
+    /// Cue edges closer than this to a shot change are reported (0 = disable)
+    pub shot_change_window_ms: u64,
+    /// Cue edges this close to a shot change count as on it
+    pub shot_change_tolerance_ms: u64,
 }
 
 impl Default for TimecodeValidatorConfig {
@@ -172,6 +210,10 @@ impl Default for TimecodeValidatorConfig {
             max_duration_ms: MAX_SUBTITLE_DURATION_MS,
             check_overlaps: true,
             max_gap_warning_ms: 0, // Disabled by default
+            min_gap_ms: 0,         // Disabled by default
+            shot_changes: None,
+            shot_change_window_ms: 0,
+            shot_change_tolerance_ms: 0,
         }
     }
 }
@@ -241,6 +283,9 @@ impl TimecodeValidator {
             });
         }
 
+        issues.extend(self.check_shot_change(entry.start_time_ms, true));
+        issues.extend(self.check_shot_change(entry.end_time_ms, false));
+
         if issues.is_empty() {
             TimecodeEntryResult::passed(entry.seq_num)
         } else {
@@ -248,6 +293,26 @@ impl TimecodeValidator {
         }
     }
 
+    /// Check that a cue edge is either on a shot change or clear of it
+    fn check_shot_change(&self, edge_ms: u64, at_start: bool) -> Option<TimecodeIssue> {
+        if self.config.shot_change_window_ms == 0 {
+            return None;
+        }
+
+        let shot_change_ms = self
+            .config
+            .shot_changes
+            .as_ref()?
+            .within(edge_ms, self.config.shot_change_window_ms)?;
+        let distance_ms = shot_change_ms.abs_diff(edge_ms);
+
+        (distance_ms > self.config.shot_change_tolerance_ms).then_some(TimecodeIssue::NearShotChange {
+            shot_change_ms,
+            distance_ms,
+            at_start,
+        })
+    }
+
     /// Validate a collection of subtitle entries
     pub fn validate_collection(&self, entries: &[SubtitleEntry]) -> TimecodeValidationResult {
         if entries.is_empty() {
@@ -294,6 +359,21 @@ impl TimecodeValidator {
                     }
                 }
 
+                // Check for gaps below the minimum if configured
+                if self.config.min_gap_ms > 0 && next.start_time_ms >= current.end_time_ms {
+                    let gap_ms = next.start_time_ms - current.end_time_ms;
+                    if gap_ms < self.config.min_gap_ms
+                        && let Some(result) = entry_results.get_mut(next_idx)
+                    {
+                        result.issues.push(TimecodeIssue::GapTooSmall {
+                            prev_seq_num: current.seq_num,
+                            gap_ms,
+                            min_gap_ms: self.config.min_gap_ms,
+                        });
+                        result.passed = false;
+                    }
+                }
+
                 // Check for large gaps if configured
                 if self.config.max_gap_warning_ms > 0 && next.start_time_ms > current.end_time_ms {
                     let gap_ms = next.start_time_ms - current.end_time_ms;
@@ -461,6 +541,56 @@ mod tests {
         assert_eq!(result.overlap_count, 0);
     }
 
+    #[test]
+    fn test_validateCollection_withGapBelowMinimum_shouldFail() {
+        let config = TimecodeValidatorConfig {
+            min_gap_ms: 83,
+            ..Default::default()
+        };
+        let validator = TimecodeValidator::with_config(config);
+        let entries = vec![
+            create_entry(1, 0, 2000, "First"),
+            create_entry(2, 2040, 4000, "Second"),
+            create_entry(3, 4100, 6000, "Third"),
+        ];
+
+        let result = validator.validate_collection(&entries);
+
+        assert!(!result.passed);
+        assert!(matches!(
+            result.entry_results[1].issues[0],
+            TimecodeIssue::GapTooSmall { prev_seq_num: 1, gap_ms: 40, .. }
+        ));
+        assert!(result.entry_results[2].passed);
+    }
+
+    #[test]
+    fn test_validateEntry_nearShotChange_shouldReportUnlessOnIt() {
+        let config = TimecodeValidatorConfig {
+            shot_changes: Some(ShotChanges::new(vec![10_000, 20_000])),
+            shot_change_window_ms: 500,
+            shot_change_tolerance_ms: 84,
+            ..Default::default()
+        };
+        let validator = TimecodeValidator::with_config(config);
+
+        // Starts on the shot change, ends two frames before the next one
+        let on_shots = validator.validate_entry(&create_entry(1, 10_000, 19_917, "Hello there"));
+        assert!(on_shots.passed);
+
+        let near = validator.validate_entry(&create_entry(2, 10_200, 12_000, "Hello there"));
+        assert!(!near.passed);
+        assert_eq!(
+            near.issues,
+            vec![TimecodeIssue::NearShotChange {
+                shot_change_ms: 10_000,
+                distance_ms: 200,
+                at_start: true,
+            }]
+        );
+        assert_eq!(near.issues[0].to_string(), "Cue starts 200ms from shot change at 00:00:10,000");
+    }
+
     #[test]
     fn test_calculateCps_shouldCalculateCorrectly() {
         let entry = create_entry(1, 0, 2000, "Hello World"); // 11 chars in 2 secs = 5.5 CPS
@@ -0,0 +1,562 @@
+/*!
+ * Frame-rate aware timing and shot-change alignment.
+ *
+ * This module provides:
+ * - `FrameRate`: exact frame rates (e.g. 24000/1001) and frame snapping
+ * - `ShotChanges`: shot-change timestamps read from a text list or ffmpeg output
+ * - `FrameAligner`: moves cue in and out points onto frames and shot changes
+ * - `TimingTransform`: constant offsets, frame-rate conversion and two-point sync
+ */
+
+// Public API - may not be used internally
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::subtitle_processor::SubtitleEntry;
+use crate::translation::document::Timecode;
+
+/// Frames an out point is kept before the shot change it is moved to
+pub const SHOT_CHANGE_OUT_FRAMES: u64 = 2;
+
+/// Extension of the shot-change list read next to a video
+const SHOT_CHANGES_EXTENSION: &str = "shots";
+
+/// Exact video frame rate expressed as a fraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FrameRate {
+    /// Frames per `denominator` seconds
+    pub numerator: u32,
+    /// Time base of the numerator
+    pub denominator: u32,
+}
+
+impl FrameRate {
+    /// Create a frame rate from a fraction
+    pub fn new(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator: denominator.max(1),
+        }
+    }
+
+    /// Parse "24000/1001", "25" or "23.976" style frame rates.
+    ///
+    /// Decimal NTSC rates map to their exact fractions. Zero rates are rejected.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let rate = if let Some((num, den)) = value.split_once('/') {
+            Self::new(num.trim().parse().ok()?, den.trim().parse().ok()?)
+        } else {
+            let fps: f64 = value.parse().ok()?;
+            match (fps * 1000.0).round() as u32 {
+                23976 => Self::new(24000, 1001),
+                29970 => Self::new(30000, 1001),
+                59940 => Self::new(60000, 1001),
+                millis => Self::new(millis, 1000),
+            }
+        };
+
+        (rate.numerator > 0).then_some(rate)
+    }
+
+    /// Read the frame rate of the first stream in ffprobe JSON output.
+    ///
+    /// Prefers `r_frame_rate` and falls back to `avg_frame_rate`.
+    pub fn from_ffprobe_json(json: &Value) -> Option<Self> {
+        let stream = json.get("streams")?.as_array()?.first()?;
+        ["r_frame_rate", "avg_frame_rate"]
+            .iter()
+            .filter_map(|key| stream.get(key)?.as_str())
+            .find_map(Self::parse)
+    }
+
+    /// Frames per second
+    pub fn fps(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Duration of one frame in milliseconds
+    pub fn frame_duration_ms(&self) -> f64 {
+        1000.0 / self.fps()
+    }
+
+    /// Index of the frame boundary nearest to a time
+    pub fn nearest_frame(&self, ms: u64) -> u64 {
+        (ms as f64 / self.frame_duration_ms()).round() as u64
+    }
+
+    /// Start time of a frame in milliseconds
+    pub fn frame_to_ms(&self, frame: u64) -> u64 {
+        (frame as f64 * self.frame_duration_ms()).round() as u64
+    }
+
+    /// Snap a time to the nearest frame boundary
+    pub fn snap_ms(&self, ms: u64) -> u64 {
+        self.frame_to_ms(self.nearest_frame(ms))
+    }
+
+    /// Length of a number of frames in milliseconds
+    pub fn frames_to_ms(&self, frames: u32) -> u64 {
+        self.frame_to_ms(frames as u64)
+    }
+}
+
+impl std::fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.3} fps", self.fps())
+    }
+}
+
+impl TryFrom<String> for FrameRate {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value).ok_or_else(|| anyhow!("Invalid frame rate: {}", value))
+    }
+}
+
+impl From<FrameRate> for String {
+    fn from(rate: FrameRate) -> Self {
+        format!("{}/{}", rate.numerator, rate.denominator)
+    }
+}
+
+/// Sorted shot-change timestamps of a video
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShotChanges {
+    timestamps_ms: Vec<u64>,
+}
+
+impl ShotChanges {
+    /// Create from timestamps in any order
+    pub fn new(mut timestamps_ms: Vec<u64>) -> Self {
+        timestamps_ms.sort_unstable();
+        timestamps_ms.dedup();
+        Self { timestamps_ms }
+    }
+
+    /// Parse a shot-change list.
+    ///
+    /// Accepts one timestamp per line, either in seconds ("12.345") or as
+    /// "HH:MM:SS,mmm", as well as ffmpeg scene-detection output, where the
+    /// `pts_time:` of each selected frame is used. Other lines are ignored.
+    pub fn parse(content: &str) -> Self {
+        let timestamps = content.lines().filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+
+            if let Some((_, rest)) = line.split_once("pts_time:") {
+                let seconds = rest.split_whitespace().next()?;
+                return Self::seconds_to_ms(seconds);
+            }
+
+            if line.contains(':') {
+                return SubtitleEntry::parse_timestamp(line).ok();
+            }
+
+            Self::seconds_to_ms(line)
+        });
+
+        Self::new(timestamps.collect())
+    }
+
+    /// Load a shot-change list from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read shot changes: {}", path.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Path of the shot-change list read next to a video (`<video>.shots`)
+    pub fn sidecar_path(video_path: &Path) -> PathBuf {
+        video_path.with_extension(SHOT_CHANGES_EXTENSION)
+    }
+
+    /// Load the shot-change list next to a video, if there is one
+    pub fn for_video(video_path: &Path) -> Result<Option<Self>> {
+        let path = Self::sidecar_path(video_path);
+        if path.exists() {
+            Self::from_file(path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn seconds_to_ms(value: &str) -> Option<u64> {
+        let seconds: f64 = value.trim().parse().ok()?;
+        (seconds >= 0.0).then(|| (seconds * 1000.0).round() as u64)
+    }
+
+    /// All timestamps in milliseconds
+    pub fn timestamps_ms(&self) -> &[u64] {
+        &self.timestamps_ms
+    }
+
+    /// Number of shot changes
+    pub fn len(&self) -> usize {
+        self.timestamps_ms.len()
+    }
+
+    /// Check if there are no shot changes
+    pub fn is_empty(&self) -> bool {
+        self.timestamps_ms.is_empty()
+    }
+
+    /// Shot change closest to a time
+    pub fn nearest(&self, ms: u64) -> Option<u64> {
+        let idx = self.timestamps_ms.partition_point(|&t| t < ms);
+        let after = self.timestamps_ms.get(idx).copied();
+        let before = idx.checked_sub(1).map(|i| self.timestamps_ms[i]);
+
+        match (before, after) {
+            (Some(b), Some(a)) => Some(if ms - b <= a - ms { b } else { a }),
+            (b, a) => b.or(a),
+        }
+    }
+
+    /// Shot change within `window_ms` of a time, if any
+    pub fn within(&self, ms: u64, window_ms: u64) -> Option<u64> {
+        self.nearest(ms).filter(|&shot| shot.abs_diff(ms) <= window_ms)
+    }
+}
+
+/// Aligns cue timings to video frames and nearby shot changes
+pub struct FrameAligner {
+    frame_rate: FrameRate,
+    shot_changes: Option<ShotChanges>,
+    window_frames: u32,
+}
+
+impl FrameAligner {
+    /// Create an aligner that only snaps to frames
+    pub fn new(frame_rate: FrameRate) -> Self {
+        Self {
+            frame_rate,
+            shot_changes: None,
+            window_frames: 0,
+        }
+    }
+
+    /// Also move cue edges within `window_frames` of a shot change onto it
+    pub fn with_shot_changes(mut self, shot_changes: ShotChanges, window_frames: u32) -> Self {
+        self.shot_changes = Some(shot_changes);
+        self.window_frames = window_frames;
+        self
+    }
+
+    /// Align entries in place and return how many changed.
+    ///
+    /// In points near a shot change move onto it, out points move two frames
+    /// before it. Every time is then snapped to a frame boundary, keeping at
+    /// least one frame per cue and never creating overlaps.
+    pub fn align(&self, entries: &mut [SubtitleEntry]) -> usize {
+        let rate = self.frame_rate;
+        let window_ms = rate.frames_to_ms(self.window_frames);
+        let original: Vec<(u64, u64)> = entries
+            .iter()
+            .map(|e| (e.start_time_ms, e.end_time_ms))
+            .collect();
+
+        for entry in entries.iter_mut() {
+            let mut start = entry.start_time_ms;
+            let mut end = entry.end_time_ms;
+
+            if let Some(shots) = &self.shot_changes {
+                if let Some(shot) = shots.within(start, window_ms) {
+                    start = rate.snap_ms(shot);
+                }
+                if let Some(shot) = shots.within(end, window_ms) {
+                    let out_frame = rate.nearest_frame(shot).saturating_sub(SHOT_CHANGE_OUT_FRAMES);
+                    end = rate.frame_to_ms(out_frame);
+                }
+            }
+
+            let snapped = Timecode::from_milliseconds(start, end).snap_to_frames(rate);
+            entry.start_time_ms = snapped.start_ms;
+            entry.end_time_ms = snapped.end_ms;
+        }
+
+        // Moving an in point back to a shot change may overlap the previous cue
+        for i in 1..entries.len() {
+            let next_start = entries[i].start_time_ms;
+            let prev = &mut entries[i - 1];
+            if prev.end_time_ms > next_start && next_start > prev.start_time_ms {
+                prev.end_time_ms = next_start;
+            }
+        }
+
+        entries
+            .iter()
+            .zip(original)
+            .filter(|(e, (start, end))| e.start_time_ms != *start || e.end_time_ms != *end)
+            .count()
+    }
+}
+
+/// Reference cue mapped to a new start time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPoint {
+    /// Sequence number of the reference cue
+    pub cue: usize,
+    /// New start time of the cue in milliseconds
+    pub time_ms: u64,
+}
+
+impl SyncPoint {
+    /// Parse "CUE=TIME", with TIME as "HH:MM:SS,mmm" or milliseconds.
+    pub fn parse(value: &str) -> Result<Self> {
+        let (cue, time) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid sync point '{}', expected CUE=TIME", value))?;
+        let cue = cue
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid cue number in sync point: {}", value))?;
+        let time = time.trim();
+        let time_ms = if time.contains(':') {
+            SubtitleEntry::parse_timestamp(time)?
+        } else {
+            time.parse()
+                .with_context(|| format!("Invalid time in sync point: {}", value))?
+        };
+
+        Ok(Self { cue, time_ms })
+    }
+}
+
+/// Linear timing transform applied to every cue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimingTransform {
+    /// Shift every cue by a constant amount (negative = earlier)
+    Offset { offset_ms: i64 },
+    /// Convert timings made for one frame rate to another
+    FrameRate { from: FrameRate, to: FrameRate },
+    /// Map two reference cues to new start times and interpolate the rest
+    Sync { first: SyncPoint, second: SyncPoint },
+}
+
+impl TimingTransform {
+    /// Scale and offset (in ms) of the transform for these entries.
+    ///
+    /// Sync points are resolved against the entries' sequence numbers.
+    pub fn linear_map(&self, entries: &[SubtitleEntry]) -> Result<(f64, f64)> {
+        match self {
+            TimingTransform::Offset { offset_ms } => Ok((1.0, *offset_ms as f64)),
+            TimingTransform::FrameRate { from, to } => Ok((from.fps() / to.fps(), 0.0)),
+            TimingTransform::Sync { first, second } => {
+                let start_of = |point: &SyncPoint| {
+                    entries
+                        .iter()
+                        .find(|e| e.seq_num == point.cue)
+                        .map(|e| e.start_time_ms as f64)
+                        .ok_or_else(|| anyhow!("Sync cue {} not found", point.cue))
+                };
+                let (from_a, from_b) = (start_of(first)?, start_of(second)?);
+                if from_a == from_b {
+                    return Err(anyhow!("Sync cues {} and {} start at the same time", first.cue, second.cue));
+                }
+
+                let scale = (second.time_ms as f64 - first.time_ms as f64) / (from_b - from_a);
+                if scale <= 0.0 {
+                    return Err(anyhow!("Sync points would reverse the cue order"));
+                }
+                Ok((scale, first.time_ms as f64 - scale * from_a))
+            }
+        }
+    }
+
+    /// Apply the transform in place and return how many cues were clamped at zero
+    pub fn apply(&self, entries: &mut [SubtitleEntry]) -> Result<usize> {
+        let (scale, offset) = self.linear_map(entries)?;
+        let map = |ms: u64| (ms as f64 * scale + offset).round();
+        let mut clamped = 0;
+
+        for entry in entries.iter_mut() {
+            let start = map(entry.start_time_ms);
+            if start < 0.0 {
+                clamped += 1;
+            }
+            entry.start_time_ms = start.max(0.0) as u64;
+            entry.end_time_ms = map(entry.end_time_ms).max(0.0) as u64;
+        }
+
+        Ok(clamped)
+    }
+
+    /// Short human-readable description
+    pub fn description(&self) -> String {
+        match self {
+            TimingTransform::Offset { offset_ms } => format!("offset {:+}ms", offset_ms),
+            TimingTransform::FrameRate { from, to } => format!("frame rate {} -> {}", from, to),
+            TimingTransform::Sync { first, second } => format!(
+                "sync cue {} -> {}, cue {} -> {}",
+                first.cue,
+                SubtitleEntry::format_timestamp(first.time_ms),
+                second.cue,
+                SubtitleEntry::format_timestamp(second.time_ms)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seq: usize, start: u64, end: u64) -> SubtitleEntry {
+        SubtitleEntry::new(seq, start, end, "Text".to_string())
+    }
+
+    #[test]
+    fn test_frameRate_parse_shouldHandleFractionsAndDecimals() {
+        assert_eq!(FrameRate::parse("24000/1001"), Some(FrameRate::new(24000, 1001)));
+        assert_eq!(FrameRate::parse("23.976"), Some(FrameRate::new(24000, 1001)));
+        assert_eq!(FrameRate::parse("25"), Some(FrameRate::new(25000, 1000)));
+        assert_eq!(FrameRate::parse("0/0"), None);
+        assert_eq!(FrameRate::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_frameRate_fromFfprobeJson_shouldFallBackToAverageRate() {
+        let json: Value = serde_json::from_str(
+            r#"{"streams": [{"r_frame_rate": "0/0", "avg_frame_rate": "30000/1001"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(FrameRate::from_ffprobe_json(&json), Some(FrameRate::new(30000, 1001)));
+        assert_eq!(FrameRate::from_ffprobe_json(&serde_json::json!({"streams": []})), None);
+    }
+
+    #[test]
+    fn test_frameRate_snapMs_shouldRoundToNearestFrame() {
+        let rate = FrameRate::new(25, 1);
+
+        assert_eq!(rate.snap_ms(1019), 1000);
+        assert_eq!(rate.snap_ms(1021), 1040);
+        assert_eq!(rate.frames_to_ms(2), 80);
+
+        let ntsc = FrameRate::new(24000, 1001);
+        assert_eq!(ntsc.snap_ms(1000), 1001);
+    }
+
+    #[test]
+    fn test_shotChanges_parse_shouldReadListsAndFfmpegOutput() {
+        let list = "# shots\n12.5\n00:00:03,000\n\n3.0\n";
+        assert_eq!(ShotChanges::parse(list).timestamps_ms(), &[3000, 12500]);
+
+        let ffmpeg = "[Parsed_showinfo_1 @ 0x1] n:   0 pts:  12012 pts_time:0.834167 duration: 1\n\
+                      frame:1    pts:96096   pts_time:4.004\n\
+                      lavfi.scene_score=0.52\n";
+        assert_eq!(ShotChanges::parse(ffmpeg).timestamps_ms(), &[834, 4004]);
+    }
+
+    #[test]
+    fn test_shotChanges_within_shouldFindNearestInWindow() {
+        let shots = ShotChanges::new(vec![5000, 1000, 9000]);
+
+        assert_eq!(shots.nearest(2800), Some(1000));
+        assert_eq!(shots.nearest(3200), Some(5000));
+        assert_eq!(shots.within(5300, 500), Some(5000));
+        assert_eq!(shots.within(6000, 500), None);
+        assert_eq!(ShotChanges::default().nearest(10), None);
+    }
+
+    #[test]
+    fn test_frameAligner_align_shouldSnapToFramesAndShotChanges() {
+        let aligner = FrameAligner::new(FrameRate::new(25, 1))
+            .with_shot_changes(ShotChanges::new(vec![4000]), 12);
+        let mut entries = vec![
+            entry(1, 1010, 3790),
+            entry(2, 4150, 6000),
+            entry(3, 8000, 8005),
+        ];
+
+        let changed = aligner.align(&mut entries);
+
+        assert_eq!(changed, 3);
+        // Out point two frames before the shot change, in point on it
+        assert_eq!((entries[0].start_time_ms, entries[0].end_time_ms), (1000, 3920));
+        assert_eq!((entries[1].start_time_ms, entries[1].end_time_ms), (4000, 6000));
+        // Cues never collapse below one frame
+        assert_eq!((entries[2].start_time_ms, entries[2].end_time_ms), (8000, 8040));
+    }
+
+    #[test]
+    fn test_frameAligner_align_withAlignedEntries_shouldChangeNothing() {
+        let aligner = FrameAligner::new(FrameRate::new(25, 1));
+        let mut entries = vec![entry(1, 1000, 2000), entry(2, 2080, 3000)];
+
+        assert_eq!(aligner.align(&mut entries), 0);
+    }
+
+    #[test]
+    fn test_timingTransform_offset_shouldShiftAndClampAtZero() {
+        let mut entries = vec![entry(1, 500, 1500), entry(2, 2000, 3000)];
+
+        let clamped = TimingTransform::Offset { offset_ms: -1000 }.apply(&mut entries).unwrap();
+
+        assert_eq!(clamped, 1);
+        assert_eq!((entries[0].start_time_ms, entries[0].end_time_ms), (0, 500));
+        assert_eq!((entries[1].start_time_ms, entries[1].end_time_ms), (1000, 2000));
+    }
+
+    #[test]
+    fn test_timingTransform_frameRate_shouldScaleFilmToPal() {
+        let mut entries = vec![entry(1, 60_000, 62_000)];
+        let transform = TimingTransform::FrameRate {
+            from: FrameRate::new(24000, 1001),
+            to: FrameRate::new(25, 1),
+        };
+
+        transform.apply(&mut entries).unwrap();
+
+        assert_eq!((entries[0].start_time_ms, entries[0].end_time_ms), (57_542, 59_461));
+    }
+
+    #[test]
+    fn test_timingTransform_sync_shouldInterpolateBetweenReferenceCues() {
+        let mut entries = vec![entry(1, 1000, 2000), entry(2, 5000, 6000), entry(3, 9000, 10_000)];
+        let transform = TimingTransform::Sync {
+            first: SyncPoint::parse("1=00:00:02,000").unwrap(),
+            second: SyncPoint::parse("3=18000").unwrap(),
+        };
+
+        transform.apply(&mut entries).unwrap();
+
+        assert_eq!((entries[0].start_time_ms, entries[0].end_time_ms), (2000, 4000));
+        assert_eq!((entries[1].start_time_ms, entries[1].end_time_ms), (10_000, 12_000));
+        assert_eq!(entries[2].start_time_ms, 18_000);
+
+        let missing = TimingTransform::Sync {
+            first: SyncPoint { cue: 1, time_ms: 0 },
+            second: SyncPoint { cue: 9, time_ms: 1000 },
+        };
+        assert!(missing.apply(&mut entries).is_err());
+        assert!(SyncPoint::parse("12").is_err());
+    }
+
+    #[test]
+    fn test_timingTransform_serde_shouldUseTaggedFormat() {
+        let json = r#"[{"type": "offset", "offset_ms": 250}, {"type": "frame_rate", "from": "23.976", "to": "25"}]"#;
+
+        let transforms: Vec<TimingTransform> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(transforms[0], TimingTransform::Offset { offset_ms: 250 });
+        assert_eq!(
+            transforms[1],
+            TimingTransform::FrameRate { from: FrameRate::new(24000, 1001), to: FrameRate::new(25000, 1000) }
+        );
+        assert_eq!(serde_json::to_value(&transforms[1]).unwrap()["from"], "24000/1001");
+    }
+}
@@ -363,6 +363,11 @@ pub fn create_mock_translation_service() -> Result<yastwai::translation::core::T
             entries_per_request: 3,
             context_entries_count: 3,
             pipeline_mode: "legacy".to_string(),
+            style_guide_path: None,
+            prompt_templates_dir: None,
+            term_policy_path: None,
+            class_policy: Default::default(),
+            retiming: None,
         },
         available_providers: vec![
             ProviderConfig {
@@ -374,6 +379,7 @@ pub fn create_mock_translation_service() -> Result<yastwai::translation::core::T
                 max_chars_per_request: 1000,
                 timeout_secs: 1,
                 rate_limit: Some(60),
+                pricing: None,
             },
         ],
     };
@@ -8,7 +8,8 @@ use yastwai::providers::Provider;
 use yastwai::providers::openai::OpenAIRequest;
 use yastwai::providers::anthropic::{Anthropic, AnthropicRequest};
 use yastwai::providers::ollama::{GenerationRequest, ChatRequest, ChatMessage};
-use crate::common::mock_providers::{MockOpenAI, MockAnthropic, MockOllama};
+use yastwai::errors::ProviderError;
+use crate::common::mock_providers::{MockOpenAI, MockAnthropic, MockOllama, MockErrorType};
 
 /// Test that we can handle missing API keys gracefully
 #[test]
@@ -152,4 +153,27 @@ async fn test_ollama_chat_withMockProvider_shouldReturnResponse() {
     let tracker = client.tracker();
     let tracker = tracker.lock().unwrap();
     assert_eq!(tracker.call_count, 1);
-} 
\ No newline at end of file
+}
+
+/// Test that a mock API error surfaces as a provider API error
+#[tokio::test]
+async fn test_openai_complete_withApiError_shouldReturnApiError() {
+    let client = MockOpenAI::new();
+    client.fail_next_call(MockErrorType::Api);
+    let request = OpenAIRequest::new("gpt-3.5-turbo")
+        .add_message("user", "Say hello!");
+    
+    let result = client.complete(request).await;
+    assert!(matches!(result, Err(ProviderError::ApiError { status_code: 400, .. })));
+}
+
+/// Test Ollama version check with mock provider
+#[tokio::test]
+async fn test_ollama_version_withMockProvider_shouldReturnVersion() {
+    let client = MockOllama::new();
+    
+    assert_eq!(client.version().await.unwrap(), "0.1.0");
+    
+    client.fail_next_call(MockErrorType::Connection);
+    assert!(matches!(client.version().await, Err(ProviderError::ConnectionError(_))));
+}
@@ -4,6 +4,12 @@
  * This file configures the test environment and imports all test modules.
  */
 
+// Test names follow the test_functionName_withCondition_shouldBehavior convention
+#![allow(non_snake_case)]
+// Lints tripped by the existing tests
+#![allow(clippy::assertions_on_constants)]
+#![allow(clippy::ptr_arg, clippy::field_reassign_with_default, clippy::useless_vec)]
+
 // Configure logging for tests
 use std::sync::Once;
 
@@ -35,6 +41,7 @@ mod unit {
     pub mod progress_bar_tests;
     pub mod errors_tests;
     pub mod cache_tests;
+    pub mod database_tests;
     
     // Provider tests
     pub mod providers_tests;
@@ -0,0 +1,104 @@
+-- Session database as written by schema v1, before history summaries and
+-- glossary terms existed. Used to test upgrades of existing user databases.
+
+PRAGMA foreign_keys=ON;
+
+CREATE TABLE schema_version (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+INSERT INTO schema_version (id, version, updated_at) VALUES (1, 1, '2025-01-15 10:00:00');
+
+CREATE TABLE sessions (
+    id TEXT PRIMARY KEY,
+    source_file_path TEXT NOT NULL,
+    source_file_hash TEXT NOT NULL,
+    source_language TEXT NOT NULL,
+    target_language TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    total_entries INTEGER NOT NULL,
+    completed_entries INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'in_progress',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT
+);
+CREATE INDEX idx_sessions_status ON sessions(status);
+CREATE INDEX idx_sessions_source_hash ON sessions(source_file_hash);
+CREATE INDEX idx_sessions_languages ON sessions(source_language, target_language);
+
+CREATE TABLE source_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    seq_num INTEGER NOT NULL,
+    start_time_ms INTEGER NOT NULL,
+    end_time_ms INTEGER NOT NULL,
+    source_text TEXT NOT NULL,
+    UNIQUE(session_id, seq_num)
+);
+CREATE INDEX idx_source_entries_session ON source_entries(session_id);
+
+CREATE TABLE translated_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_entry_id INTEGER NOT NULL REFERENCES source_entries(id) ON DELETE CASCADE,
+    translated_text TEXT NOT NULL,
+    translation_status TEXT DEFAULT 'pending',
+    quality_score REAL,
+    validation_errors TEXT,
+    attempt_count INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(source_entry_id)
+);
+CREATE INDEX idx_translated_status ON translated_entries(translation_status);
+CREATE INDEX idx_translated_source ON translated_entries(source_entry_id);
+
+CREATE TABLE translation_cache (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_text_hash TEXT NOT NULL,
+    source_text TEXT NOT NULL,
+    source_language TEXT NOT NULL,
+    target_language TEXT NOT NULL,
+    translated_text TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    hit_count INTEGER DEFAULT 1,
+    UNIQUE(source_text_hash, source_language, target_language, provider, model)
+);
+CREATE INDEX idx_cache_lookup ON translation_cache(source_text_hash, source_language, target_language);
+CREATE INDEX idx_cache_provider ON translation_cache(provider, model);
+
+CREATE TABLE validation_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    translated_entry_id INTEGER NOT NULL REFERENCES translated_entries(id) ON DELETE CASCADE,
+    validation_type TEXT NOT NULL,
+    passed INTEGER NOT NULL,
+    severity TEXT,
+    message TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_validation_entry ON validation_results(translated_entry_id);
+CREATE INDEX idx_validation_type ON validation_results(validation_type);
+
+INSERT INTO sessions (id, source_file_path, source_file_hash, source_language, target_language,
+                      provider, model, total_entries, completed_entries, status, created_at, updated_at)
+VALUES ('0f8fad5b-d9cb-469f-a165-70867728950e', '/movies/episode01.mkv', 'a3f1c2', 'en', 'fr',
+        'ollama', 'llama3.2:3b', 3, 2, 'paused', '2025-01-15T10:00:00Z', '2025-01-15T10:05:00Z');
+
+INSERT INTO source_entries (session_id, seq_num, start_time_ms, end_time_ms, source_text) VALUES
+    ('0f8fad5b-d9cb-469f-a165-70867728950e', 1, 1000, 2500, 'Hello, Frodo.'),
+    ('0f8fad5b-d9cb-469f-a165-70867728950e', 2, 3000, 4500, 'Where are you going?'),
+    ('0f8fad5b-d9cb-469f-a165-70867728950e', 3, 5000, 6500, 'To Mordor.');
+
+INSERT INTO translated_entries (source_entry_id, translated_text, translation_status, attempt_count,
+                                created_at, updated_at) VALUES
+    (1, 'Bonjour, Frodon.', 'translated', 1, '2025-01-15T10:01:00Z', '2025-01-15T10:01:00Z'),
+    (2, 'Où vas-tu ?', 'validated', 1, '2025-01-15T10:01:00Z', '2025-01-15T10:01:00Z');
+
+INSERT INTO translation_cache (source_text_hash, source_text, source_language, target_language,
+                               translated_text, provider, model, created_at, hit_count)
+VALUES ('9b71d224', 'Where are you going?', 'en', 'fr', 'Où vas-tu ?', 'ollama', 'llama3.2:3b',
+        '2025-01-15T10:01:00Z', 4);
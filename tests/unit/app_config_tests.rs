@@ -11,7 +11,7 @@ fn find_provider_config<'a>(config: &'a Config, provider: &TranslationProvider)
         TranslationProvider::OpenAI => "openai",
         TranslationProvider::Anthropic => "anthropic",
         TranslationProvider::LMStudio => "lmstudio",
-        TranslationProvider::VLLM => "vllm",
+        TranslationProvider::Vllm => "vllm",
     };
     config.translation.available_providers.iter()
         .find(|p| p.provider_type == provider_str)
@@ -146,7 +146,7 @@ fn test_providerSpecificDefaults_shouldHaveCorrectRateLimits() {
     assert_eq!(lmstudio_config.rate_limit, None);
 
     // vLLM (local high-throughput) should have no rate limit and high concurrency
-    let vllm_config = ProviderConfig::new(TranslationProvider::VLLM);
+    let vllm_config = ProviderConfig::new(TranslationProvider::Vllm);
     assert_eq!(vllm_config.rate_limit, None);
     assert_eq!(vllm_config.concurrent_requests, 16); // vLLM supports high concurrency
     assert_eq!(vllm_config.endpoint, "http://localhost:8000/v1");
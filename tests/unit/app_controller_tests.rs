@@ -6,7 +6,9 @@ use std::fs;
 use anyhow::Result;
 use yastwai::app_config::Config;
 use yastwai::app_controller::Controller;
+use yastwai::subtitle_processor::{SubtitleCollection, SubtitleEntry};
 use yastwai::translation::core::LogEntry;
+use yastwai::translation::pipeline::PipelineMode;
 use tempfile::TempDir;
 
 /// Test creating a controller with the default configuration
@@ -89,4 +91,123 @@ fn test_controller_format_duration() -> Result<()> {
     assert!(content.contains("Context: Test Context"));
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Test that saved files are QC'd only when a compliance profile is configured
+#[test]
+fn test_check_compliance_withProfile_shouldReportViolations() -> Result<()> {
+    let mut subtitles = SubtitleCollection::new("ep01.mkv".into(), "en".to_string());
+    subtitles.entries.push(SubtitleEntry::new(1, 0, 300, "Too quick to read".to_string()));
+    let input_file = std::path::Path::new("ep01.mkv");
+    let output_path = std::path::Path::new("out/ep01.fr.srt");
+
+    let controller = Controller::new_for_test()?;
+    assert!(controller.check_compliance(&subtitles, input_file, output_path)?.is_none());
+
+    let mut config = Config::default();
+    config.validation.compliance_profile = Some("netflix".to_string());
+    let controller = Controller::with_config(config)?;
+    let report = controller.check_compliance(&subtitles, input_file, output_path)?.unwrap();
+
+    assert!(!report.passed);
+    assert_eq!(report.file, "ep01.fr.srt");
+    assert_eq!(report.profile, "netflix");
+    
+    Ok(())
+}
+
+/// Test that cues are snapped to frames and sidecar shot changes when enabled
+#[tokio::test]
+async fn test_align_to_video_withFrameRateAndShots_shouldSnapCues() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input_file = temp_dir.path().join("ep01.srt");
+    fs::write(temp_dir.path().join("ep01.shots"), "4.0\n")?;
+
+    let mut subtitles = SubtitleCollection::new(input_file.clone(), "fr".to_string());
+    subtitles.entries.push(SubtitleEntry::new(1, 1010, 3790, "Bonjour".to_string()));
+    subtitles.entries.push(SubtitleEntry::new(2, 4150, 6000, "Salut".to_string()));
+
+    let controller = Controller::new_for_test()?;
+    assert_eq!(controller.align_to_video(&mut subtitles, &input_file).await?, 0);
+
+    let mut config = Config::default();
+    config.video_timing.snap_to_frames = true;
+    config.video_timing.frame_rate = Some("25".to_string());
+    let controller = Controller::with_config(config)?;
+
+    assert_eq!(controller.align_to_video(&mut subtitles, &input_file).await?, 2);
+    assert_eq!((subtitles.entries[0].start_time_ms, subtitles.entries[0].end_time_ms), (1000, 3920));
+    assert_eq!(subtitles.entries[1].start_time_ms, 4000);
+    
+    Ok(())
+}
+
+/// Test that the audio sync check is opt-in and never fails the run
+#[tokio::test]
+async fn test_check_audio_sync_withUnreadableVideo_shouldSkipWithoutError() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input_file = temp_dir.path().join("missing.mkv");
+    let mut subtitles = SubtitleCollection::new(input_file.clone(), "en".to_string());
+    subtitles.entries.push(SubtitleEntry::new(1, 1000, 2000, "Hello".to_string()));
+
+    let controller = Controller::new_for_test()?;
+    assert!(controller.check_audio_sync(&mut subtitles, &input_file).await?.is_none());
+
+    let mut config = Config::default();
+    config.validation.audio_sync.enabled = true;
+    config.validation.audio_sync.apply = true;
+    let controller = Controller::with_config(config)?;
+
+    assert!(controller.check_audio_sync(&mut subtitles, &input_file).await?.is_none());
+    assert_eq!(subtitles.entries[0].start_time_ms, 1000);
+    
+    Ok(())
+}
+
+/// Test that the semantic check, back-translation check, judge and line reflow reach the pipeline only when enabled
+#[test]
+fn test_build_pipeline_config_withQualityChecks_shouldEnableChecks() -> Result<()> {
+    let mut config = Config::default();
+    let pipeline_config = Controller::build_pipeline_config(&config, PipelineMode::Standard)?;
+    assert!(pipeline_config.back_translation.is_none());
+
+    config.validation.back_translation.enabled = true;
+    config.validation.back_translation.min_similarity = 0.5;
+    let pipeline_config = Controller::build_pipeline_config(&config, PipelineMode::Standard)?;
+
+    assert_eq!(pipeline_config.back_translation.map(|c| c.min_similarity), Some(0.5));
+    assert!(pipeline_config.judge.is_none());
+
+    config.validation.judge.enabled = true;
+    let pipeline_config = Controller::build_pipeline_config(&config, PipelineMode::Fast)?;
+
+    assert_eq!(pipeline_config.judge.map(|c| c.min_entry_score), Some(0.7));
+
+    config.experimental.enable_semantic_validation = true;
+    let pipeline_config = Controller::build_pipeline_config(&config, PipelineMode::Standard)?;
+
+    assert!(pipeline_config.validation_config.enable_semantic_validation);
+    assert!(pipeline_config.validation_config.repair_config.is_none());
+
+    config.validation.reflow_lines = true;
+    let pipeline_config = Controller::build_pipeline_config(&config, PipelineMode::Standard)?;
+
+    assert!(pipeline_config.validation_config.repair_config.is_some_and(|c| c.reflow_lines));
+    
+    Ok(())
+}
+
+/// Test that evaluating a subtitle file requires an explicit reference track
+#[tokio::test]
+async fn test_evaluate_against_reference_withSubtitleAndNoReference_shouldFail() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input_file = temp_dir.path().join("ep01.srt");
+    fs::write(&input_file, "1\n00:00:01,000 --> 00:00:02,000\nHello\n")?;
+
+    let controller = Controller::new_for_test()?;
+    let error = controller.evaluate_against_reference(&input_file, None).await.unwrap_err();
+
+    assert!(error.to_string().contains("reference subtitle file is required"));
+    
+    Ok(())
+}
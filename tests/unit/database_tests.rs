@@ -0,0 +1,90 @@
+/*!
+ * Tests for session database schema migrations
+ */
+
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use yastwai::database::models::{AnalysisScope, GlossaryTermKind, GlossaryTermRecord, RevisionOrigin, SessionStatus};
+use yastwai::database::repository::CacheQuery;
+use yastwai::database::schema::SCHEMA_VERSION;
+use yastwai::database::{DatabaseConnection, Repository};
+
+/// Create a v1 database from the fixture script
+fn create_v1_database(dir: &TempDir) -> PathBuf {
+    let path = dir.path().join("yastwai.db");
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch(include_str!("../resources/session_db_v1.sql")).unwrap();
+    path
+}
+
+fn schema_version_of(path: &Path) -> i32 {
+    DatabaseConnection::open_unmigrated(path)
+        .unwrap()
+        .migration_status()
+        .unwrap()
+        .current_version
+}
+
+#[test]
+fn test_databaseConnection_migrate_withV1FixtureAndDryRun_shouldListPendingMigrations() {
+    let dir = TempDir::new().unwrap();
+    let path = create_v1_database(&dir);
+    let db = DatabaseConnection::open_unmigrated(&path).unwrap();
+
+    let status = db.migrate(true).unwrap();
+
+    assert_eq!(status.current_version, 1);
+    assert_eq!(status.target_version, SCHEMA_VERSION);
+    assert_eq!(status.pending.first().map(|m| m.version), Some(2));
+    assert_eq!(status.pending.last().map(|m| m.version), Some(SCHEMA_VERSION));
+    assert_eq!(schema_version_of(&path), 1);
+    assert!(!db.backup_path(1).unwrap().exists());
+}
+
+#[tokio::test]
+async fn test_databaseConnection_new_withV1Fixture_shouldUpgradeAndKeepData() {
+    let dir = TempDir::new().unwrap();
+    let path = create_v1_database(&dir);
+
+    let db = DatabaseConnection::new(&path).unwrap();
+
+    assert!(db.migration_status().unwrap().is_up_to_date());
+    let backup = db.backup_path(1).unwrap();
+    assert!(backup.exists());
+    assert_eq!(schema_version_of(&backup), 1);
+
+    let repo = Repository::new(db);
+    let session_id = "0f8fad5b-d9cb-469f-a165-70867728950e";
+    let session = repo.get_session(session_id).await.unwrap().unwrap();
+    assert_eq!(session.status, SessionStatus::Paused);
+    assert!(session.source_content_hash.is_empty(), "older sessions keep matching on the file hash");
+    assert_eq!(repo.get_translated_entries(session_id).await.unwrap().len(), 2);
+    let pending = repo.get_pending_entries(session_id).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].source_text, "To Mordor.");
+
+    // Tables added by the migrations are usable
+    let scope = AnalysisScope::Session(session_id.to_string());
+    repo.save_glossary_terms(vec![GlossaryTermRecord::new(scope.clone(), GlossaryTermKind::Character, "Frodo".to_string(), None)])
+        .await
+        .unwrap();
+    assert_eq!(repo.get_glossary_terms(&scope).await.unwrap().len(), 1);
+    assert!(repo.get_history_summaries(session_id).await.unwrap().is_empty());
+
+    // Existing translations become the first revision of their entry
+    let translated = repo.get_translated_entries(session_id).await.unwrap();
+    let revisions = repo.get_revisions(translated[0].0.id).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].origin, RevisionOrigin::Translation);
+    assert_eq!(revisions[0].translated_text, translated[0].1.translated_text);
+    assert_eq!(revisions[0].provider.as_deref(), Some(session.provider.as_str()));
+
+    // Cached translations are carried over without a context key
+    let cached = repo.list_cache_entries(&CacheQuery::default()).await.unwrap();
+    assert_eq!(cached.len(), 1);
+    assert!(cached[0].context_key.is_empty());
+    assert_eq!(cached[0].last_used_at, cached[0].created_at);
+    assert_eq!(cached[0].hit_count, 4);
+}
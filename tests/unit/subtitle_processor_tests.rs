@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::fmt::Write;
 use anyhow::Result;
 use yastwai::subtitle_processor::{SubtitleEntry, SubtitleCollection};
+use yastwai::video_timing::{FrameRate, TimingTransform};
 use crate::common;
 
 /// Test timestamp parsing and formatting
@@ -201,4 +202,34 @@ fn test_fast_extract_source_subtitles_withValidVideo_shouldExtractSubtitles() ->
     assert_eq!(collection.source_language, source_language);
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Test that timing transforms are applied in order
+#[test]
+fn test_apply_timing_transforms_withScaleThenOffset_shouldApplyInOrder() -> Result<()> {
+    let mut collection = SubtitleCollection::new(PathBuf::from("test.srt"), "en".to_string());
+    collection.entries.push(SubtitleEntry::new(1, 25_000, 27_000, "Hello".to_string()));
+    
+    let transforms = vec![
+        TimingTransform::FrameRate { from: FrameRate::new(25, 1), to: FrameRate::new(24000, 1001) },
+        TimingTransform::Offset { offset_ms: 1000 },
+    ];
+    let clamped = collection.apply_timing_transforms(&transforms)?;
+    
+    assert_eq!(clamped, 0);
+    assert_eq!(collection.entries[0].start_time_ms, 27_068);
+    assert_eq!(collection.entries[0].end_time_ms, 29_153);
+    
+    Ok(())
+}
+
+/// Test parsing the bundled SRT resource
+#[test]
+fn test_parse_srt_string_withResourceFile_shouldParseEntries() -> Result<()> {
+    let content = std::fs::read_to_string(common::test_resource_path("test.srt"))?;
+    let entries = SubtitleCollection::parse_srt_string(&content)?;
+    
+    assert_eq!(entries.len(), 3);
+    
+    Ok(())
+}
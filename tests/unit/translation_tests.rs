@@ -32,6 +32,11 @@ fn get_test_config() -> TranslationConfig {
             entries_per_request: 3,
             context_entries_count: 3,
             pipeline_mode: "legacy".to_string(),
+            style_guide_path: None,
+            prompt_templates_dir: None,
+            term_policy_path: None,
+            class_policy: Default::default(),
+            retiming: None,
         },
         available_providers: vec![
             ProviderConfig {
@@ -43,6 +48,7 @@ fn get_test_config() -> TranslationConfig {
                 max_chars_per_request: 1000,
                 timeout_secs: 30,
                 rate_limit: None,
+                pricing: None,
             },
             ProviderConfig {
                 provider_type: "openai".to_string(),
@@ -53,6 +59,7 @@ fn get_test_config() -> TranslationConfig {
                 max_chars_per_request: 4000,
                 timeout_secs: 30,
                 rate_limit: Some(60),
+                pricing: None,
             },
             ProviderConfig {
                 provider_type: "anthropic".to_string(),
@@ -63,6 +70,7 @@ fn get_test_config() -> TranslationConfig {
                 max_chars_per_request: 4000,
                 timeout_secs: 30,
                 rate_limit: Some(45),
+                pricing: None,
             },
         ],
     }
@@ -605,4 +613,50 @@ fn test_defaultConcurrency_withoutProviderConfig_shouldUseFallback() {
     // Should use default concurrency (4) when no provider config exists
     assert_eq!(service.options.max_concurrent_requests, 4);
     assert_eq!(config.translation.optimal_concurrent_requests(), 4);
-} 
\ No newline at end of file
+} 
+
+/// Test that the cache keeps translations in the session storage and survives cloning
+#[tokio::test]
+async fn test_withCacheConfig_withStorage_shouldShareTranslationsAcrossServices() {
+    use yastwai::app_config::CacheConfig;
+    use yastwai::database::Repository;
+    use yastwai::database::storage::Storage;
+    use yastwai::translation::cache::CacheContext;
+
+    let storage: Arc<dyn Storage> = Arc::new(Repository::new_in_memory().unwrap());
+    let first = TranslationService::new(get_test_config())
+        .unwrap()
+        .with_cache_config(&CacheConfig::default(), Some(storage.clone()));
+    assert!(first.cache.has_l2());
+    assert!(first.clone().cache.has_l2());
+
+    first.cache.store_in_context("Right.", "en", "fr", &CacheContext::default(), "D'accord.").await;
+
+    let second = TranslationService::new(get_test_config())
+        .unwrap()
+        .with_cache_config(&CacheConfig::default(), Some(storage));
+    assert_eq!(
+        second.cache.get_in_context("Right.", "en", "fr", &CacheContext::default()).await.as_deref(),
+        Some("D'accord.")
+    );
+
+    let without_storage = TranslationService::new(get_test_config())
+        .unwrap()
+        .with_cache_config(&CacheConfig::default(), None);
+    assert!(!without_storage.cache.has_l2());
+}
+
+/// Test that cache contexts carry the common speaker, formality and neighbours
+#[test]
+fn test_cacheContext_shouldCombineSpeakerFormalityAndNeighbours() {
+    let service = TranslationService::new(get_test_config()).unwrap().with_formality("Formal");
+
+    let context = service.cache_context(&["JOHN: Right.", "JOHN: Let's go."], &["Where now?"], &[]);
+    assert_eq!(context.speaker.as_deref(), Some("john"));
+    assert_eq!(context.formality.as_deref(), Some("formal"));
+    assert!(context.neighbour_hash.is_some());
+
+    let mixed = service.cache_context(&["JOHN: Right.", "MARY: Fine."], &[], &[]);
+    assert!(mixed.speaker.is_none());
+    assert!(mixed.neighbour_hash.is_none());
+}